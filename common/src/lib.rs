@@ -0,0 +1,363 @@
+use std::{
+    env,
+    fmt::{self, Display, Formatter},
+    fs, panic,
+    path::{Path, PathBuf},
+    process,
+    sync::OnceLock,
+};
+
+use clap::{Arg, ArgAction, CommandFactory, ValueEnum};
+
+/// The format selected for reporting errors, set once by `init()`
+static FORMAT: OnceLock<Format> = OnceLock::new();
+
+/// Formats in which the binaries can report their errors
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, ValueEnum)]
+pub enum Format {
+    /// Human readable messages on standard error
+    #[default]
+    Text,
+    /// A JSON object describing the error on standard output
+    Json,
+}
+
+/// Ways in which a binary can fail, each with its own exit code
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Failure {
+    /// The input (or a command line argument) could not be parsed
+    Parse(String),
+    /// The input was parsed, but has no answer
+    Unsolvable(String),
+    /// The input could not be read or an output could not be written
+    Io(String),
+    /// The binary panicked, which is a bug in the solution rather than a problem with its input
+    Internal(String),
+}
+
+impl Failure {
+    /// Gets the exit code used for the failure
+    pub fn code(&self) -> i32 {
+        match self {
+            Failure::Parse(_) => 2,
+            Failure::Unsolvable(_) => 3,
+            Failure::Io(_) => 4,
+            Failure::Internal(_) => 5,
+        }
+    }
+
+    /// Gets the name of the kind of failure
+    pub fn kind(&self) -> &'static str {
+        match self {
+            Failure::Parse(_) => "parse",
+            Failure::Unsolvable(_) => "unsolvable",
+            Failure::Io(_) => "io",
+            Failure::Internal(_) => "internal",
+        }
+    }
+
+    /// Gets the message describing the failure
+    pub fn message(&self) -> &str {
+        match self {
+            Failure::Parse(message)
+            | Failure::Unsolvable(message)
+            | Failure::Io(message)
+            | Failure::Internal(message) => message,
+        }
+    }
+
+    /// Gets the failure as a JSON error envelope
+    pub fn to_json(&self) -> String {
+        let kind = self.kind();
+        let code = self.code();
+        let message = escape_json(self.message());
+        format!("{{\"error\":{{\"kind\":\"{kind}\",\"code\":{code},\"message\":\"{message}\"}}}}")
+    }
+}
+
+impl Display for Failure {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(f, "{} error: {}", self.kind(), self.message())
+    }
+}
+
+/// Escapes the text for use within a JSON string
+pub fn escape_json(text: &str) -> String {
+    let mut escaped = String::new();
+    for character in text.chars() {
+        match character {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            '\t' => escaped.push_str("\\t"),
+            c if c.is_control() => escaped.push_str(&format!("\\u{:04x}", c as u32)),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// Sets the format for reporting errors, and reports any panic as an internal error in that
+/// format, with its own exit code so that it can't be mistaken for a parse failure
+pub fn init(format: Format) {
+    FORMAT.get_or_init(|| format);
+    panic::set_hook(Box::new(|info| {
+        let message = if let Some(message) = info.payload().downcast_ref::<&str>() {
+            message.to_string()
+        } else if let Some(message) = info.payload().downcast_ref::<String>() {
+            message.clone()
+        } else {
+            String::from("Unknown error")
+        };
+        let location = info
+            .location()
+            .map_or(String::new(), |x| format!(" at {}:{}", x.file(), x.line()));
+        let message = format!("{message}{location}");
+        fail(Failure::Internal(message));
+    }));
+}
+
+/// Reports the failure in the selected format and exits with its exit code
+pub fn fail(failure: Failure) -> ! {
+    match FORMAT.get().copied().unwrap_or_default() {
+        Format::Text => eprintln!("{failure}"),
+        Format::Json => println!("{}", failure.to_json()),
+    }
+    process::exit(failure.code());
+}
+
+/// Reads the input file, failing with an IO error if it cannot be read or isn't valid UTF-8
+pub fn read_input(filepath: impl AsRef<Path>) -> String {
+    let filepath = filepath.as_ref();
+    String::from_utf8(read_input_bytes(filepath)).unwrap_or_else(|error| {
+        let filepath = filepath.display();
+        fail(Failure::Io(format!("Could not read {filepath}: {error}")))
+    })
+}
+
+/// Reads the raw bytes of the input file, which may not be text, failing with an IO error if it
+/// cannot be read
+pub fn read_input_bytes(filepath: impl AsRef<Path>) -> Vec<u8> {
+    let filepath = filepath.as_ref();
+    fs::read(filepath).unwrap_or_else(|error| {
+        let filepath = filepath.display();
+        fail(Failure::Io(format!("Could not read {filepath}: {error}")))
+    })
+}
+
+/// Prints the statistics describing the structure of the parsed input, one per line as
+/// `name: value`, and exits without solving the puzzle
+///
+/// Input that can't be parsed fails before this is reached, so a successful exit means the
+/// input is well formed.
+pub fn report_parse(statistics: &[(&str, usize)]) -> ! {
+    for (name, value) in statistics {
+        println!("{name}: {value}");
+    }
+    process::exit(0);
+}
+
+/// Environment variable that overrides the directory in which data derived from inputs is
+/// cached
+pub const CACHE_DIR_VAR: &str = "AOC_CACHE_DIR";
+
+/// Gets the directory in which data derived from inputs is cached, which is the one named by
+/// `AOC_CACHE_DIR` if set, or `aoc` in the user's cache directory otherwise
+///
+/// The user's cache directory is `XDG_CACHE_HOME` or `~/.cache` on Unix, or `LOCALAPPDATA` on
+/// Windows.  If none of these are set, `.aoc-cache` in the working directory is used rather
+/// than a shared directory that other users could write to.
+pub fn cache_dir() -> PathBuf {
+    let non_empty = |name: &str| {
+        env::var_os(name)
+            .filter(|x| !x.is_empty())
+            .map(PathBuf::from)
+    };
+    if let Some(directory) = non_empty(CACHE_DIR_VAR) {
+        return directory;
+    }
+    non_empty("XDG_CACHE_HOME")
+        .or_else(|| non_empty("HOME").map(|x| x.join(".cache")))
+        .or_else(|| non_empty("LOCALAPPDATA"))
+        .map_or_else(|| PathBuf::from(".aoc-cache"), |x| x.join("aoc"))
+}
+
+/// Hashes the input using 64-bit FNV-1a, which (unlike the standard library's hasher) gives
+/// the same hash for the same input in every run and build
+pub fn input_hash(input: &[u8]) -> u64 {
+    input.iter().fold(0xcbf29ce484222325, |hash, byte| {
+        (hash ^ *byte as u64).wrapping_mul(0x100000001b3)
+    })
+}
+
+/// Gets the text derived from the input under the given name and version from the cache
+/// directory, computing it and storing it there if it isn't cached yet
+///
+/// The version should be changed whenever the way the text is computed changes, so that text
+/// cached by older builds isn't used.  The cache is only an optimization, so a cache that
+/// can't be read is treated as empty, and one that can't be written is reported on standard
+/// error without failing.
+pub fn cached(name: &str, version: u32, input: &[u8], compute: impl FnOnce() -> String) -> String {
+    cached_in(&cache_dir(), name, version, input, compute)
+}
+
+/// Gets the text derived from the input under the given name and version from the given cache
+/// directory, computing it and storing it there if it isn't cached yet, like `cached()`
+pub fn cached_in(
+    directory: &Path,
+    name: &str,
+    version: u32,
+    input: &[u8],
+    compute: impl FnOnce() -> String,
+) -> String {
+    // Key the entry by the name, the version of the computation and of this crate (which
+    // decides how entries are stored), and the hash and length of the input
+    let hash = input_hash(input);
+    let length = input.len();
+    let crate_version = env!("CARGO_PKG_VERSION");
+    let filename = format!("{name}-v{version}-{crate_version}-{hash:016x}-{length}.txt");
+    let filepath = directory.join(&filename);
+
+    // Use the cached text if there is any
+    if let Ok(text) = fs::read_to_string(&filepath) {
+        return text;
+    }
+
+    // Otherwise compute the text and store it for next time, writing it to a temporary file
+    // in the same directory first and renaming it into place, so that the entry is never seen
+    // partly written
+    let text = compute();
+    let temp_filepath = directory.join(format!(".{filename}.{}.tmp", process::id()));
+    let stored = fs::create_dir_all(directory)
+        .and_then(|_| fs::write(&temp_filepath, &text))
+        .and_then(|_| fs::rename(&temp_filepath, &filepath));
+    if let Err(error) = stored {
+        let _ = fs::remove_file(&temp_filepath);
+        let filepath = filepath.display();
+        eprintln!("Could not cache {filepath}: {error}");
+    }
+    text
+}
+
+/// Structured description of the puzzle that a binary solves
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct About {
+    /// The day of the puzzle
+    pub day: u8,
+    /// The title of the puzzle
+    pub title: &'static str,
+    /// What the input file is expected to contain
+    pub input: &'static str,
+    /// The parts of the puzzle that are implemented
+    pub parts: &'static [u64],
+    /// The optional features that the binary supports
+    pub features: &'static [Feature],
+}
+
+/// Optional features that a binary may support
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Feature {
+    /// Can draw the puzzle state for a human to look at
+    Visualization,
+    /// Can spread the work across multiple threads
+    Parallel,
+}
+
+impl Display for Feature {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        let name = match self {
+            Feature::Visualization => "visualization",
+            Feature::Parallel => "parallel",
+        };
+        write!(f, "{name}")
+    }
+}
+
+impl About {
+    /// Gets the day-specific options of the given command as pairs of their usage and help
+    /// text, leaving out the positional arguments and the options shared by all binaries
+    pub fn parameters(&self, command: &clap::Command) -> Vec<(String, String)> {
+        command
+            .get_arguments()
+            .filter(|arg| !arg.is_positional())
+            .filter(|arg| !["help", "format", "about"].contains(&arg.get_id().as_str()))
+            .map(|arg| {
+                // Get the usage of the option, including its value if it takes one
+                let long = arg.get_long().unwrap_or_default();
+                let takes_value = matches!(arg.get_action(), ArgAction::Set | ArgAction::Append);
+                let usage = if !takes_value {
+                    format!("--{long}")
+                } else if arg.get_possible_values().is_empty() {
+                    let value_name = arg
+                        .get_value_names()
+                        .and_then(|x| x.first())
+                        .map_or(long.to_uppercase().replace('-', "_"), |x| x.to_string());
+                    format!("--{long} <{value_name}>")
+                } else {
+                    let values: Vec<String> = arg
+                        .get_possible_values()
+                        .iter()
+                        .map(|x| x.get_name().to_string())
+                        .collect();
+                    format!("--{long} <{}>", values.join("|"))
+                };
+
+                // Get the help text of the option
+                let help = arg.get_help().map_or(String::new(), |x| x.to_string());
+                (usage, help)
+            })
+            .collect()
+    }
+
+    /// Renders the description of the puzzle along with the day-specific options of the
+    /// given command
+    pub fn render(&self, command: &clap::Command) -> String {
+        let mut text = format!("Day {}: {}\nInput: {}\n", self.day, self.title, self.input);
+        let parts: Vec<String> = self.parts.iter().map(|x| x.to_string()).collect();
+        text.push_str(&format!("Parts: {}\n", parts.join(", ")));
+        if !self.features.is_empty() {
+            let features: Vec<String> = self.features.iter().map(|x| x.to_string()).collect();
+            text.push_str(&format!("Features: {}\n", features.join(", ")));
+        }
+        let parameters = self.parameters(command);
+        if !parameters.is_empty() {
+            text.push_str("Options:\n");
+            let width = parameters
+                .iter()
+                .map(|(usage, _)| usage.len())
+                .max()
+                .unwrap();
+            for (usage, help) in parameters {
+                text.push_str(&format!("  {usage:width$}  {help}\n"));
+            }
+        }
+        text
+    }
+}
+
+/// Prints the description of the puzzle and exits if `--about` was given, before the command
+/// line arguments of the binary (which would otherwise require a part and input file) are parsed
+pub fn handle_about<C: CommandFactory>(about: &About) {
+    let command = C::command().arg(
+        Arg::new("about")
+            .long("about")
+            .action(ArgAction::SetTrue)
+            .exclusive(true)
+            .help("Describe the puzzle, its input, and the available options"),
+    );
+    if command.clone().get_matches().get_flag("about") {
+        print!("{}", about.render(&command));
+        process::exit(0);
+    }
+}
+
+/// Terminal colors used to tell apart the labels of things drawn by the binaries
+const PALETTE: [u8; 12] = [196, 208, 226, 118, 46, 48, 51, 33, 21, 93, 201, 244];
+
+/// Paints the text in the terminal color for the given label, cycling through a fixed palette
+/// so that neighbouring labels get distinct colors
+pub fn paint(text: &str, label: usize) -> String {
+    let color = PALETTE[label % PALETTE.len()];
+    format!("\x1b[38;5;{color}m{text}\x1b[0m")
+}
@@ -0,0 +1,48 @@
+use clap::Parser;
+use common::{About, Feature};
+
+/// Command line arguments for a made-up binary
+#[derive(Parser)]
+#[allow(dead_code)]
+struct CliArgs {
+    part: u64,
+    filepath: String,
+    /// Limit the number of things
+    #[arg(long)]
+    limit: Option<usize>,
+    /// Turn on the thing
+    #[arg(long)]
+    thing: bool,
+}
+
+const ABOUT: About = About {
+    day: 0,
+    title: "Made Up",
+    input: "Nothing in particular",
+    parts: &[1, 2],
+    features: &[Feature::Visualization, Feature::Parallel],
+};
+
+#[test]
+fn lists_day_specific_options() {
+    let command = <CliArgs as clap::CommandFactory>::command();
+    assert_eq!(
+        ABOUT.parameters(&command),
+        vec![
+            (
+                String::from("--limit <LIMIT>"),
+                String::from("Limit the number of things")
+            ),
+            (String::from("--thing"), String::from("Turn on the thing")),
+        ]
+    );
+}
+
+#[test]
+fn renders_description() {
+    let command = <CliArgs as clap::CommandFactory>::command();
+    assert_eq!(
+        ABOUT.render(&command),
+        "Day 0: Made Up\nInput: Nothing in particular\nParts: 1, 2\nFeatures: visualization, parallel\nOptions:\n  --limit <LIMIT>  Limit the number of things\n  --thing          Turn on the thing\n"
+    );
+}
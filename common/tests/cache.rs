@@ -0,0 +1,69 @@
+use std::{cell::Cell, env, fs, process};
+
+use common::{cache_dir, cached_in, input_hash, CACHE_DIR_VAR};
+
+#[test]
+fn hash_is_stable() {
+    assert_eq!(input_hash(b""), 0xcbf29ce484222325);
+    assert_eq!(input_hash(b"a"), 0xaf63dc4c8601ec8c);
+    assert_ne!(input_hash(b"ab"), input_hash(b"ba"));
+}
+
+#[test]
+fn computes_once_per_input() {
+    let directory = env::temp_dir().join(format!("aoc-cache-test-{}", process::id()));
+    let calls = Cell::new(0);
+    let compute = |text: &str| {
+        calls.set(calls.get() + 1);
+        text.to_uppercase()
+    };
+
+    // The first lookup computes the text, and the second reads it back
+    assert_eq!(
+        cached_in(&directory, "upper", 1, b"abc", || compute("abc")),
+        "ABC"
+    );
+    assert_eq!(
+        cached_in(&directory, "upper", 1, b"abc", || compute("abc")),
+        "ABC"
+    );
+    assert_eq!(calls.get(), 1);
+
+    // Another input or name is computed separately
+    assert_eq!(
+        cached_in(&directory, "upper", 1, b"abd", || compute("abd")),
+        "ABD"
+    );
+    assert_eq!(
+        cached_in(&directory, "other", 1, b"abc", || compute("abc")),
+        "ABC"
+    );
+    assert_eq!(calls.get(), 3);
+
+    // Another version of the computation doesn't use the text cached by the old one
+    assert_eq!(
+        cached_in(&directory, "upper", 2, b"abc", || compute("abc")),
+        "ABC"
+    );
+    assert_eq!(calls.get(), 4);
+
+    // Only the entries themselves are left in the directory, without any temporary files
+    let entries: Vec<String> = fs::read_dir(&directory)
+        .unwrap()
+        .map(|x| x.unwrap().file_name().to_string_lossy().into_owned())
+        .collect();
+    assert_eq!(entries.len(), 4);
+    assert!(entries.iter().all(|x| !x.starts_with('.')));
+
+    fs::remove_dir_all(&directory).unwrap();
+}
+
+#[test]
+fn cache_dir_is_per_user() {
+    // The directory can be overridden, and otherwise isn't shared in the temporary directory
+    let directory = cache_dir();
+    match env::var_os(CACHE_DIR_VAR) {
+        Some(value) if !value.is_empty() => assert_eq!(directory.as_os_str(), value),
+        _ => assert_ne!(directory, env::temp_dir().join("aoc-cache")),
+    }
+}
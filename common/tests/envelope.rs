@@ -0,0 +1,31 @@
+use common::Failure;
+
+#[test]
+fn exit_codes() {
+    assert_eq!(Failure::Parse(String::new()).code(), 2);
+    assert_eq!(Failure::Unsolvable(String::new()).code(), 3);
+    assert_eq!(Failure::Io(String::new()).code(), 4);
+    assert_eq!(Failure::Internal(String::new()).code(), 5);
+}
+
+#[test]
+fn json_envelope() {
+    let failure = Failure::Io(String::from("Could not read \"in.txt\"\n"));
+    assert_eq!(
+        failure.to_json(),
+        r#"{"error":{"kind":"io","code":4,"message":"Could not read \"in.txt\"\n"}}"#
+    );
+}
+
+#[test]
+fn internal_envelope() {
+    let failure = Failure::Internal(String::from("Invalid part at src/main.rs:10"));
+    assert_eq!(
+        failure.to_string(),
+        "internal error: Invalid part at src/main.rs:10"
+    );
+    assert_eq!(
+        failure.to_json(),
+        r#"{"error":{"kind":"internal","code":5,"message":"Invalid part at src/main.rs:10"}}"#
+    );
+}
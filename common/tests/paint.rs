@@ -0,0 +1,12 @@
+use common::paint;
+
+#[test]
+fn paint_wraps_text_in_color() {
+    assert_eq!(paint("#", 0), "\x1b[38;5;196m#\x1b[0m");
+}
+
+#[test]
+fn paint_cycles_through_palette() {
+    assert_eq!(paint("#", 1), paint("#", 13));
+    assert_ne!(paint("#", 1), paint("#", 2));
+}
@@ -0,0 +1,105 @@
+use std::{io::ErrorKind, thread, time::Duration};
+
+use common::{fail, Failure};
+use day1::{
+    fields, parse_field,
+    tail::{Tail, TailRead},
+    tally::Tally,
+};
+
+/// How long to wait between checks for new lines
+const POLL_INTERVAL: Duration = Duration::from_millis(250);
+
+/// Reads the complete lines appended to the followed file since the last read, failing if it
+/// can't be read or a line isn't valid UTF-8
+///
+/// Returns None if the file was truncated, so everything read from it is gone.
+fn read_tail(tail: &mut Tail) -> Option<Vec<(usize, String)>> {
+    match tail.read_lines() {
+        Ok(TailRead::Lines(lines)) => Some(lines),
+        Ok(TailRead::Truncated) => None,
+        Err(error) if error.kind() == ErrorKind::InvalidData => {
+            let filepath = tail.filepath();
+            fail(Failure::Parse(format!("{filepath}: {error}")))
+        }
+        Err(error) => {
+            let filepath = tail.filepath();
+            fail(Failure::Io(format!("Could not read {filepath}: {error}")))
+        }
+    }
+}
+
+/// Reads the lists as lines are appended to the input file, printing the total distance and
+/// similarity score as CSV after each batch of new lines
+///
+/// The lists come from the given columns of the input file, or if a second file is given, the
+/// first list comes from the first column of the input file and the second list from the first
+/// column of the second file, skipping the header line of each file if requested.  If either
+/// file is truncated, both are read again from the beginning with empty lists.  This runs until
+/// interrupted.
+pub fn follow(
+    filepath: &str,
+    second_filepath: Option<&str>,
+    columns: (usize, usize),
+    skip_header: bool,
+    tolerance: Option<u64>,
+) -> ! {
+    // Start reading the files from the beginning, with empty lists
+    let mut first_tail = Tail::new(filepath);
+    let mut second_tail = second_filepath.map(Tail::new);
+    let mut tally = Tally::new(tolerance);
+    println!("first,second,distance,similarity");
+
+    loop {
+        // Read the new lines in each of the files
+        let first_lines = read_tail(&mut first_tail);
+        let second_lines = second_tail.as_mut().map(read_tail);
+
+        // Start over with empty lists if either file was truncated
+        let (Some(first_lines), Some(second_lines)) =
+            (first_lines, second_lines.unwrap_or(Some(Vec::new())))
+        else {
+            eprintln!("Input truncated, reading it again from the beginning");
+            first_tail = Tail::new(filepath);
+            second_tail = second_filepath.map(Tail::new);
+            tally = Tally::new(tolerance);
+            continue;
+        };
+
+        // Add the entries from the new lines in each of the files
+        let mut changed = false;
+        let parse = |fields: &[&str], column, line| {
+            parse_field(fields, column, line).unwrap_or_else(|error| fail(Failure::Parse(error)))
+        };
+        let is_header = |line| skip_header && line == 1;
+        for (line, text) in first_lines {
+            let fields = fields(&text);
+            if fields.is_empty() || is_header(line) {
+                continue;
+            }
+            changed = true;
+            if second_tail.is_some() {
+                tally.add_first(parse(&fields, 0, line));
+            } else {
+                tally.add_first(parse(&fields, columns.0, line));
+                tally.add_second(parse(&fields, columns.1, line));
+            }
+        }
+        for (line, text) in second_lines {
+            let fields = fields(&text);
+            if !fields.is_empty() && !is_header(line) {
+                changed = true;
+                tally.add_second(parse(&fields, 0, line));
+            }
+        }
+
+        // Print the updated scores if anything was added, and wait for more lines
+        if changed {
+            let (first_length, second_length) = tally.lengths();
+            let distance = tally.distance();
+            let similarity = tally.similarity();
+            println!("{first_length},{second_length},{distance},{similarity}");
+        }
+        thread::sleep(POLL_INTERVAL);
+    }
+}
@@ -0,0 +1,152 @@
+use std::collections::HashMap;
+
+pub mod tail;
+pub mod tally;
+
+/// An entry in one of the lists, along with the line number it came from
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Entry {
+    pub value: u64,
+    pub line: usize,
+}
+
+/// Gets the fields of the line, split by commas if it has any, keeping empty fields, or
+/// otherwise by whitespace
+pub fn fields(line: &str) -> Vec<&str> {
+    if line.contains(',') {
+        line.split(',').map(str::trim).collect()
+    } else {
+        line.split_whitespace().collect()
+    }
+}
+
+/// Parses the location ID in the given (0-based) column of the fields of the given line
+pub fn parse_field(fields: &[&str], column: usize, line: usize) -> Result<u64, String> {
+    let field = fields
+        .get(column)
+        .ok_or_else(|| format!("Line {line} has no column {}", column + 1))?;
+    field
+        .parse::<u64>()
+        .map_err(|_| format!("Line {line}: could not parse {field:?} as a location ID"))
+}
+
+/// Parses the lists of entries from the given (0-based) columns of the input text, keeping
+/// track of which line each entry came from, and sorts them
+///
+/// If requested, the first line is skipped as a header.  Every other line must have a location
+/// ID in both columns, so a header, blank line, or short row fails with its line number.
+pub fn parse_entry_lists(
+    contents: &str,
+    columns: (usize, usize),
+    skip_header: bool,
+) -> Result<(Vec<Entry>, Vec<Entry>), String> {
+    // Create empty, mutable lists
+    let mut first_list: Vec<Entry> = Vec::new();
+    let mut second_list: Vec<Entry> = Vec::new();
+
+    // For each line in the supplied text, parse the numbers in the selected columns, and add
+    // them to the lists
+    let num_skipped = usize::from(skip_header);
+    for (index, text) in contents.lines().enumerate().skip(num_skipped) {
+        let line = index + 1;
+        let fields = fields(text);
+        if fields.is_empty() {
+            return Err(format!("Line {line} is blank"));
+        }
+        first_list.push(Entry {
+            value: parse_field(&fields, columns.0, line)?,
+            line,
+        });
+        second_list.push(Entry {
+            value: parse_field(&fields, columns.1, line)?,
+            line,
+        });
+    }
+
+    // Sort the populated lists, keeping entries with equal values in their original order
+    first_list.sort_by_key(|x| x.value);
+    second_list.sort_by_key(|x| x.value);
+
+    // Return both lists
+    Ok((first_list, second_list))
+}
+
+/// Gets the total distance between the paired up entries of the (sorted) lists
+pub fn total_distance(first_list: &[u64], second_list: &[u64]) -> u64 {
+    first_list
+        .iter()
+        .zip(second_list)
+        .map(|(item_one, item_two)| item_one.abs_diff(*item_two))
+        .sum()
+}
+
+/// Gets the number of times each value appears in the list
+pub fn frequencies(list: &[u64]) -> HashMap<u64, u64> {
+    let mut counts = HashMap::new();
+    for value in list {
+        *counts.entry(*value).or_insert(0) += 1;
+    }
+    counts
+}
+
+/// Gets the number of values in the sorted list within the given distance of the value
+pub fn count_within(sorted_list: &[u64], value: u64, tolerance: u64) -> u64 {
+    let start = sorted_list.partition_point(|x| *x < value.saturating_sub(tolerance));
+    let end = sorted_list.partition_point(|x| *x <= value.saturating_add(tolerance));
+    (end - start) as u64
+}
+
+/// Gets the similarity score of the lists, adding each entry in the first list times the number
+/// of entries in the (sorted) second list that match it
+///
+/// Entries match if they're equal, or if a tolerance is given, within that distance of each
+/// other.
+pub fn similarity_score(first_list: &[u64], second_list: &[u64], tolerance: Option<u64>) -> u64 {
+    // Initialize the different as 0
+    let mut similarity: u64 = 0;
+
+    // If matches can be inexact, count the entries in the (sorted) list two within the
+    // tolerance of each entry in the first list, and add the similarity score to the running
+    // total
+    if let Some(tolerance) = tolerance {
+        for entry in first_list {
+            let entry_count = count_within(second_list, *entry, tolerance);
+            similarity += entry_count * entry;
+        }
+        return similarity;
+    }
+
+    // Count the number of times each value is in list two
+    let second_counts = frequencies(second_list);
+
+    // For each entry in the first list, get the number of times it's in list two,
+    // and add the similarity score to the running total
+    for entry in first_list {
+        let entry_count = second_counts.get(entry).copied().unwrap_or(0);
+        similarity += entry_count * entry;
+    }
+
+    // Return the similarity score
+    similarity
+}
+
+/// Parses the sorted lists of location IDs from the first two columns of the input text
+fn parse_lists(input: &str) -> Result<(Vec<u64>, Vec<u64>), String> {
+    let (first_list, second_list) = parse_entry_lists(input, (0, 1), false)?;
+    Ok((
+        first_list.iter().map(|x| x.value).collect(),
+        second_list.iter().map(|x| x.value).collect(),
+    ))
+}
+
+/// Solves part one for the puzzle input, getting the total distance between the lists
+pub fn part1(input: &str) -> Result<u64, String> {
+    let (first_list, second_list) = parse_lists(input)?;
+    Ok(total_distance(&first_list, &second_list))
+}
+
+/// Solves part two for the puzzle input, getting the similarity score of the lists
+pub fn part2(input: &str) -> Result<u64, String> {
+    let (first_list, second_list) = parse_lists(input)?;
+    Ok(similarity_score(&first_list, &second_list, None))
+}
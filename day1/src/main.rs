@@ -1,80 +1,296 @@
-use std::fs;
+use clap::{Parser, ValueEnum};
+use common::{fail, read_input, About, Failure, Format};
+use day1::{frequencies, parse_entry_lists, similarity_score, total_distance, Entry};
+use follow::follow;
 
-use clap::Parser;
+mod follow;
 
 #[derive(Parser)]
 struct CliArgs {
     part: u64,
     filepath: String,
+    /// Print the given number of largest distances between paired entries, with line numbers
+    #[arg(long)]
+    outliers: Option<usize>,
+    /// Print the values (with counts) resulting from the given multiset operation on the lists
+    #[arg(long, value_enum)]
+    set_op: Option<SetOperation>,
+    /// The two columns (`i,j`, counting from 1) of a table to compare, with each line split by
+    /// commas if it has any, or otherwise by whitespace
+    #[arg(long, value_parser = parse_columns, default_value = "1,2")]
+    columns: (usize, usize),
+    /// Skip the first line of each input file, such as a CSV header
+    #[arg(long)]
+    skip_header: bool,
+    /// Count entries in the second list within the given distance of each entry in the first
+    /// list as matching it when calculating the similarity score, instead of only equal ones
+    #[arg(long)]
+    tolerance: Option<u64>,
+    /// Count each location ID only once within each list when calculating the total distance
+    /// or similarity score, printing the number of duplicates dropped from each list
+    #[arg(long, conflicts_with = "follow")]
+    unique: bool,
+    /// Keep reading lines as they're appended to the input file, printing the total distance
+    /// and similarity score after each batch of new lines, until interrupted
+    #[arg(long, conflicts_with_all = ["outliers", "set_op"])]
+    follow: bool,
+    /// Read the second list from the given file instead, one entry per line, keeping the first
+    /// list in the input file
+    #[arg(long, requires = "follow")]
+    second_file: Option<String>,
+    /// Parse the input and print statistics about its structure instead of solving it
+    #[arg(long)]
+    parse_only: bool,
+    /// The format in which errors are reported
+    #[arg(long, value_enum, default_value_t = Format::Text)]
+    format: Format,
+}
+
+/// Description of the puzzle solved by this binary
+const ABOUT: About = About {
+    day: 1,
+    title: "Historian Hysteria",
+    input: "Two columns of location IDs, one pair per line",
+    parts: &[1, 2],
+    features: &[],
+};
+
+/// Multiset operations that can be performed on the two lists
+#[derive(Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum SetOperation {
+    /// Values present in both lists, counted as many times as they appear in both
+    Intersection,
+    /// Values present in one list more times than in the other, counted by the excess
+    Difference,
+}
+
+/// Parses a pair of 1-based column numbers of the form `i,j` into 0-based column indices
+fn parse_columns(text: &str) -> Result<(usize, usize), String> {
+    // Split the text into the two column numbers
+    let (first_text, second_text) = text
+        .split_once(',')
+        .ok_or_else(|| format!("Expected two columns like 1,2, got {text:?}"))?;
+
+    // Parse each of the column numbers, converting them into indices
+    let parse_column = |column: &str| match column.trim().parse::<usize>() {
+        Ok(number) if number > 0 => Ok(number - 1),
+        _ => Err(format!("Could not parse {column:?} as a column number")),
+    };
+    Ok((parse_column(first_text)?, parse_column(second_text)?))
 }
 
 fn main() {
+    // Describe the puzzle instead if requested
+    common::handle_about::<CliArgs>(&ABOUT);
+
     // Parse CLI arguments
     let cli = CliArgs::parse();
-    let file_contents = fs::read_to_string(cli.filepath).expect("Could not read file");
+
+    // Report errors in the requested format
+    common::init(cli.format);
+
+    // Only parse the input if requested
+    if cli.parse_only {
+        report_structure(read_input(&cli.filepath), cli.columns, cli.skip_header);
+    }
+
+    // Keep updating the scores as lines are appended to the input if requested
+    if cli.follow {
+        follow(
+            &cli.filepath,
+            cli.second_file.as_deref(),
+            cli.columns,
+            cli.skip_header,
+            cli.tolerance,
+        );
+    }
+    let file_contents = read_input(cli.filepath);
+
+    // Print the outlier report if requested
+    if let Some(num_outliers) = cli.outliers {
+        print_outliers(
+            file_contents.clone(),
+            cli.columns,
+            cli.skip_header,
+            num_outliers,
+        );
+    }
+
+    // Print the multiset operation results if requested
+    if let Some(operation) = cli.set_op {
+        print_set_operation(
+            file_contents.clone(),
+            cli.columns,
+            cli.skip_header,
+            operation,
+        );
+    }
 
     // Run the code for the desired challenge part
     match cli.part {
-        1 => main_part_one(file_contents),
-        2 => main_part_two(file_contents),
+        1 => main_part_one(file_contents, cli.columns, cli.skip_header, cli.unique),
+        2 => main_part_two(
+            file_contents,
+            cli.columns,
+            cli.skip_header,
+            cli.tolerance,
+            cli.unique,
+        ),
         _ => panic!("Invalid selection part selection!"),
     }
 }
 
-// Function to create sorted lists of numbers based on the input text file
-fn create_lists(contents: String) -> (Vec<u64>, Vec<u64>) {
-    // Create empty, mutable lists
-    let mut first_list: Vec<u64> = Vec::new();
-    let mut second_list: Vec<u64> = Vec::new();
-
-    // For each line in the supplied text, split the string and parse the number, and add to the list
-    for line in contents.lines() {
-        let mut numbers: Vec<u64> = line
-            .split(" ")
-            .filter(|x| !x.is_empty())
-            .map(|y| str::parse::<u64>(y).unwrap())
-            .collect();
-        first_list.push(numbers.remove(0));
-        second_list.push(numbers.remove(0));
-    }
+// Function to create sorted lists of entries from the given columns of the input text file,
+// optionally skipping its header, keeping track of which line each entry came from
+fn create_entry_lists(
+    contents: String,
+    columns: (usize, usize),
+    skip_header: bool,
+) -> (Vec<Entry>, Vec<Entry>) {
+    parse_entry_lists(&contents, columns, skip_header)
+        .unwrap_or_else(|error| fail(Failure::Parse(error)))
+}
 
-    // Sort the populated lists
-    first_list.sort();
-    second_list.sort();
+// Function to create sorted lists of numbers from the given columns of the input text file
+fn create_lists(
+    contents: String,
+    columns: (usize, usize),
+    skip_header: bool,
+) -> (Vec<u64>, Vec<u64>) {
+    let (first_list, second_list) = create_entry_lists(contents, columns, skip_header);
+    (
+        first_list.iter().map(|x| x.value).collect(),
+        second_list.iter().map(|x| x.value).collect(),
+    )
+}
+
+// Function to create sorted lists of numbers from the given columns of the input text file,
+// optionally dropping repeated values within each list and printing how many were dropped
+fn create_part_lists(
+    contents: String,
+    columns: (usize, usize),
+    skip_header: bool,
+    unique: bool,
+) -> (Vec<u64>, Vec<u64>) {
+    // Parse the file contents for the lists
+    let (mut first_list, mut second_list) = create_lists(contents, columns, skip_header);
+
+    // Drop the repeated values if requested, which are next to each other in the sorted lists
+    if unique {
+        let (first_length, second_length) = (first_list.len(), second_list.len());
+        first_list.dedup();
+        second_list.dedup();
+        let first_dropped = first_length - first_list.len();
+        let second_dropped = second_length - second_list.len();
+        println!("duplicates: first {first_dropped}, second {second_dropped}");
+    }
 
     // Return both lists
     (first_list, second_list)
 }
 
-fn main_part_one(contents: String) {
-    // Parse the file contents for the lists
-    let (first_list, second_list) = create_lists(contents);
+/// Parses the lists from the input, then prints their lengths and the number of distinct values
+/// in each and exits
+fn report_structure(contents: String, columns: (usize, usize), skip_header: bool) -> ! {
+    let (first_list, second_list) = create_lists(contents, columns, skip_header);
+    common::report_parse(&[
+        ("entries", first_list.len()),
+        ("distinct first", frequencies(&first_list).len()),
+        ("distinct second", frequencies(&second_list).len()),
+    ]);
+}
+
+fn print_set_operation(
+    contents: String,
+    columns: (usize, usize),
+    skip_header: bool,
+    operation: SetOperation,
+) {
+    // Parse the file contents for the lists, and count the values in each
+    let (first_list, second_list) = create_lists(contents, columns, skip_header);
+    let first_counts = frequencies(&first_list);
+    let second_counts = frequencies(&second_list);
+
+    // Get all of the values from both lists in order
+    let mut values: Vec<u64> = first_counts
+        .keys()
+        .chain(second_counts.keys())
+        .copied()
+        .collect();
+    values.sort();
+    values.dedup();
+
+    // Print the count for each value resulting from the operation, skipping absent values
+    for value in values {
+        let count_one = first_counts.get(&value).copied().unwrap_or(0);
+        let count_two = second_counts.get(&value).copied().unwrap_or(0);
+        match operation {
+            SetOperation::Intersection => {
+                let count = count_one.min(count_two);
+                if count > 0 {
+                    println!("{value} x{count}");
+                }
+            }
+            SetOperation::Difference => {
+                if count_one > count_two {
+                    let count = count_one - count_two;
+                    println!("{value} x{count} (first list only)");
+                } else if count_two > count_one {
+                    let count = count_two - count_one;
+                    println!("{value} x{count} (second list only)");
+                }
+            }
+        }
+    }
+}
+
+fn print_outliers(
+    contents: String,
+    columns: (usize, usize),
+    skip_header: bool,
+    num_outliers: usize,
+) {
+    // Parse the file contents for the lists of entries
+    let (first_list, second_list) = create_entry_lists(contents, columns, skip_header);
 
-    // Initialize the different as 0
-    let mut diff: u64 = 0;
+    // Pair up the entries, and sort the pairs by distance with the largest first
+    let mut pairs: Vec<(Entry, Entry)> = first_list.into_iter().zip(second_list).collect();
+    pairs.sort_by_key(|(item_one, item_two)| {
+        std::cmp::Reverse(item_one.value.abs_diff(item_two.value))
+    });
 
-    // For each pair of entries, get the absolute difference and add to the difference
-    for (item_one, item_two) in first_list.iter().zip(second_list.iter()) {
-        diff += item_one.abs_diff(*item_two);
+    // Print the largest distances, along with where each entry came from
+    for (item_one, item_two) in pairs.iter().take(num_outliers) {
+        let distance = item_one.value.abs_diff(item_two.value);
+        let (value_one, line_one) = (item_one.value, item_one.line);
+        let (value_two, line_two) = (item_two.value, item_two.line);
+        println!("{distance}: {value_one} (line {line_one}) <-> {value_two} (line {line_two})");
     }
+}
+
+fn main_part_one(contents: String, columns: (usize, usize), skip_header: bool, unique: bool) {
+    // Parse the file contents for the lists
+    let (first_list, second_list) = create_part_lists(contents, columns, skip_header, unique);
+
+    // Get the total distance between the lists
+    let diff = total_distance(&first_list, &second_list);
 
     // Print the difference
     println!("{diff}");
 }
 
-fn main_part_two(contents: String) {
+fn main_part_two(
+    contents: String,
+    columns: (usize, usize),
+    skip_header: bool,
+    tolerance: Option<u64>,
+    unique: bool,
+) {
     // Parse the file contents for the lists
-    let (first_list, second_list) = create_lists(contents);
+    let (first_list, second_list) = create_part_lists(contents, columns, skip_header, unique);
 
-    // Initialize the different as 0
-    let mut similarity: u64 = 0;
-
-    // For each entry in the first list, get the number of times it's in list two,
-    // and add the similarity score to the running total
-    for entry in first_list {
-        let entry_count = second_list.iter().filter(|x| **x == entry).count() as u64;
-        similarity += entry_count * entry;
-    }
+    // Get the similarity score of the lists
+    let similarity = similarity_score(&first_list, &second_list, tolerance);
 
     // Print the similarity score
     println!("{similarity}");
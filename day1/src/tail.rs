@@ -0,0 +1,86 @@
+use std::{
+    fs::File,
+    io::{self, ErrorKind, Read, Seek, SeekFrom},
+};
+
+/// The result of reading a file being followed
+#[derive(Debug, PartialEq, Eq)]
+pub enum TailRead {
+    /// The complete lines appended since the last read, along with their line numbers
+    Lines(Vec<(usize, String)>),
+    /// The file is shorter than what was already read, so it must have been truncated or
+    /// replaced, and is read again from the beginning on the next read
+    Truncated,
+}
+
+/// A file being read as lines are appended to it
+pub struct Tail {
+    filepath: String,
+    offset: u64,
+    /// The bytes read after the last complete line, which may end partway through a character
+    partial: Vec<u8>,
+    /// The number of complete lines read so far
+    num_lines: usize,
+}
+
+impl Tail {
+    /// Starts reading the file from the beginning
+    pub fn new(filepath: &str) -> Self {
+        Self {
+            filepath: filepath.to_owned(),
+            offset: 0,
+            partial: Vec::new(),
+            num_lines: 0,
+        }
+    }
+
+    /// Gets the path of the file being read
+    pub fn filepath(&self) -> &str {
+        &self.filepath
+    }
+
+    /// Reads the complete lines appended to the file since the last read, along with their
+    /// line numbers, leaving any line still being written for later
+    ///
+    /// A line that isn't valid UTF-8 once complete fails with `ErrorKind::InvalidData`.  A
+    /// truncation is only noticed if the file is still shorter than what was read when it's
+    /// next read.
+    pub fn read_lines(&mut self) -> io::Result<TailRead> {
+        // Start over if the file is shorter than what was already read
+        let mut file = File::open(&self.filepath)?;
+        if file.metadata()?.len() < self.offset {
+            *self = Self::new(&self.filepath);
+            return Ok(TailRead::Truncated);
+        }
+
+        // Read everything after the previous read
+        let mut bytes = Vec::new();
+        file.seek(SeekFrom::Start(self.offset))?;
+        file.read_to_end(&mut bytes)?;
+        self.offset += bytes.len() as u64;
+
+        // Split off the complete lines, keeping the rest
+        self.partial.extend(bytes);
+        let Some(end) = self.partial.iter().rposition(|x| *x == b'\n') else {
+            return Ok(TailRead::Lines(Vec::new()));
+        };
+        let rest = self.partial.split_off(end + 1);
+        let mut complete = std::mem::replace(&mut self.partial, rest);
+        complete.pop();
+
+        // Convert each of the complete lines into text
+        let mut lines = Vec::new();
+        for bytes in complete.split(|x| *x == b'\n') {
+            let line = self.num_lines + lines.len() + 1;
+            let text = String::from_utf8(bytes.to_vec()).map_err(|_| {
+                io::Error::new(
+                    ErrorKind::InvalidData,
+                    format!("Line {line} is not valid UTF-8"),
+                )
+            })?;
+            lines.push((line, text.trim_end_matches('\r').to_owned()));
+        }
+        self.num_lines += lines.len();
+        Ok(TailRead::Lines(lines))
+    }
+}
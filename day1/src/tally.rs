@@ -0,0 +1,161 @@
+use std::{
+    collections::BTreeMap,
+    ops::Bound::{Excluded, Unbounded},
+};
+
+/// The state of the walk pairing up the entries of both lists in sorted order, just before the
+/// entries of the current values are paired up
+#[derive(Clone, Copy, Debug)]
+struct Checkpoint {
+    /// The current value of the first list, and the number of its entries not yet paired
+    first: (u64, u64),
+    /// The current value of the second list, and the number of its entries not yet paired
+    second: (u64, u64),
+    /// The distance between the entries paired before this point
+    distance: u64,
+}
+
+/// The two lists, kept sorted as they grow by counting the number of times each value appears,
+/// along with the running similarity score and total distance
+pub struct Tally {
+    first_counts: BTreeMap<u64, u64>,
+    second_counts: BTreeMap<u64, u64>,
+    first_length: u64,
+    second_length: u64,
+    similarity: u64,
+    tolerance: u64,
+    /// The walk that paired up the lists for the last total distance, up to the first value
+    /// added since
+    checkpoints: Vec<Checkpoint>,
+    /// The total distance, if nothing has been added since it was last found
+    distance: Option<u64>,
+}
+
+impl Tally {
+    /// Creates the empty lists, where entries within the given distance of each other count as
+    /// matching for the similarity score
+    pub fn new(tolerance: Option<u64>) -> Self {
+        Self {
+            first_counts: BTreeMap::new(),
+            second_counts: BTreeMap::new(),
+            first_length: 0,
+            second_length: 0,
+            similarity: 0,
+            tolerance: tolerance.unwrap_or(0),
+            checkpoints: Vec::new(),
+            distance: Some(0),
+        }
+    }
+
+    /// Gets the values (with counts) in the list that match the given value
+    fn matching(&self, counts: &BTreeMap<u64, u64>, value: u64) -> Vec<(u64, u64)> {
+        let range = value.saturating_sub(self.tolerance)..=value.saturating_add(self.tolerance);
+        counts.range(range).map(|(x, y)| (*x, *y)).collect()
+    }
+
+    /// Adds the value to the first list, adding its score against the second list
+    pub fn add_first(&mut self, value: u64) {
+        let num_matches: u64 = self
+            .matching(&self.second_counts, value)
+            .iter()
+            .map(|(_, count)| count)
+            .sum();
+        self.similarity += value * num_matches;
+        *self.first_counts.entry(value).or_insert(0) += 1;
+        self.first_length += 1;
+
+        // Entries of the first list below the value keep their partners, so only the walk from
+        // the value onward has to be redone
+        let kept = self.checkpoints.partition_point(|x| x.first.0 < value);
+        self.checkpoints.truncate(kept);
+        self.distance = None;
+    }
+
+    /// Adds the value to the second list, adding the score of each entry in the first list that
+    /// now matches it once more
+    pub fn add_second(&mut self, value: u64) {
+        let score: u64 = self
+            .matching(&self.first_counts, value)
+            .iter()
+            .map(|(matched, count)| matched * count)
+            .sum();
+        self.similarity += score;
+        *self.second_counts.entry(value).or_insert(0) += 1;
+        self.second_length += 1;
+
+        // Entries of the second list below the value keep their partners, so only the walk
+        // from the value onward has to be redone
+        let kept = self.checkpoints.partition_point(|x| x.second.0 < value);
+        self.checkpoints.truncate(kept);
+        self.distance = None;
+    }
+
+    /// Gets the number of entries in each of the lists
+    pub fn lengths(&self) -> (u64, u64) {
+        (self.first_length, self.second_length)
+    }
+
+    /// Gets the total distance between the lists, pairing up their entries in sorted order
+    ///
+    /// Entries without a partner in the shorter list are left out until it catches up.  Only
+    /// the part of the pairing changed by the entries added since the last call is walked again.
+    pub fn distance(&mut self) -> u64 {
+        // Use the total distance as is if nothing has been added since it was found
+        if let Some(distance) = self.distance {
+            return distance;
+        }
+
+        // Resume the walk from the last checkpoint still valid, or from the start of the lists
+        let resume = self.checkpoints.pop();
+        let mut first_iter = self
+            .first_counts
+            .range((resume.map_or(Unbounded, |x| Excluded(x.first.0)), Unbounded))
+            .map(|(x, y)| (*x, *y));
+        let mut second_iter = self
+            .second_counts
+            .range((
+                resume.map_or(Unbounded, |x| Excluded(x.second.0)),
+                Unbounded,
+            ))
+            .map(|(x, y)| (*x, *y));
+        let (mut first, mut second, mut distance) = match resume {
+            Some(checkpoint) => (
+                Some(checkpoint.first),
+                Some(checkpoint.second),
+                checkpoint.distance,
+            ),
+            None => (first_iter.next(), second_iter.next(), 0),
+        };
+
+        // Walk both lists in order, pairing up as many entries of the current values as possible
+        while let (Some((value_one, count_one)), Some((value_two, count_two))) = (first, second) {
+            // Record where the walk is, so later additions can resume from here
+            self.checkpoints.push(Checkpoint {
+                first: (value_one, count_one),
+                second: (value_two, count_two),
+                distance,
+            });
+
+            // Pair up the entries, moving on from the values that have none left
+            let num_pairs = count_one.min(count_two);
+            distance += num_pairs * value_one.abs_diff(value_two);
+            first = match count_one - num_pairs {
+                0 => first_iter.next(),
+                remaining => Some((value_one, remaining)),
+            };
+            second = match count_two - num_pairs {
+                0 => second_iter.next(),
+                remaining => Some((value_two, remaining)),
+            };
+        }
+
+        // Keep the total distance until more entries are added, and return it
+        self.distance = Some(distance);
+        distance
+    }
+
+    /// Gets the similarity score of the lists
+    pub fn similarity(&self) -> u64 {
+        self.similarity
+    }
+}
@@ -0,0 +1,83 @@
+use day1::{parse_entry_lists, Entry};
+
+const EXAMPLE: &str = include_str!("../inputs/example.txt");
+
+/// Gets the values of the entries in the list
+fn values(list: &[Entry]) -> Vec<u64> {
+    list.iter().map(|x| x.value).collect()
+}
+
+#[test]
+fn parses_example() {
+    let (first, second) = parse_entry_lists(EXAMPLE, (0, 1), false).unwrap();
+    assert_eq!(values(&first), vec![1, 2, 3, 3, 3, 4]);
+    assert_eq!(values(&second), vec![3, 3, 3, 4, 5, 9]);
+    assert_eq!(first[0], Entry { value: 1, line: 4 });
+}
+
+#[test]
+fn parses_selected_columns() {
+    let input = "7 1 9\n8,2,10\n";
+    let (first, second) = parse_entry_lists(input, (2, 0), false).unwrap();
+    assert_eq!(values(&first), vec![9, 10]);
+    assert_eq!(values(&second), vec![7, 8]);
+}
+
+#[test]
+fn keeps_empty_comma_fields() {
+    let input = "7,,9\n8, ,10\n";
+    let (first, second) = parse_entry_lists(input, (2, 0), false).unwrap();
+    assert_eq!(values(&first), vec![9, 10]);
+    assert_eq!(values(&second), vec![7, 8]);
+}
+
+#[test]
+fn header_line_fails() {
+    let input = "first second\n3 4\n";
+    assert_eq!(
+        parse_entry_lists(input, (0, 1), false),
+        Err(String::from(
+            "Line 1: could not parse \"first\" as a location ID"
+        ))
+    );
+}
+
+#[test]
+fn blank_line_fails() {
+    let input = "3 4\n\n4 3\n";
+    assert_eq!(
+        parse_entry_lists(input, (0, 1), false),
+        Err(String::from("Line 2 is blank"))
+    );
+}
+
+#[test]
+fn short_row_fails() {
+    let input = "3 4 5\n4 3\n";
+    assert_eq!(
+        parse_entry_lists(input, (0, 2), false),
+        Err(String::from("Line 2 has no column 3"))
+    );
+}
+
+#[test]
+fn skips_header_line() {
+    let input = "first,second\n3,4\n4,3\n";
+    let (first, second) = parse_entry_lists(input, (0, 1), true).unwrap();
+    assert_eq!(
+        first,
+        vec![Entry { value: 3, line: 2 }, Entry { value: 4, line: 3 }]
+    );
+    assert_eq!(values(&second), vec![3, 4]);
+}
+
+#[test]
+fn empty_comma_field_fails() {
+    let input = "3,,4\n";
+    assert_eq!(
+        parse_entry_lists(input, (0, 1), false),
+        Err(String::from(
+            "Line 1: could not parse \"\" as a location ID"
+        ))
+    );
+}
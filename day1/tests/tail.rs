@@ -0,0 +1,94 @@
+use std::{fs, io::ErrorKind, io::Write};
+
+use day1::tail::{Tail, TailRead};
+
+/// Appends the bytes to the file
+fn append(path: &std::path::Path, bytes: &[u8]) {
+    let mut file = fs::OpenOptions::new().append(true).open(path).unwrap();
+    file.write_all(bytes).unwrap();
+}
+
+/// Reads the new lines from the file, which must not have been truncated
+fn lines(tail: &mut Tail) -> Vec<(usize, String)> {
+    match tail.read_lines().unwrap() {
+        TailRead::Lines(lines) => lines,
+        TailRead::Truncated => panic!("The file was truncated"),
+    }
+}
+
+/// Gets the lines with their line numbers
+fn numbered(lines: &[(usize, &str)]) -> Vec<(usize, String)> {
+    lines.iter().map(|(x, y)| (*x, y.to_string())).collect()
+}
+
+#[test]
+fn reads_appended_lines() {
+    let directory = tempfile::tempdir().unwrap();
+    let path = directory.path().join("lists.txt");
+    fs::write(&path, "3   4\n4   3\n").unwrap();
+    let mut tail = Tail::new(path.to_str().unwrap());
+    assert_eq!(lines(&mut tail), numbered(&[(1, "3   4"), (2, "4   3")]));
+    assert!(lines(&mut tail).is_empty());
+
+    // Blank lines still count towards the line numbers
+    append(&path, b"\n2   5\n");
+    assert_eq!(lines(&mut tail), numbered(&[(3, ""), (4, "2   5")]));
+}
+
+#[test]
+fn keeps_partial_line() {
+    let directory = tempfile::tempdir().unwrap();
+    let path = directory.path().join("lists.txt");
+    fs::write(&path, "3   4\n1   ").unwrap();
+    let mut tail = Tail::new(path.to_str().unwrap());
+    assert_eq!(lines(&mut tail), numbered(&[(1, "3   4")]));
+    append(&path, b"3\r\n");
+    assert_eq!(lines(&mut tail), numbered(&[(2, "1   3")]));
+}
+
+#[test]
+fn keeps_partial_character() {
+    // The line ends partway through the two bytes of the 'é'
+    let directory = tempfile::tempdir().unwrap();
+    let path = directory.path().join("lists.txt");
+    let text = "1 café\n";
+    let split = text.find('é').unwrap() + 1;
+    fs::write(&path, &text.as_bytes()[..split]).unwrap();
+    let mut tail = Tail::new(path.to_str().unwrap());
+    assert!(lines(&mut tail).is_empty());
+    append(&path, &text.as_bytes()[split..]);
+    assert_eq!(lines(&mut tail), numbered(&[(1, "1 café")]));
+}
+
+#[test]
+fn rejects_invalid_line() {
+    let directory = tempfile::tempdir().unwrap();
+    let path = directory.path().join("lists.txt");
+    fs::write(&path, b"1 2\n3 \xff\n").unwrap();
+    let mut tail = Tail::new(path.to_str().unwrap());
+    let error = tail.read_lines().unwrap_err();
+    assert_eq!(error.kind(), ErrorKind::InvalidData);
+    assert_eq!(error.to_string(), "Line 2 is not valid UTF-8");
+}
+
+#[test]
+fn starts_over_after_truncation() {
+    let directory = tempfile::tempdir().unwrap();
+    let path = directory.path().join("lists.txt");
+    fs::write(&path, "3   4\n4   3\n").unwrap();
+    let mut tail = Tail::new(path.to_str().unwrap());
+    assert_eq!(lines(&mut tail).len(), 2);
+
+    // The file is replaced by a shorter one
+    fs::write(&path, "9 9\n").unwrap();
+    assert_eq!(tail.read_lines().unwrap(), TailRead::Truncated);
+    assert_eq!(lines(&mut tail), numbered(&[(1, "9 9")]));
+}
+
+#[test]
+fn missing_file_fails() {
+    let directory = tempfile::tempdir().unwrap();
+    let path = directory.path().join("missing.txt");
+    let mut tail = Tail::new(path.to_str().unwrap());
+    assert_eq!(tail.read_lines().unwrap_err().kind(), ErrorKind::NotFound);
+}
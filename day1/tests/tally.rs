@@ -0,0 +1,130 @@
+use day1::tally::Tally;
+
+const EXAMPLE: &str = include_str!("../inputs/example.txt");
+
+/// Gets the total distance between the lists by sorting them and pairing up their entries,
+/// leaving out the entries without a partner in the shorter list
+fn batch_distance(first: &[u64], second: &[u64]) -> u64 {
+    let mut first = first.to_vec();
+    let mut second = second.to_vec();
+    first.sort();
+    second.sort();
+    first.iter().zip(&second).map(|(x, y)| x.abs_diff(*y)).sum()
+}
+
+/// Gets the similarity score by counting the entries in the second list within the tolerance
+/// of each entry in the first list
+fn batch_similarity(first: &[u64], second: &[u64], tolerance: u64) -> u64 {
+    first
+        .iter()
+        .map(|x| {
+            x * second
+                .iter()
+                .filter(|y| x.abs_diff(**y) <= tolerance)
+                .count() as u64
+        })
+        .sum()
+}
+
+/// Gets a sequence of pseudo-random values below the given bound
+fn values(seed: u64, count: usize, bound: u64) -> Vec<u64> {
+    let mut state = seed;
+    (0..count)
+        .map(|_| {
+            state = state
+                .wrapping_mul(6364136223846793005)
+                .wrapping_add(1442695040888963407);
+            (state >> 33) % bound
+        })
+        .collect()
+}
+
+#[test]
+fn example_scores() {
+    let mut tally = Tally::new(None);
+    for line in EXAMPLE.lines() {
+        let mut fields = line.split_whitespace().map(|x| x.parse().unwrap());
+        tally.add_first(fields.next().unwrap());
+        tally.add_second(fields.next().unwrap());
+    }
+    assert_eq!(tally.lengths(), (6, 6));
+    assert_eq!(tally.distance(), 11);
+    assert_eq!(tally.similarity(), 31);
+}
+
+#[test]
+fn empty_lists() {
+    let mut tally = Tally::new(None);
+    assert_eq!(tally.lengths(), (0, 0));
+    assert_eq!(tally.distance(), 0);
+    assert_eq!(tally.similarity(), 0);
+}
+
+#[test]
+fn unpaired_entries_are_left_out() {
+    let mut tally = Tally::new(None);
+    tally.add_first(5);
+    tally.add_first(1);
+    assert_eq!(tally.distance(), 0);
+    tally.add_second(3);
+    assert_eq!(tally.distance(), 2);
+    tally.add_second(10);
+    assert_eq!(tally.distance(), 7);
+}
+
+#[test]
+fn incremental_matches_batch() {
+    // Add the entries in an uneven order, checking the scores after every addition
+    for seed in 0..10 {
+        let additions = values(seed, 200, 50);
+        let sides = values(seed + 100, 200, 3);
+        let mut tally = Tally::new(None);
+        let mut first = Vec::new();
+        let mut second = Vec::new();
+        for (value, side) in additions.iter().zip(sides) {
+            if side == 0 {
+                tally.add_first(*value);
+                first.push(*value);
+            } else {
+                tally.add_second(*value);
+                second.push(*value);
+            }
+            assert_eq!(tally.distance(), batch_distance(&first, &second));
+            assert_eq!(tally.similarity(), batch_similarity(&first, &second, 0));
+        }
+        assert_eq!(tally.lengths(), (first.len() as u64, second.len() as u64));
+    }
+}
+
+#[test]
+fn distance_after_batches_of_additions() {
+    // Several additions between each check resume from the earliest change
+    let first_values = values(7, 120, 1000);
+    let second_values = values(8, 120, 1000);
+    let mut tally = Tally::new(None);
+    for end in (10..=120).step_by(10) {
+        for index in end - 10..end {
+            tally.add_first(first_values[index]);
+            tally.add_second(second_values[index]);
+        }
+        assert_eq!(
+            tally.distance(),
+            batch_distance(&first_values[..end], &second_values[..end])
+        );
+    }
+}
+
+#[test]
+fn tolerance_matches_batch() {
+    let first_values = values(1, 60, 30);
+    let second_values = values(2, 60, 30);
+    let mut tally = Tally::new(Some(2));
+    for (first, second) in first_values.iter().zip(&second_values) {
+        tally.add_first(*first);
+        tally.add_second(*second);
+    }
+    assert_eq!(
+        tally.similarity(),
+        batch_similarity(&first_values, &second_values, 2)
+    );
+}
@@ -0,0 +1,61 @@
+use day1::{count_within, frequencies, similarity_score};
+
+const EXAMPLE: &str = include_str!("../inputs/example.txt");
+
+/// Parses the two sorted lists from the input
+fn lists(input: &str) -> (Vec<u64>, Vec<u64>) {
+    let (mut first_list, mut second_list): (Vec<u64>, Vec<u64>) = input
+        .lines()
+        .map(|line| {
+            let mut fields = line.split_whitespace().map(|x| x.parse::<u64>().unwrap());
+            (fields.next().unwrap(), fields.next().unwrap())
+        })
+        .unzip();
+    first_list.sort();
+    second_list.sort();
+    (first_list, second_list)
+}
+
+#[test]
+fn counts_within_tolerance() {
+    let sorted_list = [1, 3, 3, 4, 7, 10];
+    assert_eq!(count_within(&sorted_list, 3, 0), 2);
+    assert_eq!(count_within(&sorted_list, 3, 1), 3);
+    assert_eq!(count_within(&sorted_list, 5, 2), 4);
+    assert_eq!(count_within(&sorted_list, 20, 5), 0);
+    assert_eq!(count_within(&[], 3, 1), 0);
+}
+
+#[test]
+fn tolerance_saturates_at_bounds() {
+    let sorted_list = [0, 1, u64::MAX - 1, u64::MAX];
+    assert_eq!(count_within(&sorted_list, 0, 1), 2);
+    assert_eq!(count_within(&sorted_list, u64::MAX, 1), 2);
+    assert_eq!(count_within(&sorted_list, 0, u64::MAX), 4);
+}
+
+#[test]
+fn zero_tolerance_matches_exact() {
+    let (first_list, second_list) = lists(EXAMPLE);
+    assert_eq!(similarity_score(&first_list, &second_list, None), 31);
+    assert_eq!(similarity_score(&first_list, &second_list, Some(0)), 31);
+}
+
+#[test]
+fn example_similarity_with_tolerance() {
+    // With a tolerance of 1, 1 matches no entries, 2 matches 3, each 3 matches 4 and 4 matches 5
+    let (first_list, second_list) = lists(EXAMPLE);
+    assert_eq!(
+        similarity_score(&first_list, &second_list, Some(1)),
+        2 * 3 + 3 * 4 * 3 + 4 * 5
+    );
+}
+
+#[test]
+fn counts_frequencies() {
+    let counts = frequencies(&[3, 4, 3, 9, 3]);
+    assert_eq!(counts.len(), 3);
+    assert_eq!(counts[&3], 3);
+    assert_eq!(counts[&4], 1);
+    assert_eq!(counts[&9], 1);
+}
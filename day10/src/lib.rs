@@ -0,0 +1,527 @@
+use std::collections::{HashMap, HashSet};
+
+use clap::ValueEnum;
+use grid::Grid;
+use rand::Rng;
+
+/// Directions in which trails can be searched
+#[derive(Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum Direction {
+    /// Search upward from each trailhead to the summits
+    Up,
+    /// Search downward from each summit to the trailheads
+    Down,
+    /// Search from whichever of the trailheads or summits are fewer
+    Auto,
+}
+
+/// Representation of an X, Y coordinate pair
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq)]
+pub struct Coordinate {
+    pub x: i64,
+    pub y: i64,
+}
+
+impl From<(i64, i64)> for Coordinate {
+    fn from(value: (i64, i64)) -> Self {
+        Self {
+            x: value.0,
+            y: value.1,
+        }
+    }
+}
+
+impl From<Coordinate> for (i64, i64) {
+    fn from(value: Coordinate) -> Self {
+        (value.x, value.y)
+    }
+}
+
+/// Representation of a location on the topography map, with coordinate and level
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq)]
+pub struct Location {
+    pub coord: Coordinate,
+    pub level: u8,
+}
+
+/// The number of locations at a level of the map, how many of them lie on a complete trail,
+/// and the number of complete trails passing through them
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct LevelCounts {
+    pub locations: usize,
+    pub on_trail: usize,
+    pub trails: u64,
+}
+
+/// Representation of the game map
+pub struct GameMap {
+    pub spaces: Grid<Location>,
+}
+
+impl GameMap {
+    // Creates a new map from the given rows, failing if they differ in length
+    pub fn new(spaces: Vec<Vec<Location>>) -> Result<Self, String> {
+        Ok(Self {
+            spaces: Grid::from_rows(spaces)?,
+        })
+    }
+
+    // Parses the map from the provided string, failing if a level isn't a digit or the rows
+    // differ in length
+    pub fn parse(value: &str) -> Result<Self, String> {
+        // Create a list for storing rows
+        let mut rows = Vec::new();
+
+        // Iterate through the string line by line
+        for (row_index, line) in value.trim().lines().enumerate() {
+            // Create a list for storing entries
+            let mut row = Vec::new();
+
+            // Iterate through the line character by character
+            for (col_index, character) in line.chars().enumerate() {
+                // Create the coordinate for the given position
+                let coord = Coordinate {
+                    x: col_index as i64,
+                    y: row_index as i64,
+                };
+
+                // Parse the topography of the location
+                let level = character.to_digit(10).ok_or_else(|| {
+                    let line_number = row_index + 1;
+                    format!("Line {line_number}: could not parse {character:?} as a level")
+                })?;
+
+                // Add the location (with topography) to the row
+                row.push(Location {
+                    coord,
+                    level: level as u8,
+                });
+            }
+
+            // Add the row to the map
+            rows.push(row);
+        }
+
+        // Return a new map with the given rows
+        Self::new(rows)
+    }
+
+    /// Get the location at a given X, Y coordinate
+    ///
+    /// Returns the requested location if valid, or None if it's
+    /// outside the bounds of the map
+    pub fn get(&self, coord: &Coordinate) -> Option<&Location> {
+        self.spaces.get_signed(coord.x, coord.y)
+    }
+
+    /// Gets the valid neighboring squares in the cardinal directions
+    pub fn neighbors(&self, coord: &Coordinate) -> Vec<&Location> {
+        // Create a list to store the neighboring locations
+        let mut neighbors = Vec::new();
+
+        // Shorthands for x and y
+        let x = coord.x;
+        let y = coord.y;
+
+        // Get the coordinates at the cardinal directions
+        let north = Coordinate::from((x, y + 1));
+        let east = Coordinate::from((x + 1, y));
+        let south = Coordinate::from((x, y - 1));
+        let west = Coordinate::from((x - 1, y));
+
+        // Add the coordinates to the list of neighbors
+        neighbors.push(self.get(&north));
+        neighbors.push(self.get(&east));
+        neighbors.push(self.get(&south));
+        neighbors.push(self.get(&west));
+
+        // Filter out invalid neighboring coordinates
+        neighbors.iter().filter_map(|x| *x).collect()
+    }
+
+    /// Gets neighboring locations that are a single step up from the given location
+    pub fn up_from(&self, loc: &Location) -> Vec<&Location> {
+        self.neighbors(&loc.coord)
+            .iter()
+            .copied()
+            .filter(|x| x.level == loc.level + 1)
+            .collect()
+    }
+
+    /// Gets neighboring locations that are a single step down from the given location
+    pub fn down_from(&self, loc: &Location) -> Vec<&Location> {
+        self.neighbors(&loc.coord)
+            .iter()
+            .copied()
+            .filter(|x| x.level + 1 == loc.level)
+            .collect()
+    }
+
+    /// Gets the trail ratings for a given start location
+    pub fn find_complete_trails(&self, path: &[Location]) -> HashSet<Vec<Location>> {
+        // Create a set of trails for the given start location
+        let mut trails = HashSet::new();
+
+        // If the first element isn't a 0, short circuit return the empty trail list
+        if path.first().expect("Path is empty").level != 0 {
+            return trails;
+        }
+
+        // Get the last location in the provided path
+        let loc = path.last().expect("Path is empty");
+
+        // Convert that path to a mutable list for try to find a complete trail
+        let mut trial_path = Vec::from(path);
+
+        // If the location is level 9, it's the end of the trail
+        if loc.level == 9 {
+            // Add the final point to the trail list
+            trial_path.push(*loc);
+
+            // Insert the final trail into the set of trails (only entry) and return it
+            trails.insert(trial_path);
+            return trails;
+        }
+
+        // Iterate through all valid next steps up from the current location
+        for next_step in self.up_from(loc) {
+            // Add the next step up to the trail list
+            trial_path.push(*next_step);
+
+            // Recursively search for completed paths and add them to the complete set of trails
+            let additional_ends = self.find_complete_trails(&trial_path);
+            trails.extend(additional_ends);
+        }
+
+        // Return the list of completed trails
+        trails
+    }
+
+    /// Gets all of the locations with the given level, in row-major order
+    pub fn locations_at_level(&self, level: u8) -> Vec<Location> {
+        self.spaces
+            .iter()
+            .filter(|x| x.level == level)
+            .copied()
+            .collect()
+    }
+
+    /// Counts the number of distinct paths from the given trailhead to each summit reachable
+    /// from it, stepping up one level at a time and summing the path counts of the locations
+    /// on the level below
+    pub fn count_paths_to_summits(&self, trailhead: &Location) -> HashMap<Location, u64> {
+        // Start with the single path consisting of just the trailhead
+        let mut counts = HashMap::from([(*trailhead, 1)]);
+
+        // Carry the path counts up from each level to the next one
+        for _level in trailhead.level..9 {
+            let mut next_counts = HashMap::new();
+            for (loc, count) in &counts {
+                for next_step in self.up_from(loc) {
+                    *next_counts.entry(*next_step).or_insert(0) += count;
+                }
+            }
+            counts = next_counts;
+        }
+
+        // Return the path counts for the summits
+        counts
+    }
+
+    /// Finds the number of steps in the shortest complete trail from the given trailhead, or
+    /// None if no summit can be reached from it
+    ///
+    /// The search is breadth first, so each layer of locations reached holds those at the same
+    /// number of steps (and so the same level) from the trailhead.
+    pub fn shortest_trail_length(&self, trailhead: &Location) -> Option<u64> {
+        // Start with just the trailhead, which is zero steps away
+        let mut frontier = HashSet::from([*trailhead]);
+        let mut steps = 0;
+
+        // Expand the layers of locations reached until one of them is a summit
+        while !frontier.is_empty() {
+            if frontier.iter().any(|x| x.level == 9) {
+                return Some(steps);
+            }
+            frontier = frontier
+                .iter()
+                .flat_map(|x| self.up_from(x))
+                .copied()
+                .collect();
+            steps += 1;
+        }
+
+        // The trails from the trailhead never reach a summit
+        None
+    }
+
+    /// Counts the number of distinct paths from the given summit down to each trailhead
+    /// reachable from it, stepping down one level at a time and summing the path counts of
+    /// the locations on the level above
+    pub fn count_paths_to_trailheads(&self, summit: &Location) -> HashMap<Location, u64> {
+        // Start with the single path consisting of just the summit
+        let mut counts = HashMap::from([(*summit, 1)]);
+
+        // Carry the path counts down from each level to the next one
+        for _level in 0..summit.level {
+            let mut next_counts = HashMap::new();
+            for (loc, count) in &counts {
+                for next_step in self.down_from(loc) {
+                    *next_counts.entry(*next_step).or_insert(0) += count;
+                }
+            }
+            counts = next_counts;
+        }
+
+        // Return the path counts for the trailheads
+        counts
+    }
+
+    /// Counts the number of distinct paths up from any trailhead to each location, carrying the
+    /// path counts up one level at a time
+    pub fn count_paths_from_all_trailheads(&self) -> HashMap<Location, u64> {
+        // Start with the single path consisting of just each trailhead
+        let mut counts: HashMap<Location, u64> = HashMap::new();
+        for trailhead in self.locations_at_level(0) {
+            counts.insert(trailhead, 1);
+        }
+
+        // Carry the path counts up from each level to the next one, keeping every level
+        for level in 0..9 {
+            for loc in self.locations_at_level(level) {
+                let count = counts.get(&loc).copied().unwrap_or(0);
+                if count == 0 {
+                    continue;
+                }
+                for next_step in self.up_from(&loc) {
+                    *counts.entry(*next_step).or_insert(0) += count;
+                }
+            }
+        }
+
+        // Return the path counts for every location reached
+        counts
+    }
+
+    /// Counts the number of distinct paths down from any summit to each location, carrying
+    /// the path counts down one level at a time
+    pub fn count_paths_from_all_summits(&self) -> HashMap<Location, u64> {
+        // Start with the single path consisting of just each summit
+        let mut counts: HashMap<Location, u64> = HashMap::new();
+        for summit in self.locations_at_level(9) {
+            counts.insert(summit, 1);
+        }
+
+        // Carry the path counts down from each level to the next one, keeping every level
+        for level in (1..=9).rev() {
+            for loc in self.locations_at_level(level) {
+                let count = counts.get(&loc).copied().unwrap_or(0);
+                if count == 0 {
+                    continue;
+                }
+                for next_step in self.down_from(&loc) {
+                    *counts.entry(*next_step).or_insert(0) += count;
+                }
+            }
+        }
+
+        // Return the path counts for every location reached
+        counts
+    }
+
+    /// Counts the locations at each level, how many of them lie on a complete trail, and the
+    /// number of complete trails passing through them, indexed by level
+    ///
+    /// Every complete trail passes through each level exactly once, so the number of trails is
+    /// the same for every level, and matches the total rating.
+    pub fn level_histogram(&self) -> Vec<LevelCounts> {
+        // Get the number of paths reaching each location from below and from above
+        let from_trailheads = self.count_paths_from_all_trailheads();
+        let from_summits = self.count_paths_from_all_summits();
+
+        // Count the statistics for each level
+        let mut histogram = Vec::new();
+        for level in 0..=9 {
+            let locations = self.locations_at_level(level);
+            let mut counts = LevelCounts {
+                locations: locations.len(),
+                ..LevelCounts::default()
+            };
+            for loc in &locations {
+                // The trails through a location combine each path up to it with each path
+                // above it
+                let paths_below = from_trailheads.get(loc).copied().unwrap_or(0);
+                let paths_above = from_summits.get(loc).copied().unwrap_or(0);
+                let trails = paths_below * paths_above;
+                if trails > 0 {
+                    counts.on_trail += 1;
+                }
+                counts.trails += trails;
+            }
+            histogram.push(counts);
+        }
+
+        // Return the statistics for every level
+        histogram
+    }
+
+    /// Samples a complete trail uniformly at random, given the number of paths down from any
+    /// summit to each location, or None if there are no complete trails
+    ///
+    /// The trailhead is picked with a chance proportional to the number of trails starting from
+    /// it, and each step up is picked with a chance proportional to the number of trails
+    /// continuing from it, so every complete trail is equally likely.
+    pub fn sample_trail<R: Rng>(
+        &self,
+        from_summits: &HashMap<Location, u64>,
+        rng: &mut R,
+    ) -> Option<Vec<Location>> {
+        // Start from a trailhead, and keep stepping up until reaching a summit
+        let mut trail = Vec::new();
+        let mut choices = self.locations_at_level(0);
+        while !choices.is_empty() {
+            // Pick one of the choices, weighted by the number of trails continuing from each
+            let total: u64 = choices
+                .iter()
+                .map(|x| from_summits.get(x).copied().unwrap_or(0))
+                .sum();
+            if total == 0 {
+                return None;
+            }
+            let mut pick = rng.gen_range(0..total);
+            let next_step = *choices
+                .iter()
+                .find(|x| {
+                    let count = from_summits.get(x).copied().unwrap_or(0);
+                    if pick < count {
+                        return true;
+                    }
+                    pick -= count;
+                    false
+                })
+                .expect("Pick is within the total");
+
+            // Add the step to the trail, and get the next steps up from it
+            trail.push(next_step);
+            choices = self.up_from(&next_step).into_iter().copied().collect();
+        }
+
+        // Return the completed trail
+        Some(trail)
+    }
+
+    /// Resolves the direction in which to search, choosing to search from whichever of the
+    /// trailheads or summits are fewer when automatic
+    pub fn resolve_direction(&self, direction: Direction) -> Direction {
+        match direction {
+            Direction::Auto => {
+                let num_trailheads = self.locations_at_level(0).len();
+                let num_summits = self.locations_at_level(9).len();
+                if num_summits < num_trailheads {
+                    Direction::Down
+                } else {
+                    Direction::Up
+                }
+            }
+            _ => direction,
+        }
+    }
+
+    // Get all of the trails, grouped by start location
+    pub fn get_trails(&self) -> HashMap<Location, HashSet<Vec<Location>>> {
+        // Create a hash map for storing trails
+        let mut trails = HashMap::new();
+
+        // Iterate through the map point by point
+        for row in self.spaces.rows() {
+            for loc in row {
+                // If the point isn't a start location (0), skip it
+                if loc.level != 0 {
+                    continue;
+                }
+
+                // Get the set of complete trails from this point
+                let complete_trails = self.find_complete_trails(&[*loc]);
+
+                // Insert the set of trails into the hash map
+                trails.insert(*loc, complete_trails);
+            }
+        }
+
+        // Return the completed hash map of trails
+        trails
+    }
+}
+
+// Convert trail ratings into trail scores
+pub fn convert_ratings_to_scores(
+    ratings: HashMap<Location, HashSet<Vec<Location>>>,
+) -> HashMap<Location, HashSet<Location>> {
+    // Create a map of scores per trail start location
+    let mut scores = HashMap::new();
+
+    // Iterate through the ratings
+    for (key, trails) in ratings {
+        // Create a new set of trail endpoints for each start location
+        let mut ends: HashSet<Location> = HashSet::new();
+
+        // For each trail in the ratings (completed trail), insert the endpoint to the running set
+        for trail in trails {
+            ends.insert(*trail.last().expect("Path is empty"));
+        }
+
+        // Insert the set of trail endpoints into the score hash map
+        scores.insert(key, ends);
+    }
+
+    // Return the hash map of scores
+    scores
+}
+
+/// Samples the given number of distinct complete trails uniformly at random, returning them
+/// along with the total number of trails
+///
+/// If there are fewer trails than requested, all of them are returned.
+pub fn sample_trails<R: Rng>(
+    map: &GameMap,
+    num_samples: usize,
+    rng: &mut R,
+) -> (Vec<Vec<Location>>, u64) {
+    // Get the number of paths down from any summit to each location, which gives the number of
+    // trails starting from each trailhead
+    let from_summits = map.count_paths_from_all_summits();
+    let num_trails: u64 = map
+        .locations_at_level(0)
+        .iter()
+        .map(|x| from_summits.get(x).copied().unwrap_or(0))
+        .sum();
+
+    // Sample trails until there are enough distinct ones, or every trail has been sampled
+    let num_samples = u64::try_from(num_samples).map_or(num_trails, |x| x.min(num_trails));
+    let mut seen = HashSet::new();
+    let mut samples = Vec::new();
+    while (samples.len() as u64) < num_samples {
+        let trail = map
+            .sample_trail(&from_summits, rng)
+            .expect("There are complete trails to sample");
+        if seen.insert(trail.clone()) {
+            samples.push(trail);
+        }
+    }
+
+    // Return the sampled trails and the total number
+    (samples, num_trails)
+}
+
+/// Solves part one for the puzzle input, getting the sum of the scores of the trailheads
+pub fn part1(input: &str) -> Result<usize, String> {
+    let map = GameMap::parse(input)?;
+    let scores = convert_ratings_to_scores(map.get_trails());
+    Ok(scores.values().map(|x| x.len()).sum())
+}
+
+/// Solves part two for the puzzle input, getting the sum of the ratings of the trailheads
+pub fn part2(input: &str) -> Result<usize, String> {
+    let map = GameMap::parse(input)?;
+    Ok(map.get_trails().values().map(|x| x.len()).sum())
+}
@@ -1,224 +1,115 @@
-use std::{
-    collections::{HashMap, HashSet},
-    fs,
-};
+use std::fs;
 
 use clap::Parser;
+use common::{fail, read_input, About, Failure, Format};
+use day10::{convert_ratings_to_scores, sample_trails, Direction, GameMap};
+use rand::{rngs::StdRng, SeedableRng};
 
 #[derive(Parser)]
 struct CliArgs {
     part: u64,
     filepath: String,
+    /// Write the number of paths from each trailhead to each summit to the given CSV file
+    #[arg(long)]
+    matrix: Option<String>,
+    /// The direction in which trails are searched
+    #[arg(long, value_enum, default_value_t = Direction::Auto)]
+    direction: Direction,
+    /// Print the number of locations at each level, how many of them lie on a complete trail,
+    /// and the number of complete trails passing through the level, before the answer
+    #[arg(long)]
+    histogram: bool,
+    /// Print the score, rating, and length of the shortest complete trail in steps for each
+    /// trailhead, before the answer
+    #[arg(long)]
+    trailheads: bool,
+    /// Print the given number of distinct complete trails, sampled uniformly at random, followed
+    /// by the total number of trails instead of solving a part
+    #[arg(long)]
+    sample: Option<usize>,
+    /// The seed used for sampling trails, which is random if not given
+    #[arg(long, requires = "sample")]
+    seed: Option<u64>,
+    /// Parse the input and print statistics about its structure instead of solving it
+    #[arg(long)]
+    parse_only: bool,
+    /// The format in which errors are reported
+    #[arg(long, value_enum, default_value_t = Format::Text)]
+    format: Format,
 }
 
-/// Representation of an X, Y coordinate pair
-#[derive(Clone, Copy, Hash, PartialEq, Eq)]
-struct Coordinate {
-    x: i64,
-    y: i64,
-}
-
-impl From<(i64, i64)> for Coordinate {
-    fn from(value: (i64, i64)) -> Self {
-        Self {
-            x: value.0,
-            y: value.1,
-        }
-    }
-}
-
-impl From<Coordinate> for (i64, i64) {
-    fn from(value: Coordinate) -> Self {
-        (value.x, value.y)
-    }
-}
-
-/// Representation of a location on the topography map, with coordinate and level
-#[derive(Clone, Copy, Hash, PartialEq, Eq)]
-struct Location {
-    coord: Coordinate,
-    level: u8,
-}
-
-/// Representation of the game map
-struct GameMap {
-    spaces: Vec<Vec<Location>>,
-}
-
-impl GameMap {
-    // Creates a new blank map
-    fn new(spaces: Vec<Vec<Location>>) -> Self {
-        Self { spaces }
-    }
-
-    // Parses the map from the provided string
-    fn parse(value: &str) -> Self {
-        // Create a list for storing rows
-        let mut rows = Vec::new();
-
-        // Iterate through the string line by line
-        for (row_index, line) in value.trim().lines().enumerate() {
-            // Create a list for storing entries
-            let mut row = Vec::new();
-
-            // Iterate through the line character by character
-            for (col_index, character) in line.chars().enumerate() {
-                // Create the coordinate for the given position
-                let coord = Coordinate {
-                    x: col_index as i64,
-                    y: row_index as i64,
-                };
-
-                // Add the location (with topography) to the row
-                row.push(Location {
-                    coord,
-                    level: character.to_digit(10).expect("Could not parse level") as u8,
-                });
-            }
-
-            // Add the row to the map
-            rows.push(row);
-        }
+/// Description of the puzzle solved by this binary
+const ABOUT: About = About {
+    day: 10,
+    title: "Hoof It",
+    input: "A topographic map of heights from 0 to 9",
+    parts: &[1, 2],
+    features: &[],
+};
 
-        // Return a new map with the given rows
-        Self::new(rows)
-    }
+/// Main entry function
+fn main() {
+    // Describe the puzzle instead if requested
+    common::handle_about::<CliArgs>(&ABOUT);
 
-    /// Get the location at a given X, Y coordinate
-    ///
-    /// Returns the requested location if valid, or None if it's
-    /// outside the bounds of the map
-    fn get(&self, coord: &Coordinate) -> Option<&Location> {
-        if coord.x < 0 || coord.y < 0 {
-            return None;
-        }
+    // Parse CLI arguments
+    let cli = CliArgs::parse();
 
-        match self.spaces.get(coord.y as usize) {
-            Some(row) => row.get(coord.x as usize),
-            None => None,
-        }
-    }
+    // Report errors in the requested format
+    common::init(cli.format);
 
-    /// Gets the valid neighboring squares in the cardinal directions
-    fn neighbors(&self, coord: &Coordinate) -> Vec<&Location> {
-        // Create a list to store the neighboring locations
-        let mut neighbors = Vec::new();
-
-        // Shorthands for x and y
-        let x = coord.x;
-        let y = coord.y;
-
-        // Get the coordinates at the cardinal directions
-        let north = Coordinate::from((x, y + 1));
-        let east = Coordinate::from((x + 1, y));
-        let south = Coordinate::from((x, y - 1));
-        let west = Coordinate::from((x - 1, y));
-
-        // Add the coordinates to the list of neighbors
-        neighbors.push(self.get(&north));
-        neighbors.push(self.get(&east));
-        neighbors.push(self.get(&south));
-        neighbors.push(self.get(&west));
-
-        // Filter out invalid neighboring coordinates
-        neighbors.iter().filter_map(|x| *x).collect()
+    // Only parse the input if requested
+    if cli.parse_only {
+        report_structure(&cli.filepath);
     }
 
-    /// Gets neighboring locations that are a single step up from the given location
-    fn up_from(&self, loc: &Location) -> Vec<&Location> {
-        self.neighbors(&loc.coord)
-            .iter()
-            .copied()
-            .filter(|x| x.level == loc.level + 1)
-            .collect()
+    // Print the histogram of the levels if requested
+    if cli.histogram {
+        print_histogram(cli.filepath.clone());
     }
 
-    /// Gets the trail ratings for a given start location
-    fn find_complete_trails(&self, path: &[Location]) -> HashSet<Vec<Location>> {
-        // Create a set of trails for the given start location
-        let mut trails = HashSet::new();
-
-        // If the first element isn't a 0, short circuit return the empty trail list
-        if path.first().expect("Path is empty").level != 0 {
-            return trails;
-        }
-
-        // Get the last location in the provided path
-        let loc = path.last().expect("Path is empty");
-
-        // Convert that path to a mutable list for try to find a complete trail
-        let mut trial_path = Vec::from(path);
-
-        // If the location is level 9, it's the end of the trail
-        if loc.level == 9 {
-            // Add the final point to the trail list
-            trial_path.push(*loc);
-
-            // Insert the final trail into the set of trails (only entry) and return it
-            trails.insert(trial_path);
-            return trails;
-        }
-
-        // Iterate through all valid next steps up from the current location
-        for next_step in self.up_from(loc) {
-            // Add the next step up to the trail list
-            trial_path.push(*next_step);
-
-            // Recursively search for completed paths and add them to the complete set of trails
-            let additional_ends = self.find_complete_trails(&trial_path);
-            trails.extend(additional_ends);
-        }
-
-        // Return the list of completed trails
-        trails
+    // Print the analysis of each trailhead if requested
+    if cli.trailheads {
+        print_trailheads(cli.filepath.clone());
     }
 
-    // Get all of the trails, grouped by start location
-    fn get_trails(&self) -> HashMap<Location, HashSet<Vec<Location>>> {
-        // Create a hash map for storing trails
-        let mut trails = HashMap::new();
-
-        // Iterate through the map point by point
-        for row in &self.spaces {
-            for loc in row {
-                // If the point isn't a start location (0), skip it
-                if loc.level != 0 {
-                    continue;
-                }
-
-                // Get the set of complete trails from this point
-                let complete_trails = self.find_complete_trails(&[*loc]);
-
-                // Insert the set of trails into the hash map
-                trails.insert(*loc, complete_trails);
-            }
-        }
-
-        // Return the completed hash map of trails
-        trails
+    // Sample the trails instead if requested
+    if let Some(num_samples) = cli.sample {
+        print_samples(cli.filepath, num_samples, cli.seed);
+        return;
     }
-}
-
-/// Main entry function
-fn main() {
-    // Parse CLI arguments
-    let cli = CliArgs::parse();
 
     // Run the code for the desired challenge part
     match cli.part {
-        1 => main_part_one(cli.filepath),
-        2 => main_part_two(cli.filepath),
+        1 => main_part_one(cli.filepath.clone(), cli.direction),
+        2 => main_part_two(cli.filepath.clone(), cli.direction),
         _ => panic!("Invalid selection part selection!"),
     }
+
+    // Export the trailhead to summit path counts if requested
+    if let Some(matrix_filepath) = cli.matrix {
+        write_matrix(cli.filepath, matrix_filepath);
+    }
 }
 
 /// Runs part one
-fn main_part_one(filepath: String) {
+fn main_part_one(filepath: String, direction: Direction) {
     // Get the trail ratings
-    let contents = fs::read_to_string(filepath).expect("Invalid filepath");
+    let contents = read_input(filepath);
 
     // Create the game map from the file contents
-    let map = GameMap::parse(&contents);
+    let map = read_map(&contents);
+
+    // If searching down, count the trailheads reachable from each summit instead
+    if map.resolve_direction(direction) == Direction::Down {
+        let total_score: usize = map
+            .locations_at_level(9)
+            .iter()
+            .map(|summit| map.count_paths_to_trailheads(summit).len())
+            .sum();
+        println!("{total_score}");
+        return;
+    }
 
     // Calculate the scores for the map
     let ratings = map.get_trails();
@@ -231,12 +122,23 @@ fn main_part_one(filepath: String) {
 }
 
 /// Runs part one
-fn main_part_two(filepath: String) {
+fn main_part_two(filepath: String, direction: Direction) {
     // Get the trail ratings
-    let contents = fs::read_to_string(filepath).expect("Invalid filepath");
+    let contents = read_input(filepath);
 
     // Create the game map from the file contents
-    let map = GameMap::parse(&contents);
+    let map = read_map(&contents);
+
+    // If searching down, count the paths to the trailheads from each summit instead
+    if map.resolve_direction(direction) == Direction::Down {
+        let total_ratings: u64 = map
+            .locations_at_level(9)
+            .iter()
+            .map(|summit| map.count_paths_to_trailheads(summit).values().sum::<u64>())
+            .sum();
+        println!("{total_ratings}");
+        return;
+    }
 
     // Calculate the scores for the map
     let ratings = map.get_trails();
@@ -247,27 +149,137 @@ fn main_part_two(filepath: String) {
     println!("{total_ratings}");
 }
 
-// Convert trail ratings into trail scores
-fn convert_ratings_to_scores(
-    ratings: HashMap<Location, HashSet<Vec<Location>>>,
-) -> HashMap<Location, HashSet<Location>> {
-    // Create a map of scores per trail start location
-    let mut scores = HashMap::new();
-
-    // Iterate through the ratings
-    for (key, trails) in ratings {
-        // Create a new set of trail endpoints for each start location
-        let mut ends: HashSet<Location> = HashSet::new();
-
-        // For each trail in the ratings (completed trail), insert the endpoint to the running set
-        for trail in trails {
-            ends.insert(*trail.last().expect("Path is empty"));
+/// Parses the map from the file contents, failing if it's malformed
+fn read_map(contents: &str) -> GameMap {
+    GameMap::parse(contents).unwrap_or_else(|error| fail(Failure::Parse(error)))
+}
+
+/// Parses the map, then prints its dimensions and the number of trailheads and summits on it
+/// and exits
+fn report_structure(filepath: &str) -> ! {
+    let map = read_map(&read_input(filepath));
+    common::report_parse(&[
+        ("rows", map.spaces.height()),
+        ("columns", map.spaces.width()),
+        ("trailheads", map.locations_at_level(0).len()),
+        ("summits", map.locations_at_level(9).len()),
+    ]);
+}
+
+/// Prints the number of locations at each level, how many of them lie on a complete trail,
+/// and the number of complete trails passing through them, as CSV
+fn print_histogram(filepath: String) {
+    // Get the contents of the given filepath
+    let contents = read_input(filepath);
+
+    // Create the game map from the file contents
+    let map = read_map(&contents);
+
+    // Print the statistics for each level
+    println!("level,locations,on_trail,trails");
+    for (level, counts) in map.level_histogram().iter().enumerate() {
+        println!(
+            "{level},{},{},{}",
+            counts.locations, counts.on_trail, counts.trails
+        );
+    }
+}
+
+/// Prints the position, score, rating, and length of the shortest complete trail for each
+/// trailhead as CSV, ordered by their position on the map
+///
+/// The length is blank for trailheads with no complete trails.
+fn print_trailheads(filepath: String) {
+    // Get the contents of the given filepath
+    let contents = read_input(filepath);
+
+    // Create the game map from the file contents
+    let map = read_map(&contents);
+
+    // Print the analysis of each of the trailheads
+    println!("x,y,score,rating,shortest");
+    for trailhead in map.locations_at_level(0) {
+        let counts = map.count_paths_to_summits(&trailhead);
+        let score = counts.len();
+        let rating: u64 = counts.values().sum();
+        let shortest = map
+            .shortest_trail_length(&trailhead)
+            .map_or(String::new(), |x| x.to_string());
+        let (x, y) = trailhead.coord.into();
+        println!("{x},{y},{score},{rating},{shortest}");
+    }
+}
+
+/// Prints the given number of distinct complete trails, sampled uniformly at random using the
+/// given seed, as CSV with a row for each location of each trail, followed by the total
+/// number of trails
+///
+/// The trails are never enumerated, so this works even when there are far too many of them.
+/// If there are fewer trails than requested, all of them are printed.
+fn print_samples(filepath: String, num_samples: usize, seed: Option<u64>) {
+    // Get the contents of the given filepath
+    let contents = read_input(filepath);
+
+    // Create the game map from the file contents
+    let map = read_map(&contents);
+
+    // Create the random number generator, seeding it if requested
+    let mut rng = match seed {
+        Some(seed) => StdRng::seed_from_u64(seed),
+        None => StdRng::from_entropy(),
+    };
+
+    // Sample the trails
+    let (samples, num_trails) = sample_trails(&map, num_samples, &mut rng);
+
+    // Print the locations of each of the sampled trails, followed by the total number
+    println!("trail,level,x,y");
+    for (index, trail) in samples.iter().enumerate() {
+        for loc in trail {
+            let (x, y) = loc.coord.into();
+            println!("{index},{},{x},{y}", loc.level);
         }
+    }
+    println!("{num_trails}");
+}
 
-        // Insert the set of trail endpoints into the score hash map
-        scores.insert(key, ends);
+/// Writes the number of paths from each trailhead (rows) to each summit (columns) as a CSV
+/// file, with both ordered by their position on the map
+fn write_matrix(filepath: String, matrix_filepath: String) {
+    // Get the contents of the given filepath
+    let contents = read_input(filepath);
+
+    // Create the game map from the file contents
+    let map = read_map(&contents);
+
+    // Get the trailheads and summits in a stable order
+    let trailheads = map.locations_at_level(0);
+    let summits = map.locations_at_level(9);
+
+    // Create the header row using the coordinates of the summits
+    let mut csv = String::from("trailhead");
+    for summit in &summits {
+        let (x, y) = summit.coord.into();
+        csv.push_str(&format!(",\"{x},{y}\""));
+    }
+    csv.push('\n');
+
+    // Add a row of path counts for each of the trailheads
+    for trailhead in &trailheads {
+        let counts = map.count_paths_to_summits(trailhead);
+        let (x, y) = trailhead.coord.into();
+        csv.push_str(&format!("\"{x},{y}\""));
+        for summit in &summits {
+            let count = counts.get(summit).unwrap_or(&0);
+            csv.push_str(&format!(",{count}"));
+        }
+        csv.push('\n');
     }
 
-    // Return the hash map of scores
-    scores
+    // Write the matrix to the given file
+    fs::write(&matrix_filepath, csv).unwrap_or_else(|error| {
+        fail(Failure::Io(format!(
+            "Could not write {matrix_filepath}: {error}"
+        )))
+    });
 }
@@ -0,0 +1,56 @@
+use day10::{GameMap, LevelCounts};
+
+const EXAMPLE: &str = include_str!("../inputs/example.txt");
+
+#[test]
+fn example_histogram() {
+    let map = GameMap::parse(EXAMPLE).unwrap();
+    let expected: Vec<(usize, usize)> = vec![
+        (9, 9),
+        (9, 8),
+        (6, 5),
+        (6, 5),
+        (6, 6),
+        (4, 4),
+        (4, 4),
+        (6, 5),
+        (7, 6),
+        (7, 7),
+    ];
+    let expected: Vec<LevelCounts> = expected
+        .into_iter()
+        .map(|(locations, on_trail)| LevelCounts {
+            locations,
+            on_trail,
+            trails: 81,
+        })
+        .collect();
+    assert_eq!(map.level_histogram(), expected);
+}
+
+#[test]
+fn every_location_is_counted() {
+    let map = GameMap::parse(EXAMPLE).unwrap();
+    let num_locations: usize = map.level_histogram().iter().map(|x| x.locations).sum();
+    assert_eq!(num_locations, 64);
+}
+
+#[test]
+fn trails_match_rating_at_every_level() {
+    // A single trail climbs straight along the row, with dead ends below it
+    let map = GameMap::parse("0123456789\n5555555555\n").unwrap();
+    for (level, counts) in map.level_histogram().iter().enumerate() {
+        assert_eq!(counts.trails, 1, "Level {level}");
+        assert_eq!(counts.on_trail, 1, "Level {level}");
+    }
+    assert_eq!(map.level_histogram()[5].locations, 11);
+}
+
+#[test]
+fn no_complete_trails() {
+    let map = GameMap::parse("0123\n4567\n").unwrap();
+    let histogram = map.level_histogram();
+    assert_eq!(histogram.len(), 10);
+    assert!(histogram.iter().all(|x| x.on_trail == 0 && x.trails == 0));
+    assert_eq!(histogram[9].locations, 0);
+}
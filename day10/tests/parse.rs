@@ -0,0 +1,13 @@
+use day10::GameMap;
+
+#[test]
+fn rejects_malformed_maps() {
+    assert_eq!(
+        GameMap::parse("0123\n45x7\n").err(),
+        Some("Line 2: could not parse 'x' as a level".to_string())
+    );
+    assert_eq!(
+        GameMap::parse("0123\n456\n").err(),
+        Some("Row 1 has 3 cells, but expected 4".to_string())
+    );
+}
@@ -0,0 +1,69 @@
+use std::collections::HashSet;
+
+use day10::{sample_trails, GameMap, Location};
+use rand::{rngs::StdRng, SeedableRng};
+
+const EXAMPLE: &str = include_str!("../inputs/example.txt");
+
+/// Checks that the trail starts at a trailhead and climbs one level at a time between
+/// neighboring locations up to a summit
+fn is_valid_trail(map: &GameMap, trail: &[Location]) -> bool {
+    trail.len() == 10
+        && trail
+            .iter()
+            .enumerate()
+            .all(|(index, loc)| map.get(&loc.coord) == Some(loc) && usize::from(loc.level) == index)
+        && trail.windows(2).all(|pair| {
+            let distance = pair[0].coord.x.abs_diff(pair[1].coord.x)
+                + pair[0].coord.y.abs_diff(pair[1].coord.y);
+            distance == 1
+        })
+}
+
+#[test]
+fn sampled_trails_are_valid() {
+    let map = GameMap::parse(EXAMPLE).unwrap();
+    for seed in 0..20 {
+        let mut rng = StdRng::seed_from_u64(seed);
+        let (samples, num_trails) = sample_trails(&map, 10, &mut rng);
+        assert_eq!(num_trails, 81);
+        assert_eq!(samples.len(), 10);
+        for trail in &samples {
+            assert!(is_valid_trail(&map, trail), "Seed {seed}");
+        }
+    }
+}
+
+#[test]
+fn sampled_trails_are_distinct() {
+    let map = GameMap::parse(EXAMPLE).unwrap();
+    let mut rng = StdRng::seed_from_u64(7);
+    let (samples, _) = sample_trails(&map, 50, &mut rng);
+    let distinct: HashSet<&Vec<Location>> = samples.iter().collect();
+    assert_eq!(distinct.len(), samples.len());
+}
+
+#[test]
+fn sampling_more_than_total_gets_every_trail() {
+    let map = GameMap::parse(EXAMPLE).unwrap();
+    let mut rng = StdRng::seed_from_u64(1);
+    let (samples, num_trails) = sample_trails(&map, 1000, &mut rng);
+    assert_eq!(samples.len() as u64, num_trails);
+    assert!(samples.iter().all(|x| is_valid_trail(&map, x)));
+    let distinct: HashSet<&Vec<Location>> = samples.iter().collect();
+    assert_eq!(distinct.len(), samples.len());
+}
+
+#[test]
+fn same_seed_same_samples() {
+    let map = GameMap::parse(EXAMPLE).unwrap();
+    let sample = |seed| sample_trails(&map, 5, &mut StdRng::seed_from_u64(seed)).0;
+    assert_eq!(sample(42), sample(42));
+}
+
+#[test]
+fn no_trails_to_sample() {
+    let map = GameMap::parse("0123\n5555\n").unwrap();
+    let mut rng = StdRng::seed_from_u64(0);
+    assert_eq!(sample_trails(&map, 3, &mut rng), (Vec::new(), 0));
+}
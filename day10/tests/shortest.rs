@@ -0,0 +1,35 @@
+use day10::{Coordinate, GameMap};
+
+const EXAMPLE: &str = include_str!("../inputs/example.txt");
+
+#[test]
+fn example_trails_take_nine_steps() {
+    let map = GameMap::parse(EXAMPLE).unwrap();
+    let trailheads = map.locations_at_level(0);
+    assert_eq!(trailheads.len(), 9);
+    for trailhead in trailheads {
+        assert_eq!(map.shortest_trail_length(&trailhead), Some(9));
+    }
+}
+
+#[test]
+fn shortest_from_partway_up() {
+    let map = GameMap::parse(EXAMPLE).unwrap();
+    let loc = *map.get(&Coordinate::from((3, 3))).unwrap();
+    assert_eq!(loc.level, 4);
+    assert_eq!(map.shortest_trail_length(&loc), Some(5));
+}
+
+#[test]
+fn unreachable_summit() {
+    let map = GameMap::parse("0123\n9876\n5554\n").unwrap();
+    let trailhead = map.locations_at_level(0)[0];
+    assert_eq!(map.shortest_trail_length(&trailhead), None);
+}
+
+#[test]
+fn summit_is_zero_steps_away() {
+    let map = GameMap::parse(EXAMPLE).unwrap();
+    let summit = map.locations_at_level(9)[0];
+    assert_eq!(map.shortest_trail_length(&summit), Some(0));
+}
@@ -0,0 +1,308 @@
+use std::collections::HashMap;
+
+/// Representation of data for blinking that should be pre-saved
+#[derive(Debug)]
+pub struct PreSaveBlinking {
+    counts: HashMap<usize, u128>,
+}
+
+/// Representation of a magic stone
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Stone {
+    pub value: u64,
+}
+
+impl Stone {
+    /// Creates a new stone with a given value
+    pub fn new(value: u64) -> Self {
+        Self { value }
+    }
+
+    /// Creates a stone of value one
+    fn one() -> Self {
+        Self { value: 1 }
+    }
+
+    /// Gets the number of digits for this stone
+    fn get_digit_count(&self) -> u64 {
+        // Initialize the base factor and number of digits
+        let mut factor = 10;
+        let mut num_digits = 1;
+
+        // While the number can still be divided by 10, increase the tally of number
+        // of digits, as power of ten used to check
+        while self.value % factor != self.value {
+            factor *= 10;
+            num_digits += 1;
+        }
+
+        // Return the number of digits
+        num_digits
+    }
+
+    /// Checks whether the stone has an even number of digits
+    fn has_even_digits(&self) -> bool {
+        self.get_digit_count().is_multiple_of(2)
+    }
+
+    /// Split the stone into two different stones by seperating the digits in half
+    fn split(&self) -> (Self, Self) {
+        // Check whether the stone is eligible to be split
+        let value = self.value;
+        let num_digits = self.get_digit_count();
+        if !num_digits.is_multiple_of(2) {
+            panic!("Cannot split stone of value {value}")
+        }
+
+        // Get the divisor needed for splitting
+        let splitter_power = self.get_digit_count() / 2;
+        let splitter = 10_u64.pow(splitter_power as u32);
+
+        // Split the stone and return the two resulting stones
+        let left_value = self.value / splitter;
+        let right_value = self.value % splitter;
+        (Stone::new(left_value), Stone::new(right_value))
+    }
+
+    /// Checks whether the value of the stone is zero
+    fn is_zero(&self) -> bool {
+        self.value == 0
+    }
+
+    /// Grow the stone by multiplying by 2024
+    fn grow(&self) -> Self {
+        if self.has_even_digits() || self.value == 0 {
+            panic!("Only stones not fitting the other rules should grow")
+        }
+        Stone::new(self.value * 2024)
+    }
+
+    /// Unobserves the stone, resulting in a change according to the game rules
+    pub fn unobserve(&self) -> StoneChange {
+        if self.is_zero() {
+            StoneChange::One(Stone::one())
+        } else if self.has_even_digits() {
+            let (left, right) = self.split();
+            StoneChange::Split(left, right)
+        } else {
+            StoneChange::Grow(self.grow())
+        }
+    }
+}
+
+/// The various actions a stone can take upon blinking
+pub enum StoneChange {
+    /// Turn the stone to a stone of value one
+    One(Stone),
+    /// Split the stone into two
+    Split(Stone, Stone),
+    /// Multiply the stone by 2024
+    Grow(Stone),
+}
+
+/// Simulate blinking n times, returning the resulting state of the stones
+pub fn simulate_blinking_saving(stones: &[Stone], n: u8) -> Vec<Stone> {
+    let mut stones = Vec::from(stones);
+    for _i in 0..n {
+        stones = blink_save(&stones);
+    }
+    stones
+}
+
+/// Create the list of pre-saved blinks for 0-9 for up to n iterations to be used
+/// for lazy solving
+fn preload_blinks(n: usize) -> HashMap<Stone, PreSaveBlinking> {
+    let mut presaves = HashMap::new();
+
+    for num in 0..10 {
+        let mut counts = HashMap::new();
+        let stone = Stone { value: num };
+        for i in 0..n {
+            let iter_count = i + 1;
+            let count = blink_count(&stone, 0, iter_count);
+            counts.insert(iter_count, count);
+        }
+        let presave = PreSaveBlinking { counts };
+        presaves.insert(stone, presave);
+    }
+
+    presaves
+}
+
+/// "Lazily" solve for the given stone.  This is done by checking whether the stone is in
+/// the pre-save hash map for the given number of iterations.  If it is, then that value is
+/// returned for the current state; otherwise, the next generation of stones is generated,
+/// and solved lazily, and that answer is returned.
+fn lazy_solve(
+    stone: &Stone,
+    remaining_i: usize,
+    presaves: &HashMap<Stone, PreSaveBlinking>,
+) -> u128 {
+    // No more stones to be generated, so this simply returns a single stone (this one)
+    if remaining_i == 0 {
+        return 1;
+    }
+
+    // If the pre-save map has the current stone for the remaining iterations, use it
+    if let Some(presave) = presaves.get(stone) {
+        if let Some(count) = presave.counts.get(&remaining_i) {
+            return *count;
+        }
+    }
+
+    // The stone doesn't exist in the pre-save map for the number of generations needed,
+    // so the next generation is generated and lazily solved
+    let mut total = 0;
+    for next_stone in blink_save(&[*stone]) {
+        total += lazy_solve(&next_stone, remaining_i - 1, presaves)
+    }
+    total
+}
+
+/// Solves the problem for a given set of stones for n iterations by
+/// pre-saving s number of generations for numbers 0-9, which cyclically
+/// result in other single digit stones.  The state for n-s iterations is
+/// then created and the remaining iterations are "lazily" solved
+pub fn lazy_solver(stones: &[Stone], s: usize, n: usize) -> u128 {
+    // Pre-save the given number of blinks
+    let presaves = preload_blinks(s);
+
+    // Create a running total of stones
+    let mut total = 0;
+
+    // Calculate the start state from the save and target iterations
+    let start_state = n - s;
+
+    // Iterate through the unique stones individually, since duplicates yield identical results
+    for (stone, multiplicity) in stone_frequencies(stones) {
+        // Create the start state for the stone
+        let state = simulate_blinking_saving(&[stone], start_state as u8);
+
+        // Lazily solve for each stone in the pre-generated state, counting it once for each
+        // duplicate of the initial stone
+        let mut stone_total = 0;
+        for state_stone in &state {
+            stone_total += lazy_solve(state_stone, s, &presaves);
+        }
+        total += stone_total * multiplicity;
+    }
+
+    // Return the number of stones generated
+    total
+}
+
+/// Collapses the given list of stones into the number of stones with each value
+pub fn stone_frequencies(stones: &[Stone]) -> HashMap<Stone, u128> {
+    let mut frequencies = HashMap::new();
+    for stone in stones {
+        *frequencies.entry(*stone).or_insert(0) += 1;
+    }
+    frequencies
+}
+
+/// Perform a blink action for the given stone frequencies, and return the frequencies
+/// of the next generation of stones
+fn blink_frequencies(frequencies: &HashMap<Stone, u128>) -> HashMap<Stone, u128> {
+    // Create a hash map for storing the new frequencies
+    let mut new_frequencies = HashMap::new();
+
+    // Iterate through the unique stones, carrying over their counts to the resulting stones
+    for (stone, count) in frequencies {
+        for new_stone in blink_save(&[*stone]) {
+            *new_frequencies.entry(new_stone).or_insert(0) += count;
+        }
+    }
+
+    // Return the created stone frequencies
+    new_frequencies
+}
+
+/// Solves the problem for a given set of stones for n iterations by tracking only
+/// the number of stones with each value, since stones with the same value always
+/// produce the same stones
+pub fn frequency_solver(stones: &[Stone], n: usize) -> u128 {
+    // Collapse the initial stones into their frequencies
+    let mut frequencies = stone_frequencies(stones);
+
+    // Blink n times
+    for _i in 0..n {
+        frequencies = blink_frequencies(&frequencies);
+    }
+
+    // Return the number of stones generated
+    frequencies.values().sum()
+}
+
+/// Parse the input text into a list of stones, failing if an engraving isn't a number
+pub fn parse_input(input: &str) -> Result<Vec<Stone>, String> {
+    let mut stones = Vec::new();
+    for text in input.trim().split(" ") {
+        let value = text
+            .parse::<u64>()
+            .map_err(|_| format!("Could not parse {text:?} as a stone"))?;
+        stones.push(Stone::new(value));
+    }
+    Ok(stones)
+}
+
+/// Perform a blink action for the given stones, and return the next generation of stones
+pub fn blink_save(stones: &[Stone]) -> Vec<Stone> {
+    // Create a list for storing new stones
+    let mut new_stones = Vec::new();
+
+    // Iterate through the given stones
+    for stone in stones {
+        // Perform the blink and push the resulting stones to the list
+        match stone.unobserve() {
+            StoneChange::Split(left, right) => {
+                new_stones.push(left);
+                new_stones.push(right);
+            }
+            StoneChange::One(new) => new_stones.push(new),
+            StoneChange::Grow(new) => new_stones.push(new),
+        }
+    }
+
+    // Return the created list of stones
+    new_stones
+}
+
+/// Perform a blink action for a given stone, and return the number of stones in the
+/// next n generations (and starting at index = i)
+fn blink_count(stone: &Stone, i: usize, n: usize) -> u128 {
+    // If iteration is complete, the path yields a single stone
+    if i == n {
+        return 1;
+    }
+
+    // Get the new index of the iteration
+    let new_i = i + 1;
+
+    // Create a total for adding recursive results
+    let mut total = 0;
+
+    // Perform the blink and add the resulting total to the running count
+    match stone.unobserve() {
+        StoneChange::Split(left, right) => {
+            total += blink_count(&left, new_i, n);
+            total += blink_count(&right, new_i, n);
+        }
+        StoneChange::One(new) => total += blink_count(&new, new_i, n),
+        StoneChange::Grow(new) => total += blink_count(&new, new_i, n),
+    }
+
+    // Return the total number of blinks
+    total
+}
+
+/// Solves part one for the puzzle input, getting the number of stones after 25 blinks
+pub fn part1(input: &str) -> Result<usize, String> {
+    let stones = parse_input(input)?;
+    Ok(simulate_blinking_saving(&stones, 25).len())
+}
+
+/// Solves part two for the puzzle input, getting the number of stones after 75 blinks
+pub fn part2(input: &str) -> Result<u128, String> {
+    let stones = parse_input(input)?;
+    Ok(frequency_solver(&stones, 75))
+}
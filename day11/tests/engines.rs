@@ -0,0 +1,20 @@
+use day11::{frequency_solver, lazy_solver, parse_input, simulate_blinking_saving};
+
+const EXAMPLE: &str = include_str!("../inputs/example.txt");
+
+#[test]
+fn engines_agree_on_example() {
+    let stones = parse_input(EXAMPLE).unwrap();
+    assert_eq!(simulate_blinking_saving(&stones, 25).len(), 55312);
+    assert_eq!(lazy_solver(&stones, 10, 25), 55312);
+    assert_eq!(frequency_solver(&stones, 25), 55312);
+    assert_eq!(lazy_solver(&stones, 20, 45), frequency_solver(&stones, 45));
+}
+
+#[test]
+fn rejects_malformed_stones() {
+    assert_eq!(
+        parse_input("125 x17").err(),
+        Some(String::from("Could not parse \"x17\" as a stone"))
+    );
+}
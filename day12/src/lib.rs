@@ -0,0 +1,999 @@
+use grid::Grid;
+use pathfinding::{flood_fill, Visit};
+use rayon::prelude::*;
+use std::collections::{HashMap, HashSet};
+
+/// Type representing information about plots, which is a hash map
+/// using the representative coordinate of each plot for keys and a
+/// hash set of locations within the plot as values
+///
+/// The representative coordinate is the first location of the plot in
+/// reading order, so the same plot has the same key no matter how the
+/// plots were found.
+pub type PlotBreakdown = HashMap<Coordinate, HashSet<Location>>;
+
+/// Representation of the plot data
+pub struct PlotData {
+    pub area: u64,
+    pub perimeter: u64,
+}
+
+/// Cost model for fencing a region, where the price is the area of the region multiplied by
+/// the cost of its fence
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PriceModel {
+    /// The cost of each unit of perimeter
+    pub perimeter_cost: u64,
+    /// The cost of each side, used for the discounted price
+    pub side_cost: u64,
+}
+
+impl PriceModel {
+    /// Gets the price of fencing a region using its perimeter
+    pub fn price(&self, plot_data: &PlotData) -> u64 {
+        plot_data.area * plot_data.perimeter * self.perimeter_cost
+    }
+
+    /// Gets the price of fencing a region using its number of sides
+    pub fn discounted_price(&self, plot_data: &PlotData, num_sides: u64) -> u64 {
+        plot_data.area * num_sides * self.side_cost
+    }
+}
+
+impl Default for PriceModel {
+    fn default() -> Self {
+        Self {
+            perimeter_cost: 1,
+            side_cost: 1,
+        }
+    }
+}
+
+/// Representation of an X, Y coordinate pair
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
+pub struct Coordinate {
+    pub x: i64,
+    pub y: i64,
+}
+
+impl From<(i64, i64)> for Coordinate {
+    fn from(value: (i64, i64)) -> Self {
+        Self {
+            x: value.0,
+            y: value.1,
+        }
+    }
+}
+
+impl From<Coordinate> for (i64, i64) {
+    fn from(value: Coordinate) -> Self {
+        (value.x, value.y)
+    }
+}
+
+/// Representation of a location on the topography map, with coordinate and level
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
+pub struct Location {
+    pub coord: Coordinate,
+    pub label: char,
+}
+
+/// Rules for which labels count as the same plant when finding regions
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct LabelEquivalence {
+    /// Whether labels that differ only in case are the same plant
+    case_insensitive: bool,
+    /// The label each merged label stands for, after any case is ignored
+    merged: HashMap<char, char>,
+}
+
+impl LabelEquivalence {
+    /// Creates rules where labels are only the same plant as themselves, or as labels that
+    /// differ only in case if requested
+    pub fn new(case_insensitive: bool) -> Self {
+        Self {
+            case_insensitive,
+            merged: HashMap::new(),
+        }
+    }
+
+    /// Makes the two given labels, and every label already the same plant as either of them,
+    /// the same plant
+    pub fn merge(&mut self, first: char, second: char) {
+        // Get the labels both classes stand for, which are already joined if they're the same
+        let first = self.canonical(first);
+        let second = self.canonical(second);
+        if first == second {
+            return;
+        }
+
+        // Point every member of the second class at the first class
+        for label in self.merged.values_mut() {
+            if *label == second {
+                *label = first;
+            }
+        }
+        self.merged.insert(second, first);
+    }
+
+    /// Gets the label that stands for every label that is the same plant as the given one
+    pub fn canonical(&self, label: char) -> char {
+        let label = if self.case_insensitive {
+            label.to_ascii_lowercase()
+        } else {
+            label
+        };
+        self.merged.get(&label).copied().unwrap_or(label)
+    }
+
+    /// Checks whether the two given labels are the same plant
+    pub fn matches(&self, first: char, second: char) -> bool {
+        first == second || self.canonical(first) == self.canonical(second)
+    }
+}
+
+/// Representation of the game map
+pub struct GameMap {
+    pub spaces: Grid<Location>,
+    /// Which labels count as the same plant when finding regions
+    pub labels: LabelEquivalence,
+}
+
+impl GameMap {
+    // Creates a new map with the given spaces, failing if the rows differ in length
+    pub fn new(spaces: Vec<Vec<Location>>) -> Result<Self, String> {
+        Ok(Self {
+            spaces: Grid::from_rows(spaces)?,
+            labels: LabelEquivalence::default(),
+        })
+    }
+
+    // Parses the map from the provided string, failing if the rows differ in length
+    pub fn parse(value: &str) -> Result<Self, String> {
+        // Create a list for storing rows
+        let mut rows = Vec::new();
+
+        // Iterate through the string line by line
+        for (row_index, line) in value.trim().lines().enumerate() {
+            // Create a list for storing entries
+            let mut row = Vec::new();
+
+            // Iterate through the line character by character
+            for (col_index, character) in line.chars().enumerate() {
+                // Create the coordinate for the given position
+                let coord = Coordinate {
+                    x: col_index as i64,
+                    y: row_index as i64,
+                };
+
+                // Add the location (with label) to the row
+                let plotspace = Location {
+                    coord,
+                    label: character,
+                };
+                row.push(plotspace);
+            }
+
+            // Add the row to the map
+            rows.push(row);
+        }
+
+        // Return a new map with the given rows
+        Self::new(rows)
+    }
+
+    /// Get the location at a given X, Y coordinate
+    ///
+    /// Returns the requested location if valid, or None if it's
+    /// outside the bounds of the map
+    pub fn get(&self, coord: &Coordinate) -> Option<&Location> {
+        self.spaces.get_signed(coord.x, coord.y)
+    }
+
+    /// Changes the label of the location at a given X, Y coordinate
+    ///
+    /// Returns the previous label if the coordinate is valid, or None if it's
+    /// outside the bounds of the map
+    fn set_label(&mut self, coord: &Coordinate, label: char) -> Option<char> {
+        let location = self.spaces.get_signed_mut(coord.x, coord.y)?;
+        let previous = location.label;
+        location.label = label;
+        Some(previous)
+    }
+
+    /// Gets the valid neighboring squares in the cardinal directions
+    pub fn neighbors(&self, coord: &Coordinate) -> Vec<&Location> {
+        // Create a list to store the neighboring locations
+        let mut neighbors = Vec::new();
+
+        // Shorthands for x and y
+        let x = coord.x;
+        let y = coord.y;
+
+        // Get the coordinates at the cardinal directions
+        let north = Coordinate::from((x, y + 1));
+        let east = Coordinate::from((x + 1, y));
+        let south = Coordinate::from((x, y - 1));
+        let west = Coordinate::from((x - 1, y));
+
+        // Add the coordinates to the list of neighbors
+        neighbors.push(self.get(&north));
+        neighbors.push(self.get(&east));
+        neighbors.push(self.get(&south));
+        neighbors.push(self.get(&west));
+
+        // Filter out invalid neighboring coordinates
+        neighbors.iter().filter_map(|x| *x).collect()
+    }
+
+    /// Gets the "cornerings" of a given coordindate, each of which is the list of the four
+    /// coordinates surrounding a point on the grid, starting with the top-left and moving
+    /// clockwise.  All four cornerings are returned for the given coordinate, starting
+    /// with the top-left corner.
+    fn cornerings(&self, coord: &Coordinate) -> Vec<Vec<Coordinate>> {
+        // Create a list for storing cornerings
+        let mut cornerings = Vec::new();
+
+        // Shorthands for x and y
+        let x = coord.x;
+        let y = coord.y;
+
+        // Get the coordinates at the cardinal directions
+        let north = Coordinate::from((x, y + 1));
+        let east = Coordinate::from((x + 1, y));
+        let south = Coordinate::from((x, y - 1));
+        let west = Coordinate::from((x - 1, y));
+
+        // Get the coordinates at the diagonal directions
+        let northwest = Coordinate::from((x - 1, y + 1));
+        let northeast = Coordinate::from((x + 1, y + 1));
+        let southeast = Coordinate::from((x + 1, y - 1));
+        let southwest = Coordinate::from((x - 1, y - 1));
+
+        // Create individual cornerings from the coordinates
+        let topleft: Vec<Coordinate> = vec![northwest, north, *coord, west];
+        let topright: Vec<Coordinate> = vec![north, northeast, east, *coord];
+        let bottomright: Vec<Coordinate> = vec![*coord, east, southeast, south];
+        let bottomleft: Vec<Coordinate> = vec![west, *coord, south, southwest];
+
+        // Add the individual cornerings to the list
+        cornerings.push(topleft);
+        cornerings.push(topright);
+        cornerings.push(bottomright);
+        cornerings.push(bottomleft);
+
+        // Return the list of cornerings
+        cornerings
+    }
+
+    /// Converts cornering coordinates into cornering locations, where all non-valid,
+    /// non-grouping locations are None
+    fn convert_cornering(
+        &self,
+        label: char,
+        cornering: &Vec<Coordinate>,
+        grouping: &HashSet<Location>,
+    ) -> Vec<Option<&Location>> {
+        // Create a list of cornering locations
+        let mut plot_corners = Vec::new();
+
+        // Iterate through the cornering coordiantes
+        for space in cornering {
+            // If the location is valid and within the grouping, add it to the list
+            if let Some(location) = self.get(space) {
+                if self.labels.matches(label, location.label) && grouping.contains(location) {
+                    plot_corners.push(Some(location));
+                    continue;
+                }
+            }
+            // Otherwise, push None to the list
+            plot_corners.push(None);
+        }
+
+        // Return the list of cornering locations
+        plot_corners
+    }
+
+    /// Finds groupings of plots by flood filling outward from the given space, adding the
+    /// grouping locations to the hash set provided.  The full set of locations that are newly
+    /// discovered as part of the grouping is returned.
+    fn find_grouping(
+        &self,
+        label: char,
+        space: &Location,
+        grouping: &mut HashSet<Location>,
+    ) -> HashSet<Location> {
+        // Create a list for storing the discovered locations
+        let mut discovered_locations = HashSet::new();
+
+        // If the provided space doesn't have a label that matches the provided one,
+        // or the space is already grouped, there is nothing to discover
+        if !self.labels.matches(label, space.label) || grouping.contains(space) {
+            return discovered_locations;
+        }
+
+        // Flood fill through the neighboring spaces that have a matching label and aren't
+        // already grouped, discovering each of them along the way
+        flood_fill(
+            [space.coord.into()],
+            |_, next| {
+                self.get(&Coordinate::from(next))
+                    .is_some_and(|x| self.labels.matches(label, x.label) && !grouping.contains(x))
+            },
+            |point, _| {
+                discovered_locations.insert(*self.get(&Coordinate::from(point)).unwrap());
+                Visit::Continue
+            },
+        );
+
+        // Add the discovered locations to the grouping
+        grouping.extend(discovered_locations.iter().copied());
+
+        // Return the list of discovered locations in the grouping
+        discovered_locations
+    }
+
+    // Get all of the trails, grouped by start location
+    pub fn get_plots(&self) -> PlotBreakdown {
+        // Create a hash map for grouped plots
+        let mut plots = HashMap::new();
+
+        // Create a hash set for keeping track of checked locations
+        let mut checked_spaces = HashSet::new();
+
+        // Iterate through the map space by space
+        for row in self.spaces.rows() {
+            for space in row {
+                // If the space has already been checked, skip it
+                if checked_spaces.contains(space) {
+                    continue;
+                }
+
+                // Create a hash set for storing groupings
+                let mut grouping = HashSet::new();
+
+                // Get the grouping for the given space
+                let explored = self.find_grouping(space.label, space, &mut grouping);
+
+                // Add the spaces from the grouping to the list of checked spaces
+                checked_spaces.extend(explored);
+
+                // Add the grouped plots to the hash map, using the space as the representative
+                // since it's the first one of the grouping in reading order
+                plots.insert(space.coord, grouping);
+            }
+        }
+
+        // Return the completed hash map of grouped plots
+        plots
+    }
+
+    /// Gets all of the plots like `get_plots`, but labels the regions in parallel
+    ///
+    /// The map is split into strips of rows which are each labelled independently, after which
+    /// the regions that touch across the boundaries between strips are merged together.
+    pub fn get_plots_parallel(&self) -> PlotBreakdown {
+        // Get the dimensions of the map for indexing locations
+        let width = self.spaces.width();
+        let height = self.spaces.height();
+        if width == 0 || height == 0 {
+            return HashMap::new();
+        }
+
+        // Split the rows into roughly one strip per thread
+        let strip_height = height.div_ceil(rayon::current_num_threads()).max(1);
+
+        // Label each of the strips independently, getting the root of each location in the strip
+        let strip_roots: Vec<Vec<usize>> = self
+            .spaces
+            .cells()
+            .par_chunks(strip_height * width)
+            .enumerate()
+            .map(|(strip_index, cells)| {
+                // Create a forest for just the locations in the strip
+                let mut sets = UnionFind::new(cells.len());
+
+                // Join each location with the neighbors to its right and below with matching labels
+                for (index, space) in cells.iter().enumerate() {
+                    if (index + 1) % width != 0
+                        && self.labels.matches(cells[index + 1].label, space.label)
+                    {
+                        sets.union(index, index + 1);
+                    }
+                    if cells
+                        .get(index + width)
+                        .is_some_and(|x| self.labels.matches(x.label, space.label))
+                    {
+                        sets.union(index, index + width);
+                    }
+                }
+
+                // Get the roots as indices within the full map
+                let offset = strip_index * strip_height * width;
+                (0..cells.len())
+                    .map(|index| offset + sets.find(index))
+                    .collect()
+            })
+            .collect();
+
+        // Combine the strips into a single forest for the full map
+        let mut sets = UnionFind::from_parents(strip_roots.concat());
+
+        // Merge the regions that touch across the boundaries between strips
+        for boundary in (strip_height..height).step_by(strip_height) {
+            for col_index in 0..width {
+                if self.labels.matches(
+                    self.spaces[(col_index, boundary - 1)].label,
+                    self.spaces[(col_index, boundary)].label,
+                ) {
+                    sets.union(
+                        (boundary - 1) * width + col_index,
+                        boundary * width + col_index,
+                    );
+                }
+            }
+        }
+
+        // Group the locations by the root of their region, using the first location of each
+        // region in reading order as its representative
+        let mut plots: PlotBreakdown = HashMap::new();
+        let mut representatives = HashMap::new();
+        for (index, space) in self.spaces.iter().enumerate() {
+            let root = sets.find(index);
+            let representative = *representatives.entry(root).or_insert(space.coord);
+            plots.entry(representative).or_default().insert(*space);
+        }
+
+        // Return the completed hash map of grouped plots
+        plots
+    }
+
+    /// Calculate the plot data from the given spaces
+    pub fn calculate_plot_data(&self, spaces: &HashSet<Location>) -> PlotData {
+        // Create variables for keeping track of the area and perimeter
+        let mut area = 0;
+        let mut perimeter = 0;
+
+        // Create a copy of the grouped plot for later use
+        let plot_spaces = spaces.clone();
+
+        // Iterate through all of the grouped plot locations
+        for space in spaces {
+            // Get the neighboring locations as a hash set
+            let neighbors: HashSet<Location> =
+                HashSet::from_iter(self.neighbors(&space.coord).iter().copied().copied());
+
+            // Get overlap between neighboring locations and locations in the grouping
+            let overlap: HashSet<&Location> = neighbors.intersection(&plot_spaces).collect();
+
+            // Increment the area by 1 (a location in the grouping is being operated on currently)
+            area += 1;
+
+            // Increment the perimeter by four minus the number of overlapping neighbor squares
+            // (these squares mean there is no boundary on that side)
+            perimeter += 4 - overlap.len() as u64;
+        }
+
+        // Return the area and perimenter plot data
+        PlotData { area, perimeter }
+    }
+
+    /// Counts the number of corners within a grouping, which (nearly) corresponds
+    /// to the number of sides for the plot
+    pub fn count_corners(&self, grouping: &HashSet<Location>) -> u64 {
+        // Create a variable for keeping track of the number of corners
+        let mut num_corners = 0;
+
+        // Create a list for keeping track of the analyzed cornerings
+        let mut analyzed_corners: Vec<HashSet<Coordinate>> = Vec::new();
+
+        // Iterate through the locations in the grouping
+        for location in grouping {
+            // Get the coordinates for the current location
+            let current_coord = location.coord;
+
+            // Get the label of the current location
+            let location_label = location.label;
+
+            // Get the cornerings of the current coordinate
+            let cornerings = self.cornerings(&current_coord);
+
+            // Iterate through each of the individual cornerings
+            for cornering in &cornerings {
+                // Convert the list of cornerings into a hash map
+                let coordinate_set = HashSet::from_iter(cornering.clone());
+
+                // If the cornerings have already been analyzed, skip them
+                if analyzed_corners.contains(&coordinate_set) {
+                    continue;
+                }
+
+                // Add the unanalyzed cornering coordinates to the list of analyzed ones
+                analyzed_corners.push(coordinate_set);
+
+                // Covert the cornering coordinate information into cornering location information
+                let plot_cornering = self.convert_cornering(location_label, cornering, grouping);
+
+                // Analyze the number of corners for the given cornering locations, and add it to the running count
+                num_corners += analyze_corners(plot_cornering);
+            }
+        }
+
+        // Return the number of corners for the grouped plot
+        num_corners
+    }
+
+    /// Traces the outline of a grouping as closed polygons of lattice points, where the
+    /// location at X, Y covers the square from (X, Y) to (X + 1, Y + 1)
+    ///
+    /// Each ring lists only the points where the outline turns, so the total number of points
+    /// matches the number of sides counted by `count_corners()`.  Rings travel clockwise
+    /// around the grouping (with Y pointing down), so holes are traced counterclockwise.
+    pub fn outline(&self, grouping: &HashSet<Location>) -> Vec<Vec<Coordinate>> {
+        // Get the coordinates in the grouping for checking neighbors
+        let coords: HashSet<(i64, i64)> = grouping.iter().map(|x| x.coord.into()).collect();
+
+        // Create the boundary edges of the grouping, each as a start point and direction,
+        // keeping the grouping on the right of the edge
+        let mut edges: HashMap<(i64, i64), Vec<(i64, i64)>> = HashMap::new();
+        for &(x, y) in &coords {
+            for (neighbor, start, direction) in [
+                ((x, y - 1), (x, y), (1, 0)),
+                ((x + 1, y), (x + 1, y), (0, 1)),
+                ((x, y + 1), (x + 1, y + 1), (-1, 0)),
+                ((x - 1, y), (x, y + 1), (0, -1)),
+            ] {
+                if !coords.contains(&neighbor) {
+                    edges.entry(start).or_default().push(direction);
+                }
+            }
+        }
+
+        // Follow the edges around into closed rings until none are left, starting each ring
+        // from its lowest point, which is always a corner with a single edge leaving it
+        let mut rings = Vec::new();
+        while let Some(&first) = edges.keys().min() {
+            // Walk around the ring, keeping track of each point and the direction leaving it
+            let mut steps = Vec::new();
+            let mut point = first;
+            let mut direction = edges[&first][0];
+            loop {
+                // Take the edge leaving the current point, preferring to turn right (towards
+                // the grouping) where two corners of it touch diagonally
+                let outgoing = edges.get_mut(&point).unwrap();
+                let right = (-direction.1, direction.0);
+                let index = outgoing
+                    .iter()
+                    .position(|x| *x == right)
+                    .or_else(|| outgoing.iter().position(|x| *x == direction))
+                    .unwrap_or(0);
+                direction = outgoing.swap_remove(index);
+                if outgoing.is_empty() {
+                    edges.remove(&point);
+                }
+                steps.push((point, direction));
+
+                // Move along the edge, stopping once the ring is closed
+                point = (point.0 + direction.0, point.1 + direction.1);
+                if point == first {
+                    break;
+                }
+            }
+
+            // Only keep the points where the outline turns
+            let ring = (0..steps.len())
+                .filter(|index| {
+                    let previous = steps[(index + steps.len() - 1) % steps.len()].1;
+                    steps[*index].1 != previous
+                })
+                .map(|index| Coordinate::from(steps[index].0))
+                .collect();
+            rings.push(ring);
+        }
+
+        // Return the rings of the outline
+        rings
+    }
+}
+
+/// Disjoint-set forest of the locations on the map, using the index of each location
+/// in row-major order
+struct UnionFind {
+    parents: Vec<usize>,
+    ranks: Vec<u8>,
+}
+
+impl UnionFind {
+    /// Creates a new forest where every location is in its own set
+    fn new(size: usize) -> Self {
+        Self {
+            parents: (0..size).collect(),
+            ranks: vec![0; size],
+        }
+    }
+
+    /// Creates a forest from the given parent of each index
+    fn from_parents(parents: Vec<usize>) -> Self {
+        let size = parents.len();
+        Self {
+            parents,
+            ranks: vec![0; size],
+        }
+    }
+
+    /// Finds the root of the set containing the given index, compressing the path along the way
+    fn find(&mut self, index: usize) -> usize {
+        let mut index = index;
+        while self.parents[index] != index {
+            self.parents[index] = self.parents[self.parents[index]];
+            index = self.parents[index];
+        }
+        index
+    }
+
+    /// Finds the root of the set containing the given index without modifying the forest
+    fn root(&self, index: usize) -> usize {
+        let mut index = index;
+        while self.parents[index] != index {
+            index = self.parents[index];
+        }
+        index
+    }
+
+    /// Joins the sets containing the two indices, returning the root of the joined set and the
+    /// root that was absorbed into it (if the sets were different)
+    fn union(&mut self, first: usize, second: usize) -> (usize, Option<usize>) {
+        // Get the roots of both sets, which are already joined if they're the same
+        let first_root = self.find(first);
+        let second_root = self.find(second);
+        if first_root == second_root {
+            return (first_root, None);
+        }
+
+        // Attach the shorter tree under the taller one
+        let (root, child) = if self.ranks[first_root] < self.ranks[second_root] {
+            (second_root, first_root)
+        } else {
+            (first_root, second_root)
+        };
+        self.parents[child] = root;
+        if self.ranks[root] == self.ranks[child] {
+            self.ranks[root] += 1;
+        }
+        (root, Some(child))
+    }
+
+    /// Splits the given index back out into its own set
+    ///
+    /// This is only valid when every other member of its previous set is being reset as well.
+    fn reset(&mut self, index: usize) {
+        self.parents[index] = index;
+        self.ranks[index] = 0;
+    }
+}
+
+/// Representation of a region of plots along with its fence prices
+pub struct Region {
+    pub spaces: HashSet<Location>,
+    pub price: u64,
+    pub discounted_price: u64,
+}
+
+/// Garden that keeps track of its regions and their prices, which are updated incrementally
+/// as the labels of individual locations are changed
+pub struct Garden {
+    map: GameMap,
+    model: PriceModel,
+    width: usize,
+    sets: UnionFind,
+    regions: HashMap<usize, Region>,
+}
+
+impl Garden {
+    /// Creates the garden from the given map, finding all of its regions and pricing them
+    /// using the default price model
+    pub fn new(map: GameMap) -> Self {
+        Self::with_model(map, PriceModel::default())
+    }
+
+    /// Creates the garden from the given map, finding all of its regions and pricing them
+    /// using the given price model
+    pub fn with_model(map: GameMap, model: PriceModel) -> Self {
+        // Get the dimensions of the map for indexing locations
+        let width = map.spaces.width();
+        let height = map.spaces.height();
+
+        // Create the garden where every location is its own region
+        let mut garden = Self {
+            map,
+            model,
+            width,
+            sets: UnionFind::new(width * height),
+            regions: HashMap::new(),
+        };
+        let coords: Vec<Coordinate> = garden.map.spaces.iter().map(|x| x.coord).collect();
+        garden.rebuild(&coords);
+
+        // Return the garden
+        garden
+    }
+
+    /// Gets the map of the garden
+    pub fn map(&self) -> &GameMap {
+        &self.map
+    }
+
+    /// Gets all of the regions in the garden
+    pub fn regions(&self) -> impl Iterator<Item = &Region> {
+        self.regions.values()
+    }
+
+    /// Gets the region containing the given X, Y coordinate, or None if it's outside the bounds
+    /// of the map
+    pub fn region_at(&self, coord: &Coordinate) -> Option<&Region> {
+        self.map.get(coord)?;
+        let root = self.sets.root(self.index(coord));
+        self.regions.get(&root)
+    }
+
+    /// Gets the total price of fencing all of the regions using their perimeters
+    pub fn total_price(&self) -> u64 {
+        self.regions.values().map(|x| x.price).sum()
+    }
+
+    /// Gets the total price of fencing all of the regions using their number of sides
+    pub fn total_discounted_price(&self) -> u64 {
+        self.regions.values().map(|x| x.discounted_price).sum()
+    }
+
+    /// Changes the label of the location at the given X, Y coordinate, updating only the
+    /// regions that are affected by the change
+    ///
+    /// Returns whether the label was changed.  Panics if the coordinate is outside the
+    /// bounds of the map.
+    pub fn set_label(&mut self, coord: &Coordinate, label: char) -> bool {
+        // Update the label on the map, stopping early if nothing changes
+        let previous = self
+            .map
+            .set_label(coord, label)
+            .expect("Coordinate outside of the garden");
+        if previous == label {
+            return false;
+        }
+
+        // Remove the region that previously contained the location, since it may now be split
+        let root = self.sets.find(self.index(coord));
+        let old_region = self
+            .regions
+            .remove(&root)
+            .expect("Location did not belong to a region");
+
+        // Rebuild the regions for only the locations of the old region, which now includes the
+        // location as a region of its own with the new label
+        let coords: Vec<Coordinate> = old_region.spaces.iter().map(|x| x.coord).collect();
+        let mut touched = self.rebuild(&coords);
+
+        // Merge the location with any neighboring regions matching its new label
+        let neighbors: Vec<Coordinate> = self
+            .map
+            .neighbors(coord)
+            .iter()
+            .filter(|x| self.map.labels.matches(x.label, label))
+            .map(|x| x.coord)
+            .collect();
+        for neighbor in neighbors {
+            self.merge(coord, &neighbor);
+        }
+        touched.insert(self.sets.find(self.index(coord)));
+
+        // Recalculate the prices of the regions that were touched and still exist
+        for root in touched {
+            let root = self.sets.find(root);
+            self.update_prices(root);
+        }
+
+        // Return that the label was changed
+        true
+    }
+
+    /// Gets the row-major index of the given X, Y coordinate
+    fn index(&self, coord: &Coordinate) -> usize {
+        coord.y as usize * self.width + coord.x as usize
+    }
+
+    /// Resets the given locations to be regions of their own, and then joins them with their
+    /// neighbors among the given locations that have matching labels
+    ///
+    /// Returns the roots of the rebuilt regions, whose prices have already been calculated.
+    fn rebuild(&mut self, coords: &[Coordinate]) -> HashSet<usize> {
+        // Reset each of the locations into a region of its own
+        for coord in coords {
+            let index = self.index(coord);
+            self.sets.reset(index);
+            let location = *self
+                .map
+                .get(coord)
+                .expect("Coordinate outside of the garden");
+            self.regions.insert(
+                index,
+                Region {
+                    spaces: HashSet::from([location]),
+                    price: 0,
+                    discounted_price: 0,
+                },
+            );
+        }
+
+        // Join each location with the neighbors being rebuilt that have matching labels
+        let rebuilding: HashSet<&Coordinate> = coords.iter().collect();
+        for coord in coords {
+            let label = self.map.get(coord).unwrap().label;
+            let neighbors: Vec<Coordinate> = self
+                .map
+                .neighbors(coord)
+                .iter()
+                .filter(|x| {
+                    self.map.labels.matches(x.label, label) && rebuilding.contains(&x.coord)
+                })
+                .map(|x| x.coord)
+                .collect();
+            for neighbor in neighbors {
+                self.merge(coord, &neighbor);
+            }
+        }
+
+        // Calculate the prices of the rebuilt regions
+        let roots: HashSet<usize> = coords
+            .iter()
+            .map(|x| {
+                let index = self.index(x);
+                self.sets.find(index)
+            })
+            .collect();
+        for root in &roots {
+            self.update_prices(*root);
+        }
+
+        // Return the roots of the rebuilt regions
+        roots
+    }
+
+    /// Joins the regions containing the two given coordinates, combining their locations
+    fn merge(&mut self, first: &Coordinate, second: &Coordinate) {
+        let (root, absorbed) = self.sets.union(self.index(first), self.index(second));
+        if let Some(absorbed) = absorbed {
+            let absorbed_region = self.regions.remove(&absorbed).unwrap();
+            let region = self.regions.get_mut(&root).unwrap();
+            region.spaces.extend(absorbed_region.spaces);
+        }
+    }
+
+    /// Recalculates the prices for the region with the given root
+    fn update_prices(&mut self, root: usize) {
+        let Some(region) = self.regions.get(&root) else {
+            return;
+        };
+        let plot_data = self.map.calculate_plot_data(&region.spaces);
+        let num_sides = self.map.count_corners(&region.spaces);
+        let region = self.regions.get_mut(&root).unwrap();
+        region.price = self.model.price(&plot_data);
+        region.discounted_price = self.model.discounted_price(&plot_data, num_sides);
+    }
+}
+
+/// Ways in which a region can differ between two versions of a garden
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RegionChange {
+    /// The region is only in the second version
+    Added,
+    /// The region is only in the first version
+    Removed,
+    /// The region is in both versions, but with a different label or locations
+    Changed,
+}
+
+/// Difference in a region between two versions of a garden, identified by its representative
+/// coordinate
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RegionDiff {
+    /// The representative coordinate of the region
+    pub representative: Coordinate,
+    /// How the region differs
+    pub change: RegionChange,
+    /// The locations of the region in the first version, if it's there
+    pub before: Option<HashSet<Location>>,
+    /// The locations of the region in the second version, if it's there
+    pub after: Option<HashSet<Location>>,
+}
+
+/// Compares the plots of two versions of a garden, returning the regions that differ ordered
+/// by their representative coordinates in reading order
+pub fn diff_plots(before: &PlotBreakdown, after: &PlotBreakdown) -> Vec<RegionDiff> {
+    // Get the representatives of the regions in either version
+    let mut representatives: Vec<&Coordinate> = before.keys().chain(after.keys()).collect();
+    representatives.sort_by_key(|x| (x.y, x.x));
+    representatives.dedup();
+
+    // Compare the region for each representative
+    representatives
+        .into_iter()
+        .filter_map(|representative| {
+            let before_spaces = before.get(representative);
+            let after_spaces = after.get(representative);
+            let change = match (before_spaces, after_spaces) {
+                (None, _) => RegionChange::Added,
+                (_, None) => RegionChange::Removed,
+                (Some(x), Some(y)) if x != y => RegionChange::Changed,
+                _ => return None,
+            };
+            Some(RegionDiff {
+                representative: *representative,
+                change,
+                before: before_spaces.cloned(),
+                after: after_spaces.cloned(),
+            })
+        })
+        .collect()
+}
+
+/// Analyzes the given set of cornering location information to determine the number of corners
+/// counted, which is identical to the number of sides (provided some points are counted twice)
+fn analyze_corners(plot_cornering: Vec<Option<&Location>>) -> u64 {
+    // If all the locations are not from the plot, it is a bad input
+    if plot_cornering.iter().all(|x| x.is_none()) {
+        panic!("No locations from which to analyze corners!")
+    }
+
+    // If all the spaces in the cornering coordinates are valid locations, there is no corner
+    if plot_cornering.iter().all(|x| x.is_some()) {
+        return 0;
+    }
+
+    // Breakout the locations from the cornering squares
+    let topleft = plot_cornering[0];
+    let topright = plot_cornering[1];
+    let bottomright = plot_cornering[2];
+    let bottomleft = plot_cornering[3];
+
+    // Get the conditionals for diagonal plot squares
+    let diagonal_a =
+        topleft.is_some() && bottomright.is_some() && topright.is_none() && bottomleft.is_none();
+    let diagonal_b =
+        topleft.is_none() && bottomright.is_none() && topright.is_some() && bottomleft.is_some();
+
+    // Get the conditional for single-line plot squares
+    let top =
+        topleft.is_some() && topright.is_some() && bottomleft.is_none() && bottomright.is_none();
+    let bottom =
+        topleft.is_none() && topright.is_none() && bottomleft.is_some() && bottomright.is_some();
+    let left =
+        topleft.is_some() && bottomleft.is_some() && topright.is_none() && bottomright.is_none();
+    let right =
+        topleft.is_none() && bottomleft.is_none() && topright.is_some() && bottomright.is_some();
+
+    // If diagonal squares are identified, count the corner twice (once for each use of it)
+    if diagonal_a || diagonal_b {
+        return 2;
+    }
+
+    // If single-line squares are identified, there is no corner
+    if top || bottom || left || right {
+        return 0;
+    }
+
+    // The remaining cases are where there are one or three plot locaions, which both
+    // indicate in a single corner
+    1
+}
+
+/// Solves part one for the puzzle input, getting the total price of fencing every region by
+/// its perimeter
+pub fn part1(input: &str) -> Result<u64, String> {
+    Ok(Garden::new(GameMap::parse(input)?).total_price())
+}
+
+/// Solves part two for the puzzle input, getting the total price of fencing every region by
+/// its number of sides
+pub fn part2(input: &str) -> Result<u64, String> {
+    Ok(Garden::new(GameMap::parse(input)?).total_discounted_price())
+}
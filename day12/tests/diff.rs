@@ -0,0 +1,63 @@
+use day12::{diff_plots, Coordinate, GameMap, RegionChange};
+
+/// Gets the change and representative of each region that differs between the two gardens
+fn changes(before: &str, after: &str) -> Vec<(RegionChange, (i64, i64))> {
+    let before = GameMap::parse(before).unwrap().get_plots();
+    let after = GameMap::parse(after).unwrap().get_plots_parallel();
+    diff_plots(&before, &after)
+        .into_iter()
+        .map(|x| (x.change, x.representative.into()))
+        .collect()
+}
+
+#[test]
+fn identical_gardens_have_no_changes() {
+    assert!(changes("AAB\nAAB\n", "AAB\nAAB\n").is_empty());
+}
+
+#[test]
+fn reports_changed_regions() {
+    let diffs = changes("AAB\nAAB\n", "AAB\nABB\n");
+    assert_eq!(
+        diffs,
+        vec![
+            (RegionChange::Changed, (0, 0)),
+            (RegionChange::Changed, (2, 0))
+        ]
+    );
+}
+
+#[test]
+fn reports_added_and_removed_regions() {
+    // The D region is absorbed into the C region
+    let diffs = changes("AB\nCD\n", "AB\nCC\n");
+    assert_eq!(
+        diffs,
+        vec![
+            (RegionChange::Changed, (0, 1)),
+            (RegionChange::Removed, (1, 1))
+        ]
+    );
+
+    // The B region splits off of the A region
+    let diffs = changes("AA\n", "AB\n");
+    assert_eq!(
+        diffs,
+        vec![
+            (RegionChange::Changed, (0, 0)),
+            (RegionChange::Added, (1, 0))
+        ]
+    );
+}
+
+#[test]
+fn relabelled_region_is_changed() {
+    let before = GameMap::parse("AA\n").unwrap().get_plots();
+    let after = GameMap::parse("BB\n").unwrap().get_plots();
+    let diffs = diff_plots(&before, &after);
+    assert_eq!(diffs.len(), 1);
+    assert_eq!(diffs[0].change, RegionChange::Changed);
+    assert_eq!(diffs[0].representative, Coordinate::from((0, 0)));
+    assert_eq!(diffs[0].before.as_ref().unwrap().len(), 2);
+    assert_eq!(diffs[0].after.as_ref().unwrap().len(), 2);
+}
@@ -0,0 +1,65 @@
+use day12::{Coordinate, GameMap, Garden, PriceModel};
+
+const EXAMPLE: &str = "AAAA\nBBCD\nBBCC\nEEEC\n";
+
+#[test]
+fn initial_prices() {
+    let garden = Garden::new(GameMap::parse(EXAMPLE).unwrap());
+    assert_eq!(garden.regions().count(), 5);
+    assert_eq!(garden.total_price(), 140);
+    assert_eq!(garden.total_discounted_price(), 80);
+}
+
+#[test]
+fn edits_match_fresh_garden() {
+    let mut garden = Garden::new(GameMap::parse(EXAMPLE).unwrap());
+
+    // Split the C region, merge the D into it, and then join the A and B regions
+    let edits = [((2, 2), 'X'), ((3, 1), 'C'), ((0, 1), 'A'), ((2, 2), 'C')];
+    let mut expected = EXAMPLE.to_string();
+    for ((x, y), label) in edits {
+        assert!(garden.set_label(&Coordinate::from((x, y)), label));
+        let offset = y as usize * 5 + x as usize;
+        expected.replace_range(offset..offset + 1, &label.to_string());
+
+        // The incrementally updated garden should match one built from scratch
+        let fresh = Garden::new(GameMap::parse(&expected).unwrap());
+        assert_eq!(garden.regions().count(), fresh.regions().count());
+        assert_eq!(garden.total_price(), fresh.total_price());
+        assert_eq!(
+            garden.total_discounted_price(),
+            fresh.total_discounted_price()
+        );
+    }
+
+    // Relabelling a location with its current label changes nothing
+    assert!(!garden.set_label(&Coordinate::from((0, 0)), 'A'));
+}
+
+#[test]
+fn region_lookup() {
+    let garden = Garden::new(GameMap::parse(EXAMPLE).unwrap());
+    let region = garden.region_at(&Coordinate::from((3, 3))).unwrap();
+    assert_eq!(region.spaces.len(), 4);
+    assert!(garden.region_at(&Coordinate::from((4, 0))).is_none());
+}
+
+#[test]
+fn custom_price_model() {
+    let model = PriceModel {
+        perimeter_cost: 3,
+        side_cost: 5,
+    };
+    let mut garden = Garden::with_model(GameMap::parse(EXAMPLE).unwrap(), model);
+    assert_eq!(garden.total_price(), 140 * 3);
+    assert_eq!(garden.total_discounted_price(), 80 * 5);
+
+    // Edited regions should be priced with the same model
+    garden.set_label(&Coordinate::from((3, 1)), 'C');
+    let fresh = Garden::with_model(GameMap::parse("AAAA\nBBCC\nBBCC\nEEEC\n").unwrap(), model);
+    assert_eq!(garden.total_price(), fresh.total_price());
+    assert_eq!(
+        garden.total_discounted_price(),
+        fresh.total_discounted_price()
+    );
+}
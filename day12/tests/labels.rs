@@ -0,0 +1,49 @@
+use day12::{GameMap, Garden, LabelEquivalence};
+
+const EXAMPLE: &str = "AAAA\nBBCD\nBBCC\nEEEC\n";
+
+/// Gets the number of regions and both total prices of the given map
+fn prices(map: GameMap) -> (usize, u64, u64) {
+    let regions = map.get_plots_parallel().len();
+    assert_eq!(map.get_plots().len(), regions);
+    let garden = Garden::new(map);
+    assert_eq!(garden.regions().count(), regions);
+    (
+        regions,
+        garden.total_price(),
+        garden.total_discounted_price(),
+    )
+}
+
+#[test]
+fn merged_labels_match_relabelled_map() {
+    let mut map = GameMap::parse(EXAMPLE).unwrap();
+    map.labels.merge('C', 'D');
+    assert_eq!(
+        prices(map),
+        prices(GameMap::parse(&EXAMPLE.replace('D', "C")).unwrap())
+    );
+}
+
+#[test]
+fn case_insensitive_labels_match_uppercase_map() {
+    let mut map = GameMap::parse("AAaa\nBbCD\nbBcC\nEeEC\n").unwrap();
+    map.labels = LabelEquivalence::new(true);
+    assert_eq!(prices(map), prices(GameMap::parse(EXAMPLE).unwrap()));
+}
+
+#[test]
+fn merges_are_transitive() {
+    let mut labels = LabelEquivalence::new(false);
+    labels.merge('A', 'B');
+    labels.merge('C', 'D');
+    assert!(!labels.matches('B', 'D'));
+    labels.merge('B', 'C');
+    assert!(labels.matches('A', 'D'));
+    assert!(!labels.matches('A', 'E'));
+
+    // Merging every label but E leaves just two regions
+    let mut map = GameMap::parse(EXAMPLE).unwrap();
+    map.labels = labels;
+    assert_eq!(prices(map).0, 2);
+}
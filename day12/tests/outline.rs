@@ -0,0 +1,44 @@
+use day12::{Coordinate, GameMap};
+
+const EXAMPLE: &str = "RRRRIICCFF\nRRRRIICCCF\nVVRRRCCFFF\nVVRCCCJFFF\nVVVVCJJCFE\nVVIVCCJJEE\nVVIIICJJEE\nMIIIIIJJEE\nMIIISIJEEE\nMMMISSJEEE\n";
+
+const TOUCHING: &str = "AAAAAA\nAAABBA\nAAABBA\nABBAAA\nABBAAA\nAAAAAA\n";
+
+/// Checks that the number of points in the outline of every plot matches its number of sides
+fn assert_outlines_match_sides(text: &str) {
+    let map = GameMap::parse(text).unwrap();
+    for spaces in map.get_plots().values() {
+        let num_points: usize = map.outline(spaces).iter().map(|x| x.len()).sum();
+        assert_eq!(num_points as u64, map.count_corners(spaces));
+    }
+}
+
+#[test]
+fn outline_points_match_sides() {
+    assert_outlines_match_sides(EXAMPLE);
+    assert_outlines_match_sides(TOUCHING);
+}
+
+#[test]
+fn outlines_square() {
+    let map = GameMap::parse("AB\nBB\n").unwrap();
+    let plots = map.get_plots();
+    let spaces = plots.values().find(|x| x.len() == 1).unwrap();
+    let expected: Vec<Coordinate> = [(0, 0), (1, 0), (1, 1), (0, 1)]
+        .into_iter()
+        .map(Coordinate::from)
+        .collect();
+    assert!(map.outline(spaces) == vec![expected]);
+}
+
+#[test]
+fn outlines_holes_as_separate_rings() {
+    // The two holes touch at a corner, so they are traced as a single ring
+    let map = GameMap::parse(TOUCHING).unwrap();
+    let plots = map.get_plots();
+    let spaces = plots.values().find(|x| x.len() == 28).unwrap();
+    let rings = map.outline(spaces);
+    assert_eq!(rings.len(), 2);
+    assert_eq!(rings[0].len(), 4);
+    assert_eq!(rings[1].len(), 8);
+}
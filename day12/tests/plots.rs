@@ -0,0 +1,54 @@
+use day12::{GameMap, PlotBreakdown};
+use std::collections::HashSet;
+
+const EXAMPLE: &str = "RRRRIICCFF\nRRRRIICCCF\nVVRRRCCFFF\nVVRCCCJFFF\nVVVVCJJCFE\nVVIVCCJJEE\nVVIIICJJEE\nMIIIIIJJEE\nMIIISIJEEE\nMMMISSJEEE\n";
+
+/// Gets the coordinates of each plot, ignoring the keys used to identify them
+fn regions(plots: &PlotBreakdown) -> HashSet<Vec<(i64, i64)>> {
+    plots
+        .values()
+        .map(|spaces| {
+            let mut coords: Vec<(i64, i64)> = spaces.iter().map(|x| x.coord.into()).collect();
+            coords.sort();
+            coords
+        })
+        .collect()
+}
+
+#[test]
+fn parallel_plots_match_sequential() {
+    let map = GameMap::parse(EXAMPLE).unwrap();
+    let expected = regions(&map.get_plots());
+
+    // Use several strip heights, including ones that don't divide the map evenly
+    for num_threads in [1, 3, 4, 10, 16] {
+        let pool = rayon::ThreadPoolBuilder::new()
+            .num_threads(num_threads)
+            .build()
+            .unwrap();
+        let plots = pool.install(|| map.get_plots_parallel());
+        assert_eq!(regions(&plots), expected, "{num_threads} threads");
+    }
+}
+
+#[test]
+fn parallel_keys_match_sequential() {
+    let map = GameMap::parse(EXAMPLE).unwrap();
+    let expected = map.get_plots();
+    for num_threads in [1, 3, 4] {
+        let pool = rayon::ThreadPoolBuilder::new()
+            .num_threads(num_threads)
+            .build()
+            .unwrap();
+        let plots = pool.install(|| map.get_plots_parallel());
+        assert_eq!(plots, expected, "{num_threads} threads");
+    }
+}
+
+#[test]
+fn rejects_ragged_maps() {
+    assert_eq!(
+        GameMap::parse("AAA\nAB\n").err(),
+        Some("Row 1 has 2 cells, but expected 3".to_string())
+    );
+}
@@ -0,0 +1,195 @@
+use regex::Regex;
+
+/// Representation of a system of equations for both x and y
+pub type SystemOfEquations = ((u64, u64, u64), (u64, u64, u64));
+
+/// Representation of the effects of a button press
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ButtonPress {
+    pub label: char,
+    pub x: u64,
+    pub y: u64,
+}
+
+/// Represenation of the prize coordinates
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PrizeLocation {
+    pub x: u64,
+    pub y: u64,
+}
+
+/// Representation of the machine game
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MachineGame {
+    pub a: ButtonPress,
+    pub b: ButtonPress,
+    pub prize: PrizeLocation,
+}
+
+impl MachineGame {
+    /// Corrent the input so that the prize location is much larger
+    pub fn correct_prize(&mut self) {
+        self.prize.x += 10000000000000;
+        self.prize.y += 10000000000000;
+    }
+    /// Returns the machine game as as system of equations
+    pub fn as_system(&self) -> SystemOfEquations {
+        let x_eq = (self.a.x, self.b.x, self.prize.x);
+        let y_eq = (self.a.y, self.b.y, self.prize.y);
+        (x_eq, y_eq)
+    }
+
+    /// Solve the system of equations for the cost to win
+    pub fn solve_for_cost(&self) -> Option<u128> {
+        if let Some((a_presses, b_presses)) = self.solve_system() {
+            return Some((3 * a_presses) + b_presses);
+        }
+        None
+    }
+
+    /// Solves the independent system of equations
+    ///
+    /// Note that this is ONLY for independent systems of equations.
+    pub fn solve_system(&self) -> Option<(u128, u128)> {
+        // Get the x and y equations of the system of equations
+        let (x_eq, y_eq) = self.as_system();
+
+        // Get the components for solving the system of equations for Button B
+        let dividend = (x_eq.0 * y_eq.2) as i128 - (y_eq.0 * x_eq.2) as i128;
+        let divisor = -(y_eq.0 as i128) * x_eq.1 as i128 + (x_eq.0 * y_eq.1) as i128;
+
+        // Check if the result is a positive integer number of presses
+        if dividend % divisor != 0 || dividend / divisor < 0 {
+            return None;
+        }
+
+        // The number of button presses for B is a positive interger, calculate it
+        let b_presses = dividend / divisor;
+
+        // Check whether the number of A presses is a positive integer
+        let a_presses_dividend = x_eq.2 as i128 - (x_eq.1 as i128 * b_presses);
+        let a_presses_divisor = x_eq.0 as i128;
+        if a_presses_dividend % a_presses_divisor != 0 || a_presses_dividend / a_presses_divisor < 0
+        {
+            return None;
+        }
+
+        // Get the number of A button presses
+        let a_presses = a_presses_dividend / a_presses_divisor;
+
+        // Return the number of A and B button presses
+        Some((a_presses as u128, b_presses as u128))
+    }
+}
+
+/// Plans which machines to play in order to win the most prizes without spending more than the
+/// given number of tokens, returning the index and cost of each machine to play
+///
+/// Since every prize is worth the same, playing the cheapest winnable machines first is optimal.
+pub fn plan_budget(games: &[MachineGame], budget: u128) -> Vec<(usize, u128)> {
+    // Get the cost of every winnable machine, cheapest first
+    let mut costs: Vec<(usize, u128)> = games
+        .iter()
+        .enumerate()
+        .filter_map(|(index, game)| game.solve_for_cost().map(|cost| (index, cost)))
+        .collect();
+    costs.sort_by_key(|(index, cost)| (*cost, *index));
+
+    // Play machines until the next one would exceed the budget
+    let mut spent = 0;
+    costs
+        .into_iter()
+        .take_while(|(_index, cost)| {
+            spent += cost;
+            spent <= budget
+        })
+        .collect()
+}
+
+/// Gets the X and Y values captured by the pattern in the text of a machine game, failing if
+/// the pattern isn't found or a value is too large
+fn capture_values(pattern: &Regex, game_text: &str, what: &str) -> Result<(u64, u64), String> {
+    let capture = pattern
+        .captures(game_text)
+        .ok_or_else(|| format!("missing the {what}"))?;
+    let value = |index: usize| {
+        let text = &capture[index];
+        text.parse::<u64>()
+            .map_err(|_| format!("could not parse {text:?} in the {what} as a number"))
+    };
+    Ok((value(1)?, value(2)?))
+}
+
+/// Parse the input string into a list of machine games, failing with the (1-based) index of
+/// any machine game that can't be parsed
+pub fn parse_input(text: &str) -> Result<Vec<MachineGame>, String> {
+    // Create a list for storing the machine games
+    let mut all_games = Vec::new();
+
+    // Create the regex patterns for finding infomration about button presses
+    // and prize locations
+    let a_re = Regex::new(r"Button A: X\+(\d+), Y\+(\d+)").unwrap();
+    let b_re = Regex::new(r"Button B: X\+(\d+), Y\+(\d+)").unwrap();
+    let prize_re = Regex::new(r"Prize: X=(\d+), Y=(\d+)").unwrap();
+
+    // Iterate through the individual game texts
+    let game_texts = text.split("\n\n").filter(|x| !x.trim().is_empty());
+    for (index, game_text) in game_texts.enumerate() {
+        let with_machine = |error: String| format!("Machine {}: {error}", index + 1);
+
+        // Parse the information regarding A button presses
+        let (a_x, a_y) = capture_values(&a_re, game_text, "A button").map_err(with_machine)?;
+        let a = ButtonPress {
+            label: 'a',
+            x: a_x,
+            y: a_y,
+        };
+
+        // Parse the information regarding B button presses
+        let (b_x, b_y) = capture_values(&b_re, game_text, "B button").map_err(with_machine)?;
+        let b = ButtonPress {
+            label: 'b',
+            x: b_x,
+            y: b_y,
+        };
+
+        // Parse the information regarding the prize location
+        let (prize_x, prize_y) =
+            capture_values(&prize_re, game_text, "prize").map_err(with_machine)?;
+        let prize = PrizeLocation {
+            x: prize_x,
+            y: prize_y,
+        };
+
+        // Create the matchine game
+        let game = MachineGame { a, b, prize };
+
+        // Add the game to the list of games
+        all_games.push(game);
+    }
+
+    // Return the list of all parsed games
+    Ok(all_games)
+}
+
+/// Gets the minimum total cost to win the maximum number of games
+pub fn total_cost(games: &[MachineGame]) -> u128 {
+    games.iter().filter_map(|x| x.solve_for_cost()).sum()
+}
+
+/// Solves part one for the puzzle input, getting the fewest tokens needed to win every prize
+/// that can be won
+pub fn part1(input: &str) -> Result<u128, String> {
+    Ok(total_cost(&parse_input(input)?))
+}
+
+/// Solves part two for the puzzle input, getting the fewest tokens needed to win every prize
+/// that can be won once the prizes are corrected
+pub fn part2(input: &str) -> Result<u128, String> {
+    let mut games = parse_input(input)?;
+    games.iter_mut().for_each(|x| x.correct_prize());
+    Ok(total_cost(&games))
+}
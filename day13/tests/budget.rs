@@ -0,0 +1,50 @@
+use day13::{parse_input, plan_budget, MachineGame};
+
+const EXAMPLE: &str = include_str!("../inputs/example.txt");
+
+/// Gets the example machines, with their prizes corrected for part two if requested
+fn example_games(corrected: bool) -> Vec<MachineGame> {
+    let mut games = parse_input(EXAMPLE).unwrap();
+    if corrected {
+        games.iter_mut().for_each(|x| x.correct_prize());
+    }
+    games
+}
+
+#[test]
+fn example_costs() {
+    let costs: Vec<Option<u128>> = example_games(false)
+        .iter()
+        .map(|x| x.solve_for_cost())
+        .collect();
+    assert_eq!(costs, vec![Some(280), None, Some(200), None]);
+}
+
+#[test]
+fn plays_cheapest_machines_first() {
+    let games = example_games(false);
+    assert_eq!(plan_budget(&games, 0), vec![]);
+    assert_eq!(plan_budget(&games, 199), vec![]);
+    assert_eq!(plan_budget(&games, 200), vec![(2, 200)]);
+    assert_eq!(plan_budget(&games, 479), vec![(2, 200)]);
+    assert_eq!(plan_budget(&games, 480), vec![(2, 200), (0, 280)]);
+    assert_eq!(plan_budget(&games, u128::MAX), vec![(2, 200), (0, 280)]);
+}
+
+#[test]
+fn unwinnable_machines_are_never_played() {
+    // Only the second and fourth machines can be won once the prizes are corrected
+    let games = example_games(true);
+    let plan = plan_budget(&games, u128::MAX);
+    let machines: Vec<usize> = plan.iter().map(|(index, _)| *index).collect();
+    assert_eq!(plan.len(), 2);
+    assert!(machines.contains(&1) && machines.contains(&3));
+    let spent: u128 = plan.iter().map(|(_, cost)| cost).sum();
+    assert_eq!(spent, 875318608908);
+}
+
+#[test]
+fn ties_keep_input_order() {
+    let games = [example_games(false)[0]; 3];
+    assert_eq!(plan_budget(&games, 600), vec![(0, 280), (1, 280)]);
+}
@@ -0,0 +1,18 @@
+use day13::parse_input;
+
+#[test]
+fn rejects_malformed_machines() {
+    let text = "Button A: X+94, Y+34\nButton B: X+22, Y+67\nPrize: X=8400, Y=5400\n\n\
+                Button A: X+26, Y+66\nPrize: X=12748, Y=12176\n";
+    assert_eq!(
+        parse_input(text).err(),
+        Some("Machine 2: missing the B button".to_string())
+    );
+    assert_eq!(
+        parse_input("Button A: X+1, Y+99999999999999999999\n").err(),
+        Some(
+            "Machine 1: could not parse \"99999999999999999999\" in the A button as a number"
+                .to_string()
+        )
+    );
+}
@@ -0,0 +1,517 @@
+use std::fmt;
+use std::{
+    cmp::Reverse,
+    collections::{HashMap, HashSet},
+    fmt::{Display, Formatter},
+    hash::{Hash, Hasher},
+    ops::Range,
+};
+
+use rayon::prelude::*;
+use regex::Regex;
+
+/// The width of the full-sized map of the puzzle
+pub const WIDTH: u64 = 101;
+
+/// The height of the full-sized map of the puzzle
+pub const HEIGHT: u64 = 103;
+
+/// Representation of a robot
+#[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Robot {
+    pub id: usize,
+    pub x_pos: u64,
+    pub y_pos: u64,
+    pub x_vel: i64,
+    pub y_vel: i64,
+}
+
+impl Robot {
+    pub fn position(&self) -> (u64, u64) {
+        (self.x_pos, self.y_pos)
+    }
+}
+
+impl PartialEq for Robot {
+    fn eq(&self, other: &Robot) -> bool {
+        self.id == other.id
+    }
+}
+
+impl Eq for Robot {}
+
+impl Hash for Robot {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.id.hash(state);
+    }
+}
+
+/// Models of how robots move when they reach the edge of the map
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Motion {
+    /// Robots teleport to the opposite edge of the map
+    #[default]
+    Wrap,
+    /// Robots reflect off the edges of the map
+    Bounce,
+}
+
+impl Motion {
+    /// Gets the position along an axis of the given size after moving from the given position
+    /// at the given velocity for n seconds
+    pub fn position(&self, position: u64, velocity: i64, n: u64, size: u64) -> u64 {
+        // Get the extended travel position
+        let extrapolated = velocity * n as i64 + position as i64;
+
+        match self {
+            // Correct for the wrap-around teleporation, putting negative positions back on the
+            // map by adding the size
+            Motion::Wrap => extrapolated.rem_euclid(size as i64) as u64,
+            // Reflect back and forth across the map, which repeats every two traversals
+            Motion::Bounce => {
+                let last = size as i64 - 1;
+                if last == 0 {
+                    return 0;
+                }
+                let folded = extrapolated.rem_euclid(2 * last);
+                if folded > last {
+                    (2 * last - folded) as u64
+                } else {
+                    folded as u64
+                }
+            }
+        }
+    }
+
+    /// Gets a number of seconds after which the robots are guaranteed to have looped back to
+    /// their current state on a map of the given size
+    pub fn cycle_length(&self, width: u64, height: u64) -> u64 {
+        match self {
+            Motion::Wrap => width * height,
+            Motion::Bounce => {
+                (2 * width.saturating_sub(1)).max(1) * (2 * height.saturating_sub(1)).max(1)
+            }
+        }
+    }
+}
+
+/// A frame in which the robots are in the same positions as they were in an earlier frame
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Repeat {
+    /// The earliest second with the robots in these positions
+    pub first: u64,
+    /// The later second with the robots back in these positions
+    pub second: u64,
+}
+
+/// The result of analyzing the frames of a full cycle of robot movement
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CycleAnalysis {
+    /// The number of seconds until the robots are first back in their starting positions
+    pub period: u64,
+    /// The frames before the end of the period that repeat an earlier frame, in order
+    pub repeats: Vec<Repeat>,
+    /// The number of frames whose hash matched an earlier frame that turned out to be
+    /// different, which is only known when the matches are confirmed
+    pub collisions: usize,
+}
+
+/// Representation of the game map
+#[derive(Debug, Clone)]
+pub struct GameMap {
+    pub robots: Vec<Robot>,
+    pub width: u64,
+    pub height: u64,
+    pub motion: Motion,
+}
+
+impl GameMap {
+    /// Parses the map from the provided string, failing with the line number of any robot that
+    /// can't be parsed
+    pub fn parse(text: &str, width: u64, height: u64) -> Result<Self, String> {
+        // Create a list for storing robots
+        let mut robots = Vec::new();
+
+        // Create the regex pattern for parsing robot information
+        let re = Regex::new(r"p=(\d+),(\d+) v=(-?\d+),(-?\d+)").unwrap();
+
+        // Iterate through the string line by line
+        for (id, line) in text.trim().lines().enumerate() {
+            // Parse the line of text for the robot informations
+            let line_number = id + 1;
+            let Some((_text, [x_pos, y_pos, x_vel, y_vel])) =
+                re.captures(line).map(|x| x.extract())
+            else {
+                return Err(format!(
+                    "Line {line_number}: expected a robot like p=0,4 v=3,-3, got {line:?}"
+                ));
+            };
+            let too_large = |text: &str| format!("Line {line_number}: {text} is too large");
+
+            // Create the robot
+            let robot = Robot {
+                id,
+                x_pos: x_pos.parse::<u64>().map_err(|_| too_large(x_pos))?,
+                y_pos: y_pos.parse::<u64>().map_err(|_| too_large(y_pos))?,
+                x_vel: x_vel.parse::<i64>().map_err(|_| too_large(x_vel))?,
+                y_vel: y_vel.parse::<i64>().map_err(|_| too_large(y_vel))?,
+            };
+
+            // Add the robot to the list
+            robots.push(robot);
+        }
+
+        // Return a new map with the given rows
+        Ok(Self {
+            robots,
+            width,
+            height,
+            motion: Motion::Wrap,
+        })
+    }
+
+    // Extrapolates the location of all the robots after n seconds
+    pub fn extrapolate(&mut self, n: u64) {
+        // Iterate through the robots, updating their positions using the motion model
+        for robot in &mut self.robots {
+            robot.x_pos = self
+                .motion
+                .position(robot.x_pos, robot.x_vel, n, self.width);
+            robot.y_pos = self
+                .motion
+                .position(robot.y_pos, robot.y_vel, n, self.height);
+        }
+    }
+
+    /// Finds the second with the lowest entropy (fewest groupings of robots) within a full cycle
+    /// of robot movement, returning the second and the number of groupings
+    ///
+    /// Each second is evaluated independently in parallel by extrapolating a copy of the map
+    /// from its current state, with ties going to the earliest second.
+    pub fn find_lowest_entropy(&self) -> (u64, usize) {
+        self.find_lowest_entropies(1)
+            .pop()
+            .expect("Entropy detection failed")
+    }
+
+    /// Finds the k seconds with the lowest entropy within a full cycle of robot movement,
+    /// returning the second and number of groupings for each, ordered from most to least ordered
+    ///
+    /// Ties are ordered by the earliest second.
+    pub fn find_lowest_entropies(&self, k: usize) -> Vec<(u64, usize)> {
+        // The robots are guaranteed to have looped back to the current state after this long
+        let cycle_length = self.motion.cycle_length(self.width, self.height);
+
+        // Evaluate the number of groupings for every second in the cycle
+        let mut frames = self.scan_frames(1..cycle_length, |frame| frame.get_groupings().len());
+
+        // Keep only the k most ordered frames
+        frames.sort_by_key(|(secs_elapsed, num_groupings)| (*num_groupings, *secs_elapsed));
+        frames.truncate(k);
+        frames
+    }
+
+    /// Finds the second with the most left-right symmetry within a full cycle of robot
+    /// movement, returning the second and its symmetry score
+    ///
+    /// Ties go to the earliest second.
+    pub fn find_most_symmetric(&self) -> (u64, usize) {
+        self.find_most_symmetric_frames(1)
+            .pop()
+            .expect("Symmetry detection failed")
+    }
+
+    /// Finds the k seconds with the most left-right symmetry within a full cycle of robot
+    /// movement, returning the second and symmetry score for each, ordered from most to least
+    /// symmetric
+    ///
+    /// Ties are ordered by the earliest second.
+    pub fn find_most_symmetric_frames(&self, k: usize) -> Vec<(u64, usize)> {
+        // The robots are guaranteed to have looped back to the current state after this long
+        let cycle_length = self.motion.cycle_length(self.width, self.height);
+
+        // Evaluate the symmetry score for every second in the cycle
+        let mut frames = self.scan_frames(1..cycle_length, |frame| frame.symmetry_score());
+
+        // Keep only the k most symmetric frames
+        frames.sort_by_key(|(secs_elapsed, score)| (Reverse(*score), *secs_elapsed));
+        frames.truncate(k);
+        frames
+    }
+
+    /// Gets the safety factor for every second within a full cycle of robot movement, starting
+    /// from the current state, in order of the seconds elapsed
+    pub fn safety_series(&self) -> Vec<(u64, usize)> {
+        let cycle_length = self.motion.cycle_length(self.width, self.height);
+        self.scan_frames(0..cycle_length, |frame| frame.safety_factor())
+    }
+
+    /// Analyzes the frames of a full cycle of robot movement from the current state, finding
+    /// how long it takes for the robots to return to their current positions and any frames
+    /// before then that repeat an earlier one
+    ///
+    /// Frames are compared by their hash, which is calculated for every second in parallel.  If
+    /// requested, frames with matching hashes are confirmed to have the same positions too.
+    pub fn analyze_cycle(&self, confirm: bool) -> CycleAnalysis {
+        // Get the hash of every frame in the cycle, including its end
+        let cycle_length = self.motion.cycle_length(self.width, self.height);
+        let hashes = self.scan_frames(0..cycle_length + 1, |frame| frame.frame_hash());
+
+        // Create a map of each hash to the earliest seconds with it, and the analysis so far
+        let mut seen: HashMap<u64, Vec<u64>> = HashMap::new();
+        let mut repeats = Vec::new();
+        let mut collisions = 0;
+
+        // Iterate through the frames in order until the robots are back where they started
+        for (secs_elapsed, hash) in hashes {
+            // Find the earliest matching frame, confirming the positions match if requested
+            let earlier_seconds = seen.entry(hash).or_default();
+            let mut matching = None;
+            for earlier_secs_elapsed in earlier_seconds.iter() {
+                if !confirm
+                    || self.frame_at(*earlier_secs_elapsed).as_state()
+                        == self.frame_at(secs_elapsed).as_state()
+                {
+                    matching = Some(*earlier_secs_elapsed);
+                    break;
+                }
+                collisions += 1;
+            }
+
+            // Record the frame as new, a return to the start, or a repeat of another frame
+            match matching {
+                None => earlier_seconds.push(secs_elapsed),
+                Some(0) => {
+                    return CycleAnalysis {
+                        period: secs_elapsed,
+                        repeats,
+                        collisions,
+                    }
+                }
+                Some(first) => repeats.push(Repeat {
+                    first,
+                    second: secs_elapsed,
+                }),
+            }
+        }
+
+        // The motion model guarantees the robots are back at the end of the cycle
+        panic!("The robots never returned to their starting positions");
+    }
+
+    /// Creates a copy of the map after the given number of seconds elapsed
+    fn frame_at(&self, secs_elapsed: u64) -> GameMap {
+        let mut frame = self.clone();
+        frame.extrapolate(secs_elapsed);
+        frame
+    }
+
+    /// Measures the frame for each of the given seconds elapsed, returning the second and the
+    /// measurement for each in order of the seconds elapsed
+    ///
+    /// Each second is evaluated independently in parallel by extrapolating a copy of the map
+    /// from its current state.
+    pub fn scan_frames<T, M>(&self, seconds: Range<u64>, measure: M) -> Vec<(u64, T)>
+    where
+        T: Send,
+        M: Fn(&GameMap) -> T + Sync,
+    {
+        seconds
+            .into_par_iter()
+            .map(|secs_elapsed| {
+                let mut frame = self.clone();
+                frame.extrapolate(secs_elapsed);
+                (secs_elapsed, measure(&frame))
+            })
+            .collect()
+    }
+
+    /// Gets the neightbors for given robot, which is any robot within a single square
+    pub fn neighbors(&self, robot: &Robot) -> HashSet<&Robot> {
+        self.robots
+            .iter()
+            .filter(|x| robot.x_pos.abs_diff(x.x_pos) <= 1 && robot.y_pos.abs_diff(x.y_pos) <= 1)
+            .collect()
+    }
+
+    /// Gathers a grouping of robots for the given robot, searching recursively if necessary
+    pub fn gather_grouping(&self, robot: &Robot, grouping: &mut HashSet<Robot>) -> HashSet<Robot> {
+        // Create a list for storing the robot grouping
+        let mut discovered_robots = HashSet::new();
+
+        // If the robot is not already grouping, it should be added
+        if !grouping.contains(robot) {
+            // Add the robot to the grouping and list of discovered robots
+            grouping.insert(*robot);
+            discovered_robots.insert(*robot);
+
+            // Get the neighbors of the current robot
+            let neighbors = self.neighbors(robot);
+
+            // Recursively checking if the new robot is part of the grouping
+            for new_neighbor in neighbors {
+                let other_robots = self.gather_grouping(new_neighbor, grouping);
+                discovered_robots.extend(other_robots);
+            }
+        }
+
+        // Return the list of discovered robots in the grouping
+        discovered_robots
+    }
+
+    /// Gets the groupings for the current state of the map
+    pub fn get_groupings(&self) -> Vec<HashSet<Robot>> {
+        // Create a list for groupings of robots
+        let mut groupings = Vec::new();
+
+        // Create a hash set for keeping track of checked robots
+        let mut checked_robots = HashSet::new();
+
+        // Iterate through the robots one by one
+        for robot in &self.robots {
+            // If the robot has already been checked, skip it
+            if checked_robots.contains(robot) {
+                continue;
+            }
+
+            // Create a hash set for storing groupings
+            let mut grouping = HashSet::new();
+
+            // Get the grouping for the given robot
+            let explored = self.gather_grouping(robot, &mut grouping);
+
+            // Add the robots from the grouping to the list of checked robots
+            checked_robots.extend(explored);
+
+            // Add the grouped robots to the list
+            groupings.push(grouping);
+        }
+
+        // Return the completed list of grouped robots
+        groupings
+    }
+
+    /// Scores how symmetric the current state of the map is about its vertical center line, as
+    /// the number of spaces with robots whose mirror image across the line also has robots
+    ///
+    /// Spaces on the center line are their own mirror image, so they aren't counted.
+    pub fn symmetry_score(&self) -> usize {
+        // Get the spaces with robots in them
+        let occupied: HashSet<(u64, u64)> = self.robots.iter().map(|x| x.position()).collect();
+
+        // Count the spaces off the center line whose mirror image is also occupied
+        occupied
+            .iter()
+            .filter(|(x, y)| {
+                let mirrored_x = self.width - 1 - x;
+                mirrored_x != *x && occupied.contains(&(mirrored_x, *y))
+            })
+            .count()
+    }
+
+    /// Calculates the safety factor for the current state of the map
+    pub fn safety_factor(&self) -> usize {
+        // Get the halfway marks
+        let half_width = self.width / 2;
+        let half_height = self.height / 2;
+
+        // println!("Half width = {half_width}. half height = {half_height}");
+
+        // Create lists for storing robots
+        let mut topleft = Vec::new();
+        let mut topright = Vec::new();
+        let mut bottomright = Vec::new();
+        let mut bottomleft = Vec::new();
+
+        // Place robots in their respective quadrant groups
+        for robot in &self.robots {
+            // Get shorthand for X and Y coordinates
+            let x = robot.x_pos;
+            let y = robot.y_pos;
+
+            // Place the robot in the appropriate quadrant group
+            if x < half_width && y < half_height {
+                topleft.push(robot);
+            } else if x > half_width && y < half_height {
+                topright.push(robot);
+            } else if x > half_width && y > half_height {
+                bottomright.push(robot);
+            } else if x < half_width && y > half_height {
+                bottomleft.push(robot);
+            }
+        }
+
+        // Return the product of the group quantities
+        topleft.len() * topright.len() * bottomright.len() * bottomleft.len()
+    }
+
+    /// Gets the state of the map as a unique vector
+    pub fn as_state(&self) -> Vec<(u64, u64)> {
+        let mut states = Vec::new();
+        for robot in &self.robots {
+            states.push((robot.x_pos, robot.y_pos));
+        }
+        states
+    }
+
+    /// Gets a hash of the positions of the robots, rolled over the robots in order, so that
+    /// frames with the same hash very likely have the same state
+    pub fn frame_hash(&self) -> u64 {
+        self.robots.iter().fold(0, |hash, robot| {
+            let position = robot.y_pos * self.width + robot.x_pos;
+            hash.wrapping_mul(0x100000001b3).wrapping_add(position + 1)
+        })
+    }
+}
+
+impl Display for GameMap {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        // Create a string for pushing map unformation
+        let mut map_string = String::new();
+
+        // Iterate through tha possible map coordinates
+        for row_index in 0..self.height {
+            for col_index in 0..self.width {
+                let mut space_string = String::new();
+                // space_string.push('[');
+
+                let located_robots: Vec<&Robot> = self
+                    .robots
+                    .iter()
+                    .filter(|x| x.position() == (col_index, row_index))
+                    .collect();
+                let num_robots = located_robots.len();
+
+                if num_robots != 0 {
+                    space_string.push_str(&num_robots.to_string());
+                } else {
+                    space_string.push('.');
+                }
+                // space_string.push(']');
+
+                map_string.push_str(&space_string);
+            }
+
+            // Add a newline character to the end of the row
+            map_string.push('\n');
+        }
+
+        write!(f, "{}", map_string)
+    }
+}
+
+/// Solves part one for the puzzle input, getting the safety factor of the full-sized map after
+/// 100 seconds
+pub fn part1(input: &str) -> Result<usize, String> {
+    let mut gamemap = GameMap::parse(input, WIDTH, HEIGHT)?;
+    gamemap.extrapolate(100);
+    Ok(gamemap.safety_factor())
+}
+
+/// Solves part two for the puzzle input, getting the number of seconds until the robots on the
+/// full-sized map are arranged with the lowest entropy, which is when they draw the tree
+pub fn part2(input: &str) -> Result<u64, String> {
+    let gamemap = GameMap::parse(input, WIDTH, HEIGHT)?;
+    Ok(gamemap.find_lowest_entropy().0)
+}
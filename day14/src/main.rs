@@ -1,292 +1,160 @@
-use std::fmt;
-use std::{
-    collections::HashSet,
-    fmt::{Display, Formatter},
-    fs,
-    hash::{Hash, Hasher},
-};
+use std::fs;
 
-use clap::Parser;
-use regex::Regex;
+use clap::{Parser, ValueEnum};
+use common::{fail, read_input, About, Failure, Feature, Format};
+use day14::{GameMap, Motion, HEIGHT, WIDTH};
 
 /// CLI arguments
 #[derive(Parser)]
 struct CliArgs {
     part: u64,
     filepath: String,
+    /// Report the given number of frames with the lowest entropy instead of only the lowest
+    #[arg(long)]
+    top: Option<usize>,
+    /// How to detect the frame with the tree
+    #[arg(long, value_enum, default_value_t = Detector::Entropy)]
+    detector: Detector,
+    /// Render the map for each of the reported frames
+    #[arg(long)]
+    render: bool,
+    /// Have the robots bounce off the edges of the map instead of wrapping around
+    #[arg(long)]
+    bounce: bool,
+    /// Write the safety factor for every second of a full cycle to the given CSV file, and
+    /// report the seconds with the lowest and highest safety factor
+    #[arg(long)]
+    safety_series: Option<String>,
+    /// Report how many seconds it takes the robots to return to their starting positions, and
+    /// any frames before then that repeat an earlier frame
+    #[arg(long)]
+    cycles: bool,
+    /// Confirm that frames with matching hashes have the same robot positions when analyzing
+    /// the cycle
+    #[arg(long, requires = "cycles")]
+    confirm: bool,
+    /// Parse the input and print statistics about its structure instead of solving it
+    #[arg(long)]
+    parse_only: bool,
+    /// The format in which errors are reported
+    #[arg(long, value_enum, default_value_t = Format::Text)]
+    format: Format,
 }
 
-/// Representation of a robot
-#[derive(Debug, Clone, Copy)]
-struct Robot {
-    id: usize,
-    x_pos: u64,
-    y_pos: u64,
-    x_vel: i64,
-    y_vel: i64,
-}
-
-impl Robot {
-    fn position(&self) -> (u64, u64) {
-        (self.x_pos, self.y_pos)
-    }
-}
-
-impl PartialEq for Robot {
-    fn eq(&self, other: &Robot) -> bool {
-        self.id == other.id
-    }
-}
-
-impl Eq for Robot {}
-
-impl Hash for Robot {
-    fn hash<H: Hasher>(&self, state: &mut H) {
-        self.id.hash(state);
-    }
-}
+/// Description of the puzzle solved by this binary
+const ABOUT: About = About {
+    day: 14,
+    title: "Restroom Redoubt",
+    input: "One robot per line, as its position (p=X,Y) and velocity (v=X,Y)",
+    parts: &[1, 2],
+    features: &[Feature::Visualization, Feature::Parallel],
+};
 
-/// Representation of the game map
-#[derive(Debug, Clone)]
-struct GameMap {
-    robots: Vec<Robot>,
-    width: u64,
-    height: u64,
+/// Ways of detecting the frame in which the robots form a tree
+#[derive(Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum Detector {
+    /// Find the frame where the robots form the fewest groupings
+    Entropy,
+    /// Find the frame where the robots are most symmetric about the vertical center line
+    Symmetry,
 }
 
-impl GameMap {
-    /// Parses the map from the provided string
-    fn parse(text: &str, width: u64, height: u64) -> Self {
-        // Create a list for storing robots
-        let mut robots = Vec::new();
-
-        // Create the regex pattern for parsing robot information
-        let re = Regex::new(r"p=(\d+),(\d+) v=(-?\d+),(-?\d+)").unwrap();
-
-        // Iterate through the string line by line
-        for (id, line) in text.trim().lines().enumerate() {
-            // Parse the line of text for the robot informations
-            let Some((_text, [x_pos, y_pos, x_vel, y_vel])) =
-                re.captures(line).map(|x| x.extract())
-            else {
-                panic!("Could not parse text for robot information")
-            };
-
-            // Create the robot
-            let robot = Robot {
-                id,
-                x_pos: x_pos.parse::<u64>().unwrap(),
-                y_pos: y_pos.parse::<u64>().unwrap(),
-                x_vel: x_vel.parse::<i64>().unwrap(),
-                y_vel: y_vel.parse::<i64>().unwrap(),
-            };
-
-            // Add the robot to the list
-            robots.push(robot);
-        }
-
-        // Return a new map with the given rows
-        Self {
-            robots,
-            width,
-            height,
-        }
-    }
-
-    // Extrapolates the location of all the robots after n seconds
-    fn extrapolate(&mut self, n: u64) {
-        // Iterate through the robots
-        for robot in &mut self.robots {
-            // Get the extended X and Y travel position
-            let extrapolated_x = robot.x_vel * n as i64 + robot.x_pos as i64;
-            let extrapolated_y = robot.y_vel * n as i64 + robot.y_pos as i64;
-
-            // Correct for the wrap-around teleporation
-            let mut map_x = extrapolated_x % self.width as i64;
-            let mut map_y = extrapolated_y % self.height as i64;
-
-            // If the map X or Y is negative, put it back on the map by adding
-            // the width or height respectively
-            if map_x < 0 {
-                map_x += self.width as i64;
-            }
-            if map_y < 0 {
-                map_y += self.height as i64;
-            }
-
-            // Update the robots position
-            robot.x_pos = map_x as u64;
-            robot.y_pos = map_y as u64;
+impl Detector {
+    /// Gets the name of the measurement the detector makes of each frame
+    fn measurement(&self) -> &'static str {
+        match self {
+            Detector::Entropy => "groupings",
+            Detector::Symmetry => "symmetry",
         }
     }
 
-    /// Gets the neightbors for given robot, which is any robot within a single square
-    fn neighbors(&self, robot: &Robot) -> HashSet<&Robot> {
-        self.robots
-            .iter()
-            .filter(|x| robot.x_pos.abs_diff(x.x_pos) <= 1 && robot.y_pos.abs_diff(x.y_pos) <= 1)
-            .collect()
-    }
-
-    /// Gathers a grouping of robots for the given robot, searching recursively if necessary
-    fn gather_grouping(&self, robot: &Robot, grouping: &mut HashSet<Robot>) -> HashSet<Robot> {
-        // Create a list for storing the robot grouping
-        let mut discovered_robots = HashSet::new();
-
-        // If the robot is not already grouping, it should be added
-        if !grouping.contains(robot) {
-            // Add the robot to the grouping and list of discovered robots
-            grouping.insert(*robot);
-            discovered_robots.insert(*robot);
-
-            // Get the neighbors of the current robot
-            let neighbors = self.neighbors(robot);
-
-            // Recursively checking if the new robot is part of the grouping
-            for new_neighbor in neighbors {
-                let other_robots = self.gather_grouping(new_neighbor, grouping);
-                discovered_robots.extend(other_robots);
-            }
+    /// Finds the k frames most likely to have the tree in the given map, returning the second
+    /// and measurement for each, ordered from most to least likely
+    fn find_frames(&self, gamemap: &GameMap, k: usize) -> Vec<(u64, usize)> {
+        match self {
+            Detector::Entropy => gamemap.find_lowest_entropies(k),
+            Detector::Symmetry => gamemap.find_most_symmetric_frames(k),
         }
-
-        // Return the list of discovered robots in the grouping
-        discovered_robots
     }
+}
 
-    /// Gets the groupings for the current state of the map
-    fn get_groupings(&self) -> Vec<HashSet<Robot>> {
-        // Create a list for groupings of robots
-        let mut groupings = Vec::new();
-
-        // Create a hash set for keeping track of checked robots
-        let mut checked_robots = HashSet::new();
-
-        // Iterate through the robots one by one
-        for robot in &self.robots {
-            // If the robot has already been checked, skip it
-            if checked_robots.contains(robot) {
-                continue;
-            }
-
-            // Create a hash set for storing groupings
-            let mut grouping = HashSet::new();
-
-            // Get the grouping for the given robot
-            let explored = self.gather_grouping(robot, &mut grouping);
+/// Main entry function
+fn main() {
+    // Describe the puzzle instead if requested
+    common::handle_about::<CliArgs>(&ABOUT);
 
-            // Add the robots from the grouping to the list of checked robots
-            checked_robots.extend(explored);
+    // Parse CLI arguments
+    let cli = CliArgs::parse();
 
-            // Add the grouped robots to the list
-            groupings.push(grouping);
-        }
+    // Report errors in the requested format
+    common::init(cli.format);
 
-        // Return the completed list of grouped robots
-        groupings
+    // Only parse the input if requested
+    if cli.parse_only {
+        report_structure(cli.filepath);
     }
 
-    /// Calculates the safety factor for the current state of the map
-    fn safety_factor(&self) -> usize {
-        // Get the halfway marks
-        let half_width = self.width / 2;
-        let half_height = self.height / 2;
-
-        // println!("Half width = {half_width}. half height = {half_height}");
-
-        // Create lists for storing robots
-        let mut topleft = Vec::new();
-        let mut topright = Vec::new();
-        let mut bottomright = Vec::new();
-        let mut bottomleft = Vec::new();
-
-        // Place robots in their respective quadrant groups
-        for robot in &self.robots {
-            // Get shorthand for X and Y coordinates
-            let x = robot.x_pos;
-            let y = robot.y_pos;
-
-            // Place the robot in the appropriate quadrant group
-            if x < half_width && y < half_height {
-                topleft.push(robot);
-            } else if x > half_width && y < half_height {
-                topright.push(robot);
-            } else if x > half_width && y > half_height {
-                bottomright.push(robot);
-            } else if x < half_width && y > half_height {
-                bottomleft.push(robot);
-            }
-        }
+    // Get the model of how the robots move at the edges of the map
+    let motion = if cli.bounce {
+        Motion::Bounce
+    } else {
+        Motion::Wrap
+    };
 
-        // Return the product of the group quantities
-        topleft.len() * topright.len() * bottomright.len() * bottomleft.len()
+    // Write the safety factor series if requested
+    if let Some(series_filepath) = cli.safety_series {
+        write_safety_series(cli.filepath.clone(), series_filepath, motion);
     }
 
-    /// Gets the state of the map as a unique vector
-    fn as_state(&self) -> Vec<(u64, u64)> {
-        let mut states = Vec::new();
-        for robot in &self.robots {
-            states.push((robot.x_pos, robot.y_pos));
-        }
-        states
-    }
-}
-
-impl Display for GameMap {
-    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
-        // Create a string for pushing map unformation
-        let mut map_string = String::new();
-
-        // Iterate through tha possible map coordinates
-        for row_index in 0..self.height {
-            for col_index in 0..self.width {
-                let mut space_string = String::new();
-                // space_string.push('[');
-
-                let located_robots: Vec<&Robot> = self
-                    .robots
-                    .iter()
-                    .filter(|x| x.position() == (col_index, row_index))
-                    .collect();
-                let num_robots = located_robots.len();
-
-                if num_robots != 0 {
-                    space_string.push_str(&num_robots.to_string());
-                } else {
-                    space_string.push('.');
-                }
-                // space_string.push(']');
-
-                map_string.push_str(&space_string);
-            }
-
-            // Add a newline character to the end of the row
-            map_string.push('\n');
-        }
-
-        write!(f, "{}", map_string)
+    // Analyze the cycle of robot movement if requested
+    if cli.cycles {
+        print_cycle_analysis(cli.filepath.clone(), motion, cli.confirm);
     }
-}
-
-/// Main entry function
-fn main() {
-    // Parse CLI arguments
-    let cli = CliArgs::parse();
 
     // Run the code for the desired challenge part
     match cli.part {
-        1 => main_part_one(cli.filepath),
-        2 => main_part_two(cli.filepath),
+        1 => main_part_one(cli.filepath, motion),
+        2 => match cli.top {
+            Some(k) => print_top_frames(cli.filepath, k, cli.render, motion, cli.detector),
+            None => main_part_two(cli.filepath, motion, cli.detector),
+        },
         _ => panic!("Invalid selection part selection!"),
     }
 }
 
+/// Parses the robots on the full-size map, exiting with a parse failure if any can't be parsed
+fn read_map(contents: &str) -> GameMap {
+    GameMap::parse(contents, WIDTH, HEIGHT).unwrap_or_else(|error| fail(Failure::Parse(error)))
+}
+
+/// Parses the robots, then prints the number of robots, how many of them start outside the map,
+/// and how many don't move and exits
+fn report_structure(filepath: String) -> ! {
+    let gamemap = read_map(&read_input(filepath));
+    let robots = &gamemap.robots;
+    let num_outside = robots
+        .iter()
+        .filter(|x| x.x_pos >= gamemap.width || x.y_pos >= gamemap.height)
+        .count();
+    let num_stationary = robots
+        .iter()
+        .filter(|x| x.x_vel == 0 && x.y_vel == 0)
+        .count();
+    common::report_parse(&[
+        ("robots", robots.len()),
+        ("outside map", num_outside),
+        ("stationary", num_stationary),
+    ]);
+}
+
 /// Runs part one
-fn main_part_one(filepath: String) {
+fn main_part_one(filepath: String, motion: Motion) {
     // Get the trail ratings
-    let contents = fs::read_to_string(filepath).expect("Invalid filepath");
+    let contents = read_input(filepath);
 
     // Parse the inout file contents into the game map
-    let mut gamemap = GameMap::parse(&contents, 101, 103);
+    let mut gamemap = read_map(&contents);
+    gamemap.motion = motion;
 
     // Simulate 100 seconds
     gamemap.extrapolate(100);
@@ -297,57 +165,121 @@ fn main_part_one(filepath: String) {
 }
 
 /// Runs part two
-fn main_part_two(filepath: String) {
+fn main_part_two(filepath: String, motion: Motion, detector: Detector) {
     // Get the trail ratings
-    let contents = fs::read_to_string(filepath).expect("Invalid filepath");
+    let contents = read_input(filepath);
 
     // Parse the inout file contents into the game map
-    let mut gamemap = GameMap::parse(&contents, 101, 103);
+    let mut gamemap = read_map(&contents);
+    gamemap.motion = motion;
 
-    // Get the original map and it's state for comparison
-    let mut original_game = gamemap.clone();
-    let orginal_state = gamemap.as_state();
-
-    // Simulate until the Christmas tree is shown
-    let mut secs_elapsed = 0;
+    // Print information about the search
+    println!("Searching through game states in parallel...");
+    match detector {
+        Detector::Entropy => println!("The game with the lowest entropy will be displayed"),
+        Detector::Symmetry => println!("The game with the most symmetry will be displayed"),
+    }
 
-    // Keep track of the amount of order in the map
-    let mut entropy: Option<(u64, usize)> = None;
+    // Get the time elapsed for the moment detected
+    let (elapsed, ..) = match detector {
+        Detector::Entropy => gamemap.find_lowest_entropy(),
+        Detector::Symmetry => gamemap.find_most_symmetric(),
+    };
 
-    // Print information about the search
-    println!("Searching through game states until loop detected...");
-    println!("The game with the lowest entropy will be displayed");
+    // Create the state of the map at the moment detected
+    gamemap.extrapolate(elapsed);
 
-    // Simulate the robot's actions
-    loop {
-        // Simulate the next round of robots
-        secs_elapsed += 1;
-        gamemap.extrapolate(1);
+    // Print the map and the number of seconds elapsed
+    println!("{gamemap}");
+    println!("{elapsed}");
+}
 
-        // Get the state of the current iteration
-        let new_state = gamemap.as_state();
+/// Prints the given number of frames most likely to have the tree according to the given
+/// detector, optionally rendering each one
+fn print_top_frames(filepath: String, k: usize, render: bool, motion: Motion, detector: Detector) {
+    // Get the trail ratings
+    let contents = read_input(filepath);
 
-        // If the robots have looped into the same state again, stop searching
-        if new_state == orginal_state {
-            break;
+    // Parse the inout file contents into the game map
+    let mut gamemap = read_map(&contents);
+    gamemap.motion = motion;
+
+    // Print the second and measurement for each of the most likely frames
+    println!("second,{}", detector.measurement());
+    for (elapsed, measurement) in detector.find_frames(&gamemap, k) {
+        println!("{elapsed},{measurement}");
+
+        // Render the frame if requested
+        if render {
+            let mut frame = gamemap.clone();
+            frame.extrapolate(elapsed);
+            println!("{frame}");
         }
+    }
+}
 
-        // Get the number of groupings in this iteration
-        let num_groupings = gamemap.get_groupings().len();
+/// Writes the safety factor for every second of a full cycle to the given CSV file, and prints
+/// the seconds with the lowest and highest safety factor
+fn write_safety_series(filepath: String, series_filepath: String, motion: Motion) {
+    // Get the trail ratings
+    let contents = read_input(filepath);
 
-        // If the entropy is not set and the entropy is lower, save the information
-        if entropy.is_none() || num_groupings < entropy.unwrap().1 {
-            entropy = Some((secs_elapsed, num_groupings))
-        }
+    // Parse the inout file contents into the game map
+    let mut gamemap = read_map(&contents);
+    gamemap.motion = motion;
+
+    // Get the safety factor for every second of the cycle
+    let series = gamemap.safety_series();
+
+    // Write the series to the given file
+    let mut csv = String::from("second,safety_factor\n");
+    for (elapsed, safety_factor) in &series {
+        csv.push_str(&format!("{elapsed},{safety_factor}\n"));
     }
+    fs::write(&series_filepath, csv).unwrap_or_else(|error| {
+        fail(Failure::Io(format!(
+            "Could not write {series_filepath}: {error}"
+        )))
+    });
+
+    // Print the earliest seconds with the lowest and highest safety factors
+    let (min_elapsed, min_safety_factor) = series
+        .iter()
+        .min_by_key(|(elapsed, safety_factor)| (*safety_factor, *elapsed))
+        .expect("The cycle is empty");
+    let (max_elapsed, max_safety_factor) = series
+        .iter()
+        .max_by_key(|(elapsed, safety_factor)| (*safety_factor, std::cmp::Reverse(*elapsed)))
+        .expect("The cycle is empty");
+    println!("Lowest safety factor: {min_safety_factor} at second {min_elapsed}");
+    println!("Highest safety factor: {max_safety_factor} at second {max_elapsed}");
+}
 
-    // Get the time elapsed for the moment saved
-    let (elapsed, ..) = entropy.expect("Entropy detection failed");
+/// Prints the frames that repeat an earlier frame as CSV, followed by the number of seconds it
+/// takes the robots to return to their starting positions
+fn print_cycle_analysis(filepath: String, motion: Motion, confirm: bool) {
+    // Get the trail ratings
+    let contents = read_input(filepath);
 
-    // Create the state of the map with the lowest entropy
-    original_game.extrapolate(elapsed);
+    // Parse the inout file contents into the game map
+    let mut gamemap = read_map(&contents);
+    gamemap.motion = motion;
 
-    // Print the map and the number of seconds elapsed
-    println!("{original_game}");
-    println!("{elapsed}");
+    // Analyze the frames of the cycle
+    let analysis = gamemap.analyze_cycle(confirm);
+
+    // Print the repeated frames and the period
+    println!("first,second");
+    for repeat in &analysis.repeats {
+        println!("{},{}", repeat.first, repeat.second);
+    }
+    println!(
+        "The robots return to their starting positions after {} seconds",
+        analysis.period
+    );
+
+    // Print the number of hash collisions found if they were checked for
+    if confirm {
+        println!("Hash collisions: {}", analysis.collisions);
+    }
 }
@@ -0,0 +1,44 @@
+use day14::{GameMap, Motion, Repeat};
+
+const EXAMPLE: &str = include_str!("../inputs/example.txt");
+
+#[test]
+fn example_returns_after_full_cycle() {
+    let gamemap = GameMap::parse(EXAMPLE, 11, 7).unwrap();
+    let analysis = gamemap.analyze_cycle(true);
+    assert_eq!(analysis.period, 77);
+    assert!(analysis.repeats.is_empty());
+    assert_eq!(analysis.collisions, 0);
+}
+
+#[test]
+fn stationary_robots_return_immediately() {
+    let gamemap = GameMap::parse("p=1,2 v=0,0\np=3,4 v=0,0\n", 11, 7).unwrap();
+    assert_eq!(gamemap.analyze_cycle(false).period, 1);
+}
+
+#[test]
+fn bouncing_robot_repeats_intermediate_frames() {
+    // The robot passes back through the middle on its way back to where it started
+    let mut gamemap = GameMap::parse("p=0,0 v=1,0\n", 3, 1).unwrap();
+    gamemap.motion = Motion::Bounce;
+    let analysis = gamemap.analyze_cycle(true);
+    assert_eq!(analysis.period, 4);
+    assert_eq!(
+        analysis.repeats,
+        [Repeat {
+            first: 1,
+            second: 3
+        }]
+    );
+}
+
+#[test]
+fn matching_hashes_match_states() {
+    let gamemap = GameMap::parse(EXAMPLE, 11, 7).unwrap();
+    let mut frame = gamemap.clone();
+    frame.extrapolate(77);
+    assert_eq!(frame.frame_hash(), gamemap.frame_hash());
+    frame.extrapolate(1);
+    assert_ne!(frame.frame_hash(), gamemap.frame_hash());
+}
@@ -0,0 +1,20 @@
+use day14::GameMap;
+
+const EXAMPLE: &str = include_str!("../inputs/example.txt");
+
+#[test]
+fn top_frame_is_lowest_entropy() {
+    let gamemap = GameMap::parse(EXAMPLE, 11, 7).unwrap();
+    let frames = gamemap.find_lowest_entropies(5);
+    assert_eq!(frames.len(), 5);
+    assert_eq!(frames[0], gamemap.find_lowest_entropy());
+}
+
+#[test]
+fn top_frames_are_ordered() {
+    let gamemap = GameMap::parse(EXAMPLE, 11, 7).unwrap();
+    let frames = gamemap.find_lowest_entropies(10);
+    assert!(frames
+        .windows(2)
+        .all(|pair| (pair[0].1, pair[0].0) < (pair[1].1, pair[1].0)));
+}
@@ -0,0 +1,27 @@
+use day14::Motion;
+
+#[test]
+fn wraps_around_edges() {
+    assert_eq!(Motion::Wrap.position(2, 3, 1, 4), 1);
+    assert_eq!(Motion::Wrap.position(1, -3, 1, 4), 2);
+}
+
+#[test]
+fn bounces_off_edges() {
+    let positions: Vec<u64> = (0..8)
+        .map(|n| Motion::Bounce.position(1, 1, n, 4))
+        .collect();
+    assert_eq!(positions, vec![1, 2, 3, 2, 1, 0, 1, 2]);
+    assert_eq!(Motion::Bounce.position(1, -3, 1, 4), 2);
+}
+
+#[test]
+fn bounce_cycle_returns_to_start() {
+    let cycle_length = Motion::Bounce.cycle_length(11, 7);
+    for position in 0..11 {
+        assert_eq!(
+            Motion::Bounce.position(position, -4, cycle_length, 11),
+            position
+        );
+    }
+}
@@ -0,0 +1,19 @@
+use day14::GameMap;
+
+#[test]
+fn rejects_malformed_robots() {
+    assert_eq!(
+        GameMap::parse("p=0,4 v=3,-3\ngarbage line !!\n", 11, 7).err(),
+        Some(String::from(
+            "Line 2: expected a robot like p=0,4 v=3,-3, got \"garbage line !!\""
+        ))
+    );
+}
+
+#[test]
+fn rejects_positions_that_are_too_large() {
+    assert_eq!(
+        GameMap::parse("p=99999999999999999999,4 v=3,-3\n", 11, 7).err(),
+        Some(String::from("Line 1: 99999999999999999999 is too large"))
+    );
+}
@@ -0,0 +1,16 @@
+use day14::GameMap;
+
+const EXAMPLE: &str = include_str!("../inputs/example.txt");
+
+#[test]
+fn renders_initial_example() {
+    let gamemap = GameMap::parse(EXAMPLE, 11, 7).unwrap();
+    insta::assert_snapshot!(gamemap.to_string());
+}
+
+#[test]
+fn renders_example_after_100_seconds() {
+    let mut gamemap = GameMap::parse(EXAMPLE, 11, 7).unwrap();
+    gamemap.extrapolate(100);
+    insta::assert_snapshot!(gamemap.to_string());
+}
@@ -0,0 +1,35 @@
+use day14::{GameMap, Motion};
+
+const EXAMPLE: &str = include_str!("../inputs/example.txt");
+
+#[test]
+fn series_covers_full_cycle() {
+    let gamemap = GameMap::parse(EXAMPLE, 11, 7).unwrap();
+    let series = gamemap.safety_series();
+    assert_eq!(series.len(), 77);
+    assert!(series
+        .iter()
+        .enumerate()
+        .all(|(index, (elapsed, _))| index as u64 == *elapsed));
+    assert_eq!(series[0].1, gamemap.safety_factor());
+}
+
+#[test]
+fn series_matches_extrapolation() {
+    let gamemap = GameMap::parse(EXAMPLE, 11, 7).unwrap();
+    let series = gamemap.safety_series();
+    assert_eq!(series[100 % 77].1, 12);
+}
+
+#[test]
+fn bounce_series_covers_longer_cycle() {
+    let mut gamemap = GameMap::parse(EXAMPLE, 11, 7).unwrap();
+    gamemap.motion = Motion::Bounce;
+    let series = gamemap.safety_series();
+    assert_eq!(series.len() as u64, Motion::Bounce.cycle_length(11, 7));
+    for (elapsed, safety_factor) in series.iter().step_by(13) {
+        let mut frame = gamemap.clone();
+        frame.extrapolate(*elapsed);
+        assert_eq!(frame.safety_factor(), *safety_factor);
+    }
+}
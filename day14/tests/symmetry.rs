@@ -0,0 +1,35 @@
+use day14::GameMap;
+
+const EXAMPLE: &str = include_str!("../inputs/example.txt");
+
+#[test]
+fn scores_mirrored_spaces() {
+    let mirrored = GameMap::parse("p=0,0 v=0,0\np=4,0 v=0,0\np=1,1 v=0,0\n", 5, 2).unwrap();
+    assert_eq!(mirrored.symmetry_score(), 2);
+
+    let lopsided = GameMap::parse("p=0,0 v=0,0\np=3,0 v=0,0\n", 5, 2).unwrap();
+    assert_eq!(lopsided.symmetry_score(), 0);
+}
+
+#[test]
+fn ignores_center_line() {
+    let centered = GameMap::parse("p=2,0 v=0,0\np=2,1 v=0,0\n", 5, 2).unwrap();
+    assert_eq!(centered.symmetry_score(), 0);
+}
+
+#[test]
+fn finds_most_symmetric_second() {
+    // The moving robot mirrors the still one after three seconds
+    let gamemap = GameMap::parse("p=0,0 v=1,0\np=1,0 v=0,0\n", 5, 1).unwrap();
+    assert_eq!(gamemap.find_most_symmetric(), (3, 2));
+}
+
+#[test]
+fn top_frames_are_ordered() {
+    let gamemap = GameMap::parse(EXAMPLE, 11, 7).unwrap();
+    let frames = gamemap.find_most_symmetric_frames(10);
+    assert_eq!(frames[0], gamemap.find_most_symmetric());
+    assert!(frames
+        .windows(2)
+        .all(|pair| (pair[1].1, pair[0].0) < (pair[0].1, pair[1].0)));
+}
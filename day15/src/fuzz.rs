@@ -0,0 +1,142 @@
+use std::collections::HashSet;
+
+use rand::{rngs::StdRng, Rng, SeedableRng};
+
+use crate::{Coordinate, Direction, GameMap, MovePolicy};
+
+/// The characters for each of the instructions
+const INSTRUCTIONS: [char; 4] = ['^', '>', 'v', '<'];
+
+/// Generates a random warehouse of the given size (including its outer walls), with a single
+/// robot, some walls and boxes inside, and the given number of random instructions for the
+/// robot, which are the same each time for the same seed
+///
+/// The warehouse is given as text in the same format as the puzzle input.
+pub fn generate_warehouse(
+    seed: u64,
+    width: usize,
+    height: usize,
+    num_instructions: usize,
+) -> String {
+    assert!(
+        width >= 3 && height >= 3,
+        "The warehouse must be at least 3x3 to fit the robot inside its walls"
+    );
+    let mut rng = StdRng::seed_from_u64(seed);
+
+    // Fill the inside of the warehouse with walls, boxes, and empty space
+    let mut rows: Vec<Vec<char>> = (0..height)
+        .map(|y| {
+            (0..width)
+                .map(|x| {
+                    if x == 0 || y == 0 || x == width - 1 || y == height - 1 {
+                        return '#';
+                    }
+                    match rng.gen_range(0..100) {
+                        0..=14 => '#',
+                        15..=39 => 'O',
+                        _ => '.',
+                    }
+                })
+                .collect()
+        })
+        .collect();
+
+    // Place the robot somewhere inside the walls
+    let x = rng.gen_range(1..width - 1);
+    let y = rng.gen_range(1..height - 1);
+    rows[y][x] = '@';
+
+    // Create the instructions for the robot
+    let instructions: String = (0..num_instructions)
+        .map(|_| INSTRUCTIONS[rng.gen_range(0..INSTRUCTIONS.len())])
+        .collect();
+
+    // Return the warehouse as text
+    let map_text: Vec<String> = rows.iter().map(|x| x.iter().collect()).collect();
+    format!("{}\n\n{instructions}\n", map_text.join("\n"))
+}
+
+/// Checks that moving from the map before to the map after in the given direction kept the
+/// warehouse consistent, returning a description of the first problem found
+///
+/// Every entity must still be inside the map without overlapping any other, walls must not
+/// move, boxes must keep their shape, and anything that moved must have moved one space in the
+/// given direction.
+pub fn check_invariants(
+    before: &GameMap,
+    after: &GameMap,
+    direction: &Direction,
+) -> Result<(), String> {
+    // Check that nothing was added or removed
+    let before_entities: Vec<_> = before.robots.iter().chain(&before.entities).collect();
+    let after_entities: Vec<_> = after.robots.iter().chain(&after.entities).collect();
+    if before_entities.len() != after_entities.len() {
+        return Err(format!(
+            "There were {} entities but now there are {}",
+            before_entities.len(),
+            after_entities.len()
+        ));
+    }
+
+    // Check each of the entities against itself before the move
+    let mut occupied: HashSet<Coordinate> = HashSet::new();
+    for (old, new) in before_entities.into_iter().zip(after_entities) {
+        let id = new.id;
+
+        // Walls must stay put, and anything else may only move one space in the direction
+        let moved_to = old.left.coordinate_for(direction);
+        if !new.moveable && new != old {
+            return Err(format!("Wall {id} moved"));
+        }
+        if new.left != old.left && new.left != moved_to {
+            return Err(format!("Entity {id} moved to {:?}", new.left));
+        }
+
+        // Boxes must keep their shape, which is two spaces wide on a wide map
+        let width = new.right.x - new.left.x;
+        let expected_width = isize::from(after.wide && new.moveable && !after.is_robot(id));
+        if new.right.y != new.left.y || width != expected_width {
+            return Err(format!("Entity {id} changed shape"));
+        }
+
+        // Entities must be inside the map without overlapping each other
+        for coord in HashSet::from([new.left, new.right]) {
+            if coord.x < 0
+                || coord.y < 0
+                || coord.x >= after.width as isize
+                || coord.y >= after.height as isize
+            {
+                return Err(format!("Entity {id} is outside the map at {coord:?}"));
+            }
+            if !occupied.insert(coord) {
+                return Err(format!("Entity {id} overlaps another at {coord:?}"));
+            }
+        }
+    }
+
+    // The warehouse is consistent
+    Ok(())
+}
+
+/// Plays out the instructions for the map's only robot one at a time using the given movement
+/// policy, checking the invariants after each one
+///
+/// Returns the number of instructions played and a description of the problem for the first
+/// instruction that breaks them.
+pub fn play_checked<P: MovePolicy>(
+    gamemap: &mut GameMap,
+    policy: &P,
+) -> Result<(), (usize, String)> {
+    assert!(
+        gamemap.robots.len() == 1,
+        "Only maps with a single robot can be checked"
+    );
+    let instructions = gamemap.instructions.first().cloned().unwrap_or_default();
+    for (index, direction) in instructions.iter().enumerate() {
+        let before = gamemap.clone();
+        gamemap.move_robot_with(policy, 0, direction);
+        check_invariants(&before, gamemap, direction).map_err(|x| (index + 1, x))?;
+    }
+    Ok(())
+}
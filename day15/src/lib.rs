@@ -0,0 +1,669 @@
+use std::collections::HashSet;
+use std::fmt::{self, Display, Formatter};
+
+pub use fuzz::{check_invariants, generate_warehouse, play_checked};
+
+mod fuzz;
+
+/// Representation of a map coordinate
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Coordinate {
+    pub x: isize,
+    pub y: isize,
+}
+
+impl Coordinate {
+    /// Gets the coordinate in a specific direction relative to this one
+    pub fn coordinate_for(&self, direction: &Direction) -> Coordinate {
+        match direction {
+            Direction::Up => Coordinate::from((self.x, self.y - 1)),
+            Direction::Down => Coordinate::from((self.x, self.y + 1)),
+            Direction::Right => Coordinate::from((self.x + 1, self.y)),
+            Direction::Left => Coordinate::from((self.x - 1, self.y)),
+        }
+    }
+}
+
+impl From<(isize, isize)> for Coordinate {
+    fn from(value: (isize, isize)) -> Self {
+        Self {
+            x: value.0,
+            y: value.1,
+        }
+    }
+}
+
+impl From<Coordinate> for (isize, isize) {
+    fn from(value: Coordinate) -> Self {
+        (value.x, value.y)
+    }
+}
+
+/// Representation of an entity on the map
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Entity {
+    /// Unique identifier
+    pub id: usize,
+    /// The coordinate representing the left edge
+    pub left: Coordinate,
+    /// The coordinate representing the right edge
+    pub right: Coordinate,
+    /// Whether the entity is moveable
+    pub moveable: bool,
+}
+
+impl Entity {
+    /// Pushes the box in the given direction, updating it's coordinates
+    pub fn slide(&mut self, direction: &Direction) {
+        self.left = self.left.coordinate_for(direction);
+        self.right = self.right.coordinate_for(direction);
+    }
+}
+
+/// The vaarious directions in which entities can move
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Direction {
+    Up,
+    Down,
+    Right,
+    Left,
+}
+
+impl Direction {
+    /// Gets the direction opposite to this one
+    pub fn opposite(&self) -> Direction {
+        match self {
+            Direction::Up => Direction::Down,
+            Direction::Down => Direction::Up,
+            Direction::Right => Direction::Left,
+            Direction::Left => Direction::Right,
+        }
+    }
+}
+
+/// A strategy for how a robot moves entities around the map
+///
+/// Other robots on the map can't be moved, and block the robot like walls.
+pub trait MovePolicy {
+    /// Moves the robot with the given ID on the given map in the given direction
+    fn move_robot(&self, gamemap: &mut GameMap, robot: usize, direction: &Direction);
+}
+
+/// Movement policy where the robot pushes the boxes in front of it
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Push;
+
+impl MovePolicy for Push {
+    fn move_robot(&self, gamemap: &mut GameMap, robot: usize, direction: &Direction) {
+        // Create a list for tracking IDs of entities to move
+        let mut moveable_ids = Vec::new();
+
+        // Attempt to push the robot
+        gamemap.push_entity(&gamemap.robots[robot].clone(), direction, &mut moveable_ids);
+
+        // For objects that should be moved (if successful), move them
+        for moveable_id in moveable_ids {
+            gamemap.slide_entity(moveable_id, direction);
+        }
+    }
+}
+
+/// Movement policy where the robot drags the box directly behind it
+///
+/// The robot only moves if the space in front of it is empty, and the box behind it only follows
+/// if nothing else blocks it from doing so.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Pull;
+
+impl MovePolicy for Pull {
+    fn move_robot(&self, gamemap: &mut GameMap, robot: usize, direction: &Direction) {
+        // Don't move if the robot would collide with anything
+        let robot = gamemap.robots[robot];
+        if !gamemap.collisions_for(&robot, direction).is_empty() {
+            return;
+        }
+
+        // Get the box directly behind the robot, if there is one
+        let behind = robot.left.coordinate_for(&direction.opposite());
+        let trailing_id = match gamemap.get(&behind) {
+            Some(Some(entity)) if entity.moveable && !gamemap.is_robot(entity.id) => {
+                Some(entity.id)
+            }
+            _ => None,
+        };
+
+        // Move the robot forward
+        gamemap.slide_entity(robot.id, direction);
+
+        // Drag the box along behind it if the space it would move into is clear
+        if let Some(trailing_id) = trailing_id {
+            let trailing = *gamemap.get_by_id(trailing_id);
+            if gamemap.collisions_for(&trailing, direction).is_empty() {
+                gamemap.slide_entity(trailing_id, direction);
+            }
+        }
+    }
+}
+
+/// Representation of the game map
+#[derive(Debug, Clone)]
+pub struct GameMap {
+    /// The robots on the map in reading order, where each robot's ID is its index here
+    pub robots: Vec<Entity>,
+    pub entities: Vec<Entity>,
+    /// The instructions for each robot, in the same order as the robots, or none at all if
+    /// every robot stays put
+    pub instructions: Vec<Vec<Direction>>,
+    pub width: usize,
+    pub height: usize,
+    pub wide: bool,
+}
+
+impl GameMap {
+    /// Parses the game map from the provided string
+    ///
+    /// The instructions follow the map after a blank line, and may be left out.  If the map
+    /// has more than one robot, the instructions for each robot are given in the same order as
+    /// the robots, separated by blank lines.
+    pub fn parse(text: &str, wide: bool) -> Result<Self, String> {
+        // Split the given text into the map and instructions portion, where the instructions
+        // may be left out
+        let (map_text, instruction_text) = text.split_once("\n\n").unwrap_or((text, ""));
+
+        // Parse the map from the map text
+        let mut map = Self::parse_map(map_text, wide)?;
+
+        // Parse the instructions for each robot from the instruction text in the map
+        map.instructions = map.parse_instruction_streams(instruction_text)?;
+
+        // Return the finalized map
+        Ok(map)
+    }
+
+    /// Creates a copy of the map with its instructions replaced by the ones in the given text
+    pub fn with_instructions(&self, instruction_text: &str) -> Result<Self, String> {
+        let mut map = self.clone();
+        map.instructions = self.parse_instruction_streams(instruction_text)?;
+        Ok(map)
+    }
+
+    /// Parses the map text portion
+    fn parse_map(map_text: &str, wide: bool) -> Result<Self, String> {
+        // Create lists for storing robots and the other entities
+        let mut robots = Vec::new();
+        let mut entities = Vec::new();
+
+        // Create an id for uniquely identifing entities
+        let mut id = 0;
+
+        // Iterate through the characters of the map text
+        for (row_index, row) in map_text.trim().lines().enumerate() {
+            for (col_index, character) in row.chars().enumerate() {
+                // Increment the unique identifier
+                id += 1;
+
+                // Get the column index, depending on whether the map should be widened
+                let wide_col_index = if wide { col_index * 2 } else { col_index };
+
+                // Get the left and right coordinates
+                let left_coord = Coordinate::from((wide_col_index as isize, row_index as isize));
+                let right_coord = if wide {
+                    Coordinate::from((wide_col_index as isize + 1, row_index as isize))
+                } else {
+                    left_coord
+                };
+
+                // Get the entity based on the character in the map
+                let entity = match character {
+                    '#' => Entity {
+                        id,
+                        left: left_coord,
+                        right: left_coord,
+                        moveable: false,
+                    },
+                    '@' => {
+                        robots.push(Entity {
+                            id,
+                            left: left_coord,
+                            right: left_coord,
+                            moveable: true,
+                        });
+                        continue;
+                    }
+                    'O' => {
+                        // println!("Found obstacle @ {left_coord:?} & {right_coord:?}!");
+                        Entity {
+                            id,
+                            left: left_coord,
+                            right: right_coord,
+                            moveable: true,
+                        }
+                    }
+                    '.' => continue,
+                    _ => {
+                        return Err(format!(
+                            "Line {}: unexpected character {character:?} in the map",
+                            row_index + 1
+                        ))
+                    }
+                };
+
+                // Add the entity to the tracked list
+                entities.push(entity);
+
+                // If the map should be widened, more actions are required
+                if wide {
+                    // Increment the unique identifier
+                    id += 1;
+
+                    // Create the second entity if necessary
+                    let entity = match character {
+                        '#' => Entity {
+                            id,
+                            left: right_coord,
+                            right: right_coord,
+                            moveable: false,
+                        },
+                        _ => continue,
+                    };
+
+                    // Add the second entity to the tracked list
+                    entities.push(entity);
+                }
+            }
+        }
+
+        // Calculate the map height and width
+        let height = map_text.trim().lines().count();
+        let Some(last_row) = map_text.trim().lines().last() else {
+            return Err(String::from("The map is empty"));
+        };
+        let mut width = last_row.len();
+        width = if wide { width * 2 } else { width };
+
+        // Return the map object with a blank set of instructions
+        Ok(Self {
+            robots,
+            entities,
+            instructions: Vec::new(),
+            width,
+            height,
+            wide,
+        })
+    }
+
+    /// Parses the instructions text into the list of directions for each robot to move, which
+    /// are separated by blank lines
+    ///
+    /// Returns no lists at all if there are no instructions.
+    fn parse_instruction_streams(
+        &self,
+        instruction_text: &str,
+    ) -> Result<Vec<Vec<Direction>>, String> {
+        // Split the instructions text into the text for each robot
+        let streams: Vec<&str> = instruction_text
+            .trim()
+            .split("\n\n")
+            .filter(|x| !x.trim().is_empty())
+            .collect();
+
+        // If there are no instructions, every robot stays put
+        if streams.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        // Make sure there are instructions for every robot
+        let num_robots = self.robots.len();
+        let num_streams = streams.len();
+        if num_streams != num_robots {
+            return Err(format!(
+                "Expected instructions for {num_robots} robots but found {num_streams}"
+            ));
+        }
+
+        // Parse the instructions for each robot
+        streams.into_iter().map(Self::parse_instructions).collect()
+    }
+
+    /// Parses the instuctions text to return a list of directions for the robot to move
+    fn parse_instructions(instruction_text: &str) -> Result<Vec<Direction>, String> {
+        // Create a list for storing parsed instructions
+        let mut instructions = Vec::new();
+
+        // Iterate through the list of instructions
+        for row in instruction_text.trim().lines() {
+            for character in row.trim().chars() {
+                // Get the direction based on the character encountered
+                let direction = match character {
+                    '^' => Direction::Up,
+                    '>' => Direction::Right,
+                    'v' => Direction::Down,
+                    '<' => Direction::Left,
+                    _ => return Err(format!("Could not parse {character:?} as a direction")),
+                };
+
+                // Add the direction to the tracked list
+                instructions.push(direction);
+            }
+        }
+
+        // Return the finalized list of instructions
+        Ok(instructions)
+    }
+
+    /// Gets the collisions for a given entity in the given direction
+    ///
+    /// Returns a hash set of IDs of objects that this entity would collide with
+    fn collisions_for(&self, entity: &Entity, direction: &Direction) -> HashSet<usize> {
+        // Create a list for keeping track of collisions
+        let mut collisions = HashSet::new();
+
+        // Check based on the direction
+        match direction {
+            // For up and down, check both the left and right coordinates of the entity
+            Direction::Up | Direction::Down => {
+                let leftside = entity.left.coordinate_for(direction);
+                if let Some(left_neightbor) = self.get(&leftside).unwrap() {
+                    collisions.insert(left_neightbor.id);
+                }
+
+                let rightside = entity.right.coordinate_for(direction);
+                if let Some(right_neighbor) = self.get(&rightside).unwrap() {
+                    collisions.insert(right_neighbor.id);
+                }
+            }
+            // For left, check the left side of the entity
+            Direction::Left => {
+                let nextspace = entity.left.coordinate_for(direction);
+                if let Some(neighbor) = self.get(&nextspace).unwrap() {
+                    collisions.insert(neighbor.id);
+                }
+            }
+            // For right, check the right side of the entity
+            Direction::Right => {
+                let nextspace = entity.right.coordinate_for(direction);
+                if let Some(neighbor) = self.get(&nextspace).unwrap() {
+                    collisions.insert(neighbor.id);
+                }
+            }
+        }
+
+        collisions
+    }
+
+    /// Moves the robot with the given ID in the given direction, pushing any boxes in the way
+    pub fn move_robot(&mut self, robot: usize, direction: &Direction) {
+        self.move_robot_with(&Push, robot, direction);
+    }
+
+    /// Moves the robot with the given ID in the given direction using the given movement policy
+    pub fn move_robot_with<P: MovePolicy>(
+        &mut self,
+        policy: &P,
+        robot: usize,
+        direction: &Direction,
+    ) {
+        policy.move_robot(self, robot, direction);
+    }
+
+    /// Plays out all of the instructions using the given movement policy, with the robots all
+    /// moving at once, calling the given callback with the number of instructions played so far
+    /// by each robot after every `every` instructions
+    pub fn run_instructions<P, F>(&mut self, policy: &P, every: usize, mut callback: F)
+    where
+        P: MovePolicy,
+        F: FnMut(usize, &GameMap),
+    {
+        let num_ticks = self.instructions.iter().map(|x| x.len()).max().unwrap_or(0);
+        for tick in 0..num_ticks {
+            self.tick_with(policy, tick);
+            let num_played = tick + 1;
+            if every > 0 && num_played % every == 0 {
+                callback(num_played, self);
+            }
+        }
+    }
+
+    /// Moves every robot that has an instruction at the given index at the same time, using the
+    /// given movement policy
+    ///
+    /// Each robot's move is worked out against the map as it was before any of them moved, so
+    /// the other robots block it even if they move out of the way.  The moves are then made in
+    /// order of robot ID, skipping any that would move an entity that's already been moved or
+    /// move into a space that's already been moved into, so lower IDs win any collision.
+    pub fn tick_with<P: MovePolicy>(&mut self, policy: &P, tick: usize) {
+        // Get the robots with an instruction at the index, along with the instruction
+        let moves: Vec<(usize, Direction)> = self
+            .instructions
+            .iter()
+            .enumerate()
+            .filter_map(|(robot, stream)| stream.get(tick).map(|x| (robot, *x)))
+            .collect();
+
+        // A single robot moving can't collide with any others, so it can just be moved
+        if let [(robot, direction)] = moves[..] {
+            self.move_robot_with(policy, robot, &direction);
+            return;
+        }
+
+        // Get the entities that each robot would move, and where they would move to
+        let plans: Vec<Vec<Entity>> = moves
+            .iter()
+            .map(|(robot, direction)| {
+                let mut planned = self.without_instructions();
+                planned.move_robot_with(policy, *robot, direction);
+                planned
+                    .robots
+                    .iter()
+                    .chain(&planned.entities)
+                    .zip(self.robots.iter().chain(&self.entities))
+                    .filter(|(after, before)| after != before)
+                    .map(|(after, _)| *after)
+                    .collect()
+            })
+            .collect();
+
+        // Make the moves in order, skipping any that collide with the ones already made
+        let mut moved_ids = HashSet::new();
+        let mut claimed_coords = HashSet::new();
+        for plan in plans {
+            let collides = plan.iter().any(|x| {
+                moved_ids.contains(&x.id)
+                    || claimed_coords.contains(&x.left)
+                    || claimed_coords.contains(&x.right)
+            });
+            if collides {
+                continue;
+            }
+            for entity in plan {
+                moved_ids.insert(entity.id);
+                claimed_coords.insert(entity.left);
+                claimed_coords.insert(entity.right);
+                *self.get_by_id_mut(entity.id) = entity;
+            }
+        }
+    }
+
+    /// Creates a copy of the map without any instructions, for trying out moves
+    fn without_instructions(&self) -> Self {
+        Self {
+            robots: self.robots.clone(),
+            entities: self.entities.clone(),
+            instructions: Vec::new(),
+            width: self.width,
+            height: self.height,
+            wide: self.wide,
+        }
+    }
+
+    /// Checks whether the entity with the given ID is a robot
+    pub fn is_robot(&self, id: usize) -> bool {
+        self.robots.iter().any(|x| x.id == id)
+    }
+
+    /// Push the given entity in the given direction, checking for collisions and
+    /// recursively pushing as needed
+    fn push_entity(
+        &mut self,
+        entity: &Entity,
+        direction: &Direction,
+        moveable_ids: &mut Vec<usize>,
+    ) -> bool {
+        // Get IDs for objects that the entity collides with
+        let next_ids = self.collisions_for(entity, direction);
+
+        // Keep a copy of the current moveable IDs in case the move fails
+        let moveable_ids_clone = moveable_ids.clone();
+
+        // Iterate through the colliding entity IDs to check if the can be pushed
+        for next_id in next_ids {
+            // Get the entity with the ID
+            let neighbor = self.get_by_id(next_id);
+
+            // If the neighbor is not moveable or is another robot, reset the moveable IDs to
+            // before any moves and return the failure result
+            if !neighbor.moveable || self.is_robot(next_id) {
+                *moveable_ids = moveable_ids_clone;
+                return false;
+            }
+
+            // Get the next moveable entity
+            let next_entity = *self.get_by_id(next_id);
+
+            // If the next entity cannot be moved, reset the moveable IDs to before any moves
+            // and return the failure result
+            if !self.push_entity(&next_entity, direction, moveable_ids) {
+                *moveable_ids = moveable_ids_clone;
+                return false;
+            }
+        }
+
+        // Add the current entity ID to the list of moveable IDs and return the success result
+        moveable_ids.push(entity.id);
+        true
+    }
+
+    /// Slides an entity with the given ID in the given direction
+    fn slide_entity(&mut self, id: usize, direction: &Direction) {
+        let entity = self.get_by_id_mut(id);
+        entity.slide(direction);
+    }
+
+    /// Gets the entity at a given coordinate
+    ///
+    /// Returns the a space if valid, or None if it's outside the bounds of the map.
+    /// The answer is either the entity in the location, or None if it is empty.
+    pub fn get(&self, coord: &Coordinate) -> Option<Option<&Entity>> {
+        if coord.x < 0
+            || coord.y < 0
+            || coord.x >= self.width as isize
+            || coord.y >= self.height as isize
+        {
+            return None;
+        }
+
+        match self.entities.iter().position(|e| {
+            (e.left.x == coord.x && e.left.y == coord.y)
+                || (e.right.x == coord.x && e.right.y == coord.y)
+        }) {
+            Some(pos) => Some(Some(&self.entities[pos])),
+            None => Some(self.robots.iter().find(|e| e.left == *coord)),
+        }
+    }
+
+    /// Gets the entity at a given coordinate by ID
+    pub fn get_by_id(&self, id: usize) -> &Entity {
+        if let Some(robot) = self.robots.iter().find(|e| e.id == id) {
+            return robot;
+        }
+
+        let pos = self
+            .entities
+            .iter()
+            .position(|e| e.id == id)
+            .unwrap_or_else(|| panic!("Could not get entity with the given ID: {id}"));
+        &self.entities[pos]
+    }
+
+    /// Gets the entity at a given coordinate by ID, mutably
+    fn get_by_id_mut(&mut self, id: usize) -> &mut Entity {
+        if let Some(pos) = self.robots.iter().position(|e| e.id == id) {
+            return &mut self.robots[pos];
+        }
+
+        let pos = self
+            .entities
+            .iter()
+            .position(|e| e.id == id)
+            .unwrap_or_else(|| panic!("Could not get entity with the given ID: {id}"));
+        &mut self.entities[pos]
+    }
+
+    /// Gets the GPS coordinates for all moveable entities on the map
+    pub fn gps_coordinates(&self) -> Vec<u128> {
+        self.entities
+            .iter()
+            .filter(|x| x.moveable)
+            .map(|e| (100 * e.left.y as u128) + e.left.x as u128)
+            .collect()
+    }
+}
+
+impl Display for GameMap {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let mut map_string = String::new();
+
+        let mut skip_next = false;
+
+        for row_index in 0..self.height as isize {
+            for col_index in 0..self.width as isize {
+                match self.get(&Coordinate::from((col_index, row_index))).unwrap() {
+                    Some(entity) => {
+                        if skip_next {
+                            // println!("Remove skip flag");
+                            skip_next = false;
+                            continue;
+                        } else if !entity.moveable {
+                            map_string.push('#');
+                        } else if self.is_robot(entity.id) {
+                            map_string.push('@');
+                        } else if self.wide {
+                            map_string.push_str("[]");
+                            skip_next = true;
+                        } else {
+                            map_string.push('O');
+                        }
+                    }
+                    None => map_string.push('.'),
+                }
+            }
+
+            map_string.push('\n');
+        }
+
+        write!(f, "{}", map_string)
+    }
+}
+
+/// Gets the sum of the GPS coordinates of the boxes once the robots in the warehouse of the
+/// input text have pushed them around
+fn pushed_gps_sum(input: &str, wide: bool) -> Result<u128, String> {
+    let mut gamemap = GameMap::parse(input, wide)?;
+    gamemap.run_instructions(&Push, 0, |_, _| {});
+    Ok(gamemap.gps_coordinates().iter().sum())
+}
+
+/// Solves part one for the puzzle input, getting the sum of the GPS coordinates of the boxes
+/// once the robot has finished moving
+pub fn part1(input: &str) -> Result<u128, String> {
+    pushed_gps_sum(input, false)
+}
+
+/// Solves part two for the puzzle input, getting the sum of the GPS coordinates of the boxes
+/// in the widened warehouse once the robot has finished moving
+pub fn part2(input: &str) -> Result<u128, String> {
+    pushed_gps_sum(input, true)
+}
@@ -1,469 +1,134 @@
-use std::collections::HashSet;
-use std::fmt::{self, Display, Formatter};
-use std::fs;
+use std::{fs, path::PathBuf};
 
 use clap::Parser;
+use common::{fail, read_input, About, Failure, Feature, Format};
+use day15::{generate_warehouse, play_checked, GameMap, MovePolicy, Pull, Push};
+use rayon::prelude::*;
 
 /// CLI arguments
 #[derive(Parser)]
 struct CliArgs {
     part: u64,
-    filepath: String,
+    #[arg(required_unless_present = "fuzz")]
+    filepath: Option<String>,
+    /// Have the robot pull the box behind it instead of pushing the boxes in front of it
+    #[arg(long)]
+    pull: bool,
+    /// Print the running sum of the GPS coordinates every given number of instructions
+    #[arg(long)]
+    gps_every: Option<usize>,
+    /// Play each file of instructions in the given directory against the map, in parallel,
+    /// and print the final sum of the GPS coordinates for each
+    #[arg(long, conflicts_with = "gps_every")]
+    batch: Option<PathBuf>,
+    /// Play the given number of random warehouses instead of the input, checking after every
+    /// move that nothing overlaps, walls stay put, and boxes keep their shape
+    #[arg(long, conflicts_with_all = ["filepath", "gps_every", "batch", "parse_only"])]
+    fuzz: Option<u64>,
+    /// The seed for the first random warehouse, with each one after using the next seed
+    #[arg(long, requires = "fuzz", default_value_t = 0)]
+    seed: u64,
+    /// Parse the input and print statistics about its structure instead of solving it
+    #[arg(long)]
+    parse_only: bool,
+    /// The format in which errors are reported
+    #[arg(long, value_enum, default_value_t = Format::Text)]
+    format: Format,
 }
 
-/// Representation of a map coordinate
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
-struct Coordinate {
-    x: isize,
-    y: isize,
-}
-
-impl Coordinate {
-    /// Gets the coordinate in a specific direction relative to this one
-    fn coordinate_for(&self, direction: &Direction) -> Coordinate {
-        match direction {
-            Direction::Up => Coordinate::from((self.x, self.y - 1)),
-            Direction::Down => Coordinate::from((self.x, self.y + 1)),
-            Direction::Right => Coordinate::from((self.x + 1, self.y)),
-            Direction::Left => Coordinate::from((self.x - 1, self.y)),
-        }
-    }
-}
-
-impl From<(isize, isize)> for Coordinate {
-    fn from(value: (isize, isize)) -> Self {
-        Self {
-            x: value.0,
-            y: value.1,
-        }
-    }
-}
-
-impl From<Coordinate> for (isize, isize) {
-    fn from(value: Coordinate) -> Self {
-        (value.x, value.y)
-    }
-}
-
-/// Representation of an entity on the map
-#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
-struct Entity {
-    /// Unique identifier
-    id: usize,
-    /// The coordinate representing the left edge
-    left: Coordinate,
-    /// The coordinate representing the right edge
-    right: Coordinate,
-    /// Whether the entity is moveable
-    moveable: bool,
-}
-
-impl Entity {
-    /// Pushes the box in the given direction, updating it's coordinates
-    fn slide(&mut self, direction: &Direction) {
-        self.left = self.left.coordinate_for(direction);
-        self.right = self.right.coordinate_for(direction);
-    }
-}
-
-/// The vaarious directions in which entities can move
-#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
-enum Direction {
-    Up,
-    Down,
-    Right,
-    Left,
-}
-
-/// Representation of the game map
-#[derive(Debug, Clone)]
-struct GameMap {
-    robot: Entity,
-    entities: Vec<Entity>,
-    instructions: Vec<Direction>,
-    width: usize,
-    height: usize,
-    wide: bool,
-}
-
-impl GameMap {
-    /// Parses the game map from the provided string
-    fn parse(text: &str, wide: bool) -> Self {
-        // Split the given text into the map and instructions portion
-        let text_parts: Vec<&str> = text.split("\n\n").collect();
-        let map_text = text_parts[0];
-        let instruction_text = text_parts[1];
-
-        // Parse the map from the map text
-        let mut map = Self::parse_map(map_text, wide);
-
-        // Parse the instructions from the instruction text in the map
-        map.instructions = Self::parse_instructions(instruction_text);
-
-        // Return the finalized map
-        map
-    }
-
-    /// Parses the map text portion
-    fn parse_map(map_text: &str, wide: bool) -> Self {
-        // Initialize the robot
-        let template_coord = Coordinate::from((0, 0));
-        let mut robot = Entity {
-            id: 0,
-            left: template_coord,
-            right: template_coord,
-            moveable: true,
-        };
-
-        // Create a list for storing entities
-        let mut entities = Vec::new();
-
-        // Create an id for uniquely identifing entities
-        let mut id = 0;
-
-        // Iterate through the characters of the map text
-        for (row_index, row) in map_text.trim().lines().enumerate() {
-            for (col_index, character) in row.chars().enumerate() {
-                // Increment the unique identifier
-                id += 1;
-
-                // Get the column index, depending on whether the map should be widened
-                let wide_col_index = if wide { col_index * 2 } else { col_index };
-
-                // Get the left and right coordinates
-                let left_coord = Coordinate::from((wide_col_index as isize, row_index as isize));
-                let right_coord = if wide {
-                    Coordinate::from((wide_col_index as isize + 1, row_index as isize))
-                } else {
-                    left_coord
-                };
-
-                // Get the entity based on the character in the map
-                let entity = match character {
-                    '#' => Entity {
-                        id,
-                        left: left_coord,
-                        right: left_coord,
-                        moveable: false,
-                    },
-                    '@' => {
-                        robot = Entity {
-                            id,
-                            left: left_coord,
-                            right: left_coord,
-                            moveable: true,
-                        };
-                        continue;
-                    }
-                    'O' => {
-                        // println!("Found obstacle @ {left_coord:?} & {right_coord:?}!");
-                        Entity {
-                            id,
-                            left: left_coord,
-                            right: right_coord,
-                            moveable: true,
-                        }
-                    }
-                    '.' => continue,
-                    _ => panic!("Could not parse character: {character}"),
-                };
-
-                // Add the entity to the tracked list
-                entities.push(entity);
-
-                // If the map should be widened, more actions are required
-                if wide {
-                    // Increment the unique identifier
-                    id += 1;
-
-                    // Create the second entity if necessary
-                    let entity = match character {
-                        '#' => Entity {
-                            id,
-                            left: right_coord,
-                            right: right_coord,
-                            moveable: false,
-                        },
-                        'O' | '@' | '.' => continue,
-                        _ => panic!("Could not parse character: {character}"),
-                    };
-
-                    // Add the second entity to the tracked list
-                    entities.push(entity);
-                }
-            }
-        }
-
-        // Calculate the map height and width
-        let height = map_text.trim().lines().count();
-        let mut width = map_text.trim().lines().last().unwrap().len();
-        width = if wide { width * 2 } else { width };
-
-        // Return the map object with a blank set of instructions
-        Self {
-            robot,
-            entities,
-            instructions: Vec::new(),
-            width,
-            height,
-            wide,
-        }
-    }
-
-    /// Parses the instuctions text to return a list of directions for the robot to move
-    fn parse_instructions(instruction_text: &str) -> Vec<Direction> {
-        // Create a list for storing parsed instructions
-        let mut instructions = Vec::new();
-
-        // Iterate through the list of instructions
-        for row in instruction_text.trim().lines() {
-            for character in row.trim().chars() {
-                // Get the direction based on the character encountered
-                let direction = match character {
-                    '^' => Direction::Up,
-                    '>' => Direction::Right,
-                    'v' => Direction::Down,
-                    '<' => Direction::Left,
-                    _ => panic!("Could not parse direction: {character}"),
-                };
-
-                // Add the direction to the tracked list
-                instructions.push(direction);
-            }
-        }
-
-        // Return the finalized list of instructions
-        instructions
-    }
-
-    /// Gets the collisions for a given entity in the given direction
-    ///
-    /// Returns a hash set of IDs of objects that this entity would collide with
-    fn collisions_for(&self, entity: &Entity, direction: &Direction) -> HashSet<usize> {
-        // Create a list for keeping track of collisions
-        let mut collisions = HashSet::new();
-
-        // Check based on the direction
-        match direction {
-            // For up and down, check both the left and right coordinates of the entity
-            Direction::Up | Direction::Down => {
-                let leftside = entity.left.coordinate_for(direction);
-                if let Some(left_neightbor) = self.get(&leftside).unwrap() {
-                    collisions.insert(left_neightbor.id);
-                }
-
-                let rightside = entity.right.coordinate_for(direction);
-                if let Some(right_neighbor) = self.get(&rightside).unwrap() {
-                    collisions.insert(right_neighbor.id);
-                }
-            }
-            // For left, check the left side of the entity
-            Direction::Left => {
-                let nextspace = entity.left.coordinate_for(direction);
-                if let Some(neighbor) = self.get(&nextspace).unwrap() {
-                    collisions.insert(neighbor.id);
-                }
-            }
-            // For right, check the right side of the entity
-            Direction::Right => {
-                let nextspace = entity.right.coordinate_for(direction);
-                if let Some(neighbor) = self.get(&nextspace).unwrap() {
-                    collisions.insert(neighbor.id);
-                }
-            }
-        }
-
-        collisions
-    }
-
-    /// Moves the robot in the given direction
-    fn move_robot(&mut self, direction: &Direction) {
-        // Create a list for tracking IDs of entities to move
-        let mut moveable_ids = Vec::new();
-
-        // Attempt to push the robot
-        self.push_entity(&self.robot.clone(), direction, &mut moveable_ids);
-
-        // For objects that should be moved (if successful), move them
-        for moveable_id in moveable_ids {
-            self.slide_entity(moveable_id, direction);
-        }
-    }
-
-    /// Push the given entity in the given direction, checking for collisions and
-    /// recursively pushing as needed
-    fn push_entity(
-        &mut self,
-        entity: &Entity,
-        direction: &Direction,
-        moveable_ids: &mut Vec<usize>,
-    ) -> bool {
-        // Get IDs for objects that the entity collides with
-        let next_ids = self.collisions_for(entity, direction);
-
-        // Keep a copy of the current moveable IDs in case the move fails
-        let moveable_ids_clone = moveable_ids.clone();
-
-        // Iterate through the colliding entity IDs to check if the can be pushed
-        for next_id in next_ids {
-            // Get the entity with the ID
-            let neighbor = self.get_by_id(next_id);
-
-            // If the neighbor is not moveable, reset the moveable IDs to before any moves
-            // and return the failure result
-            if !neighbor.moveable {
-                *moveable_ids = moveable_ids_clone;
-                return false;
-            }
-
-            // Get the next moveable entity
-            let next_entity = *self.get_by_id(next_id);
-
-            // If the next entity cannot be moved, reset the moveable IDs to before any moves
-            // and return the failure result
-            if !self.push_entity(&next_entity, direction, moveable_ids) {
-                *moveable_ids = moveable_ids_clone;
-                return false;
-            }
-        }
+/// Description of the puzzle solved by this binary
+const ABOUT: About = About {
+    day: 15,
+    title: "Warehouse Woes",
+    input: "A warehouse map, a blank line, then each robot's moves (^, >, v, <), separated by blank lines",
+    parts: &[1, 2],
+    features: &[Feature::Parallel],
+};
 
-        // Add the current entity ID to the list of moveable IDs and return the success result
-        moveable_ids.push(entity.id);
-        true
-    }
+/// Main entry function
+fn main() {
+    // Describe the puzzle instead if requested
+    common::handle_about::<CliArgs>(&ABOUT);
 
-    /// Slides an entity with the given ID in the given direction
-    fn slide_entity(&mut self, id: usize, direction: &Direction) {
-        let entity = self.get_by_id_mut(id);
-        entity.slide(direction);
-    }
+    // Parse CLI arguments
+    let cli = CliArgs::parse();
 
-    /// Gets the entity at a given coordinate
-    ///
-    /// Returns the a space if valid, or None if it's outside the bounds of the map.
-    /// The answer is either the entity in the location, or None if it is empty.
-    fn get(&self, coord: &Coordinate) -> Option<Option<&Entity>> {
-        if coord.x < 0
-            || coord.y < 0
-            || coord.x >= self.width as isize
-            || coord.y >= self.height as isize
-        {
-            return None;
-        }
+    // Report errors in the requested format
+    common::init(cli.format);
 
-        match self.entities.iter().position(|e| {
-            (e.left.x == coord.x && e.left.y == coord.y)
-                || (e.right.x == coord.x && e.right.y == coord.y)
-        }) {
-            Some(pos) => Some(Some(&self.entities[pos])),
-            None => {
-                if self.robot.left.x == coord.x && self.robot.left.y == coord.y {
-                    Some(Some(&self.robot))
-                } else {
-                    Some(None)
-                }
-            }
+    // Play random warehouses instead if requested
+    if let Some(num_cases) = cli.fuzz {
+        let wide = is_wide(cli.part);
+        if cli.pull {
+            fuzz(num_cases, cli.seed, wide, &Pull);
+        } else {
+            fuzz(num_cases, cli.seed, wide, &Push);
         }
+        return;
     }
+    let filepath = cli.filepath.expect("No input file given");
 
-    /// Gets the entity at a given coordinate by ID
-    fn get_by_id(&self, id: usize) -> &Entity {
-        if id == self.robot.id {
-            return &self.robot;
-        }
-
-        let pos = self
-            .entities
-            .iter()
-            .position(|e| (e.id == id))
-            .unwrap_or_else(|| panic!("Could not get entity with the given ID: {id}"));
-        &self.entities[pos]
+    // Only parse the input if requested
+    if cli.parse_only {
+        report_structure(filepath, is_wide(cli.part));
     }
 
-    /// Gets the entity at a given coordinate by ID, mutably
-    fn get_by_id_mut(&mut self, id: usize) -> &mut Entity {
-        if id == self.robot.id {
-            return &mut self.robot;
-        }
-
-        let pos = self
-            .entities
-            .iter()
-            .position(|e| (e.id == id))
-            .unwrap_or_else(|| panic!("Could not get entity with the given ID: {id}"));
-        &mut self.entities[pos]
+    // Score the batch of instruction files instead if requested
+    if let Some(directory) = cli.batch {
+        score_batch(filepath, is_wide(cli.part), cli.pull, directory);
+        return;
     }
 
-    /// Gets the GPS coordinates for all moveable entities on the map
-    fn gps_coordinates(&self) -> Vec<u128> {
-        self.entities
-            .iter()
-            .filter(|x| x.moveable)
-            .map(|e| (100 * e.left.y as u128) + e.left.x as u128)
-            .collect()
+    // Run the code for the desired challenge part
+    match cli.part {
+        1 => main_part_one(filepath, cli.pull, cli.gps_every),
+        2 => main_part_two(filepath, cli.pull, cli.gps_every),
+        _ => panic!("Invalid selection part selection!"),
     }
 }
 
-impl Display for GameMap {
-    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
-        let mut map_string = String::new();
-
-        let mut skip_next = false;
-
-        for row_index in 0..self.height as isize {
-            for col_index in 0..self.width as isize {
-                match self.get(&Coordinate::from((col_index, row_index))).unwrap() {
-                    Some(entity) => {
-                        if skip_next {
-                            // println!("Remove skip flag");
-                            skip_next = false;
-                            continue;
-                        } else if !entity.moveable {
-                            map_string.push('#');
-                        } else if entity.left == self.robot.left {
-                            map_string.push('@');
-                        } else if self.wide {
-                            map_string.push_str("[]");
-                            skip_next = true;
-                        } else {
-                            map_string.push('O');
-                        }
-                    }
-                    None => map_string.push('.'),
-                }
-            }
-
-            map_string.push('\n');
-        }
-
-        write!(f, "{}", map_string)
+/// Gets whether the map is widened for the given part
+fn is_wide(part: u64) -> bool {
+    match part {
+        1 => false,
+        2 => true,
+        _ => panic!("Invalid selection part selection!"),
     }
 }
 
-/// Main entry function
-fn main() {
-    // Parse CLI arguments
-    let cli = CliArgs::parse();
+/// Parses the warehouse, exiting with a parse failure if it can't be parsed
+fn read_map(contents: &str, wide: bool) -> GameMap {
+    GameMap::parse(contents, wide).unwrap_or_else(|error| fail(Failure::Parse(error)))
+}
 
-    // Run the code for the desired challenge part
-    match cli.part {
-        1 => main_part_one(cli.filepath),
-        2 => main_part_two(cli.filepath),
-        _ => panic!("Invalid selection part selection!"),
-    }
+/// Parses the warehouse, then prints its dimensions, the number of robots, boxes and walls in
+/// it, and the number of moves given to the robots and exits
+fn report_structure(filepath: String, wide: bool) -> ! {
+    let gamemap = read_map(&read_input(filepath), wide);
+    let num_boxes = gamemap.entities.iter().filter(|x| x.moveable).count();
+    common::report_parse(&[
+        ("rows", gamemap.height),
+        ("columns", gamemap.width),
+        ("robots", gamemap.robots.len()),
+        ("boxes", num_boxes),
+        ("walls", gamemap.entities.len() - num_boxes),
+        ("moves", gamemap.instructions.iter().map(|x| x.len()).sum()),
+    ]);
 }
 
 /// Runs part one
-fn main_part_one(filepath: String) {
+fn main_part_one(filepath: String, pull: bool, gps_every: Option<usize>) {
     // Get the trail ratings
-    let contents = fs::read_to_string(filepath).expect("Invalid filepath");
+    let contents = read_input(filepath);
 
     // Parse the input file contents into the game map
-    let mut gamemap = GameMap::parse(&contents, false);
+    let mut gamemap = read_map(&contents, false);
 
-    // Play out the instructions
-    for instruction in &gamemap.instructions.clone() {
-        gamemap.move_robot(instruction);
+    // Play out the instructions using the requested movement policy
+    if pull {
+        play(&mut gamemap, &Pull, gps_every);
+    } else {
+        play(&mut gamemap, &Push, gps_every);
     }
 
     // Print the sum of the GPS coordinates
@@ -472,19 +137,102 @@ fn main_part_one(filepath: String) {
 }
 
 /// Runs part two
-fn main_part_two(filepath: String) {
+fn main_part_two(filepath: String, pull: bool, gps_every: Option<usize>) {
     // Get the trail ratings
-    let contents = fs::read_to_string(filepath).expect("Invalid filepath");
+    let contents = read_input(filepath);
 
     // Parse the input file contents into the game map
-    let mut gamemap = GameMap::parse(&contents, true);
+    let mut gamemap = read_map(&contents, true);
 
-    // Play out the instructions
-    for instruction in gamemap.instructions.clone() {
-        gamemap.move_robot(&instruction);
+    // Play out the instructions using the requested movement policy
+    if pull {
+        play(&mut gamemap, &Pull, gps_every);
+    } else {
+        play(&mut gamemap, &Push, gps_every);
     }
 
     // Print the sum of the GPS coordinates
     let gps_sum: u128 = gamemap.gps_coordinates().iter().sum();
     println!("{gps_sum}");
 }
+
+/// Plays out all of the instructions for the given map using the given movement policy,
+/// printing the running sum of the GPS coordinates periodically if requested
+fn play<P: MovePolicy>(gamemap: &mut GameMap, policy: &P, gps_every: Option<usize>) {
+    // Print the header for the running sums if they're requested
+    if gps_every.is_some() {
+        println!("instruction,gps_sum");
+    }
+
+    // Play out the instructions, printing the running sums along the way
+    gamemap.run_instructions(policy, gps_every.unwrap_or(0), |num_played, gamemap| {
+        let gps_sum: u128 = gamemap.gps_coordinates().iter().sum();
+        println!("{num_played},{gps_sum}");
+    });
+}
+
+/// Plays each of the instruction files in the given directory against the map in parallel,
+/// printing the final sum of the GPS coordinates for each file as CSV
+fn score_batch(filepath: String, wide: bool, pull: bool, directory: PathBuf) {
+    // Parse the map from the input file, ignoring any instructions it contains
+    let contents = read_input(filepath);
+    let gamemap = read_map(&contents, wide);
+
+    // Get the files in the directory, in order of their names
+    let entries = fs::read_dir(&directory).unwrap_or_else(|error| {
+        let directory = directory.display();
+        fail(Failure::Io(format!("Could not read {directory}: {error}")))
+    });
+    let mut filepaths: Vec<PathBuf> = entries
+        .filter_map(|x| x.ok())
+        .map(|x| x.path())
+        .filter(|x| x.is_file())
+        .collect();
+    filepaths.sort();
+
+    // Play out the instructions of each file against its own copy of the map
+    let gps_sums: Vec<u128> = filepaths
+        .par_iter()
+        .map(|instruction_filepath| {
+            let mut batch_map = gamemap
+                .with_instructions(&read_input(instruction_filepath))
+                .unwrap_or_else(|error| fail(Failure::Parse(error)));
+            if pull {
+                batch_map.run_instructions(&Pull, 0, |_, _| {});
+            } else {
+                batch_map.run_instructions(&Push, 0, |_, _| {});
+            }
+            batch_map.gps_coordinates().iter().sum()
+        })
+        .collect();
+
+    // Print the final sums for each of the files
+    println!("file,gps_sum");
+    for (instruction_filepath, gps_sum) in filepaths.iter().zip(gps_sums) {
+        let name = instruction_filepath
+            .file_name()
+            .map_or(String::new(), |x| x.to_string_lossy().into_owned());
+        println!("{name},{gps_sum}");
+    }
+}
+
+/// Plays the given number of random warehouses using the given movement policy, starting from
+/// the given seed, checking the invariants after every move and printing the number of cases
+/// once they all pass
+///
+/// Panics with the seed and the warehouse of the first case that breaks the invariants.
+fn fuzz<P: MovePolicy>(num_cases: u64, seed: u64, wide: bool, policy: &P) {
+    for case_seed in seed..seed + num_cases {
+        // Generate a warehouse whose size depends on the seed
+        let width = 4 + (case_seed % 13) as usize;
+        let height = 4 + (case_seed / 13 % 9) as usize;
+        let text = generate_warehouse(case_seed, width, height, 200);
+
+        // Play it out, checking every move
+        let mut gamemap = GameMap::parse(&text, wide).expect("Generated an invalid warehouse");
+        if let Err((num_played, problem)) = play_checked(&mut gamemap, policy) {
+            panic!("Seed {case_seed} broke after {num_played} instructions: {problem}\n{text}");
+        }
+    }
+    println!("{num_cases} cases passed");
+}
@@ -0,0 +1,32 @@
+use day15::{GameMap, Push};
+
+const EXAMPLE: &str = include_str!("../inputs/example.txt");
+
+/// Gets the sum of the GPS coordinates after playing out all of the instructions
+fn final_gps_sum(mut gamemap: GameMap) -> u128 {
+    gamemap.run_instructions(&Push, 0, |_, _| {});
+    gamemap.gps_coordinates().iter().sum()
+}
+
+#[test]
+fn replaced_instructions_match_original() {
+    let (map_text, instruction_text) = EXAMPLE.split_once("\n\n").unwrap();
+    for wide in [false, true] {
+        let original = GameMap::parse(EXAMPLE, wide).unwrap();
+        let replaced = GameMap::parse(map_text, wide)
+            .unwrap()
+            .with_instructions(instruction_text)
+            .unwrap();
+        assert_eq!(replaced.instructions, original.instructions);
+        assert_eq!(final_gps_sum(replaced), final_gps_sum(original));
+    }
+}
+
+#[test]
+fn map_without_instructions_stays_put() {
+    let (map_text, _) = EXAMPLE.split_once("\n\n").unwrap();
+    let gamemap = GameMap::parse(map_text, false).unwrap();
+    assert!(gamemap.instructions.is_empty());
+    let before: u128 = gamemap.gps_coordinates().iter().sum();
+    assert_eq!(final_gps_sum(gamemap), before);
+}
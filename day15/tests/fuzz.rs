@@ -0,0 +1,61 @@
+use day15::{check_invariants, generate_warehouse, play_checked, Direction, GameMap, Push};
+
+#[test]
+fn generator_is_reproducible() {
+    let text = generate_warehouse(7, 10, 8, 50);
+    assert_eq!(text, generate_warehouse(7, 10, 8, 50));
+    assert_ne!(text, generate_warehouse(8, 10, 8, 50));
+
+    // The warehouse has a single robot with all of its instructions
+    let gamemap = GameMap::parse(&text, false).unwrap();
+    assert_eq!(gamemap.robots.len(), 1);
+    assert_eq!(gamemap.instructions[0].len(), 50);
+}
+
+#[test]
+fn pushing_keeps_invariants() {
+    for seed in 0..40 {
+        let text = generate_warehouse(seed, 4 + seed as usize % 9, 6, 100);
+        for wide in [false, true] {
+            let mut gamemap = GameMap::parse(&text, wide).unwrap();
+            assert_eq!(play_checked(&mut gamemap, &Push), Ok(()), "{text}");
+        }
+    }
+}
+
+#[test]
+fn catches_broken_moves() {
+    let before = GameMap::parse("#####\n#@O.#\n#####\n", false).unwrap();
+
+    // A box pushed into a wall overlaps it
+    let mut overlapping = before.clone();
+    overlapping
+        .entities
+        .iter_mut()
+        .find(|x| x.moveable)
+        .unwrap()
+        .left
+        .x = 0;
+    let result = check_invariants(&before, &overlapping, &Direction::Left);
+    assert!(result.unwrap_err().contains("moved to"));
+
+    // A wall that moves is caught even if it doesn't overlap anything
+    let mut moved_wall = before.clone();
+    let wall = moved_wall
+        .entities
+        .iter_mut()
+        .find(|x| !x.moveable)
+        .unwrap();
+    wall.left.y -= 1;
+    wall.right.y -= 1;
+    let result = check_invariants(&before, &moved_wall, &Direction::Up);
+    assert!(result.unwrap_err().starts_with("Wall"));
+
+    // A box moved one space onto another entity overlaps it
+    let mut stacked = before.clone();
+    let moved_box = stacked.entities.iter_mut().find(|x| x.moveable).unwrap();
+    moved_box.left.x -= 1;
+    moved_box.right.x -= 1;
+    let result = check_invariants(&before, &stacked, &Direction::Left);
+    assert!(result.unwrap_err().contains("overlaps"));
+}
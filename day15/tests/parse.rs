@@ -0,0 +1,25 @@
+use day15::GameMap;
+
+#[test]
+fn rejects_unexpected_map_characters() {
+    assert_eq!(
+        GameMap::parse("garbage line !!\nfoo", false).err(),
+        Some(String::from("Line 1: unexpected character 'g' in the map"))
+    );
+}
+
+#[test]
+fn rejects_unexpected_directions() {
+    assert_eq!(
+        GameMap::parse("#####\n#@..#\n#####\n\n>x\n", true).err(),
+        Some(String::from("Could not parse 'x' as a direction"))
+    );
+}
+
+#[test]
+fn rejects_empty_maps() {
+    assert_eq!(
+        GameMap::parse("\n", false).err(),
+        Some(String::from("The map is empty"))
+    );
+}
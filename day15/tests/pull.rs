@@ -0,0 +1,35 @@
+use day15::{GameMap, Pull};
+
+const CORRIDOR: &str = "#######\n#.O@..#\n#######\n\n>>>\n";
+
+/// Plays out all of the instructions for the given map by pulling boxes
+fn play(gamemap: &mut GameMap) {
+    for instruction in gamemap.instructions[0].clone() {
+        gamemap.move_robot_with(&Pull, 0, &instruction);
+    }
+}
+
+#[test]
+fn pulls_box_behind_robot() {
+    let mut gamemap = GameMap::parse(CORRIDOR, false).unwrap();
+    play(&mut gamemap);
+    assert_eq!(gamemap.to_string(), "#######\n#...O@#\n#######\n");
+    assert_eq!(gamemap.gps_coordinates(), vec![104]);
+}
+
+#[test]
+fn pulls_wide_box_behind_robot() {
+    let mut gamemap = GameMap::parse(CORRIDOR, true).unwrap();
+    play(&mut gamemap);
+    assert_eq!(
+        gamemap.to_string(),
+        "##############\n##.....[]@..##\n##############\n"
+    );
+}
+
+#[test]
+fn leaves_pushable_boxes_in_place() {
+    let mut gamemap = GameMap::parse("#####\n#@O.#\n#####\n\n>\n", false).unwrap();
+    play(&mut gamemap);
+    assert_eq!(gamemap.to_string(), "#####\n#@O.#\n#####\n");
+}
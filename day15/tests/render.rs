@@ -0,0 +1,36 @@
+use day15::GameMap;
+
+const EXAMPLE: &str = include_str!("../inputs/example.txt");
+
+/// Plays out all of the instructions for the given map
+fn play(gamemap: &mut GameMap) {
+    for instruction in gamemap.instructions[0].clone() {
+        gamemap.move_robot(0, &instruction);
+    }
+}
+
+#[test]
+fn renders_initial_example() {
+    let gamemap = GameMap::parse(EXAMPLE, false).unwrap();
+    insta::assert_snapshot!(gamemap.to_string());
+}
+
+#[test]
+fn renders_final_example() {
+    let mut gamemap = GameMap::parse(EXAMPLE, false).unwrap();
+    play(&mut gamemap);
+    insta::assert_snapshot!(gamemap.to_string());
+}
+
+#[test]
+fn renders_initial_wide_example() {
+    let gamemap = GameMap::parse(EXAMPLE, true).unwrap();
+    insta::assert_snapshot!(gamemap.to_string());
+}
+
+#[test]
+fn renders_final_wide_example() {
+    let mut gamemap = GameMap::parse(EXAMPLE, true).unwrap();
+    play(&mut gamemap);
+    insta::assert_snapshot!(gamemap.to_string());
+}
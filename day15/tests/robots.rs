@@ -0,0 +1,44 @@
+use day15::{GameMap, Push};
+
+/// Plays out all of the instructions for the given map, returning the final map
+fn play(text: &str) -> String {
+    let mut gamemap = GameMap::parse(text, false).unwrap();
+    gamemap.run_instructions(&Push, 0, |_, _| {});
+    gamemap.to_string()
+}
+
+#[test]
+fn robots_move_at_once() {
+    let text = "#######\n#@.O.@#\n#######\n\n>\n\n<\n";
+    assert_eq!(play(text), "#######\n#.@O@.#\n#######\n");
+}
+
+#[test]
+fn lower_id_wins_collision() {
+    // Both robots want the same space, so only the first one moves, pushing its box
+    let text = "######\n#@O.@#\n######\n\n>\n\n<\n";
+    assert_eq!(play(text), "######\n#.@O@#\n######\n");
+}
+
+#[test]
+fn robots_block_each_other() {
+    // The second robot moves out of the way, but only after the first one tried to move
+    let text = "#####\n#@@.#\n#####\n\n>>\n\n>\n";
+    assert_eq!(play(text), "#####\n#.@@#\n#####\n");
+}
+
+#[test]
+fn robots_cannot_push_robots() {
+    let text = "######\n#@O@.#\n######\n\n>\n\n<\n";
+    assert_eq!(play(text), "######\n#@O@.#\n######\n");
+}
+
+#[test]
+fn instructions_needed_for_every_robot() {
+    assert_eq!(
+        GameMap::parse("#####\n#@.@#\n#####\n\n>\n", false).err(),
+        Some(String::from(
+            "Expected instructions for 2 robots but found 1"
+        ))
+    );
+}
@@ -0,0 +1,903 @@
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap, HashSet};
+use std::fmt::{self, Display, Formatter};
+use std::hash::Hash;
+
+use pathfinding::{dijkstra, flood_fill, k_lowest_costs, reverse_moves, Visit};
+
+/// Type for the reindeer scores
+pub type Score = u64;
+
+/// Type for the transit nodes
+pub type Transit = (Coordinate, Direction);
+
+// Type for the transit node information (score and previous nodes with that same score)
+pub type NodeInfo = (Score, Vec<Transit>);
+
+/// Representation of a map coordinate
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Coordinate {
+    pub x: isize,
+    pub y: isize,
+}
+
+impl Coordinate {
+    /// Gets the coordinate in a specific direction relative to this one
+    pub fn coordinate_for(&self, direction: &Direction) -> Coordinate {
+        self.offset_by(direction, 1)
+    }
+
+    /// Gets the coordinate the given number of spaces in a specific direction relative to this
+    /// one, where a negative distance goes the opposite way
+    pub fn offset_by(&self, direction: &Direction, distance: isize) -> Coordinate {
+        let (x_step, y_step) = direction.step();
+        Coordinate::from((self.x + x_step * distance, self.y + y_step * distance))
+    }
+
+    // Gets the coordinates in the cardinal directions from the given coordinate
+    pub fn cardinals(&self) -> Vec<(Coordinate, Direction)> {
+        let mut coords = Vec::new();
+        for direction in [
+            Direction::North,
+            Direction::East,
+            Direction::South,
+            Direction::West,
+        ] {
+            coords.push((self.coordinate_for(&direction), direction));
+        }
+        coords
+    }
+}
+
+impl From<(isize, isize)> for Coordinate {
+    fn from(value: (isize, isize)) -> Self {
+        Self {
+            x: value.0,
+            y: value.1,
+        }
+    }
+}
+
+impl From<Coordinate> for (isize, isize) {
+    fn from(value: Coordinate) -> Self {
+        (value.x, value.y)
+    }
+}
+
+impl Display for Coordinate {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "({}, {})", self.x, self.y)
+    }
+}
+
+/// The vaarious directions in which entities can move
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Direction {
+    North,
+    South,
+    East,
+    West,
+    NorthEast,
+    SouthEast,
+    SouthWest,
+    NorthWest,
+}
+
+impl Direction {
+    /// Gets all of the cardinal directions
+    pub fn all() -> [Direction; 4] {
+        [
+            Direction::North,
+            Direction::East,
+            Direction::South,
+            Direction::West,
+        ]
+    }
+
+    /// Gets all of the diagonal directions
+    pub fn diagonals() -> [Direction; 4] {
+        [
+            Direction::NorthEast,
+            Direction::SouthEast,
+            Direction::SouthWest,
+            Direction::NorthWest,
+        ]
+    }
+
+    /// Gets the change in the X and Y coordinates for a single step in this direction
+    pub fn step(&self) -> (isize, isize) {
+        match self {
+            Direction::North => (0, -1),
+            Direction::South => (0, 1),
+            Direction::East => (1, 0),
+            Direction::West => (-1, 0),
+            Direction::NorthEast => (1, -1),
+            Direction::SouthEast => (1, 1),
+            Direction::SouthWest => (-1, 1),
+            Direction::NorthWest => (-1, -1),
+        }
+    }
+
+    /// Checks whether this is one of the diagonal directions
+    pub fn is_diagonal(&self) -> bool {
+        let (x_step, y_step) = self.step();
+        x_step != 0 && y_step != 0
+    }
+
+    /// Gets the direction opposite of this one
+    pub fn opposite(&self) -> Direction {
+        match self {
+            Direction::North => Direction::South,
+            Direction::East => Direction::West,
+            Direction::South => Direction::North,
+            Direction::West => Direction::East,
+            Direction::NorthEast => Direction::SouthWest,
+            Direction::SouthEast => Direction::NorthWest,
+            Direction::SouthWest => Direction::NorthEast,
+            Direction::NorthWest => Direction::SouthEast,
+        }
+    }
+}
+
+impl Display for Direction {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let character = match self {
+            Direction::North => '^',
+            Direction::East => '>',
+            Direction::South => 'v',
+            Direction::West => '<',
+            Direction::NorthEast | Direction::SouthWest => '/',
+            Direction::SouthEast | Direction::NorthWest => '\\',
+        };
+        write!(f, "{}", character)
+    }
+}
+
+/// The moves the reindeer can make from each tile, where every move leaves it facing the
+/// direction it moved in
+///
+/// By default, the reindeer can only step to the neighboring tiles in the cardinal directions.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct MoveSet {
+    /// The cost of a diagonal step onto an ordinary tile, which is multiplied by the weight of
+    /// weighted tiles, or None if the reindeer can't move diagonally
+    pub diagonal_cost: Option<Score>,
+    /// Whether the reindeer can also jump two tiles at once, over whatever is in between,
+    /// which costs as much as two steps onto the tile it lands on
+    pub jumps: bool,
+}
+
+impl MoveSet {
+    /// Gets the directions the reindeer can move (and so face) in
+    pub fn directions(&self) -> Vec<Direction> {
+        let mut directions = Direction::all().to_vec();
+        if self.diagonal_cost.is_some() {
+            directions.extend(Direction::diagonals());
+        }
+        directions
+    }
+
+    /// Gets the numbers of tiles the reindeer can move at once
+    pub fn distances(&self) -> &'static [isize] {
+        if self.jumps {
+            &[1, 2]
+        } else {
+            &[1]
+        }
+    }
+
+    /// Gets the cost of moving the given number of tiles in the given direction onto a tile
+    /// with the given step cost, not including the cost of turning
+    pub fn cost(&self, direction: &Direction, distance: isize, step_cost: Score) -> Score {
+        let step_cost = match self.diagonal_cost {
+            Some(diagonal_cost) if direction.is_diagonal() => step_cost * diagonal_cost,
+            _ => step_cost,
+        };
+        step_cost * distance as Score
+    }
+}
+
+/// The result of searching the maze, holding the best score for each transit node and
+/// every previous transit node that reaches it with that score
+#[derive(Debug, Clone)]
+pub struct SearchResult {
+    end: Coordinate,
+    scores: HashMap<Transit, Score>,
+    predecessors: HashMap<Transit, Vec<Transit>>,
+}
+
+impl SearchResult {
+    /// Gets the best score for reaching the transit node, or None if it can't be reached
+    pub fn score(&self, transit: &Transit) -> Option<Score> {
+        self.scores.get(transit).copied()
+    }
+
+    /// Gets the previous transit nodes that reach the given one with its best score
+    pub fn predecessors(&self, transit: &Transit) -> &[Transit] {
+        self.predecessors.get(transit).map_or(&[], |x| x.as_slice())
+    }
+
+    /// Gets the best score for reaching the end, or None if it can't be reached
+    pub fn best_end_score(&self) -> Option<Score> {
+        self.scores
+            .iter()
+            .filter(|((coord, _), _)| *coord == self.end)
+            .map(|(_, score)| *score)
+            .min()
+    }
+
+    /// Gets the transit nodes at the end that are reached with the best score
+    pub fn best_end_transits(&self) -> Vec<Transit> {
+        let Some(best_score) = self.best_end_score() else {
+            return Vec::new();
+        };
+        self.scores
+            .iter()
+            .filter(|((coord, _), score)| *coord == self.end && **score == best_score)
+            .map(|(transit, _)| *transit)
+            .collect()
+    }
+
+    /// Gets all coordinates that are part of at least one best route from the start to the end
+    pub fn best_locations(&self) -> HashSet<Coordinate> {
+        // Walk backwards through the predecessors, starting at the best end transit nodes
+        let mut seen = HashSet::new();
+        let mut stack = self.best_end_transits();
+        while let Some(transit) = stack.pop() {
+            if seen.insert(transit) {
+                stack.extend(self.predecessors(&transit));
+            }
+        }
+
+        // Return the coordinates of the transit nodes found
+        seen.into_iter().map(|x| x.0).collect()
+    }
+}
+
+/// Representation of the game map
+#[derive(Debug)]
+pub struct GameMap {
+    pub start: Coordinate,
+    pub end: Coordinate,
+    pub visited: HashMap<Transit, NodeInfo>,
+    pub unvisited: HashMap<Transit, NodeInfo>,
+    pub spaces: HashSet<Coordinate>,
+    /// The cost of stepping onto each weighted terrain space, where other spaces cost 1
+    pub weights: HashMap<Coordinate, Score>,
+    pub width: usize,
+    pub height: usize,
+    /// The moves the reindeer can make from each tile
+    pub moves: MoveSet,
+}
+
+impl Display for GameMap {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let mut map_string = String::new();
+
+        for row_index in 0..self.height {
+            for col_index in 0..self.width {
+                let coord = Coordinate::from((col_index as isize, row_index as isize));
+                if coord == self.start {
+                    map_string.push('S');
+                } else if coord == self.end {
+                    map_string.push('E');
+                } else if let Some(weight) = self.weights.get(&coord) {
+                    map_string.push_str(&weight.to_string());
+                } else if self.check_free(&coord).unwrap() {
+                    map_string.push('.');
+                } else {
+                    map_string.push('#');
+                }
+            }
+
+            map_string.push('\n');
+        }
+
+        write!(f, "{}", map_string)
+    }
+}
+
+impl GameMap {
+    /// Checks whether a given coordinate is empty
+    ///
+    /// Returns an error if the space is off the map.
+    fn check_free(&self, coord: &Coordinate) -> Result<bool, ()> {
+        //
+        if coord.x < 0
+            || coord.y < 0
+            || coord.x >= self.width as isize
+            || coord.y >= self.height as isize
+        {
+            return Err(());
+        }
+
+        Ok(self.spaces.contains(coord))
+    }
+
+    /// Gets the cost of stepping onto the given space
+    fn step_cost(&self, coord: &Coordinate) -> Score {
+        self.weights.get(coord).copied().unwrap_or(1)
+    }
+
+    /// Gets the valid moves in the move set onto free spaces, along with the cost of each move
+    /// (not including the cost of turning)
+    fn valid_moves(&self, transit: &Transit) -> Vec<(Transit, Score)> {
+        let (coord, _direction) = transit;
+        let mut moves = Vec::new();
+        for direction in self.moves.directions() {
+            for distance in self.moves.distances() {
+                let new_coord = coord.offset_by(&direction, *distance);
+                if self.check_free(&new_coord).unwrap_or(false) {
+                    let cost = self
+                        .moves
+                        .cost(&direction, *distance, self.step_cost(&new_coord));
+                    moves.push(((new_coord, direction), cost));
+                }
+            }
+        }
+        moves
+    }
+
+    /// Gets the next available moves for a given transit node
+    ///
+    /// This is needed for Dijkstra's algorithm, for calculating the new scores for connections
+    /// from the current node being used (the given transit node).
+    ///
+    /// Returns a list of new coordinates the given transit node connects to, the direction
+    /// to travel in order to reach that coordinate, and the score associated with that move.
+    fn next_moves(&self, transit: &Transit) -> Vec<(Coordinate, Direction, Score)> {
+        // Break up the current transit node into its base components for ease of use
+        let (.., direction) = transit;
+
+        // Get all the valid moves that can be performed from the given transit node
+        let valid_moves: Vec<(Transit, Score)> = self.valid_moves(transit);
+
+        // Get the list of valid moves that would go to coordinates not yet visited
+        let new_moves: Vec<(Transit, Score)> = valid_moves
+            .iter()
+            .filter(|(m, _)| !self.visited.contains_key(m))
+            .copied()
+            .collect();
+
+        // Get the score of the current transit node
+        let (current_score, ..) = self
+            .unvisited
+            .get(transit)
+            .expect("Could not get visited node");
+
+        // Create a list for new moves with associated scores
+        let mut new_scored_moves = Vec::new();
+
+        // Iterate through the new moves
+        for ((new_coordinate, new_direction), move_cost) in new_moves {
+            // Add the score for moving forward (and turning if needed)
+            let new_score = current_score + move_score(direction, &new_direction, move_cost);
+
+            // Add the new move set to the list
+            new_scored_moves.push((new_coordinate, new_direction, new_score));
+        }
+
+        // Return the list of moves
+        new_scored_moves
+    }
+
+    /// Gets the moves leading out of the given transit node, along with the score of each move
+    fn forward_moves(&self, transit: &Transit) -> Vec<(Transit, Score)> {
+        self.valid_moves(transit)
+            .into_iter()
+            .map(|(next_transit, move_cost)| {
+                (
+                    next_transit,
+                    move_score(&transit.1, &next_transit.1, move_cost),
+                )
+            })
+            .collect()
+    }
+
+    /// Gets the moves leading into the given transit node, along with the score of each move
+    ///
+    /// This is the reverse of `forward_moves`, where the previous coordinate may be found by
+    /// moving backwards against the direction of travel by each distance in the move set, and
+    /// the previous direction may be any direction in the move set.
+    fn reverse_moves(&self, transit: &Transit) -> Vec<(Transit, Score)> {
+        let (coord, direction) = transit;
+        let candidates = self
+            .moves
+            .distances()
+            .iter()
+            .map(|distance| coord.offset_by(direction, -distance))
+            .filter(|previous_coord| self.check_free(previous_coord).unwrap_or(false))
+            .flat_map(|previous_coord| {
+                self.moves
+                    .directions()
+                    .into_iter()
+                    .map(move |previous_direction| (previous_coord, previous_direction))
+            });
+        reverse_moves(transit, candidates, |x| self.forward_moves(x))
+    }
+
+    /// Finds the best score from the start to the end by searching from both ends at
+    /// the same time, stopping once the two searches can no longer find a better meeting point
+    ///
+    /// Returns None if the end cannot be reached.
+    pub fn bidirectional_best_score(&self) -> Option<Score> {
+        // Create the scores and queues for the forward search, seeded with the start node
+        let start_transit = (self.start, Direction::East);
+        let mut forward_scores = HashMap::from([(start_transit, 0)]);
+        let mut forward_queue = BinaryHeap::from([Reverse((0, start_transit))]);
+
+        // Create the scores and queues for the backward search, seeded with the end node
+        // facing in every direction
+        let mut backward_scores = HashMap::new();
+        let mut backward_queue = BinaryHeap::new();
+        for direction in self.moves.directions() {
+            backward_scores.insert((self.end, direction), 0);
+            backward_queue.push(Reverse((0, (self.end, direction))));
+        }
+
+        // Keep track of the best score found through a meeting point
+        let mut best_score: Option<Score> = None;
+
+        // Get the lowest scores waiting in each of the queues, stopping if either is empty
+        while let (Some(Reverse((forward_top, _))), Some(Reverse((backward_top, _)))) =
+            (forward_queue.peek(), backward_queue.peek())
+        {
+            // If no path through the remaining nodes can beat the best score, stop searching
+            if best_score.is_some_and(|x| forward_top + backward_top >= x) {
+                break;
+            }
+
+            // Expand whichever search currently has the lower score
+            let forward = forward_top <= backward_top;
+            let (queue, scores, other_scores) = if forward {
+                (&mut forward_queue, &mut forward_scores, &backward_scores)
+            } else {
+                (&mut backward_queue, &mut backward_scores, &forward_scores)
+            };
+            let Reverse((score, transit)) = queue.pop().expect("Queue is empty");
+
+            // If a better score was already found for this node, skip it
+            if scores.get(&transit).is_some_and(|x| *x < score) {
+                continue;
+            }
+
+            // Get the moves from this node in the direction of the search
+            let moves = if forward {
+                self.forward_moves(&transit)
+            } else {
+                self.reverse_moves(&transit)
+            };
+
+            // Iterate through the moves, updating the scores as needed
+            for (next_transit, move_cost) in moves {
+                // If the new score isn't an improvement, ignore it
+                let next_score = score + move_cost;
+                if scores.get(&next_transit).is_some_and(|x| *x <= next_score) {
+                    continue;
+                }
+
+                // Update the score and add the node to the queue
+                scores.insert(next_transit, next_score);
+                queue.push(Reverse((next_score, next_transit)));
+
+                // If the other search has reached this node, check whether it's a better meeting point
+                if let Some(other_score) = other_scores.get(&next_transit) {
+                    let total = next_score + other_score;
+                    if best_score.is_none_or(|x| total < x) {
+                        best_score = Some(total);
+                    }
+                }
+            }
+        }
+
+        // Return the best score found
+        best_score
+    }
+
+    /// Gets the best score for reaching every reachable transit node from the start
+    pub fn cost_from_start(&self) -> HashMap<Transit, Score> {
+        dijkstra([(self.start, Direction::East)], |transit| {
+            self.forward_moves(transit)
+        })
+    }
+
+    /// Gets the given number of lowest distinct scores for routes from the start to the end, in
+    /// increasing order
+    ///
+    /// Routes never visit the same tile twice, so there may be fewer scores than requested.
+    /// The best remaining score from each transit node is used to search the most promising
+    /// routes first.
+    pub fn best_scores(&self, k: usize) -> Vec<Score> {
+        let cost_to_end = self.cost_to_end();
+        k_lowest_costs(
+            [(self.start, Direction::East)],
+            |transit| self.forward_moves(transit),
+            |(coord, _)| *coord == self.end,
+            |(coord, _)| *coord,
+            |transit| cost_to_end.get(transit).copied(),
+            k,
+        )
+    }
+
+    /// Gets the best remaining score for reaching the end from every transit node that can
+    /// reach it, by searching backwards from the end
+    pub fn cost_to_end(&self) -> HashMap<Transit, Score> {
+        let seeds = self
+            .moves
+            .directions()
+            .into_iter()
+            .map(|direction| (self.end, direction));
+        dijkstra(seeds, |transit| self.reverse_moves(transit))
+    }
+
+    /// Gets all coordinates that are part of at least one best route from the start to the end
+    ///
+    /// A transit node is on a best route exactly when its best score from the start and its
+    /// best remaining score to the end add up to the best score overall.
+    pub fn optimal_locations(&self) -> HashSet<Coordinate> {
+        // Get the scores from the start and to the end
+        let forward = self.cost_from_start();
+        let backward = self.cost_to_end();
+
+        // Get the best score overall, which is the best remaining score from the start
+        let Some(best_score) = backward.get(&(self.start, Direction::East)).copied() else {
+            return HashSet::new();
+        };
+
+        // Find the coordinates of the transit nodes where the scores add up to the best score
+        forward
+            .iter()
+            .filter(|(transit, score)| {
+                backward
+                    .get(transit)
+                    .is_some_and(|x| *x + **score == best_score)
+            })
+            .map(|(transit, _)| transit.0)
+            .collect()
+    }
+
+    /// Gets the closet (score-wise) unvisited node
+    fn get_closest_unvisited(&self) -> (Coordinate, Direction) {
+        // If there is only one unvisited node, return that one
+        if self.unvisited.len() == 1 {
+            return *self
+                .unvisited
+                .iter()
+                .last()
+                .expect("Could not get last element")
+                .0;
+        }
+
+        // Get return the unvisited node with the lowest score
+        *self
+            .unvisited
+            .iter()
+            .min_by(|x, y| x.1 .0.cmp(&y.1 .0))
+            .expect("No items to sort out minimum")
+            .0
+    }
+
+    /// Performs a single iteration of Dijkstra's algorithm
+    fn perform_dijkstra_iteration(&mut self) {
+        // Get the closest (score-wise) node from the start
+        let closest_transit = self.get_closest_unvisited();
+
+        // Get the neighbor connections/moves from the closest node
+        let next_moves = self.next_moves(&closest_transit);
+
+        // Iterate through the connections/moves
+        for (next_coordinate, next_direction, next_score) in next_moves {
+            // Create the new transit node for the given coordinate and direction of the move
+            let next_transit = (next_coordinate, next_direction);
+
+            // Update the set of unvisited nodes
+            match self.unvisited.get_mut(&next_transit) {
+                // This transit nodes has been visited before
+                Some(info) => {
+                    // If new score would be larger than the stored one, ignore
+                    if next_score > info.0 {
+                        continue;
+                    }
+
+                    // If the new score ties the stored one, this is another previous node
+                    if next_score == info.0 {
+                        info.1.push(closest_transit);
+                        continue;
+                    }
+
+                    // Otherwise, update the score and previous node for this node
+                    *info = (next_score, vec![closest_transit]);
+                }
+                // This transit node is being visied for the first time
+                None => {
+                    // Add an entry for this transit node
+                    self.unvisited
+                        .insert(next_transit, (next_score, vec![closest_transit]));
+                }
+            }
+        }
+
+        // Remove the current transit node from the unvisited set, and add it to the visited set
+        let closest_entry = self
+            .unvisited
+            .remove(&closest_transit)
+            .expect("Could not complete marking as visited");
+        self.visited.insert(closest_transit, closest_entry);
+    }
+
+    /// Visit all nodes in the maze, using Dijkstra's algorithm
+    pub fn visit_nodes(&mut self) {
+        loop {
+            if self.unvisited.is_empty() {
+                break;
+            }
+            self.perform_dijkstra_iteration();
+        }
+    }
+
+    /// Visits all nodes in the maze, and gets the result of the search
+    pub fn search(&mut self) -> SearchResult {
+        // Visit all of the nodes in the maze
+        self.visit_nodes();
+
+        // Split the visited node information into the scores and previous nodes
+        let scores = self.visited.iter().map(|(k, v)| (*k, v.0)).collect();
+        let predecessors = self
+            .visited
+            .iter()
+            .map(|(k, v)| (*k, v.1.clone()))
+            .collect();
+
+        // Return the result of the search
+        SearchResult {
+            end: self.end,
+            scores,
+            predecessors,
+        }
+    }
+}
+
+/// Gets the score for moving forward, given the current and new directions of travel and the
+/// cost of the move onto the new space
+fn move_score(direction: &Direction, new_direction: &Direction, move_cost: Score) -> Score {
+    // Add the cost of moving onto the new space for the associated move forward
+    let mut score = move_cost;
+
+    // If the direction of the move is not the current direction, add 1000 points for the necessary turn
+    if direction != new_direction {
+        score += 1000;
+    }
+
+    // Return the score for the move
+    score
+}
+
+/// Problems that make a maze invalid
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MazeError {
+    /// A character other than `.`, `#`, `S`, `E`, or a weight from 1 to 9 was found
+    UnrecognizedCharacter(char, Coordinate),
+    /// A row has a different width than the first row
+    RaggedRow(usize, usize, usize),
+    /// There is no start tile
+    MissingStart,
+    /// There is no end tile
+    MissingEnd,
+    /// There is more than one start tile
+    MultipleStarts(Vec<Coordinate>),
+    /// There is more than one end tile
+    MultipleEnds(Vec<Coordinate>),
+    /// A tile on the border of the maze is not a wall
+    OpenBorder(Coordinate),
+    /// The end cannot be reached from the start, regardless of cost
+    Unreachable,
+}
+
+impl Display for MazeError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            MazeError::UnrecognizedCharacter(character, coord) => {
+                write!(f, "Unrecognized character {character:?} at {coord}")
+            }
+            MazeError::RaggedRow(row, row_width, width) => {
+                write!(f, "Row {row} has width {row_width} instead of {width}")
+            }
+            MazeError::MissingStart => write!(f, "No start tile (S)"),
+            MazeError::MissingEnd => write!(f, "No end tile (E)"),
+            MazeError::MultipleStarts(coords) => {
+                let coords: Vec<String> = coords.iter().map(|x| x.to_string()).collect();
+                write!(f, "Multiple start tiles (S) at {}", coords.join(", "))
+            }
+            MazeError::MultipleEnds(coords) => {
+                let coords: Vec<String> = coords.iter().map(|x| x.to_string()).collect();
+                write!(f, "Multiple end tiles (E) at {}", coords.join(", "))
+            }
+            MazeError::OpenBorder(coord) => write!(f, "Border tile at {coord} is not a wall"),
+            MazeError::Unreachable => write!(f, "The end cannot be reached from the start"),
+        }
+    }
+}
+
+/// Validates that the input text is a well-formed maze, with exactly one start and end tile,
+/// a closed border of walls, and an end that can be reached from the start
+///
+/// Returns all of the problems found, in the order they appear in the maze.
+pub fn validate_maze(text: &str) -> Result<(), Vec<MazeError>> {
+    // Create lists for storing the problems, start and end tiles, and empty spaces
+    let mut errors = Vec::new();
+    let mut starts = Vec::new();
+    let mut ends = Vec::new();
+    let mut spaces = HashSet::new();
+
+    // Get the dimensions of the maze from the first row and number of rows
+    let lines: Vec<&str> = text.trim().lines().collect();
+    let height = lines.len();
+    let width = lines.first().map_or(0, |x| x.chars().count());
+
+    // Iterate through the string character by character
+    for (row_index, line) in lines.iter().enumerate() {
+        // Check that the row is the same width as the first
+        let row_width = line.chars().count();
+        if row_width != width {
+            errors.push(MazeError::RaggedRow(row_index, row_width, width));
+        }
+
+        for (col_index, character) in line.chars().enumerate() {
+            // Get the current coordinate based on the iteration
+            let coord = Coordinate::from((col_index as isize, row_index as isize));
+
+            // Keep track of the start, end and empty spaces
+            match character {
+                '.' | '1'..='9' => {}
+                'S' => starts.push(coord),
+                'E' => ends.push(coord),
+                '#' => continue,
+                _ => {
+                    errors.push(MazeError::UnrecognizedCharacter(character, coord));
+                    continue;
+                }
+            }
+            spaces.insert(coord);
+
+            // Check that the space isn't on the border of the maze
+            if row_index == 0
+                || col_index == 0
+                || row_index == height - 1
+                || col_index == row_width - 1
+            {
+                errors.push(MazeError::OpenBorder(coord));
+            }
+        }
+    }
+
+    // Check that there is exactly one start and end tile
+    match starts.len() {
+        0 => errors.push(MazeError::MissingStart),
+        1 => {}
+        _ => errors.push(MazeError::MultipleStarts(starts.clone())),
+    }
+    match ends.len() {
+        0 => errors.push(MazeError::MissingEnd),
+        1 => {}
+        _ => errors.push(MazeError::MultipleEnds(ends.clone())),
+    }
+
+    // If there is a single start and end, check that the end can be reached from the start
+    if let ([start], [end]) = (starts.as_slice(), ends.as_slice()) {
+        let end = (end.x as i64, end.y as i64);
+        let mut reached = false;
+        flood_fill(
+            [(start.x as i64, start.y as i64)],
+            |_, next| spaces.contains(&Coordinate::from((next.0 as isize, next.1 as isize))),
+            |point, _| {
+                if point == end {
+                    reached = true;
+                    return Visit::Stop;
+                }
+                Visit::Continue
+            },
+        );
+        if !reached {
+            errors.push(MazeError::Unreachable);
+        }
+    }
+
+    // Return the problems found, if any
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(errors)
+    }
+}
+
+/// Describes the given maze problems, one per line
+pub fn describe_errors(errors: &[MazeError]) -> String {
+    let descriptions: Vec<String> = errors.iter().map(|x| x.to_string()).collect();
+    descriptions.join("\n")
+}
+
+/// Parses the given string into the game map
+pub fn parse_game(text: &str) -> GameMap {
+    // Create default start and end nodes
+    let mut start = Coordinate::from((0, 0));
+    let mut end = Coordinate::from((0, 0));
+
+    // Create a list for storing empty spaces, and the weights of weighted terrain
+    let mut spaces = HashSet::new();
+    let mut weights = HashMap::new();
+
+    // Iterate through the string character by character
+    for (row_index, line) in text.trim().lines().enumerate() {
+        for (col_index, character) in line.chars().enumerate() {
+            // Get the current coordinate based on the iteration
+            let coord = Coordinate::from((col_index as isize, row_index as isize));
+
+            // Handle each space on the map
+            match character {
+                '.' => {
+                    spaces.insert(coord);
+                }
+                '1'..='9' => {
+                    spaces.insert(coord);
+                    weights.insert(coord, Score::from(character.to_digit(10).unwrap()));
+                }
+                'S' => {
+                    start = coord;
+                    spaces.insert(coord);
+                }
+                'E' => {
+                    end = coord;
+                    spaces.insert(coord);
+                }
+                '#' => continue,
+                _char => panic!("Encountered unrecognized character: {_char}"),
+            }
+        }
+    }
+
+    // Calculate the height and width of the map from the given string
+    let height = text.trim().lines().count();
+    let width = text.trim().lines().last().unwrap().len();
+
+    // Create the set of unvisited nodes, seeding the start node into it
+    let mut unvisited = HashMap::new();
+    let start_transit = (start, Direction::East);
+    let start_node = (0, Vec::new());
+    unvisited.insert(start_transit, start_node);
+
+    // Return the finalized game map
+    GameMap {
+        start,
+        end,
+        visited: HashMap::new(),
+        unvisited,
+        spaces,
+        weights,
+        width,
+        height,
+        moves: MoveSet::default(),
+    }
+}
+
+/// Validates and parses the maze in the input text, failing with a description of its problems
+fn load_maze(input: &str) -> Result<GameMap, String> {
+    validate_maze(input).map_err(|errors| describe_errors(&errors))?;
+    Ok(parse_game(input))
+}
+
+/// Solves part one for the puzzle input, getting the lowest score the reindeer can reach the
+/// end with
+pub fn part1(input: &str) -> Result<Score, String> {
+    load_maze(input)?
+        .search()
+        .best_end_score()
+        .ok_or_else(|| String::from("Could not reach the end"))
+}
+
+/// Solves part two for the puzzle input, getting the number of tiles on any of the best paths
+/// through the maze
+pub fn part2(input: &str) -> Result<usize, String> {
+    Ok(load_maze(input)?.optimal_locations().len())
+}
@@ -1,474 +1,263 @@
-use std::collections::{HashMap, HashSet};
-use std::fmt::{self, Display, Formatter};
-use std::fs;
-use std::hash::Hash;
+use std::{collections::HashMap, fs};
 
-use clap::Parser;
-
-/// Type for the reindeer scores
-type Score = u64;
-
-/// Type for the transit nodes
-type Transit = (Coordinate, Direction);
-
-// Type for the transit node information (score and previous node)
-type NodeInfo = (Score, Transit);
+use clap::{error::ErrorKind, CommandFactory, Parser, ValueEnum};
+use common::{fail, read_input, About, Failure, Format};
+use day16::{
+    describe_errors, parse_game, validate_maze, Coordinate, GameMap, MazeError, MoveSet, Score,
+};
 
 /// CLI arguments
 #[derive(Parser)]
 struct CliArgs {
     part: u64,
     filepath: String,
+    /// The search algorithm to use for finding the best score in part one [default: dijkstra]
+    /// (rejected for part two, which always visits every node to find the best tiles)
+    #[arg(long, value_enum)]
+    algo: Option<Algorithm>,
+    /// The strategy to use for finding the tiles on any best route
+    #[arg(long, value_enum, default_value_t = TileStrategy::Fields)]
+    tiles: TileStrategy,
+    /// Write the best remaining cost to the end from every free tile to the given CSV file
+    #[arg(long)]
+    cost_field: Option<String>,
+    /// Print the given number of lowest distinct scores of routes that never revisit a tile,
+    /// and how much worse each is than the best, instead of solving a part
+    #[arg(long)]
+    best: Option<usize>,
+    /// Allow the reindeer to step diagonally, at the given cost for an ordinary tile (which is
+    /// multiplied by the weight of weighted tiles)
+    #[arg(long)]
+    diagonal_cost: Option<Score>,
+    /// Allow the reindeer to jump two tiles at once, over whatever is in between
+    #[arg(long)]
+    jumps: bool,
+    /// Only check that the maze is valid, reporting any problems found
+    #[arg(long)]
+    validate: bool,
+    /// Parse the input and print statistics about its structure instead of solving it
+    #[arg(long)]
+    parse_only: bool,
+    /// The format in which errors are reported
+    #[arg(long, value_enum, default_value_t = Format::Text)]
+    format: Format,
 }
 
-/// Representation of a map coordinate
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
-struct Coordinate {
-    x: isize,
-    y: isize,
-}
-
-impl Coordinate {
-    /// Gets the coordinate in a specific direction relative to this one
-    fn coordinate_for(&self, direction: &Direction) -> Coordinate {
-        match direction {
-            Direction::North => Coordinate::from((self.x, self.y - 1)),
-            Direction::South => Coordinate::from((self.x, self.y + 1)),
-            Direction::East => Coordinate::from((self.x + 1, self.y)),
-            Direction::West => Coordinate::from((self.x - 1, self.y)),
-        }
-    }
-
-    // Gets the coordinates in the cardinal directions from the given coordinate
-    fn cardinals(&self) -> Vec<(Coordinate, Direction)> {
-        let mut coords = Vec::new();
-        for direction in [
-            Direction::North,
-            Direction::East,
-            Direction::South,
-            Direction::West,
-        ] {
-            coords.push((self.coordinate_for(&direction), direction));
-        }
-        coords
-    }
-}
-
-impl From<(isize, isize)> for Coordinate {
-    fn from(value: (isize, isize)) -> Self {
-        Self {
-            x: value.0,
-            y: value.1,
-        }
-    }
-}
-
-impl From<Coordinate> for (isize, isize) {
-    fn from(value: Coordinate) -> Self {
-        (value.x, value.y)
-    }
-}
-
-impl Display for Coordinate {
-    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
-        write!(f, "({}, {})", self.x, self.y)
-    }
-}
-
-/// The vaarious directions in which entities can move
-#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
-enum Direction {
-    North,
-    South,
-    East,
-    West,
-}
-
-impl Display for Direction {
-    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
-        let character = match self {
-            Direction::North => '^',
-            Direction::East => '>',
-            Direction::South => 'v',
-            Direction::West => '<',
-        };
-        write!(f, "{}", character)
-    }
-}
-
-/// Representation of the game map
-#[derive(Debug)]
-struct GameMap {
-    start: Coordinate,
-    end: Coordinate,
-    visited: HashMap<Transit, NodeInfo>,
-    unvisited: HashMap<Transit, NodeInfo>,
-    spaces: HashSet<Coordinate>,
-    width: usize,
-    height: usize,
+/// Description of the puzzle solved by this binary
+const ABOUT: About = About {
+    day: 16,
+    title: "Reindeer Maze",
+    input:
+        "A maze of walls (#) with a start (S) and end (E) tile, and optionally weighted tiles (1-9)",
+    parts: &[1, 2],
+    features: &[],
+};
+
+/// Search algorithms available for finding the best score
+#[derive(Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum Algorithm {
+    /// Visit every node in the maze from the start
+    Dijkstra,
+    /// Search from both the start and end, meeting in the middle
+    Bidirectional,
 }
 
-impl Display for GameMap {
-    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
-        let mut map_string = String::new();
-
-        for row_index in 0..self.height {
-            for col_index in 0..self.width {
-                let coord = Coordinate::from((col_index as isize, row_index as isize));
-                if coord == self.start {
-                    map_string.push('S');
-                } else if coord == self.end {
-                    map_string.push('E');
-                } else if self.check_free(&coord).unwrap() {
-                    map_string.push('.');
-                } else {
-                    map_string.push('#');
-                }
-            }
-
-            map_string.push('\n');
-        }
-
-        write!(f, "{}", map_string)
-    }
+/// Strategies available for finding the tiles on any best route
+#[derive(Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum TileStrategy {
+    /// Combine the costs from the start with the costs to the end
+    Fields,
+    /// Walk backwards through the predecessors of the best end nodes
+    Backtrack,
 }
 
-impl GameMap {
-    /// Checks whether a given coordinate is empty
-    ///
-    /// Returns an error if the space is off the map.
-    fn check_free(&self, coord: &Coordinate) -> Result<bool, ()> {
-        //
-        if coord.x < 0
-            || coord.y < 0
-            || coord.x >= self.width as isize
-            || coord.y >= self.height as isize
-        {
-            return Err(());
-        }
-
-        Ok(self.spaces.contains(coord))
-    }
-
-    /// Gets the valid moves in cardinal directions
-    fn valid_cardinal_moves(&self, transit: &Transit) -> Vec<Transit> {
-        let (coord, _direction) = transit;
-        let cardinal_moves = coord.cardinals();
-        cardinal_moves
-            .iter()
-            .filter(|m| self.check_free(&m.0).expect("Invalid space"))
-            .copied()
-            .collect()
-    }
-
-    /// Gets the next available moves for a given transit node
-    ///
-    /// This is needed for Dijkstra's algorithm, for calculating the new scores for connections
-    /// from the current node being used (the given transit node).
-    ///
-    /// Returns a list of new coordinates the given transit node connects to, the direction
-    /// to travel in order to reach that coordinate, and the score associated with that move.
-    fn next_moves(&self, transit: &Transit) -> Vec<(Coordinate, Direction, Score)> {
-        // Break up the current transit node into its base components for ease of use
-        let (.., direction) = transit;
-
-        // Get all the valid moves that can be performed from the given transit node
-        let valid_moves: Vec<Transit> = self.valid_cardinal_moves(transit);
-
-        // Get the list of valid moves that would go to coordinates not yet visited
-        let new_moves: Vec<Transit> = valid_moves
-            .iter()
-            .filter(|m| !self.visited.contains_key(m))
-            .copied()
-            .collect();
-
-        // Get the score of the current transit node
-        let (current_score, ..) = self
-            .unvisited
-            .get(transit)
-            .expect("Could not get visited node");
-
-        // Create a list for new moves with associated scores
-        let mut new_scored_moves = Vec::new();
-
-        // Iterate through the new moves
-        for (new_coordinate, new_direction) in new_moves {
-            // Add one to the score for the associated move forward
-            let mut new_score = current_score + 1;
-
-            // If the direction of the move is not the current direction, add 1000 points for the necessary turn
-            if *direction != new_direction {
-                new_score += 1000;
-            }
-
-            // Add the new move set to the list
-            new_scored_moves.push((new_coordinate, new_direction, new_score));
-        }
-
-        // Return the list of moves
-        new_scored_moves
-    }
-
-    /// Gets the closet (score-wise) unvisited node
-    fn get_closest_unvisited(&self) -> (Coordinate, Direction) {
-        // If there is only one unvisited node, return that one
-        if self.unvisited.len() == 1 {
-            return *self
-                .unvisited
-                .iter()
-                .last()
-                .expect("Could not get last element")
-                .0;
-        }
+/// Main entry function
+fn main() {
+    // Describe the puzzle instead if requested
+    common::handle_about::<CliArgs>(&ABOUT);
 
-        // Get return the unvisited node with the lowest score
-        *self
-            .unvisited
-            .iter()
-            .min_by(|x, y| x.1 .0.cmp(&y.1 .0))
-            .expect("No items to sort out minimum")
-            .0
+    // Parse CLI arguments, only allowing the search algorithm to be chosen for part one
+    let cli = CliArgs::parse();
+    if cli.part != 1 && cli.algo.is_some() {
+        CliArgs::command()
+            .error(
+                ErrorKind::ArgumentConflict,
+                "the argument '--algo <ALGO>' can only be used with part 1",
+            )
+            .exit();
     }
 
-    /// Performs a single iteration of Dijkstra's algorithm
-    fn perform_dijkstra_iteration(&mut self) {
-        // Get the closest (score-wise) node from the start
-        let closest_transit = self.get_closest_unvisited();
-
-        // Get the neighbor connections/moves from the closest node
-        let next_moves = self.next_moves(&closest_transit);
+    // Report errors in the requested format
+    common::init(cli.format);
 
-        // Iterate through the connections/moves
-        for (next_coordinate, next_direction, next_score) in next_moves {
-            // Create the new transit node for the given coordinate and direction of the move
-            let next_transit = (next_coordinate, next_direction);
-
-            // Update the set of unvisited nodes
-            match self.unvisited.get_mut(&next_transit) {
-                // This transit nodes has been visited before
-                Some(info) => {
-                    // If new score would be at least as large as the stored one, ignore
-                    if next_score >= info.0 {
-                        continue;
-                    }
-
-                    // Otherwise, update the score for this node
-                    info.0 = next_score;
-                }
-                // This transit node is being visied for the first time
-                None => {
-                    // Add an entry for this transit node
-                    self.unvisited
-                        .insert(next_transit, (next_score, closest_transit));
-                }
-            }
-        }
-
-        // Remove the current transit node from the unvisited set, and add it to the visited set
-        let closest_entry = self
-            .unvisited
-            .remove(&closest_transit)
-            .expect("Could not complete marking as visited");
-        self.visited.insert(closest_transit, closest_entry);
+    // Only parse the input if requested
+    if cli.parse_only {
+        report_structure(cli.filepath);
     }
 
-    /// Visit all nodes in the maze, using Dijkstra's algorithm
-    fn visit_nodes(&mut self) {
-        loop {
-            if self.unvisited.is_empty() {
-                break;
-            }
-            self.perform_dijkstra_iteration();
+    // Only validate the maze if requested
+    if cli.validate {
+        let contents = read_input(cli.filepath);
+        if let Err(errors) = validate_maze(&contents) {
+            fail(Failure::Parse(describe_errors(&errors)));
         }
+        println!("The maze is valid");
+        return;
     }
 
-    /// Gets the end node entry with the minimum score
-    fn get_best_end_node(&self) -> (&Transit, &(Score, Transit)) {
-        self.visited
-            .iter()
-            .filter(|m| m.0 .0 == self.end)
-            .min_by(|x, y| x.1 .0.cmp(&y.1 .0))
-            .expect("Could not get end score")
-    }
-
-    /// Rewinds a completed map to find all best possible routes
-    fn rewind_route(&self, current_transit: Transit, best_locations: &mut HashSet<Coordinate>) {
-        // Add this coordinate to the list of best locations
-        best_locations.insert(current_transit.0);
-
-        // If the current transit node being analyzed is the start, no need to continuing searching
-        if current_transit.0 == self.start {
-            return;
-        }
-
-        // Get the information for the current transit node
-        let current_info = self
-            .visited
-            .get(&current_transit)
-            .expect("Could not get current info");
+    // Get the moves the reindeer can make
+    let moves = MoveSet {
+        diagonal_cost: cli.diagonal_cost,
+        jumps: cli.jumps,
+    };
 
-        // Get the valid cardinal moves from the current transit node
-        let valid_cardinal_moves = self.valid_cardinal_moves(&current_transit);
-
-        // Iterate through each coordinate in the valid cardinal moves
-        for (coordinate, ..) in valid_cardinal_moves {
-            // If the coordinate is already a best location, skip further analysis
-            if best_locations.contains(&coordinate) {
-                continue;
-            }
-
-            // Get the applicable visit nodes with the coordinate of the move
-            let applicable_visits: HashMap<&Transit, &(Score, Transit)> = self
-                .visited
-                .iter()
-                .filter(|v| v.0 .0 == coordinate)
-                .collect();
-
-            // Iterate through each applicable visit node
-            for (applicable_visit_transit, applicable_visit_info) in applicable_visits {
-                // The directions of the current transit node and the applicable visit node are the same
-                if applicable_visit_transit.1 == current_transit.1 {
-                    // If the score is different by 1, it is valid, and the rewind can continue via this node
-                    if applicable_visit_info.0 == current_info.0 - 1 {
-                        self.rewind_route(*applicable_visit_transit, best_locations);
-                    }
-                }
-                // The directions of the current transit node and applicable visit node are different
-                else {
-                    // If the score is different by 1001, it is valid, and the rewind can continue via this node
-                    if applicable_visit_info.0 == current_info.0 - 1001 {
-                        self.rewind_route(*applicable_visit_transit, best_locations);
-                    }
-                }
-            }
-        }
+    // Write the cost field if requested
+    if let Some(cost_field_filepath) = cli.cost_field {
+        write_cost_field(&load_maze(cli.filepath.clone(), moves), cost_field_filepath);
     }
 
-    /// Backtracks from the end node to the start node to find all coordinates associated
-    /// with a best possible route
-    fn backtrack(&self) -> HashSet<Coordinate> {
-        // Create a hash set for storing the best locations
-        let mut best_locations = HashSet::new();
-
-        // Get all end transit nodes with the lowest score
-        let best_end_node = self.get_best_end_node();
-        let end_nodes: HashMap<&Transit, &(Score, Transit)> = self
-            .visited
-            .iter()
-            .filter(|m| m.0 .0 == self.end && m.1 .0 == best_end_node.1 .0)
-            .collect();
-
-        // Rewind through the applicable end nodes
-        for end_node in end_nodes {
-            self.rewind_route(*end_node.0, &mut best_locations);
-        }
-
-        // Return the set of best locations
-        best_locations
+    // Print the lowest route scores instead if requested
+    if let Some(k) = cli.best {
+        print_best_scores(&load_maze(cli.filepath, moves), k);
+        return;
     }
-}
-
-/// Main entry function
-fn main() {
-    // Parse CLI arguments
-    let cli = CliArgs::parse();
 
     // Run the code for the desired challenge part
     match cli.part {
-        1 => main_part_one(cli.filepath),
-        2 => main_part_two(cli.filepath),
+        1 => main_part_one(cli.filepath, cli.algo.unwrap_or(Algorithm::Dijkstra), moves),
+        2 => main_part_two(cli.filepath, cli.tiles, moves),
         _ => panic!("Invalid selection part selection!"),
     }
 }
 
 /// Runs part one
-fn main_part_one(filepath: String) {
-    // Get the trail ratings
-    let contents = fs::read_to_string(filepath).expect("Invalid filepath");
-
-    // Parse the input file contents into the game map
-    let mut gamemap = parse_game(&contents);
+fn main_part_one(filepath: String, algo: Algorithm, moves: MoveSet) {
+    // Get the validated game map from the input file
+    let mut gamemap = load_maze(filepath, moves);
+
+    // If requested, search from both ends instead of visiting every node
+    if algo == Algorithm::Bidirectional {
+        let final_score = gamemap
+            .bidirectional_best_score()
+            .unwrap_or_else(|| fail(Failure::Unsolvable(String::from("Could not reach the end"))));
+        println!("{final_score}");
+        return;
+    }
 
     // Visit all possible nodes in the game map
-    gamemap.visit_nodes();
+    let result = gamemap.search();
 
     // Get the best possible score for reaching the end
-    let final_entry = gamemap.get_best_end_node();
-    let final_score = final_entry.1 .0;
+    let final_score = result
+        .best_end_score()
+        .unwrap_or_else(|| fail(Failure::Unsolvable(String::from("Could not reach the end"))));
     println!("{final_score}");
 }
 
 /// Runs part two
-fn main_part_two(filepath: String) {
-    // Get the trail ratings
-    let contents = fs::read_to_string(filepath).expect("Invalid filepath");
-
-    // Parse the input file contents into the game map
-    let mut gamemap = parse_game(&contents);
-
-    // Visit all possible nodes in the game map
-    gamemap.visit_nodes();
-
-    // Backtrack from the end node to find all possible best locations
-    let best_locations = gamemap.backtrack();
+fn main_part_two(filepath: String, tiles: TileStrategy, moves: MoveSet) {
+    // Get the validated game map from the input file
+    let mut gamemap = load_maze(filepath, moves);
+
+    // Find all possible best locations using the requested strategy
+    let best_locations = match tiles {
+        // Find where the costs from the start and to the end add up to the best score
+        TileStrategy::Fields => gamemap.optimal_locations(),
+        // Visit all possible nodes in the game map, then backtrack from the end node
+        TileStrategy::Backtrack => gamemap.search().best_locations(),
+    };
 
     // Print the number of best locations
     let num_locations = best_locations.len();
     println!("{num_locations}");
 }
 
-/// Parses the given string into the game map
-fn parse_game(text: &str) -> GameMap {
-    // Create default start and end nodes
-    let mut start = Coordinate::from((0, 0));
-    let mut end = Coordinate::from((0, 0));
+/// Prints the given number of lowest distinct route scores as CSV, along with how much worse
+/// each is than the best
+fn print_best_scores(gamemap: &GameMap, k: usize) {
+    let scores = gamemap.best_scores(k);
+    let Some(best_score) = scores.first().copied() else {
+        fail(Failure::Unsolvable(String::from("Could not reach the end")));
+    };
+    println!("rank,score,difference");
+    for (index, score) in scores.iter().enumerate() {
+        println!("{},{score},{}", index + 1, score - best_score);
+    }
+}
 
-    // Create a list for storing empty spaces
-    let mut spaces = HashSet::new();
+/// Writes the best remaining cost to the end from every free tile that can reach it, facing
+/// whichever direction is best, to the given CSV file
+fn write_cost_field(gamemap: &GameMap, cost_field_filepath: String) {
+    // Get the best cost for each tile over all of the directions it can be faced in
+    let mut costs: HashMap<Coordinate, Score> = HashMap::new();
+    for ((coord, _), cost) in gamemap.cost_to_end() {
+        costs
+            .entry(coord)
+            .and_modify(|x| *x = cost.min(*x))
+            .or_insert(cost);
+    }
 
-    // Iterate through the string character by character
-    for (row_index, line) in text.trim().lines().enumerate() {
-        for (col_index, character) in line.chars().enumerate() {
-            // Get the current coordinate based on the iteration
-            let coord = Coordinate::from((col_index as isize, row_index as isize));
+    // Build the CSV of the costs, ordered by row and then column
+    let mut tiles: Vec<(Coordinate, Score)> = costs.into_iter().collect();
+    tiles.sort_by_key(|(coord, _)| (coord.y, coord.x));
+    let mut csv = String::from("x,y,cost\n");
+    for (coord, cost) in tiles {
+        csv.push_str(&format!("{},{},{cost}\n", coord.x, coord.y));
+    }
+
+    // Write the costs to the given file
+    fs::write(&cost_field_filepath, csv).unwrap_or_else(|error| {
+        fail(Failure::Io(format!(
+            "Could not write {cost_field_filepath}: {error}"
+        )))
+    });
+}
 
-            // Handle each space on the map
-            match character {
-                '.' => {
-                    spaces.insert(coord);
-                }
-                'S' => {
-                    start = coord;
-                    spaces.insert(coord);
-                }
-                'E' => {
-                    end = coord;
-                    spaces.insert(coord);
-                }
-                '#' => continue,
-                _char => panic!("Encountered unrecognized character: {_char}"),
+/// Reads and validates the maze from the given input file, and parses it into the game map
+/// where the reindeer can make the given moves
+///
+/// A maze whose only problem is that the end can't be reached is reported as unsolvable,
+/// while any other problem is reported as a parsing error.  Reachability is checked using
+/// cardinal steps, so an unreachable end is left to the search when other moves are allowed.
+fn load_maze(filepath: String, moves: MoveSet) -> GameMap {
+    // Get the contents of the given filepath
+    let contents = read_input(filepath);
+
+    // Check the maze for problems before parsing it
+    if let Err(errors) = validate_maze(&contents) {
+        let message = describe_errors(&errors);
+        if errors.iter().all(|x| *x == MazeError::Unreachable) {
+            if moves == MoveSet::default() {
+                fail(Failure::Unsolvable(message));
             }
+        } else {
+            fail(Failure::Parse(message));
         }
     }
 
-    // Calculate the height and width of the map from the given string
-    let height = text.trim().lines().count();
-    let width = text.trim().lines().last().unwrap().len();
-
-    // Create the set of unvisited nodes, seeding the start node into it
-    let mut unvisited = HashMap::new();
-    let start_transit = (start, Direction::East);
-    let start_node = (0, start_transit);
-    unvisited.insert(start_transit, start_node);
+    // Parse the input file contents into the game map
+    let mut gamemap = parse_game(&contents);
+    gamemap.moves = moves;
+    gamemap
+}
 
-    // Return the finalized game map
-    GameMap {
-        start,
-        end,
-        visited: HashMap::new(),
-        unvisited,
-        spaces,
-        width,
-        height,
+/// Parses the maze, failing if it has problems other than the end being unreachable, then prints
+/// its dimensions and the number of open and weighted tiles in it and exits
+fn report_structure(filepath: String) -> ! {
+    // Check the maze for problems that keep it from being parsed
+    let contents = read_input(filepath);
+    if let Err(errors) = validate_maze(&contents) {
+        if errors.iter().any(|x| *x != MazeError::Unreachable) {
+            fail(Failure::Parse(describe_errors(&errors)));
+        }
     }
+
+    // Describe the parsed maze
+    let gamemap = parse_game(&contents);
+    common::report_parse(&[
+        ("rows", gamemap.height),
+        ("columns", gamemap.width),
+        ("open tiles", gamemap.spaces.len()),
+        ("weighted tiles", gamemap.weights.len()),
+    ]);
 }
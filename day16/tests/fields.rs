@@ -0,0 +1,40 @@
+use day16::{parse_game, Direction};
+
+const EXAMPLE: &str = include_str!("../inputs/example.txt");
+
+#[test]
+fn cost_to_end_from_start_is_best_score() {
+    let gamemap = parse_game(EXAMPLE);
+    let backward = gamemap.cost_to_end();
+    assert_eq!(backward.get(&(gamemap.start, Direction::East)), Some(&7036));
+    for direction in Direction::all() {
+        assert_eq!(backward.get(&(gamemap.end, direction)), Some(&0));
+    }
+}
+
+#[test]
+fn cost_from_start_matches_search() {
+    let mut gamemap = parse_game(EXAMPLE);
+    let forward = gamemap.cost_from_start();
+    let result = gamemap.search();
+    for (transit, score) in forward {
+        assert_eq!(result.score(&transit), Some(score), "{transit:?}");
+    }
+}
+
+#[test]
+fn optimal_locations_match_backtracking() {
+    let mut gamemap = parse_game(EXAMPLE);
+    let optimal_locations = gamemap.optimal_locations();
+    assert_eq!(optimal_locations.len(), 45);
+    assert_eq!(optimal_locations, gamemap.search().best_locations());
+}
+
+#[test]
+fn unreachable_end_has_no_optimal_locations() {
+    let gamemap = parse_game("#####\n#S#E#\n#####\n");
+    assert!(!gamemap
+        .cost_to_end()
+        .contains_key(&(gamemap.start, Direction::East)));
+    assert!(gamemap.optimal_locations().is_empty());
+}
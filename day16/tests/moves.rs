@@ -0,0 +1,83 @@
+use day16::{parse_game, GameMap, MoveSet};
+
+const EXAMPLE: &str = include_str!("../inputs/example.txt");
+
+const OPEN: &str = "\
+#####
+#..E#
+#...#
+#S..#
+#####
+";
+
+const WALLED: &str = "\
+#####
+#S#E#
+#####
+";
+
+fn with_moves(text: &str, moves: MoveSet) -> GameMap {
+    let mut gamemap = parse_game(text);
+    gamemap.moves = moves;
+    gamemap
+}
+
+#[test]
+fn default_is_cardinal() {
+    let result = with_moves(EXAMPLE, MoveSet::default()).search();
+    assert_eq!(result.best_end_score(), Some(7036));
+    assert_eq!(with_moves(OPEN, MoveSet::default()).best_scores(1), [1004]);
+}
+
+#[test]
+fn diagonal_cost() {
+    let cheap = MoveSet {
+        diagonal_cost: Some(1),
+        jumps: false,
+    };
+    let expensive = MoveSet {
+        diagonal_cost: Some(5),
+        jumps: false,
+    };
+    assert_eq!(
+        with_moves(OPEN, cheap).search().best_end_score(),
+        Some(1002)
+    );
+    assert_eq!(
+        with_moves(OPEN, expensive).search().best_end_score(),
+        Some(1004)
+    );
+}
+
+#[test]
+fn jumps_cross_walls() {
+    let jumps = MoveSet {
+        diagonal_cost: None,
+        jumps: true,
+    };
+    assert_eq!(
+        with_moves(WALLED, MoveSet::default())
+            .search()
+            .best_end_score(),
+        None
+    );
+    assert_eq!(with_moves(WALLED, jumps).search().best_end_score(), Some(2));
+    assert_eq!(
+        with_moves(WALLED, jumps).bidirectional_best_score(),
+        Some(2)
+    );
+}
+
+#[test]
+fn searches_agree() {
+    let moves = MoveSet {
+        diagonal_cost: Some(2),
+        jumps: true,
+    };
+    let result = with_moves(EXAMPLE, moves).search();
+    let gamemap = with_moves(EXAMPLE, moves);
+    assert_eq!(gamemap.bidirectional_best_score(), result.best_end_score());
+    assert_eq!(gamemap.best_scores(1), [result.best_end_score().unwrap()]);
+    assert_eq!(gamemap.optimal_locations(), result.best_locations());
+    assert!(result.best_end_score().unwrap() < 7036);
+}
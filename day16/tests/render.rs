@@ -0,0 +1,9 @@
+use day16::parse_game;
+
+const EXAMPLE: &str = include_str!("../inputs/example.txt");
+
+#[test]
+fn renders_example() {
+    let gamemap = parse_game(EXAMPLE);
+    insta::assert_snapshot!(gamemap.to_string());
+}
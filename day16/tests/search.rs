@@ -0,0 +1,55 @@
+use day16::{parse_game, Direction};
+
+const EXAMPLE: &str = include_str!("../inputs/example.txt");
+
+#[test]
+fn best_end_score() {
+    let result = parse_game(EXAMPLE).search();
+    assert_eq!(result.best_end_score(), Some(7036));
+}
+
+#[test]
+fn best_locations() {
+    let result = parse_game(EXAMPLE).search();
+    assert_eq!(result.best_locations().len(), 45);
+}
+
+#[test]
+fn predecessors_share_best_score() {
+    let gamemap = &mut parse_game(EXAMPLE);
+    let result = gamemap.search();
+    for transit in result.best_end_transits() {
+        let score = result.score(&transit).unwrap();
+        for previous in result.predecessors(&transit) {
+            let step = if previous.1 == transit.1 { 1 } else { 1001 };
+            assert_eq!(result.score(previous), Some(score - step));
+        }
+    }
+    assert!(result
+        .predecessors(&(gamemap.start, Direction::East))
+        .is_empty());
+}
+
+#[test]
+fn best_scores() {
+    let scores = parse_game(EXAMPLE).best_scores(5);
+    assert_eq!(scores.len(), 5);
+    assert_eq!(scores[0], 7036);
+    assert!(scores.windows(2).all(|x| x[0] < x[1]));
+}
+
+#[test]
+fn best_scores_of_corridor() {
+    // Any other route would have to double back over a tile
+    let scores = parse_game("#####\n#S.E#\n#####\n").best_scores(3);
+    assert_eq!(scores, vec![2]);
+}
+
+#[test]
+fn best_scores_around_pillar() {
+    // The end can be reached straight along the top, along the bottom and up the far side,
+    // or along the bottom and back up through the middle gap
+    let maze = "#######\n#S...E#\n#.#.#.#\n#.....#\n#######\n";
+    assert_eq!(parse_game(maze).best_scores(1), vec![4]);
+    assert_eq!(parse_game(maze).best_scores(5), vec![4, 3008, 4008]);
+}
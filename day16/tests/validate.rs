@@ -0,0 +1,53 @@
+use day16::{validate_maze, Coordinate, MazeError};
+
+const EXAMPLE: &str = include_str!("../inputs/example.txt");
+
+#[test]
+fn example_is_valid() {
+    assert_eq!(validate_maze(EXAMPLE), Ok(()));
+}
+
+#[test]
+fn missing_end() {
+    let maze = "#####\n#S..#\n#####\n";
+    assert_eq!(validate_maze(maze), Err(vec![MazeError::MissingEnd]));
+}
+
+#[test]
+fn multiple_starts() {
+    let maze = "#####\n#SSE#\n#####\n";
+    assert_eq!(
+        validate_maze(maze),
+        Err(vec![MazeError::MultipleStarts(vec![
+            Coordinate::from((1, 1)),
+            Coordinate::from((2, 1)),
+        ])])
+    );
+}
+
+#[test]
+fn open_border() {
+    let maze = "#####\n#S.E.\n#####\n";
+    assert_eq!(
+        validate_maze(maze),
+        Err(vec![MazeError::OpenBorder(Coordinate::from((4, 1)))])
+    );
+}
+
+#[test]
+fn unreachable_end() {
+    let maze = "#####\n#S#E#\n#####\n";
+    assert_eq!(validate_maze(maze), Err(vec![MazeError::Unreachable]));
+}
+
+#[test]
+fn unrecognized_character() {
+    let maze = "#####\n#S?E#\n#####\n";
+    assert_eq!(
+        validate_maze(maze),
+        Err(vec![
+            MazeError::UnrecognizedCharacter('?', Coordinate::from((2, 1))),
+            MazeError::Unreachable,
+        ])
+    );
+}
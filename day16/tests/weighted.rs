@@ -0,0 +1,56 @@
+use day16::{parse_game, validate_maze};
+
+/// A single corridor, where the weighted space costs 9 to step onto instead of 1
+const CORRIDOR: &str = "#######
+#S.9.E#
+#######
+";
+
+/// Two routes around a wall with the same turns, where the top one is slowed by a weighted
+/// space
+///
+/// The bottom route costs 1001 + 1001 + 3 + 1001 = 3006, while the top route costs
+/// 1001 + 1001 + 1 + 9 + 1 + 1001 = 3014.
+const DETOUR: &str = "#######
+#...9.#
+#S###E#
+#.....#
+#######
+";
+
+#[test]
+fn weighted_mazes_are_valid() {
+    assert_eq!(validate_maze(CORRIDOR), Ok(()));
+    assert_eq!(validate_maze(DETOUR), Ok(()));
+}
+
+#[test]
+fn corridor_pays_for_weighted_space() {
+    let mut map = parse_game(CORRIDOR);
+    assert_eq!(map.search().best_end_score(), Some(12));
+    assert_eq!(map.bidirectional_best_score(), Some(12));
+}
+
+#[test]
+fn detour_avoids_weighted_space() {
+    let mut map = parse_game(DETOUR);
+    let result = map.search();
+    assert_eq!(result.best_end_score(), Some(3006));
+    assert_eq!(map.bidirectional_best_score(), Some(3006));
+
+    // Only the bottom route is on a best path
+    assert_eq!(result.best_locations().len(), 7);
+    assert_eq!(map.optimal_locations().len(), 7);
+}
+
+#[test]
+fn unweighted_detour_ties() {
+    let mut map = parse_game(&DETOUR.replace('9', "."));
+    assert_eq!(map.search().best_end_score(), Some(3006));
+    assert_eq!(map.optimal_locations().len(), 12);
+}
+
+#[test]
+fn weighted_spaces_are_displayed() {
+    assert_eq!(parse_game(DETOUR).to_string(), DETOUR);
+}
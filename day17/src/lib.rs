@@ -0,0 +1,596 @@
+use std::ops::Range;
+
+use rayon::prelude::*;
+use regex::Regex;
+
+/// A candidate value of Register A tested while reverse engineering, along with the
+/// candidates tested from it for the next output number
+#[derive(Debug, Clone)]
+pub struct SearchNode {
+    /// The candidate value of Register A
+    pub register_a: u64,
+    /// The index of the output number the candidate needed to produce
+    pub digit: usize,
+    /// The output number the candidate needed to produce
+    pub target: u8,
+    /// The output number the candidate actually produced
+    pub printed: u8,
+    /// The candidates tested from this one, if it was accepted
+    pub children: Vec<SearchNode>,
+}
+
+impl SearchNode {
+    /// Checks whether the candidate produced the needed output number
+    pub fn accepted(&self) -> bool {
+        self.printed == self.target
+    }
+
+    /// Counts the candidates in the tree rooted at this one, including itself
+    pub fn count(&self) -> usize {
+        1 + self.children.iter().map(|x| x.count()).sum::<usize>()
+    }
+
+    /// Gets the tree rooted at this candidate as JSON
+    pub fn to_json(&self) -> String {
+        let children: Vec<String> = self.children.iter().map(|x| x.to_json()).collect();
+        format!(
+            "{{\"register_a\":{},\"digit\":{},\"target\":{},\"printed\":{},\"accepted\":{},\"children\":[{}]}}",
+            self.register_a,
+            self.digit,
+            self.target,
+            self.printed,
+            self.accepted(),
+            children.join(",")
+        )
+    }
+}
+
+/// The parameters of the virtual machine, which default to those of the puzzle
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MachineSpec {
+    /// The modulus applied to the values of the BST and OUT instructions
+    pub modulus: u64,
+    /// The base raised to the power of the combo operand for the division instructions
+    pub divisor_base: u64,
+    /// The number of bits in each register
+    pub register_bits: u32,
+}
+
+impl MachineSpec {
+    /// Truncates the given value to fit in a register
+    pub fn truncate(&self, value: u64) -> u64 {
+        if self.register_bits >= u64::BITS {
+            value
+        } else {
+            value & ((1 << self.register_bits) - 1)
+        }
+    }
+
+    /// Divides the given numerator by the divisor base raised to the given power, which is
+    /// zero if the divisor is too large to be represented
+    fn divide(&self, numerator: u64, exp: u64) -> u64 {
+        u32::try_from(exp)
+            .ok()
+            .and_then(|x| self.divisor_base.checked_pow(x))
+            .map_or(0, |divisor| numerator / divisor)
+    }
+}
+
+impl Default for MachineSpec {
+    fn default() -> Self {
+        Self {
+            modulus: 8,
+            divisor_base: 2,
+            register_bits: 64,
+        }
+    }
+}
+
+/// The result of running a program to completion
+#[derive(Debug, Clone)]
+pub struct ProgramResult {
+    /// The output numbers separated with commas
+    pub output: String,
+    /// Whether the program was stopped for exceeding the maximum number of output values
+    pub truncated: bool,
+}
+
+/// The number of times a single instruction of the program was executed
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CoverageLine {
+    /// The address of the instruction's opcode within the program
+    pub address: usize,
+    /// The opcode of the instruction
+    pub opcode: u8,
+    /// The operand of the instruction
+    pub operand: u8,
+    /// The mnemonic of the instruction, such as `adv`
+    pub mnemonic: &'static str,
+    /// The number of times the instruction was executed
+    pub count: u64,
+}
+
+/// Type representing a literal operand
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+struct LiteralOperand(u8);
+
+impl LiteralOperand {
+    /// Gets the value of the literal operand
+    fn value(&self) -> u64 {
+        self.0 as u64
+    }
+}
+
+/// Type representing a combo operand
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+struct ComboOperand(u8);
+
+/// Type representing the operand when it is not needed
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+struct UnusedOperand(u8);
+
+/// Instructions that the computer can perfrom
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+enum Instruction {
+    Adv(ComboOperand),
+    Bxl(LiteralOperand),
+    Bst(ComboOperand),
+    Jnz(LiteralOperand),
+    Bxc(UnusedOperand),
+    Out(ComboOperand),
+    Bdv(ComboOperand),
+    Cdv(ComboOperand),
+}
+
+impl Instruction {
+    /// Parses an opcode and operand into the associated instruction
+    fn parse(opcode: u8, operand: u8) -> Result<Instruction, String> {
+        let literal = LiteralOperand(operand);
+        let combo = ComboOperand(operand);
+        let unused = UnusedOperand(operand);
+        match opcode {
+            0 => Ok(Self::Adv(combo)),
+            1 => Ok(Self::Bxl(literal)),
+            2 => Ok(Self::Bst(combo)),
+            3 => Ok(Self::Jnz(literal)),
+            4 => Ok(Self::Bxc(unused)),
+            5 => Ok(Self::Out(combo)),
+            6 => Ok(Self::Bdv(combo)),
+            7 => Ok(Self::Cdv(combo)),
+            _o => Err(format!("Could not parse {_o} as an opcode")),
+        }
+    }
+
+    /// Gets the instruction as the pair of integers it represents
+    fn as_numbers(&self) -> (u8, u8) {
+        match *self {
+            Self::Adv(combo) => (0, combo.0),
+            Self::Bxl(literal) => (1, literal.0),
+            Self::Bst(combo) => (2, combo.0),
+            Self::Jnz(literal) => (3, literal.0),
+            Self::Bxc(unused) => (4, unused.0),
+            Self::Out(combo) => (5, combo.0),
+            Self::Bdv(combo) => (6, combo.0),
+            Self::Cdv(combo) => (7, combo.0),
+        }
+    }
+
+    /// Gets the mnemonic of the instruction
+    fn mnemonic(&self) -> &'static str {
+        match self {
+            Self::Adv(_) => "adv",
+            Self::Bxl(_) => "bxl",
+            Self::Bst(_) => "bst",
+            Self::Jnz(_) => "jnz",
+            Self::Bxc(_) => "bxc",
+            Self::Out(_) => "out",
+            Self::Bdv(_) => "bdv",
+            Self::Cdv(_) => "cdv",
+        }
+    }
+}
+
+/// The computer that will execute the program
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Computer {
+    /// Register A
+    pub register_a: u64,
+    /// Register B
+    pub register_b: u64,
+    /// Register C
+    pub register_c: u64,
+    /// List of instructions to execute
+    instructions: Vec<Instruction>,
+    /// Pointer that points to the index of the instruction to execute next
+    pointer: usize,
+    /// Running list of output numbers from the program
+    output: Vec<u64>,
+    /// Maximum number of output numbers before the program is stopped, if any
+    pub max_output: Option<usize>,
+    /// Whether the program was stopped for exceeding the maximum number of output numbers
+    truncated: bool,
+    /// The parameters of the machine the program runs on
+    spec: MachineSpec,
+    /// The number of times each instruction has been executed
+    counts: Vec<u64>,
+}
+
+impl Computer {
+    /// Creates a computer from the given string input, failing if a register is missing or
+    /// the program can't be parsed
+    pub fn from_string(text: &str) -> Result<Self, String> {
+        // Get the values of the registers
+        let register_a = Self::register_value(text, 'A')?;
+        let register_b = Self::register_value(text, 'B')?;
+        let register_c = Self::register_value(text, 'C')?;
+
+        // Create the regex pattern for parsing instructions
+        let instructions_re = Regex::new(r"(?:Program: )*(?: *)(\d+),(\d+)").unwrap();
+
+        // Create a list for storing instructions
+        let mut instructions = Vec::new();
+
+        // Iterate through the captures for the instructions
+        for (_, [opcode_str, operand_str]) in
+            instructions_re.captures_iter(text).map(|c| c.extract())
+        {
+            // Convert the captures into the opcode and operand
+            let opcode = opcode_str
+                .parse::<u8>()
+                .map_err(|_| format!("Could not parse {opcode_str} as an opcode"))?;
+            let operand = operand_str
+                .parse::<u8>()
+                .map_err(|_| format!("Could not parse {operand_str} as an operand"))?;
+
+            // Parse the instruction
+            let instruction = Instruction::parse(opcode, operand)?;
+
+            // Add the instruction to the list
+            instructions.push(instruction);
+        }
+
+        // Create and return the computer
+        let counts = vec![0; instructions.len()];
+        Ok(Self {
+            register_a,
+            register_b,
+            register_c,
+            instructions,
+            pointer: 0,
+            output: Vec::new(),
+            max_output: None,
+            truncated: false,
+            spec: MachineSpec::default(),
+            counts,
+        })
+    }
+
+    /// Gets the value of the register with the given name from the string input
+    fn register_value(text: &str, name: char) -> Result<u64, String> {
+        let register_re = Regex::new(&format!(r"Register {name}: (\d+)")).unwrap();
+        let Some((_, [value])) = register_re.captures(text).map(|c| c.extract()) else {
+            return Err(format!("Missing the value of register {name}"));
+        };
+        value
+            .parse::<u64>()
+            .map_err(|_| format!("Could not parse {value} as the value of register {name}"))
+    }
+
+    /// Sets the parameters of the machine, truncating the registers to fit
+    pub fn set_spec(&mut self, spec: MachineSpec) {
+        self.spec = spec;
+        self.register_a = spec.truncate(self.register_a);
+        self.register_b = spec.truncate(self.register_b);
+        self.register_c = spec.truncate(self.register_c);
+    }
+
+    /// Gets the value of the given combo operand
+    fn get_combo_operand_value(&self, combo: &ComboOperand) -> u64 {
+        match combo.0 {
+            0..=3 => combo.0 as u64,
+            4 => self.register_a,
+            5 => self.register_b,
+            6 => self.register_c,
+            _ => panic!("Encounter unrecognized combo operand"),
+        }
+    }
+
+    /// Runs the programs and returns the output string of numbers, along with whether it
+    /// was truncated
+    pub fn run_program(&mut self) -> ProgramResult {
+        while let Some(instruction) = self.fetch_instruction() {
+            self.execute_instruction(&instruction);
+        }
+        ProgramResult {
+            output: self.create_output(),
+            truncated: self.truncated,
+        }
+    }
+
+    /// Runs a single cycle of the instructions and returns the output number for that cycle
+    fn run_program_once(&mut self) -> u8 {
+        // Reset the instruction pointer to the first instruction
+        self.pointer = 0;
+
+        // Reset the output list of numbers
+        self.output = Vec::new();
+        self.truncated = false;
+
+        // Get the number of instructions
+        let num_instructions = self.instructions.len();
+
+        // Iterate through the instructions
+        while let Some(instruction) = self.fetch_instruction() {
+            // Execute the next instruction
+            self.execute_instruction(&instruction);
+
+            // If the pointer has jumped to the start or exceeded available instructions,
+            // return the last number output
+            if self.pointer == 0 || self.pointer == num_instructions {
+                return *self.output.last().unwrap() as u8;
+            }
+        }
+
+        // Something went wrong
+        panic!("Could not get output number for this cycle");
+    }
+
+    /// Fetches the next instruction
+    fn fetch_instruction(&self) -> Option<Instruction> {
+        self.instructions.get(self.pointer).copied()
+    }
+
+    // Executes the given instruction
+    fn execute_instruction(&mut self, instruction: &Instruction) {
+        // Count the execution against the instruction at the pointer
+        self.counts[self.pointer] += 1;
+
+        // Execute the instruction and determine whether the pointer should be incremented
+        let advance_pointer = match instruction {
+            Instruction::Adv(op) => self.perform_adv(op),
+            Instruction::Bxl(op) => self.perform_bxl(op),
+            Instruction::Bst(op) => self.perform_bst(op),
+            Instruction::Jnz(op) => self.perform_jnz(op),
+            Instruction::Bxc(_unused) => self.perform_bxc(),
+            Instruction::Out(op) => self.perform_out(op),
+            Instruction::Bdv(op) => self.perform_bdv(op),
+            Instruction::Cdv(op) => self.perform_cdv(op),
+        };
+
+        // Increment the pointer if needed
+        if advance_pointer {
+            self.pointer += 1;
+        }
+    }
+
+    /// Performs the ADV instruction
+    fn perform_adv(&mut self, combo: &ComboOperand) -> bool {
+        let numerator = self.register_a;
+        let exp = self.get_combo_operand_value(combo);
+        let result = self.spec.divide(numerator, exp);
+        self.register_a = self.spec.truncate(result);
+        true
+    }
+
+    /// Performs the BXL instruction
+    fn perform_bxl(&mut self, literal: &LiteralOperand) -> bool {
+        let x = self.register_b;
+        let y = literal.value();
+        let result = x ^ y;
+        self.register_b = self.spec.truncate(result);
+        true
+    }
+
+    /// Performs the BST instruction
+    fn perform_bst(&mut self, combo: &ComboOperand) -> bool {
+        let x = self.get_combo_operand_value(combo);
+        let result = x % self.spec.modulus;
+        self.register_b = self.spec.truncate(result);
+        true
+    }
+
+    /// Performs the JNZ instruction
+    fn perform_jnz(&mut self, literal: &LiteralOperand) -> bool {
+        if self.register_a == 0 {
+            return true;
+        }
+
+        let jump_location = literal.value();
+        self.pointer = jump_location as usize;
+        false
+    }
+
+    /// Performs the BXC instruction
+    fn perform_bxc(&mut self) -> bool {
+        let x = self.register_b;
+        let y = self.register_c;
+        let result = x ^ y;
+        self.register_b = self.spec.truncate(result);
+        true
+    }
+
+    /// Performs the OUT instruction
+    ///
+    /// If the output is already at the maximum size, the program is halted instead.
+    fn perform_out(&mut self, combo: &ComboOperand) -> bool {
+        if self.max_output.is_some_and(|x| self.output.len() >= x) {
+            self.truncated = true;
+            self.pointer = self.instructions.len();
+            return false;
+        }
+
+        let value = self.get_combo_operand_value(combo);
+        let result = value % self.spec.modulus;
+        self.output.push(result);
+        true
+    }
+
+    /// Performs the BDV instruction
+    fn perform_bdv(&mut self, combo: &ComboOperand) -> bool {
+        let numerator = self.register_a;
+        let exp = self.get_combo_operand_value(combo);
+        let result = self.spec.divide(numerator, exp);
+        self.register_b = self.spec.truncate(result);
+        true
+    }
+
+    /// Performs the CDV instruction
+    fn perform_cdv(&mut self, combo: &ComboOperand) -> bool {
+        let numerator = self.register_a;
+        let exp = self.get_combo_operand_value(combo);
+        let result = self.spec.divide(numerator, exp);
+        self.register_c = self.spec.truncate(result);
+        true
+    }
+
+    /// Gets the number of times each instruction of the program has been executed so far, in
+    /// the order they appear in the program
+    pub fn coverage(&self) -> Vec<CoverageLine> {
+        self.instructions
+            .iter()
+            .zip(&self.counts)
+            .enumerate()
+            .map(|(index, (instruction, count))| {
+                let (opcode, operand) = instruction.as_numbers();
+                CoverageLine {
+                    address: index * 2,
+                    opcode,
+                    operand,
+                    mnemonic: instruction.mnemonic(),
+                    count: *count,
+                }
+            })
+            .collect()
+    }
+
+    /// Creates a string of the output numbers separated with commas
+    fn create_output(&self) -> String {
+        let strings: Vec<String> = self.output.iter().map(|o| o.to_string()).collect();
+        strings.join(",")
+    }
+
+    /// Finds the lowest value of Register A that creates an output of its own instructions,
+    /// along with the tree of candidate values tested along the way
+    pub fn find_self_outputing_register_a(&mut self) -> (u64, Vec<SearchNode>) {
+        // Create the list of output numbers from the instructions
+        let mut output = Vec::new();
+        for instruction in &self.instructions {
+            let (x, y) = instruction.as_numbers();
+            output.push(x);
+            output.push(y);
+        }
+
+        // Start with the register at the needed value of zero
+        let register_a = 0;
+
+        // Reverse engineer the value for Register A
+        let mut tree = Vec::new();
+        let result = self.reverse_engineer_register_a(register_a, &mut output, &mut tree);
+
+        // Return the value of Register A and the candidates tested
+        (result.1, tree)
+    }
+
+    /// Reverse engineers the value of Register A recursively as needed
+    ///
+    /// The input program uses only register A to calculate the values of Registers B and C,
+    /// which in turn is what specifies the output number.  The value of Register A is then
+    /// divided by 8, and the cycle repeats until Register A equals 0.  This method determines
+    /// what the output number is for possible values of Register A that would create the
+    /// current output, and recursively searches to make sure it can output all other values
+    /// of the output, searching until a match is found.
+    ///
+    /// Each candidate value tested is added to the given tree.
+    fn reverse_engineer_register_a(
+        &mut self,
+        register_a: u64,
+        output: &mut Vec<u8>,
+        tree: &mut Vec<SearchNode>,
+    ) -> (bool, u64) {
+        // If there is no additional output to reverse engindeer, return the current value of Register A
+        if output.is_empty() {
+            return (true, register_a);
+        }
+
+        // Get the next value to reverse engineer
+        let printout = output.pop().unwrap();
+
+        // Get the bounds of the new Register A values to test
+        let new_register_a_base = register_a * 8;
+        let bound_register_a = new_register_a_base + 8;
+
+        // Check the possible values for Register A
+        for a in new_register_a_base..bound_register_a {
+            // Set Register A to the test value
+            self.register_a = a;
+
+            // Get the out number for a single cycle of the program, and record the candidate
+            let printed = self.run_program_once();
+            let mut node = SearchNode {
+                register_a: a,
+                digit: output.len(),
+                target: printout,
+                printed,
+                children: Vec::new(),
+            };
+
+            // If the output number matches the necessary number, recursively search for the
+            // next number using the current value of Register A
+            if node.accepted() {
+                let (finished, answer) =
+                    self.reverse_engineer_register_a(a, output, &mut node.children);
+                tree.push(node);
+                if finished {
+                    return (true, answer);
+                }
+            } else {
+                tree.push(node);
+            }
+        }
+
+        // This search did not yield a possible result, return the printout number to the output list
+        // and allow the caller to keep searching the previous printout number
+        output.push(printout);
+        (false, register_a)
+    }
+}
+
+/// Runs both of the given programs from each of the given initial values of Register A, in
+/// parallel, and gets the lowest one for which their outputs differ, or None if they're
+/// equivalent over the whole range
+///
+/// Registers B and C start with the values each program was created with.  Programs that never
+/// halt will never be compared, so a maximum number of output values should be set for each
+/// program if that's possible, in which case whether each was truncated is compared too.
+pub fn equivalent(prog_a: &Computer, prog_b: &Computer, a_range: Range<u64>) -> Option<u64> {
+    a_range.into_par_iter().find_first(|register_a| {
+        // Run each of the programs from the initial value of Register A
+        let [result_a, result_b] = [prog_a, prog_b].map(|program| {
+            let mut computer = program.clone();
+            computer.register_a = computer.spec.truncate(*register_a);
+            computer.run_program()
+        });
+
+        // Check whether the outputs differ
+        result_a.output != result_b.output || result_a.truncated != result_b.truncated
+    })
+}
+
+/// Solves part one for the puzzle input, getting the output of the program
+pub fn part1(input: &str) -> Result<String, String> {
+    Ok(Computer::from_string(input)?.run_program().output)
+}
+
+/// Solves part two for the puzzle input, getting the lowest value of Register A that makes the
+/// program output itself
+pub fn part2(input: &str) -> Result<u64, String> {
+    Ok(Computer::from_string(input)?
+        .find_self_outputing_register_a()
+        .0)
+}
@@ -1,431 +1,218 @@
 use std::fs;
 
-use clap::Parser;
-
-use regex::Regex;
+use clap::{Parser, ValueEnum};
+use common::{fail, read_input, About, Failure, Format};
+use day17::{Computer, MachineSpec};
 
 /// CLI arguments
 #[derive(Parser)]
 struct CliArgs {
     part: u64,
     filepath: String,
+    /// The maximum number of values the program may output
+    #[arg(long)]
+    max_output: Option<usize>,
+    /// What to do when the program exceeds the maximum number of output values
+    #[arg(long, value_enum, default_value_t = Overflow::Abort)]
+    overflow: Overflow,
+    /// The modulus applied to the values of the BST and OUT instructions
+    #[arg(long, default_value_t = MachineSpec::default().modulus, value_parser = clap::value_parser!(u64).range(1..))]
+    modulus: u64,
+    /// The base raised to the power of the combo operand for the division instructions
+    #[arg(long, default_value_t = MachineSpec::default().divisor_base, value_parser = clap::value_parser!(u64).range(2..))]
+    divisor_base: u64,
+    /// The number of bits in each register, which values are truncated to
+    #[arg(long, default_value_t = MachineSpec::default().register_bits, value_parser = clap::value_parser!(u32).range(1..=64))]
+    register_bits: u32,
+    /// Print the number of times each instruction was executed before the answer
+    #[arg(long)]
+    coverage: bool,
+    /// Print the number of candidate values of Register A tested before the answer
+    #[arg(long)]
+    stats: bool,
+    /// Write the tree of candidate values of Register A tested to the given JSON file
+    #[arg(long)]
+    search_tree: Option<String>,
+    /// Parse the input and print statistics about its structure instead of solving it
+    #[arg(long)]
+    parse_only: bool,
+    /// The format in which errors are reported
+    #[arg(long, value_enum, default_value_t = Format::Text)]
+    format: Format,
 }
 
-/// Type representing a literal operand
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
-
-struct LiteralOperand(u8);
-
-impl LiteralOperand {
-    /// Gets the value of the literal operand
-    fn value(&self) -> u64 {
-        self.0 as u64
-    }
-}
-
-/// Type representing a combo operand
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
-
-struct ComboOperand(u8);
-
-/// Type representing the operand when it is not needed
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
-
-struct UnusedOperand(u8);
-
-/// Instructions that the computer can perfrom
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
-enum Instruction {
-    Adv(ComboOperand),
-    Bxl(LiteralOperand),
-    Bst(ComboOperand),
-    Jnz(LiteralOperand),
-    Bxc(UnusedOperand),
-    Out(ComboOperand),
-    Bdv(ComboOperand),
-    Cdv(ComboOperand),
-}
-
-impl Instruction {
-    /// Parses an opcode and operand into the associated instruction
-    fn parse(opcode: u8, operand: u8) -> Instruction {
-        let literal = LiteralOperand(operand);
-        let combo = ComboOperand(operand);
-        let unused = UnusedOperand(operand);
-        match opcode {
-            0 => Self::Adv(combo),
-            1 => Self::Bxl(literal),
-            2 => Self::Bst(combo),
-            3 => Self::Jnz(literal),
-            4 => Self::Bxc(unused),
-            5 => Self::Out(combo),
-            6 => Self::Bdv(combo),
-            7 => Self::Cdv(combo),
-            _o => panic!("Cound not parse opcode: {_o}"),
-        }
-    }
-
-    /// Gets the instruction as the pair of integers it represents
-    fn as_numbers(&self) -> (u8, u8) {
-        match *self {
-            Self::Adv(combo) => (0, combo.0),
-            Self::Bxl(literal) => (1, literal.0),
-            Self::Bst(combo) => (2, combo.0),
-            Self::Jnz(literal) => (3, literal.0),
-            Self::Bxc(unused) => (4, unused.0),
-            Self::Out(combo) => (5, combo.0),
-            Self::Bdv(combo) => (6, combo.0),
-            Self::Cdv(combo) => (7, combo.0),
-        }
-    }
-}
-
-/// The computer that will execute the program
-#[derive(Debug, Clone)]
-struct Computer {
-    /// Register A
-    register_a: u64,
-    /// Register B
-    register_b: u64,
-    /// Register C
-    register_c: u64,
-    /// List of instructions to execute
-    instructions: Vec<Instruction>,
-    /// Pointer that points to the index of the instruction to execute next
-    pointer: usize,
-    /// Running list of output numbers from the program
-    output: Vec<u64>,
+/// Description of the puzzle solved by this binary
+const ABOUT: About = About {
+    day: 17,
+    title: "Chronospatial Computer",
+    input: "The initial register values, a blank line, then the program",
+    parts: &[1, 2],
+    features: &[],
+};
+
+/// Policies for when a program exceeds the maximum number of output values
+#[derive(Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum Overflow {
+    /// Stop and report that the program has no answer
+    Abort,
+    /// Stop and report the output up to the maximum, marked as truncated
+    Truncate,
 }
 
-impl Computer {
-    /// Creates a computer from the given string input
-    fn from_string(text: &str) -> Self {
-        // Create the regex patterns for the register portions of the text
-        let register_a_re = Regex::new(r"Register A: (\d+)").unwrap();
-        let register_b_re = Regex::new(r"Register B: (\d+)").unwrap();
-        let register_c_re = Regex::new(r"Register C: (\d+)").unwrap();
-
-        // Get the value of register A
-        let register_a_captures = register_a_re
-            .captures(text)
-            .expect("Could not get match for Register A");
-        let register_a = register_a_captures
-            .get(1)
-            .expect("Invalid capture group")
-            .as_str()
-            .parse::<u64>()
-            .expect("Could not parse Register A data to u64");
-
-        // Get the value of register B
-        let register_b_captures = register_b_re
-            .captures(text)
-            .expect("Could not get match for Register B");
-        let register_b = register_b_captures
-            .get(1)
-            .expect("Invalid capture group")
-            .as_str()
-            .parse::<u64>()
-            .expect("Could not parse Register B data to u64");
-
-        // Get the value of register C
-        let register_c_captures = register_c_re
-            .captures(text)
-            .expect("Could not get match for Register C");
-        let register_c = register_c_captures
-            .get(1)
-            .expect("Invalid capture group")
-            .as_str()
-            .parse::<u64>()
-            .expect("Could not parse Register C data to u64");
-
-        // Create the regex pattern for parsing instructions
-        let instructions_re = Regex::new(r"(?:Program: )*(?: *)(\d+),(\d+)").unwrap();
-
-        // Create a list for storing instructions
-        let mut instructions = Vec::new();
-
-        // Iterate through the captures for the instructions
-        for (_, [opcode_str, operand_str]) in
-            instructions_re.captures_iter(text).map(|c| c.extract())
-        {
-            // Convert the captures into the opcode and operand
-            let opcode = opcode_str.parse::<u8>().expect("Could not parse opcode");
-            let operand = operand_str.parse::<u8>().expect("Could not parse operand");
-
-            // Parse the instruction
-            let instruction = Instruction::parse(opcode, operand);
-
-            // Add the instruction to the list
-            instructions.push(instruction);
-        }
-
-        // Create and return the computer
-        Self {
-            register_a,
-            register_b,
-            register_c,
-            instructions,
-            pointer: 0,
-            output: Vec::new(),
-        }
-    }
-
-    /// Gets the value of the given combo operand
-    fn get_combo_operand_value(&self, combo: &ComboOperand) -> u64 {
-        match combo.0 {
-            0..=3 => combo.0 as u64,
-            4 => self.register_a,
-            5 => self.register_b,
-            6 => self.register_c,
-            _ => panic!("Encounter unrecognized combo operand"),
-        }
-    }
-
-    /// Runs the programs and returns the output string of numbers
-    fn run_program(&mut self) -> String {
-        while let Some(instruction) = self.fetch_instruction() {
-            self.execute_instruction(&instruction);
-        }
-        self.create_output()
-    }
-
-    /// Runs a single cycle of the instructions and returns the output number for that cycle
-    fn run_program_once(&mut self) -> u8 {
-        // Reset the instruction pointer to the first instruction
-        self.pointer = 0;
-
-        // Reset the output list of numbers
-        self.output = Vec::new();
-
-        // Get the number of instructions
-        let num_instructions = self.instructions.len();
-
-        // Iterate through the instructions
-        while let Some(instruction) = self.fetch_instruction() {
-            // Execute the next instruction
-            self.execute_instruction(&instruction);
-
-            // If the pointer has jumped to the start or exceeded available instructions,
-            // return the last number output
-            if self.pointer == 0 || self.pointer == num_instructions {
-                return *self.output.last().unwrap() as u8;
-            }
-        }
-
-        // Something went wrong
-        panic!("Could not get output number for this cycle");
-    }
-
-    /// Fetches the next instruction
-    fn fetch_instruction(&self) -> Option<Instruction> {
-        self.instructions.get(self.pointer).copied()
-    }
-
-    // Executes the given instruction
-    fn execute_instruction(&mut self, instruction: &Instruction) {
-        // Execute the instruction and determine whether the pointer should be incremented
-        let advance_pointer = match instruction {
-            Instruction::Adv(op) => self.perform_adv(op),
-            Instruction::Bxl(op) => self.perform_bxl(op),
-            Instruction::Bst(op) => self.perform_bst(op),
-            Instruction::Jnz(op) => self.perform_jnz(op),
-            Instruction::Bxc(_unused) => self.perform_bxc(),
-            Instruction::Out(op) => self.perform_out(op),
-            Instruction::Bdv(op) => self.perform_bdv(op),
-            Instruction::Cdv(op) => self.perform_cdv(op),
-        };
-
-        // Increment the pointer if needed
-        if advance_pointer {
-            self.pointer += 1;
-        }
-    }
-
-    /// Performs the ADV instruction
-    fn perform_adv(&mut self, combo: &ComboOperand) -> bool {
-        let numerator = self.register_a;
-        let exp = self.get_combo_operand_value(combo) as u32;
-        let result = numerator / 2_u64.pow(exp);
-        self.register_a = result;
-        true
-    }
-
-    /// Performs the BXL instruction
-    fn perform_bxl(&mut self, literal: &LiteralOperand) -> bool {
-        let x = self.register_b;
-        let y = literal.value();
-        let result = x ^ y;
-        self.register_b = result;
-        true
-    }
-
-    /// Performs the BST instruction
-    fn perform_bst(&mut self, combo: &ComboOperand) -> bool {
-        let x = self.get_combo_operand_value(combo);
-        let result = x % 8;
-        self.register_b = result;
-        true
-    }
-
-    /// Performs the JNZ instruction
-    fn perform_jnz(&mut self, literal: &LiteralOperand) -> bool {
-        if self.register_a == 0 {
-            return true;
-        }
-
-        let jump_location = literal.value();
-        self.pointer = jump_location as usize;
-        false
-    }
-
-    /// Performs the BXC instruction
-    fn perform_bxc(&mut self) -> bool {
-        let x = self.register_b;
-        let y = self.register_c;
-        let result = x ^ y;
-        self.register_b = result;
-        true
-    }
-
-    /// Performs the OUT instruction
-    fn perform_out(&mut self, combo: &ComboOperand) -> bool {
-        let value = self.get_combo_operand_value(combo);
-        let result = value % 8;
-        self.output.push(result);
-        true
-    }
-
-    /// Performs the BDV instruction
-    fn perform_bdv(&mut self, combo: &ComboOperand) -> bool {
-        let numerator = self.register_a;
-        let exp = self.get_combo_operand_value(combo) as u32;
-        let result = numerator / 2_u64.pow(exp);
-        self.register_b = result;
-        true
-    }
-
-    /// Performs the CDV instruction
-    fn perform_cdv(&mut self, combo: &ComboOperand) -> bool {
-        let numerator = self.register_a;
-        let exp = self.get_combo_operand_value(combo) as u32;
-        let result = numerator / 2_u64.pow(exp);
-        self.register_c = result;
-        true
-    }
-
-    /// Creates a string of the output numbers separated with commas
-    fn create_output(&self) -> String {
-        let strings: Vec<String> = self.output.iter().map(|o| o.to_string()).collect();
-        strings.join(",")
-    }
-
-    /// Finds the lowest value of Register A that creates an output of its own instructions
-    fn find_self_outputing_register_a(&mut self) -> u64 {
-        // Create the list of output numbers from the instructions
-        let mut output = Vec::new();
-        for instruction in &self.instructions {
-            let (x, y) = instruction.as_numbers();
-            output.push(x);
-            output.push(y);
-        }
-
-        // Start with the register at the needed value of zero
-        let register_a = 0;
-
-        // Reverse engineer the value for Register A
-        let result = self.reverse_engineer_register_a(register_a, &mut output);
-
-        // Return the value of Register A
-        result.1
-    }
-
-    /// Reverse engineers the value of Register A recursively as needed
-    ///
-    /// The input program uses only register A to calculate the values of Registers B and C,
-    /// which in turn is what specifies the output number.  The value of Register A is then
-    /// divided by 8, and the cycle repeats until Register A equals 0.  This method determines
-    /// what the output number is for possible values of Register A that would create the
-    /// current output, and recursively searches to make sure it can output all other values
-    /// of the output, searching until a match is found.
-    fn reverse_engineer_register_a(
-        &mut self,
-        register_a: u64,
-        output: &mut Vec<u8>,
-    ) -> (bool, u64) {
-        // If there is no additional output to reverse engindeer, return the current value of Register A
-        if output.is_empty() {
-            return (true, register_a);
-        }
-
-        // Get the next value to reverse engineer
-        let printout = output.pop().unwrap();
-
-        // Get the bounds of the new Register A values to test
-        let new_register_a_base = register_a * 8;
-        let bound_register_a = new_register_a_base + 8;
-
-        // Check the possible values for Register A
-        for a in new_register_a_base..bound_register_a {
-            // Set Register A to the test value
-            self.register_a = a;
+/// Main entry function
+fn main() {
+    // Describe the puzzle instead if requested
+    common::handle_about::<CliArgs>(&ABOUT);
 
-            // Get the out number for a single cycle of the program
-            let printed = self.run_program_once();
+    // Parse CLI arguments
+    let cli = CliArgs::parse();
 
-            // If the output number matches the necessary number, recursively search for the
-            // next number using the current value of Register A
-            if printed == printout {
-                let (finished, answer) = self.reverse_engineer_register_a(a, output);
-                if finished {
-                    return (true, answer);
-                }
-            }
-        }
+    // Report errors in the requested format
+    common::init(cli.format);
 
-        // This search did not yield a possible result, return the printout number to the output list
-        // and allow the caller to keep searching the previous printout number
-        output.push(printout);
-        (false, register_a)
+    // Only parse the input if requested
+    if cli.parse_only {
+        report_structure(&cli.filepath);
     }
-}
 
-/// Main entry function
-fn main() {
-    // Parse CLI arguments
-    let cli = CliArgs::parse();
+    // Get the parameters of the machine
+    let spec = MachineSpec {
+        modulus: cli.modulus,
+        divisor_base: cli.divisor_base,
+        register_bits: cli.register_bits,
+    };
 
     // Run the code for the desired challenge part
     match cli.part {
-        1 => main_part_one(cli.filepath),
-        2 => main_part_two(cli.filepath),
+        1 => main_part_one(
+            cli.filepath,
+            cli.max_output,
+            cli.overflow,
+            spec,
+            cli.coverage,
+        ),
+        2 => main_part_two(
+            cli.filepath,
+            cli.max_output,
+            spec,
+            cli.stats,
+            cli.search_tree,
+        ),
         _ => panic!("Invalid selection part selection!"),
     }
 }
 
+/// Parses the computer, exiting with a parse failure if it can't be parsed
+fn read_computer(contents: &str) -> Computer {
+    Computer::from_string(contents).unwrap_or_else(|error| fail(Failure::Parse(error)))
+}
+
+/// Parses the program, then prints the number of instructions in it and how many of them jump
+/// or output and exits
+fn report_structure(filepath: &str) -> ! {
+    let computer = read_computer(&read_input(filepath));
+    let lines = computer.coverage();
+    let count = |mnemonic: &str| lines.iter().filter(|x| x.mnemonic == mnemonic).count();
+    common::report_parse(&[
+        ("instructions", lines.len()),
+        ("jumps", count("jnz")),
+        ("outputs", count("out")),
+    ]);
+}
+
 /// Runs part one
-fn main_part_one(filepath: String) {
+fn main_part_one(
+    filepath: String,
+    max_output: Option<usize>,
+    overflow: Overflow,
+    spec: MachineSpec,
+    coverage: bool,
+) {
     // Get the trail ratings
-    let contents = fs::read_to_string(filepath).expect("Invalid filepath");
+    let contents = read_input(filepath);
 
     // Get the computer, initialized
-    let mut computer = Computer::from_string(&contents);
+    let mut computer = read_computer(&contents);
+    computer.max_output = max_output;
+    computer.set_spec(spec);
 
     // Run the program
-    let output = computer.run_program();
+    let result = computer.run_program();
+
+    // If the program was stopped for producing too much output, handle it per the policy
+    if result.truncated {
+        let max_output = max_output.unwrap_or_default();
+        match overflow {
+            Overflow::Abort => fail(Failure::Unsolvable(format!(
+                "Program output more than {max_output} values"
+            ))),
+            Overflow::Truncate => eprintln!("Output truncated to {max_output} values"),
+        }
+    }
+
+    // Print the number of times each instruction was executed if requested
+    if coverage {
+        print_coverage(&computer);
+    }
 
     // Output the readout from the program
+    let output = result.output;
     println!("{output}");
 }
 
 /// Runs part two
-fn main_part_two(filepath: String) {
+fn main_part_two(
+    filepath: String,
+    max_output: Option<usize>,
+    spec: MachineSpec,
+    stats: bool,
+    search_tree: Option<String>,
+) {
     // Get the trail ratings
-    let contents = fs::read_to_string(filepath).expect("Invalid filepath");
+    let contents = read_input(filepath);
 
     // Get the computer, initialized
-    let mut computer = Computer::from_string(&contents);
+    let mut computer = read_computer(&contents);
+    computer.max_output = max_output;
+    computer.set_spec(spec);
 
     // Get the value of Register A for the self-outputting program
-    let register_a = computer.find_self_outputing_register_a();
+    let (register_a, tree) = computer.find_self_outputing_register_a();
+
+    // Write the tree of candidates tested if requested
+    if let Some(search_tree_filepath) = search_tree {
+        let nodes: Vec<String> = tree.iter().map(|x| x.to_json()).collect();
+        let json = format!("[{}]\n", nodes.join(","));
+        fs::write(&search_tree_filepath, json).unwrap_or_else(|error| {
+            fail(Failure::Io(format!(
+                "Could not write {search_tree_filepath}: {error}"
+            )))
+        });
+    }
+
+    // Print the number of candidates tested if requested
+    if stats {
+        let candidates: usize = tree.iter().map(|x| x.count()).sum();
+        println!("candidates: {candidates}");
+    }
+
     println!("{register_a}");
 }
+
+/// Prints the number of times each instruction of the program was executed as CSV, followed by
+/// the number of instructions that were never executed
+fn print_coverage(computer: &Computer) {
+    let coverage = computer.coverage();
+    println!("address,opcode,operand,mnemonic,count");
+    for line in &coverage {
+        println!(
+            "{},{},{},{},{}",
+            line.address, line.opcode, line.operand, line.mnemonic, line.count
+        );
+    }
+    let unexecuted = coverage.iter().filter(|x| x.count == 0).count();
+    println!(
+        "{unexecuted} of {} instructions were never executed",
+        coverage.len()
+    );
+}
@@ -0,0 +1,35 @@
+use day17::Computer;
+
+#[test]
+fn counts_each_loop_iteration() {
+    // The example divides Register A by 8 and outputs it until it reaches zero, so each
+    // instruction runs once per output value
+    let mut computer = Computer::from_string(
+        "Register A: 729\nRegister B: 0\nRegister C: 0\n\nProgram: 0,1,5,4,3,0\n",
+    )
+    .unwrap();
+    let result = computer.run_program();
+    assert_eq!(result.output, "4,6,3,5,6,3,5,2,1,0");
+    let coverage = computer.coverage();
+    let listing: Vec<_> = coverage
+        .iter()
+        .map(|x| (x.address, x.mnemonic, x.count))
+        .collect();
+    assert_eq!(listing, [(0, "adv", 10), (2, "out", 10), (4, "jnz", 10)]);
+}
+
+#[test]
+fn reports_unexecuted_instructions() {
+    // Register A never changes inside the loop, so the program only stops once it has output
+    // too many values and never reaches the final ADV instruction
+    let mut computer = Computer::from_string(
+        "Register A: 1\nRegister B: 0\nRegister C: 0\n\nProgram: 5,4,3,0,0,1\n",
+    )
+    .unwrap();
+    computer.max_output = Some(3);
+    assert!(computer.run_program().truncated);
+    let counts: Vec<u64> = computer.coverage().iter().map(|x| x.count).collect();
+    assert_eq!(counts, [4, 3, 0]);
+    let last = computer.coverage()[2];
+    assert_eq!((last.opcode, last.operand), (0, 1));
+}
@@ -0,0 +1,40 @@
+use day17::{equivalent, Computer};
+
+/// Creates a computer with empty registers for the given program
+fn program(program: &str) -> Computer {
+    Computer::from_string(&format!(
+        "Register A: 0\nRegister B: 0\nRegister C: 0\n\nProgram: {program}\n"
+    ))
+    .unwrap()
+}
+
+#[test]
+fn rewrite_is_equivalent() {
+    // Dividing into Register B first and outputting that is the same as outputting Register A
+    let original = program("0,3,5,4,3,0");
+    let rewrite = program("6,3,0,3,5,5,3,0");
+    assert_eq!(equivalent(&original, &rewrite, 0..10_000), None);
+}
+
+#[test]
+fn finds_first_difference() {
+    // Dividing by 4 instead of 8 first gives a different output once Register A is 4
+    let original = program("0,3,5,4,3,0");
+    let rewrite = program("0,2,5,4,3,0");
+    assert_eq!(equivalent(&original, &rewrite, 0..10_000), Some(4));
+    assert_eq!(equivalent(&original, &rewrite, 5..10_000), Some(5));
+}
+
+#[test]
+fn compares_truncated_programs() {
+    // Neither program ever halts on its own, but they output the same values until stopped
+    let mut original = program("5,4,3,0");
+    let mut rewrite = program("6,0,5,5,3,0");
+    original.max_output = Some(3);
+    rewrite.max_output = Some(3);
+    assert_eq!(equivalent(&original, &rewrite, 1..100), None);
+
+    // Stopping one of them sooner makes their outputs differ
+    rewrite.max_output = Some(2);
+    assert_eq!(equivalent(&original, &rewrite, 1..100), Some(1));
+}
@@ -0,0 +1,18 @@
+use day17::Computer;
+
+#[test]
+fn rejects_missing_registers() {
+    assert_eq!(
+        Computer::from_string("garbage line !!\nfoo").err(),
+        Some(String::from("Missing the value of register A"))
+    );
+}
+
+#[test]
+fn rejects_unknown_opcodes() {
+    assert_eq!(
+        Computer::from_string("Register A: 0\nRegister B: 0\nRegister C: 0\n\nProgram: 9,1\n")
+            .err(),
+        Some(String::from("Could not parse 9 as an opcode"))
+    );
+}
@@ -0,0 +1,41 @@
+use day17::{Computer, MachineSpec};
+
+const EXAMPLE: &str = "Register A: 729\nRegister B: 0\nRegister C: 0\n\nProgram: 0,1,5,4,3,0\n";
+
+#[test]
+fn runs_with_decimal_machine() {
+    // Dividing by ten and outputting modulo ten prints the digits of Register A after the
+    // first, last to first
+    let mut computer = Computer::from_string(EXAMPLE).unwrap();
+    computer.set_spec(MachineSpec {
+        modulus: 10,
+        divisor_base: 10,
+        ..MachineSpec::default()
+    });
+    assert_eq!(computer.run_program().output, "2,7,0");
+}
+
+#[test]
+fn truncates_registers_to_fit() {
+    // Only the lowest four bits of Register A are kept, leaving 729 & 15 = 9 to be halved
+    let mut computer = Computer::from_string(EXAMPLE).unwrap();
+    computer.set_spec(MachineSpec {
+        register_bits: 4,
+        ..MachineSpec::default()
+    });
+    assert_eq!(computer.run_program().output, "4,2,1,0");
+}
+
+#[test]
+fn truncate_keeps_full_width_registers() {
+    let spec = MachineSpec {
+        register_bits: 64,
+        ..MachineSpec::default()
+    };
+    assert_eq!(spec.truncate(u64::MAX), u64::MAX);
+    let spec = MachineSpec {
+        register_bits: 3,
+        ..MachineSpec::default()
+    };
+    assert_eq!(spec.truncate(0b1010), 0b010);
+}
@@ -0,0 +1,748 @@
+use std::{
+    collections::HashMap,
+    fmt::{self, Display, Formatter},
+    fs::{self, File},
+    io::{BufReader, BufWriter, Write},
+    path::Path,
+};
+
+use clap::ValueEnum;
+use pathfinding::{dijkstra, flood_fill, Point, Visit};
+use rand::{rngs::StdRng, seq::SliceRandom, SeedableRng};
+use serde::{Deserialize, Serialize};
+
+pub type Distance = usize;
+
+/// The width and height of the full-sized memory space of the puzzle
+pub const SIZE: usize = 71;
+
+/// The number of bytes that have fallen in part one of the puzzle
+pub const NUM_FALLEN: usize = 1024;
+
+pub type VisitMap = HashMap<Coordinate, VisitInfo>;
+
+/// Coordinates that can be travelled to on the map
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+pub struct Coordinate {
+    pub x: isize,
+    pub y: isize,
+}
+
+impl Coordinate {
+    /// Gets the coordinate in a specific direction relative to this one
+    pub fn coordinate_for(&self, direction: &Direction) -> Coordinate {
+        match direction {
+            Direction::North => Coordinate::from((self.x, self.y - 1)),
+            Direction::South => Coordinate::from((self.x, self.y + 1)),
+            Direction::East => Coordinate::from((self.x + 1, self.y)),
+            Direction::West => Coordinate::from((self.x - 1, self.y)),
+        }
+    }
+
+    /// Gets the coordinate as a point for flood filling
+    fn as_point(&self) -> Point {
+        (self.x as i64, self.y as i64)
+    }
+
+    /// Gets the coordinate from a point used for flood filling
+    fn from_point(point: Point) -> Self {
+        Self {
+            x: point.0 as isize,
+            y: point.1 as isize,
+        }
+    }
+
+    // Gets the coordinates in the cardinal directions from the given coordinate
+    pub fn cardinals(&self) -> Vec<Coordinate> {
+        let mut coords = Vec::new();
+        for direction in [
+            Direction::North,
+            Direction::East,
+            Direction::South,
+            Direction::West,
+        ] {
+            coords.push(self.coordinate_for(&direction));
+        }
+        coords
+    }
+}
+
+impl From<(isize, isize)> for Coordinate {
+    fn from(value: (isize, isize)) -> Self {
+        Self {
+            x: value.0,
+            y: value.1,
+        }
+    }
+}
+
+impl From<Coordinate> for (isize, isize) {
+    fn from(value: Coordinate) -> Self {
+        (value.x, value.y)
+    }
+}
+
+impl Display for Coordinate {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "({}, {})", self.x, self.y)
+    }
+}
+
+/// The various directions in which the player can move
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Direction {
+    North,
+    South,
+    East,
+    West,
+}
+
+impl Display for Direction {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let character = match self {
+            Direction::North => '^',
+            Direction::East => '>',
+            Direction::South => 'v',
+            Direction::West => '<',
+        };
+        write!(f, "{}", character)
+    }
+}
+
+/// Algorithms available for searching the map from the start
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum Algorithm {
+    /// Visit every reachable coordinate in order of distance from the start
+    Dijkstra,
+    /// Visit coordinates in order of distance from the start plus the Manhattan distance
+    /// to the end, stopping once the end is visited
+    Astar,
+}
+
+/// Information about specific coordinates visited during Dijkstra's algorithm
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct VisitInfo {
+    /// Distance from the start node
+    pub distance: Distance,
+    /// The node that led to this one
+    pub previous: Coordinate,
+}
+
+/// Problems that prevent the falling bytes from being parsed, each with its line number
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseError {
+    /// The line doesn't have a comma separating the X and Y values
+    MissingComma(usize),
+    /// The X or Y value isn't a valid number
+    InvalidNumber(usize, String),
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseError::MissingComma(line) => write!(f, "Line {line}: expected X,Y"),
+            ParseError::InvalidNumber(line, text) => {
+                write!(f, "Line {line}: could not parse {text:?} as a number")
+            }
+        }
+    }
+}
+
+/// Suspicious falling bytes that can still be parsed, each with its line number
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseWarning {
+    /// The byte falls on the same coordinate as the byte on the given earlier line
+    Duplicate(usize, Coordinate, usize),
+    /// The byte falls outside of the map
+    OutOfBounds(usize, Coordinate),
+}
+
+impl Display for ParseWarning {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseWarning::Duplicate(line, coord, first_line) => {
+                write!(f, "Line {line}: {coord} duplicates line {first_line}")
+            }
+            ParseWarning::OutOfBounds(line, coord) => {
+                write!(f, "Line {line}: {coord} is outside of the map")
+            }
+        }
+    }
+}
+
+/// The map of the program
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProgramMap {
+    /// The height of the map
+    pub height: usize,
+    /// The width of the map
+    pub width: usize,
+    /// The start coordinate
+    pub start: Coordinate,
+    /// The end coordinate
+    pub end: Coordinate,
+    /// Active obstacles on the map
+    pub obstacles: Vec<Coordinate>,
+    /// Planned obstacles to be added to the map
+    pub planned_obstacles: Vec<Coordinate>,
+    /// The set of visited coordinates
+    pub visited: VisitMap,
+    /// The set of discovered but unvisited coordinates
+    pub unvisited: VisitMap,
+}
+
+impl ProgramMap {
+    /// Parses the program map from the given text, panicking on any malformed line
+    ///
+    /// Duplicate and out-of-bounds bytes are kept; use `try_from_string()` to control this
+    /// and get the problems found instead.
+    pub fn from_string(text: &str, height: usize, width: usize) -> Self {
+        let (program_map, _warnings) = Self::try_from_string(text, height, width, false)
+            .unwrap_or_else(|error| panic!("{error}"));
+        program_map
+    }
+
+    /// Parses the program map from the given text, returning it along with warnings about any
+    /// duplicate or out-of-bounds bytes, which are dropped if `dedupe` is set (duplicates) or
+    /// kept as they are (out-of-bounds bytes, which can never block a path)
+    ///
+    /// Returns an error for the first malformed line found.
+    pub fn try_from_string(
+        text: &str,
+        height: usize,
+        width: usize,
+        dedupe: bool,
+    ) -> Result<(Self, Vec<ParseWarning>), ParseError> {
+        // Create lists for storing the obstacles planned to fall and the problems found
+        let mut planned_obstacles = Vec::new();
+        let mut warnings = Vec::new();
+
+        // Keep track of the line each coordinate was first seen on
+        let mut first_lines = HashMap::new();
+
+        // Iterate through the non-blank lines of the input text, numbered from one
+        for (line_index, line) in text.lines().enumerate() {
+            let line_number = line_index + 1;
+            let line = line.trim();
+            if line.is_empty() {
+                continue;
+            }
+
+            // Split the line by the comma
+            let Some((x_text, y_text)) = line.split_once(",") else {
+                return Err(ParseError::MissingComma(line_number));
+            };
+
+            // Parse the coordinate for the obstacle
+            let x = x_text
+                .trim()
+                .parse::<isize>()
+                .map_err(|_| ParseError::InvalidNumber(line_number, x_text.to_string()))?;
+            let y = y_text
+                .trim()
+                .parse::<isize>()
+                .map_err(|_| ParseError::InvalidNumber(line_number, y_text.to_string()))?;
+            let coord = Coordinate::from((x, y));
+
+            // Warn about bytes falling outside of the map
+            if x < 0 || y < 0 || x >= width as isize || y >= height as isize {
+                warnings.push(ParseWarning::OutOfBounds(line_number, coord));
+            }
+
+            // Warn about bytes falling on the same coordinate as an earlier one, dropping them
+            // if requested
+            if let Some(first_line) = first_lines.get(&coord) {
+                warnings.push(ParseWarning::Duplicate(line_number, coord, *first_line));
+                if dedupe {
+                    continue;
+                }
+            } else {
+                first_lines.insert(coord, line_number);
+            }
+
+            // Add the obstacle to the list of planned obstacle
+            planned_obstacles.push(coord);
+        }
+
+        // Reverse the list of planned obstacles so they can be popped off later
+        planned_obstacles.reverse();
+
+        // Get the start and end nodes of the map
+        let start = Coordinate::from((0, 0));
+        let end = Coordinate::from((width as isize - 1, height as isize - 1));
+
+        // Create the list of unvisited coordinates, seeding the start location
+        let mut unvisited = HashMap::new();
+        let start_info = VisitInfo {
+            distance: 0,
+            previous: start,
+        };
+        unvisited.insert(start, start_info);
+
+        // Create and return the program map along with the warnings
+        let program_map = Self {
+            height,
+            width,
+            start,
+            end,
+            obstacles: Vec::new(),
+            planned_obstacles,
+            visited: HashMap::new(),
+            unvisited,
+        };
+        Ok((program_map, warnings))
+    }
+
+    /// Creates a square program map of the given size where the given number of bytes are
+    /// planned to fall on distinct random coordinates, which are the same for the same seed
+    ///
+    /// Bytes never fall on the start or end.  Panics if there are more bytes than coordinates
+    /// for them to fall on.
+    pub fn synthetic(count: usize, size: usize, seed: u64) -> Self {
+        // Get every coordinate of the map other than the start and end
+        let start = Coordinate::from((0, 0));
+        let end = Coordinate::from((size as isize - 1, size as isize - 1));
+        let mut coords: Vec<Coordinate> = (0..size as isize)
+            .flat_map(|y| (0..size as isize).map(move |x| Coordinate::from((x, y))))
+            .filter(|x| *x != start && *x != end)
+            .collect();
+        assert!(
+            count <= coords.len(),
+            "Only {} bytes can fall on a {size}x{size} map",
+            coords.len()
+        );
+
+        // Choose the coordinates for the bytes in a random order
+        let mut rng = StdRng::seed_from_u64(seed);
+        let (chosen, _) = coords.partial_shuffle(&mut rng, count);
+
+        // Create the program map with the bytes planned to fall in that order
+        let text: String = chosen
+            .iter()
+            .map(|x| format!("{},{}\n", x.x, x.y))
+            .collect();
+        Self::from_string(&text, size, size)
+    }
+
+    /// Loads a previously saved program map, including its obstacles and visited coordinates
+    pub fn load_state(path: impl AsRef<Path>) -> bincode::Result<Self> {
+        let reader = BufReader::new(File::open(path)?);
+        bincode::deserialize_from(reader)
+    }
+
+    /// Saves the program map, including its obstacles and visited coordinates, so that it
+    /// can be loaded again later
+    ///
+    /// The state is written to a temporary file next to the given one and then moved into
+    /// place, so an interrupted save leaves any earlier state intact.
+    pub fn save_state(&self, path: impl AsRef<Path>) -> bincode::Result<()> {
+        let path = path.as_ref();
+        let mut temp_name = path.file_name().unwrap_or_default().to_os_string();
+        temp_name.push(".tmp");
+        let temp_path = path.with_file_name(temp_name);
+        let mut writer = BufWriter::new(File::create(&temp_path)?);
+        bincode::serialize_into(&mut writer, self)?;
+        writer.flush()?;
+        drop(writer);
+        fs::rename(&temp_path, path)?;
+        Ok(())
+    }
+
+    /// Resets the list of visited nodes
+    pub fn reset_visited(&mut self) {
+        // Recreate the original set of unvisited coordinates
+        let start = self.start;
+        let mut unvisited = HashMap::new();
+        let start_info = VisitInfo {
+            distance: 0,
+            previous: start,
+        };
+        unvisited.insert(start, start_info);
+
+        // Reset the sets of unvisited and visited coordinates
+        self.unvisited = unvisited;
+        self.visited = HashMap::new();
+    }
+
+    /// Checks whether a given coordinate is free of an obstacle
+    ///
+    /// Returns an error if the space is off the map.
+    fn check_free(&self, coord: &Coordinate) -> Result<bool, ()> {
+        //
+        if coord.x < 0
+            || coord.y < 0
+            || coord.x >= self.width as isize
+            || coord.y >= self.height as isize
+        {
+            return Err(());
+        }
+
+        Ok(!self.obstacles.contains(coord))
+    }
+
+    /// Gets the valid moves in cardinal directions
+    fn valid_cardinal_moves(&self, coord: &Coordinate) -> Vec<Coordinate> {
+        let cardinal_moves = coord.cardinals();
+        cardinal_moves
+            .iter()
+            .filter(|m| self.check_free(m).is_ok())
+            .filter(|m| self.check_free(m).expect("Invalid space"))
+            .copied()
+            .collect()
+    }
+
+    /// Gets the next available moves for a given coordinate
+    ///
+    /// This is needed for Dijkstra's algorithm, for calculating the new scores for connections
+    /// from the current location being used (the given coordinate).
+    ///
+    /// Returns a list of new coordinates the given location connects to and the distance from
+    /// the start associated with that move.
+    fn next_moves(&self, coord: &Coordinate) -> Vec<(Coordinate, Distance)> {
+        // Get all the valid moves that can be performed from the given location
+        let valid_moves = self.valid_cardinal_moves(coord);
+
+        // Get the list of valid moves that would go to coordinates not yet visited
+        let new_moves: Vec<Coordinate> = valid_moves
+            .iter()
+            .filter(|m| !self.visited.contains_key(m))
+            .copied()
+            .collect();
+
+        // Get the distance of the current location
+        let visit_info = self
+            .unvisited
+            .get(coord)
+            .expect("Could not get visited node");
+
+        // Create a list for new moves with associated scores
+        let mut new_measured_moves = Vec::new();
+
+        // Iterate through the new moves
+        for new_coordinate in new_moves {
+            // Add one to the distance for the associated move forward
+            let new_distance = visit_info.distance + 1;
+
+            // Add the new move set to the list of new moves
+            new_measured_moves.push((new_coordinate, new_distance));
+        }
+
+        // Return the list of new moves
+        new_measured_moves
+    }
+
+    /// Corrupts the next space, moving the next planned obstacle to the list of active obstacles
+    pub fn corrupt_next_space(&mut self) {
+        let next_corruption = self
+            .planned_obstacles
+            .pop()
+            .expect("Could not get next obstacle");
+        self.obstacles.push(next_corruption);
+    }
+
+    /// Uncorrupts the last space, moving the last planned obstacle to the list of planned obstacles
+    pub fn uncorrupt_next_space(&mut self) {
+        let next_corruption = self.obstacles.pop().expect("Could not get next obstacle");
+        self.planned_obstacles.push(next_corruption);
+    }
+
+    /// Gets the Manhattan distance from the given coordinate to the end, which is a lower
+    /// bound on the remaining distance
+    fn distance_bound(&self, coord: &Coordinate) -> Distance {
+        coord.x.abs_diff(self.end.x) + coord.y.abs_diff(self.end.y)
+    }
+
+    /// Gets the closet unvisited location, adding the lower bound on the remaining distance
+    /// to the end when requested
+    fn get_closest_unvisited(&self, bounded: bool) -> Coordinate {
+        // If there is only one unvisited location, return that one
+        if self.unvisited.len() == 1 {
+            return *self
+                .unvisited
+                .iter()
+                .last()
+                .expect("Could not get last element")
+                .0;
+        }
+
+        // Return the unvisited coordinate with the lowest distance, breaking ties by the bound
+        // (so that A* heads toward the end) and then by position so that the discovered route
+        // is the same every time
+        *self
+            .unvisited
+            .iter()
+            .min_by_key(|x| {
+                let bound = if bounded { self.distance_bound(x.0) } else { 0 };
+                (x.1.distance + bound, bound, x.0.y, x.0.x)
+            })
+            .expect("No items to sort out minimum")
+            .0
+    }
+
+    /// Performs a single iteration of Dijkstra's algorithm, or of A* if bounded
+    fn perform_dijkstra_iteration(&mut self, bounded: bool) {
+        // Get the closest coordinate from the start
+        let closest_coordinate = self.get_closest_unvisited(bounded);
+
+        // Get the neighbor connections/moves from the closest coordinate
+        let next_moves = self.next_moves(&closest_coordinate);
+
+        // Iterate through the connections/moves
+        for (next_coordinate, next_distance) in next_moves {
+            // Update the set of unvisited nodes
+            match self.unvisited.get_mut(&next_coordinate) {
+                // This coordinate has been visited before
+                Some(info) => {
+                    // If new distance would be at least as large as the stored one, ignore it
+                    if next_distance >= info.distance {
+                        continue;
+                    }
+
+                    // Otherwise, update the distnace for this node
+                    info.distance = next_distance;
+                }
+                // This coordinate is being visited for the first time
+                None => {
+                    // Create the new entry
+                    let next_visit = VisitInfo {
+                        distance: next_distance,
+                        previous: closest_coordinate,
+                    };
+
+                    // Add an entry for this next coordinate
+                    self.unvisited.insert(next_coordinate, next_visit);
+                }
+            }
+        }
+
+        // Remove the current coordinate from the unvisited set, and add it to the visited set
+        let closest_entry = self
+            .unvisited
+            .remove(&closest_coordinate)
+            .expect("Could not complete marking as visited");
+        self.visited.insert(closest_coordinate, closest_entry);
+    }
+
+    /// Visit all possible coordinates in the maze, using Dijkstra's algorithm
+    pub fn visit_nodes(&mut self) {
+        loop {
+            if self.unvisited.is_empty() {
+                break;
+            }
+            self.perform_dijkstra_iteration(false);
+        }
+    }
+
+    /// Visit coordinates in the maze until the end is visited, using A* with the Manhattan
+    /// distance to the end as the heuristic
+    ///
+    /// Since the heuristic never overestimates and each move costs the same, the end is
+    /// visited with its shortest distance.
+    pub fn visit_nodes_toward_end(&mut self) {
+        while !self.unvisited.is_empty() && !self.visited.contains_key(&self.end) {
+            self.perform_dijkstra_iteration(true);
+        }
+    }
+
+    /// Visits coordinates using the given algorithm, returning the number of coordinates
+    /// expanded by the search
+    pub fn search(&mut self, algorithm: Algorithm) -> usize {
+        self.search_with_checkpoints(algorithm, usize::MAX, |_| {})
+    }
+
+    /// Visits coordinates using the given algorithm like `search()`, calling `checkpoint` with
+    /// the partially searched map after every `interval` coordinates expanded, so that the
+    /// search can be saved and later resumed
+    pub fn search_with_checkpoints<F>(
+        &mut self,
+        algorithm: Algorithm,
+        interval: usize,
+        mut checkpoint: F,
+    ) -> usize
+    where
+        F: FnMut(&Self),
+    {
+        let bounded = algorithm == Algorithm::Astar;
+        let mut expanded: usize = 0;
+        while !self.unvisited.is_empty() && !(bounded && self.visited.contains_key(&self.end)) {
+            self.perform_dijkstra_iteration(bounded);
+            expanded += 1;
+            if expanded.is_multiple_of(interval.max(1)) {
+                checkpoint(self);
+            }
+        }
+        self.visited.len()
+    }
+
+    /// Gets the distance to every reachable coordinate from the nearest of the given starts,
+    /// using a breadth-first search that ignores the visited and unvisited sets
+    pub fn distances_from(&self, starts: &[Coordinate]) -> HashMap<Coordinate, Distance> {
+        self.flood_fill_from(starts, |_, _| Visit::Continue)
+            .into_iter()
+            .map(|(point, distance)| (Coordinate::from_point(point), distance))
+            .collect()
+    }
+
+    /// Gets the shortest distance from any of the given starts to any of the given targets
+    ///
+    /// Returns None if none of the targets can be reached.
+    pub fn distance_between(
+        &self,
+        starts: &[Coordinate],
+        targets: &[Coordinate],
+    ) -> Option<Distance> {
+        // Search outward from the starts, stopping at the first (and so closest) target found
+        let mut target_distance = None;
+        self.flood_fill_from(starts, |point, distance| {
+            if targets.contains(&Coordinate::from_point(point)) {
+                target_distance = Some(distance);
+                return Visit::Stop;
+            }
+            Visit::Continue
+        });
+        target_distance
+    }
+
+    /// Flood fills outward through the free spaces from the free coordinates among the
+    /// given starts, calling the given callback as each coordinate is visited
+    fn flood_fill_from<V>(&self, starts: &[Coordinate], on_visit: V) -> HashMap<Point, Distance>
+    where
+        V: FnMut(Point, Distance) -> Visit,
+    {
+        let free_starts = starts
+            .iter()
+            .filter(|x| self.check_free(x).unwrap_or(false))
+            .map(|x| x.as_point());
+        flood_fill(
+            free_starts,
+            |_, next| {
+                self.check_free(&Coordinate::from_point(next))
+                    .unwrap_or(false)
+            },
+            on_visit,
+        )
+    }
+
+    /// Gets the shortest distance from the start to the end when the planned obstacles keep
+    /// falling while walking, one with each step, or None if the end can't be reached
+    ///
+    /// This searches the time-expanded map, where each coordinate is paired with the number of
+    /// steps taken so far, since whether it's free depends on when it's reached.  Once every
+    /// planned obstacle has fallen the map no longer changes, so the time stops counting there.
+    pub fn dynamic_distance(&self) -> Option<Distance> {
+        // Get the number of steps after which each coordinate is corrupted, where the active
+        // obstacles are already there and the next planned obstacle falls with the first step
+        let mut fall_times: HashMap<Coordinate, usize> = HashMap::new();
+        for obstacle in &self.obstacles {
+            fall_times.insert(*obstacle, 0);
+        }
+        for (index, obstacle) in self.planned_obstacles.iter().rev().enumerate() {
+            fall_times.entry(*obstacle).or_insert(index + 1);
+        }
+        let last_fall = self.planned_obstacles.len();
+        let is_corrupted =
+            |coord: &Coordinate, time: usize| fall_times.get(coord).is_some_and(|x| *x <= time);
+
+        // If the start is already corrupted, the end can't be reached
+        if is_corrupted(&self.start, 0) {
+            return None;
+        }
+
+        // Search the time-expanded map, only stepping onto coordinates that are still free
+        // once the step is taken
+        let distances = dijkstra([(self.start, 0)], |(coord, time)| {
+            let next_time = time + 1;
+            coord
+                .cardinals()
+                .into_iter()
+                .filter(|next| self.check_free(next).is_ok() && !is_corrupted(next, next_time))
+                .map(|next| ((next, next_time.min(last_fall)), 1))
+                .collect::<Vec<_>>()
+        });
+
+        // Get the shortest distance to the end at any time
+        distances
+            .into_iter()
+            .filter(|((coord, _), _)| *coord == self.end)
+            .map(|(_, distance)| distance as Distance)
+            .min()
+    }
+
+    /// Presimulate the maze corruption with the first n obstacles
+    pub fn presimulate_corruption(&mut self, n: usize) {
+        for _i in 0..n {
+            self.corrupt_next_space();
+        }
+    }
+}
+
+impl Display for ProgramMap {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let mut map_string = String::new();
+
+        let mut route_coords = Vec::from_iter([self.end]);
+        if self.visited.contains_key(&self.end) {
+            let mut current_coord = self.end;
+            while current_coord != self.start {
+                current_coord = self.visited.get(&current_coord).unwrap().previous;
+                route_coords.push(current_coord);
+            }
+        }
+
+        for row_index in 0..self.height as isize {
+            for col_index in 0..self.width as isize {
+                let coord = Coordinate::from((col_index, row_index));
+                if route_coords.contains(&coord) {
+                    map_string.push('@');
+                } else if self.obstacles.contains(&coord) {
+                    map_string.push('#');
+                } else {
+                    map_string.push('.');
+                }
+            }
+            map_string.push('\n');
+        }
+
+        write!(f, "{}", map_string)
+    }
+}
+
+/// Parses the bytes falling into the full-sized memory space from the input text
+fn parse_full_size(input: &str) -> Result<ProgramMap, String> {
+    let (program_map, _warnings) =
+        ProgramMap::try_from_string(input, SIZE, SIZE, false).map_err(|x| x.to_string())?;
+    Ok(program_map)
+}
+
+/// Solves part one for the puzzle input, getting the fewest steps to the exit once the first
+/// bytes have fallen
+pub fn part1(input: &str) -> Result<Distance, String> {
+    let mut program_map = parse_full_size(input)?;
+    let num_planned = program_map.planned_obstacles.len();
+    program_map.presimulate_corruption(NUM_FALLEN.min(num_planned));
+    program_map.search(Algorithm::Dijkstra);
+    program_map
+        .visited
+        .get(&program_map.end)
+        .map(|x| x.distance)
+        .ok_or_else(|| String::from("Could not reach the end"))
+}
+
+/// Solves part two for the puzzle input, getting the coordinates of the first byte that blocks
+/// the path to the exit
+pub fn part2(input: &str) -> Result<String, String> {
+    let mut program_map = parse_full_size(input)?;
+    let num_obstacles = program_map.planned_obstacles.len();
+    program_map.presimulate_corruption(num_obstacles);
+    program_map.search(Algorithm::Dijkstra);
+    while !program_map.visited.contains_key(&program_map.end) {
+        program_map.uncorrupt_next_space();
+        program_map.reset_visited();
+        program_map.search(Algorithm::Dijkstra);
+    }
+    let blocker = program_map
+        .planned_obstacles
+        .last()
+        .ok_or_else(|| String::from("No obstacle blocks the end"))?;
+    Ok(format!("{},{}", blocker.x, blocker.y))
+}
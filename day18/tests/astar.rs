@@ -0,0 +1,41 @@
+use day18::{Algorithm, ProgramMap};
+
+const EXAMPLE: &str = include_str!("../inputs/example.txt");
+
+/// Creates the example map with the first given number of bytes fallen
+fn example_map(num_bytes: usize) -> ProgramMap {
+    let mut program_map = ProgramMap::from_string(EXAMPLE, 7, 7);
+    program_map.presimulate_corruption(num_bytes);
+    program_map
+}
+
+#[test]
+fn astar_matches_dijkstra_distance() {
+    let mut dijkstra_map = example_map(12);
+    let dijkstra_expansions = dijkstra_map.search(Algorithm::Dijkstra);
+    let mut astar_map = example_map(12);
+    let astar_expansions = astar_map.search(Algorithm::Astar);
+
+    let end = astar_map.end;
+    assert_eq!(astar_map.visited[&end].distance, 22);
+    assert_eq!(dijkstra_map.visited[&end].distance, 22);
+    assert!(astar_expansions <= dijkstra_expansions);
+}
+
+#[test]
+fn astar_expands_less_on_an_open_map() {
+    let mut dijkstra_map = example_map(0);
+    let mut astar_map = example_map(0);
+    assert_eq!(dijkstra_map.search(Algorithm::Dijkstra), 49);
+    assert_eq!(astar_map.search(Algorithm::Astar), 13);
+    assert_eq!(astar_map.visited[&astar_map.end].distance, 12);
+}
+
+#[test]
+fn astar_reports_unreachable_end() {
+    let num_bytes = EXAMPLE.lines().count();
+    let mut program_map = example_map(num_bytes);
+    program_map.search(Algorithm::Astar);
+    assert!(!program_map.visited.contains_key(&program_map.end));
+    assert!(program_map.unvisited.is_empty());
+}
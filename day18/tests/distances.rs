@@ -0,0 +1,32 @@
+use day18::{Coordinate, ProgramMap};
+
+const EXAMPLE: &str = include_str!("../inputs/example.txt");
+
+/// Creates the example map with the first 12 bytes fallen
+fn example_map() -> ProgramMap {
+    let mut program_map = ProgramMap::from_string(EXAMPLE, 7, 7);
+    program_map.presimulate_corruption(12);
+    program_map
+}
+
+#[test]
+fn corner_to_corner_distance() {
+    let program_map = example_map();
+    let distances = program_map.distances_from(&[program_map.start]);
+    assert_eq!(distances[&program_map.end], 22);
+}
+
+#[test]
+fn nearest_of_multiple_starts_and_targets() {
+    let program_map = example_map();
+    let starts = [Coordinate::from((0, 0)), Coordinate::from((6, 0))];
+    let targets = [Coordinate::from((6, 6)), Coordinate::from((0, 3))];
+    assert_eq!(program_map.distance_between(&starts, &targets), Some(3));
+}
+
+#[test]
+fn blocked_starts_reach_nothing() {
+    let program_map = example_map();
+    let blocked = Coordinate::from((3, 0));
+    assert!(program_map.distances_from(&[blocked]).is_empty());
+}
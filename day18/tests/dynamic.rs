@@ -0,0 +1,41 @@
+use day18::{Algorithm, ProgramMap};
+
+const EXAMPLE: &str = include_str!("../inputs/example.txt");
+
+#[test]
+fn dynamic_matches_static_once_bytes_stop() {
+    // With every planned byte fallen, nothing changes while walking
+    let mut program_map = ProgramMap::from_string(EXAMPLE, 7, 7);
+    program_map.presimulate_corruption(12);
+    program_map.planned_obstacles.clear();
+    let dynamic = program_map.dynamic_distance();
+    program_map.search(Algorithm::Dijkstra);
+    assert_eq!(
+        dynamic,
+        Some(program_map.visited[&program_map.end].distance)
+    );
+    assert_eq!(dynamic, Some(22));
+}
+
+#[test]
+fn bytes_behind_the_walker_do_not_block() {
+    // The start corrupts after the first step, and the middle after the second, by which time
+    // the walker has passed both
+    let program_map = ProgramMap::from_string("0,0\n1,0\n", 1, 3);
+    assert_eq!(program_map.dynamic_distance(), Some(2));
+}
+
+#[test]
+fn bytes_ahead_of_the_walker_block() {
+    // The middle corrupts with the first step, just as the walker would step onto it
+    let program_map = ProgramMap::from_string("1,0\n0,0\n", 1, 3);
+    assert_eq!(program_map.dynamic_distance(), None);
+}
+
+#[test]
+fn bytes_closing_in_on_the_end_block() {
+    // Both neighbors of the end corrupt before the walker can reach either of them
+    let mut program_map = ProgramMap::from_string("1,1\n2,1\n1,2\n", 3, 3);
+    program_map.presimulate_corruption(1);
+    assert_eq!(program_map.dynamic_distance(), None);
+}
@@ -0,0 +1,75 @@
+use day18::{Coordinate, ParseError, ParseWarning, ProgramMap};
+
+const EXAMPLE: &str = include_str!("../inputs/example.txt");
+
+#[test]
+fn parses_example() {
+    let (program_map, warnings) = ProgramMap::try_from_string(EXAMPLE, 7, 7, false).unwrap();
+    assert!(warnings.is_empty());
+    assert_eq!(program_map.planned_obstacles.len(), 25);
+
+    // The obstacles are popped off the end in the order they fall
+    assert_eq!(
+        program_map.planned_obstacles.last(),
+        Some(&Coordinate::from((5, 4)))
+    );
+    assert_eq!(program_map.end, Coordinate::from((6, 6)));
+}
+
+#[test]
+fn skips_blank_lines_and_whitespace() {
+    let (program_map, warnings) =
+        ProgramMap::try_from_string("\n 1, 2 \n\n3,4\n", 7, 7, false).unwrap();
+    assert!(warnings.is_empty());
+    assert_eq!(
+        program_map.planned_obstacles,
+        vec![Coordinate::from((3, 4)), Coordinate::from((1, 2))]
+    );
+}
+
+#[test]
+fn rejects_missing_comma() {
+    let error = ProgramMap::try_from_string("1,2\n\n3 4\n", 7, 7, false).unwrap_err();
+    assert_eq!(error, ParseError::MissingComma(3));
+    assert_eq!(error.to_string(), "Line 3: expected X,Y");
+}
+
+#[test]
+fn rejects_invalid_x() {
+    let error = ProgramMap::try_from_string("a,2\n", 7, 7, false).unwrap_err();
+    assert_eq!(error, ParseError::InvalidNumber(1, String::from("a")));
+    assert_eq!(
+        error.to_string(),
+        "Line 1: could not parse \"a\" as a number"
+    );
+}
+
+#[test]
+fn rejects_invalid_y() {
+    let error = ProgramMap::try_from_string("1,2\n3,4,5\n", 7, 7, false).unwrap_err();
+    assert_eq!(error, ParseError::InvalidNumber(2, String::from("4,5")));
+}
+
+#[test]
+fn first_error_is_reported() {
+    let error = ProgramMap::try_from_string("x,1\n2\n", 7, 7, false).unwrap_err();
+    assert_eq!(error, ParseError::InvalidNumber(1, String::from("x")));
+}
+
+#[test]
+fn warns_about_suspicious_bytes() {
+    let text = "1,1\n9,0\n1,1\n";
+    let (program_map, warnings) = ProgramMap::try_from_string(text, 7, 7, false).unwrap();
+    assert_eq!(
+        warnings,
+        vec![
+            ParseWarning::OutOfBounds(2, Coordinate::from((9, 0))),
+            ParseWarning::Duplicate(3, Coordinate::from((1, 1)), 1),
+        ]
+    );
+    assert_eq!(program_map.planned_obstacles.len(), 3);
+
+    // Duplicates are dropped when deduplicating
+    let (program_map, _) = ProgramMap::try_from_string(text, 7, 7, true).unwrap();
+    assert_eq!(program_map.planned_obstacles.len(), 2);
+}
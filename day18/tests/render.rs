@@ -0,0 +1,18 @@
+use day18::ProgramMap;
+
+const EXAMPLE: &str = include_str!("../inputs/example.txt");
+
+#[test]
+fn renders_corrupted_example() {
+    let mut program_map = ProgramMap::from_string(EXAMPLE, 7, 7);
+    program_map.presimulate_corruption(12);
+    insta::assert_snapshot!(program_map.to_string());
+}
+
+#[test]
+fn renders_example_route() {
+    let mut program_map = ProgramMap::from_string(EXAMPLE, 7, 7);
+    program_map.presimulate_corruption(12);
+    program_map.visit_nodes();
+    insta::assert_snapshot!(program_map.to_string());
+}
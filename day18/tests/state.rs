@@ -0,0 +1,51 @@
+use day18::{Algorithm, ProgramMap};
+
+const EXAMPLE: &str = include_str!("../inputs/example.txt");
+
+#[test]
+fn save_and_load_state() {
+    // Partially explore the example map and save it
+    let mut program_map = ProgramMap::from_string(EXAMPLE, 7, 7);
+    program_map.presimulate_corruption(12);
+    program_map.visit_nodes();
+    let directory = tempfile::tempdir().unwrap();
+    let path = directory.path().join("state.bin");
+    program_map.save_state(&path).unwrap();
+
+    // The loaded map should have the same obstacles and visited distances
+    let loaded = ProgramMap::load_state(&path).unwrap();
+    assert_eq!(loaded.obstacles, program_map.obstacles);
+    assert_eq!(loaded.planned_obstacles, program_map.planned_obstacles);
+    assert_eq!(loaded.visited, program_map.visited);
+    assert_eq!(loaded.visited[&loaded.end].distance, 22);
+}
+
+#[test]
+fn resume_from_checkpoint() {
+    // Save a checkpoint every few coordinates expanded, stopping the search at the first one
+    // by only keeping that
+    let mut program_map = ProgramMap::from_string(EXAMPLE, 7, 7);
+    program_map.presimulate_corruption(12);
+    let directory = tempfile::tempdir().unwrap();
+    let path = directory.path().join("state.bin");
+    let mut checkpoints = 0;
+    program_map.search_with_checkpoints(Algorithm::Dijkstra, 5, |x| {
+        if checkpoints == 0 {
+            x.save_state(&path).unwrap();
+        }
+        checkpoints += 1;
+    });
+    assert!(checkpoints > 1);
+
+    // The checkpoint should be partway through the search, which resumes to the same answer
+    let mut resumed = ProgramMap::load_state(&path).unwrap();
+    assert_eq!(resumed.visited.len(), 5);
+    assert!(!resumed.visited.contains_key(&resumed.end));
+    resumed.search(Algorithm::Dijkstra);
+    assert_eq!(resumed.visited, program_map.visited);
+    assert_eq!(resumed.visited[&resumed.end].distance, 22);
+
+    // Only the saved state should be left behind
+    let files: Vec<_> = std::fs::read_dir(directory.path()).unwrap().collect();
+    assert_eq!(files.len(), 1);
+}
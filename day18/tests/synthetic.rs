@@ -0,0 +1,32 @@
+use std::collections::HashSet;
+
+use day18::ProgramMap;
+
+#[test]
+fn same_seed_gives_same_bytes() {
+    let first = ProgramMap::synthetic(500, 101, 7);
+    let second = ProgramMap::synthetic(500, 101, 7);
+    let other = ProgramMap::synthetic(500, 101, 8);
+    assert_eq!(first.planned_obstacles, second.planned_obstacles);
+    assert_ne!(first.planned_obstacles, other.planned_obstacles);
+}
+
+#[test]
+fn bytes_are_distinct_and_avoid_start_and_end() {
+    let program_map = ProgramMap::synthetic(23, 5, 3);
+    let bytes: HashSet<_> = program_map.planned_obstacles.iter().collect();
+    assert_eq!(program_map.planned_obstacles.len(), 23);
+    assert_eq!(bytes.len(), 23);
+    assert!(!bytes.contains(&program_map.start));
+    assert!(!bytes.contains(&program_map.end));
+    assert!(program_map
+        .planned_obstacles
+        .iter()
+        .all(|x| x.x < 5 && x.y < 5));
+}
+
+#[test]
+#[should_panic(expected = "Only 23 bytes can fall on a 5x5 map")]
+fn too_many_bytes() {
+    ProgramMap::synthetic(24, 5, 3);
+}
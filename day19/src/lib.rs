@@ -0,0 +1,183 @@
+use std::{
+    collections::HashMap,
+    fmt::{self, Display, Formatter},
+};
+
+/// Type representation of a single towel
+pub type Towel = String;
+
+/// Memory structure for storing a previous calculated number of ways
+/// to create a given towel pattern
+pub type PatternCache = HashMap<String, usize>;
+
+/// Towel pattern to be created
+pub struct TowelPattern {
+    pub pattern: String,
+}
+
+impl TowelPattern {
+    /// Checks whether the given pattern is possible using the given array of towels
+    pub fn is_pattern_possible_using(pattern: &str, towels: &[Towel]) -> bool {
+        // If the pattern is empty, all previous parts have been created
+        if pattern.is_empty() {
+            return true;
+        }
+
+        // For each towel, check whether it can be used as the next towel, and recursively
+        // checking the resulting pattern to see whether it can be created using the given
+        // array of towels
+        for towel in towels {
+            if let Some(remaining_pattern) = pattern.strip_prefix(towel) {
+                if Self::is_pattern_possible_using(remaining_pattern, towels) {
+                    return true;
+                }
+            }
+        }
+
+        // The given towel pattern cannot be made using the given array of towels
+        false
+    }
+
+    /// Checks whether this towel pattern is possible using the given array of towels
+    pub fn is_possible_using(&self, towels: &[Towel]) -> bool {
+        Self::is_pattern_possible_using(&self.pattern, towels)
+    }
+
+    /// Checks the number of ways the given towel pattern can be created using the given
+    /// array of towels, and returns it in the given `count` variable, utilizing a given
+    /// cache of previously created towel pattern results
+    pub fn pattern_variations_using(
+        pattern: &str,
+        towels: &[Towel],
+        count: &mut usize,
+        pattern_cache: &mut PatternCache,
+    ) {
+        // If the pattern is empty, it represents a completed to create a towel pattern
+        if pattern.is_empty() {
+            *count += 1;
+            return;
+        }
+
+        // If the towel pattern has been created previously, use the cached results
+        if let Some(cached) = pattern_cache.get(pattern) {
+            *count += *cached;
+            return;
+        }
+
+        // Store the number of ways to create the overall pattern before creating this sub-pattern
+        let initial_count = *count;
+
+        // For each towel, check whether it can be used as the next towel, and recursively
+        // checking the resulting pattern to see how many ways the remaining pattern can be
+        // created using the given array of towels
+        for towel in towels {
+            if let Some(remaining_pattern) = pattern.strip_prefix(towel) {
+                Self::pattern_variations_using(remaining_pattern, towels, count, pattern_cache);
+            }
+        }
+
+        // Get the number of ways to create the overall pattern aftter creating this sub-pattern
+        let new_count = *count;
+
+        // Calculate the nubmer of ways to create the specific, given sub-pattern and insert it
+        // into the cache memory
+        let diff_count = new_count - initial_count;
+        pattern_cache.insert(String::from(pattern), diff_count);
+    }
+
+    /// Calculates the number of ways to create the towel pattern using the given array of towels
+    pub fn variations_using(&self, towels: &[Towel]) -> usize {
+        // Create a variable for tracking the number of ways to create the towel pattern
+        let mut count = 0;
+
+        // Create a blank cache memory for sub-pattern results
+        let mut pattern_cache = PatternCache::new();
+
+        // Calculate the number of ways to create this towel pattern and return it
+        Self::pattern_variations_using(&self.pattern, towels, &mut count, &mut pattern_cache);
+        count
+    }
+}
+
+impl Display for TowelPattern {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.pattern)
+    }
+}
+
+/// Splits the given towels into the minimal basis of towels and the redundant towels, which
+/// can themselves be created using other towels, both in their original order
+///
+/// Any way of creating a towel from other towels uses only shorter towels (or an identical
+/// one), so towels are checked from shortest to longest against the basis found so far.
+pub fn minimal_basis(towels: &[Towel]) -> (Vec<Towel>, Vec<Towel>) {
+    // Get the towels ordered from shortest to longest
+    let mut by_length: Vec<&Towel> = towels.iter().collect();
+    by_length.sort_by_key(|t| t.len());
+
+    // Add each towel to the basis unless it can be created from the basis so far
+    let mut basis: Vec<Towel> = Vec::new();
+    for towel in by_length {
+        if !TowelPattern::is_pattern_possible_using(towel, &basis) {
+            basis.push(towel.clone());
+        }
+    }
+
+    // Split the towels in their original order, only keeping a single copy of duplicates in the basis
+    let mut basis_in_order = Vec::new();
+    let mut redundant = Vec::new();
+    for towel in towels {
+        if basis.contains(towel) && !basis_in_order.contains(towel) {
+            basis_in_order.push(towel.clone());
+        } else {
+            redundant.push(towel.clone());
+        }
+    }
+
+    // Return the basis and redundant towels
+    (basis_in_order, redundant)
+}
+
+/// Parses the input text into the array of towels and towel patterns, failing if the blank line
+/// between them is missing
+pub fn parse(text: &str) -> Result<(Vec<Towel>, Vec<TowelPattern>), String> {
+    // Split the input text into the towel and towel patterns portions
+    let Some((towels_text, patterns_text)) = text.split_once("\n\n") else {
+        return Err(String::from(
+            "Missing the blank line between the towels and the patterns",
+        ));
+    };
+
+    // Get the array of towels from the towel portion
+    let towels: Vec<Towel> = towels_text
+        .split(",")
+        .map(|t| String::from(t.trim()))
+        .collect();
+
+    // Create the array of towel patterns from the towel portion
+    let mut patterns = Vec::new();
+    for pattern_text in patterns_text.trim().lines() {
+        let pattern = TowelPattern {
+            pattern: String::from(pattern_text),
+        };
+        patterns.push(pattern);
+    }
+
+    // Return the towels and towel patterns
+    Ok((towels, patterns))
+}
+
+/// Solves part one for the puzzle input, getting the number of patterns that can be made
+pub fn part1(input: &str) -> Result<usize, String> {
+    let (towels, patterns) = parse(input)?;
+    Ok(patterns
+        .iter()
+        .filter(|p| p.is_possible_using(&towels))
+        .count())
+}
+
+/// Solves part two for the puzzle input, getting the total number of ways to make the patterns
+pub fn part2(input: &str) -> Result<usize, String> {
+    let (towels, patterns) = parse(input)?;
+    Ok(patterns.iter().map(|p| p.variations_using(&towels)).sum())
+}
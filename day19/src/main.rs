@@ -1,124 +1,53 @@
-use std::{
-    collections::HashMap,
-    fmt::{self, Display, Formatter},
-    fs,
-};
-
 use clap::Parser;
-
-/// Type representation of a single towel
-type Towel = String;
-
-/// Memory structure for storing a previous calculated number of ways
-/// to create a given towel pattern
-type PatternCache = HashMap<String, usize>;
+use common::{fail, read_input, About, Failure, Format};
+use day19::{minimal_basis, parse, Towel, TowelPattern};
 
 /// CLI arguments
 #[derive(Parser)]
 struct CliArgs {
     part: u64,
     filepath: String,
+    /// Report the minimal basis of towels, which excludes towels that can be made from others
+    #[arg(long)]
+    basis: bool,
+    /// Parse the input and print statistics about its structure instead of solving it
+    #[arg(long)]
+    parse_only: bool,
+    /// The format in which errors are reported
+    #[arg(long, value_enum, default_value_t = Format::Text)]
+    format: Format,
 }
 
-/// Towel pattern to be created
-struct TowelPattern {
-    pattern: String,
-}
-
-impl TowelPattern {
-    /// Checks whether the given pattern is possible using the given array of towels
-    fn is_pattern_possible_using(pattern: &str, towels: &[Towel]) -> bool {
-        // If the pattern is empty, all previous parts have been created
-        if pattern.is_empty() {
-            return true;
-        }
-
-        // For each towel, check whether it can be used as the next towel, and recursively
-        // checking the resulting pattern to see whether it can be created using the given
-        // array of towels
-        for towel in towels {
-            if let Some(remaining_pattern) = pattern.strip_prefix(towel) {
-                if Self::is_pattern_possible_using(remaining_pattern, towels) {
-                    return true;
-                }
-            }
-        }
-
-        // The given towel pattern cannot be made using the given array of towels
-        false
-    }
-
-    /// Checks whether this towel pattern is possible using the given array of towels
-    fn is_possible_using(&self, towels: &[Towel]) -> bool {
-        Self::is_pattern_possible_using(&self.pattern, towels)
-    }
+/// Description of the puzzle solved by this binary
+const ABOUT: About = About {
+    day: 19,
+    title: "Linen Layout",
+    input: "Comma-separated towel patterns, a blank line, then one design per line",
+    parts: &[1, 2],
+    features: &[],
+};
 
-    /// Checks the number of ways the given towel pattern can be created using the given
-    /// array of towels, and returns it in the given `count` variable, utilizing a given
-    /// cache of previously created towel pattern results
-    fn pattern_variations_using(
-        pattern: &str,
-        towels: &[Towel],
-        count: &mut usize,
-        pattern_cache: &mut PatternCache,
-    ) {
-        // If the pattern is empty, it represents a completed to create a towel pattern
-        if pattern.is_empty() {
-            *count += 1;
-            return;
-        }
-
-        // If the towel pattern has been created previously, use the cached results
-        if let Some(cached) = pattern_cache.get(pattern) {
-            *count += *cached;
-            return;
-        }
-
-        // Store the number of ways to create the overall pattern before creating this sub-pattern
-        let initial_count = *count;
-
-        // For each towel, check whether it can be used as the next towel, and recursively
-        // checking the resulting pattern to see how many ways the remaining pattern can be
-        // created using the given array of towels
-        for towel in towels {
-            if let Some(remaining_pattern) = pattern.strip_prefix(towel) {
-                Self::pattern_variations_using(remaining_pattern, towels, count, pattern_cache);
-            }
-        }
-
-        // Get the number of ways to create the overall pattern aftter creating this sub-pattern
-        let new_count = *count;
-
-        // Calculate the nubmer of ways to create the specific, given sub-pattern and insert it
-        // into the cache memory
-        let diff_count = new_count - initial_count;
-        pattern_cache.insert(String::from(pattern), diff_count);
-    }
+/// Main entry function
+fn main() {
+    // Describe the puzzle instead if requested
+    common::handle_about::<CliArgs>(&ABOUT);
 
-    /// Calculates the number of ways to create the towel pattern using the given array of towels
-    fn variations_using(&self, towels: &[Towel]) -> usize {
-        // Create a variable for tracking the number of ways to create the towel pattern
-        let mut count = 0;
+    // Parse CLI arguments
+    let cli = CliArgs::parse();
 
-        // Create a blank cache memory for sub-pattern results
-        let mut pattern_cache = PatternCache::new();
+    // Report errors in the requested format
+    common::init(cli.format);
 
-        // Calculate the number of ways to create this towel pattern and return it
-        Self::pattern_variations_using(&self.pattern, towels, &mut count, &mut pattern_cache);
-        count
+    // Only parse the input if requested
+    if cli.parse_only {
+        report_structure(cli.filepath);
     }
-}
 
-impl Display for TowelPattern {
-    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
-        write!(f, "{}", self.pattern)
+    // Analyze the towel basis instead if requested
+    if cli.basis {
+        print_basis_analysis(cli.filepath);
+        return;
     }
-}
-
-/// Main entry function
-fn main() {
-    // Parse CLI arguments
-    let cli = CliArgs::parse();
 
     // Run the code for the desired challenge part
     match cli.part {
@@ -128,13 +57,36 @@ fn main() {
     }
 }
 
+/// Parses the towels and patterns, exiting with a parse failure if they can't be parsed
+fn read_towels(contents: &str) -> (Vec<Towel>, Vec<TowelPattern>) {
+    parse(contents).unwrap_or_else(|error| fail(Failure::Parse(error)))
+}
+
+/// Parses the towels and patterns, then prints how many of each there are and the length of
+/// the longest of each and exits
+fn report_structure(filepath: String) -> ! {
+    let (towels, patterns) = read_towels(&read_input(filepath));
+    common::report_parse(&[
+        ("towels", towels.len()),
+        (
+            "longest towel",
+            towels.iter().map(|x| x.len()).max().unwrap_or(0),
+        ),
+        ("patterns", patterns.len()),
+        (
+            "longest pattern",
+            patterns.iter().map(|x| x.pattern.len()).max().unwrap_or(0),
+        ),
+    ]);
+}
+
 /// Runs part one
 fn main_part_one(filepath: String) {
     // Get the contents of the given filepath
-    let contents = fs::read_to_string(filepath).expect("Invalid filepath");
+    let contents = read_input(filepath);
 
     // Get the set of towels and towel patterns
-    let (towels, patterns) = parse(&contents);
+    let (towels, patterns) = read_towels(&contents);
 
     // Calculate the number of possible towel patterns
     let num_possible = patterns
@@ -147,10 +99,10 @@ fn main_part_one(filepath: String) {
 /// Runs part two
 fn main_part_two(filepath: String) {
     // Get the contents of the given filepath
-    let contents = fs::read_to_string(filepath).expect("Invalid filepath");
+    let contents = read_input(filepath);
 
     // Get the set of towels and towel patterns
-    let (towels, patterns) = parse(&contents);
+    let (towels, patterns) = read_towels(&contents);
 
     // Calculate the number of ways to create all possible towel patterns
     let mut total_count = 0;
@@ -160,28 +112,32 @@ fn main_part_two(filepath: String) {
     println!("{total_count}");
 }
 
-/// Parses the input text into the array of towels and towel patterns
-fn parse(text: &str) -> (Vec<Towel>, Vec<TowelPattern>) {
-    // Split the input text into the towel and towel patterns portions
-    let texts: Vec<&str> = text.split("\n\n").collect();
-    let towels_text = texts[0];
-    let patterns_text = texts[1];
-
-    // Get the array of towels from the towel portion
-    let towels: Vec<Towel> = towels_text
-        .split(",")
-        .map(|t| String::from(t.trim()))
-        .collect();
-
-    // Create the array of towel patterns from the towel portion
-    let mut patterns = Vec::new();
-    for pattern_text in patterns_text.trim().lines() {
-        let pattern = TowelPattern {
-            pattern: String::from(pattern_text),
-        };
-        patterns.push(pattern);
-    }
+/// Prints the minimal basis of towels and the redundant towels, along with the number of
+/// possible towel patterns and ways to create them when using only the basis
+fn print_basis_analysis(filepath: String) {
+    // Get the contents of the given filepath
+    let contents = read_input(filepath);
+
+    // Get the set of towels and towel patterns
+    let (towels, patterns) = read_towels(&contents);
+
+    // Find the minimal basis of towels
+    let (basis, redundant) = minimal_basis(&towels);
+    let num_basis = basis.len();
+    let num_redundant = redundant.len();
+    let basis_str = basis.join(", ");
+    let redundant_str = redundant.join(", ");
+    println!("Basis ({num_basis}): {basis_str}");
+    println!("Redundant ({num_redundant}): {redundant_str}");
+
+    // Recalculate the number of possible patterns, which is unchanged by the redundant towels
+    let num_possible = patterns
+        .iter()
+        .filter(|p| p.is_possible_using(&basis))
+        .count();
+    println!("Possible patterns using basis: {num_possible}");
 
-    // Return the towels and towel patterns
-    (towels, patterns)
+    // Recalculate the number of ways to create the patterns using only the basis
+    let total_count: usize = patterns.iter().map(|p| p.variations_using(&basis)).sum();
+    println!("Variations using basis: {total_count}");
 }
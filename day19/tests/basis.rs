@@ -0,0 +1,53 @@
+use day19::{minimal_basis, parse, Towel};
+
+const EXAMPLE: &str = include_str!("../inputs/example.txt");
+
+/// Converts the given towel names into towels
+fn towels(names: &[&str]) -> Vec<Towel> {
+    names.iter().map(|name| name.to_string()).collect()
+}
+
+#[test]
+fn example_basis() {
+    let (towels_available, _) = parse(EXAMPLE).unwrap();
+    let (basis, redundant) = minimal_basis(&towels_available);
+    assert_eq!(basis, towels(&["r", "wr", "b", "g", "bwu"]));
+    assert_eq!(redundant, towels(&["rb", "gb", "br"]));
+}
+
+#[test]
+fn basis_makes_the_same_patterns() {
+    let (towels_available, patterns) = parse(EXAMPLE).unwrap();
+    let (basis, _) = minimal_basis(&towels_available);
+    for pattern in &patterns {
+        assert_eq!(
+            pattern.is_possible_using(&basis),
+            pattern.is_possible_using(&towels_available),
+            "{pattern}"
+        );
+    }
+}
+
+#[test]
+fn basis_has_fewer_variations() {
+    let (towels_available, patterns) = parse(EXAMPLE).unwrap();
+    let (basis, _) = minimal_basis(&towels_available);
+    let variations =
+        |towels: &[Towel]| -> usize { patterns.iter().map(|x| x.variations_using(towels)).sum() };
+    assert_eq!(variations(&towels_available), 16);
+    assert_eq!(variations(&basis), 6);
+}
+
+#[test]
+fn duplicates_are_redundant() {
+    let (basis, redundant) = minimal_basis(&towels(&["ab", "a", "b", "a"]));
+    assert_eq!(basis, towels(&["a", "b"]));
+    assert_eq!(redundant, towels(&["ab", "a"]));
+}
+
+#[test]
+fn no_towels_have_an_empty_basis() {
+    let (basis, redundant) = minimal_basis(&[]);
+    assert!(basis.is_empty());
+    assert!(redundant.is_empty());
+}
@@ -0,0 +1,11 @@
+use day19::parse;
+
+#[test]
+fn rejects_missing_blank_line() {
+    assert_eq!(
+        parse("garbage line !!\nfoo").err(),
+        Some(String::from(
+            "Missing the blank line between the towels and the patterns"
+        ))
+    );
+}
@@ -0,0 +1,235 @@
+use rules::RuleSet;
+
+pub mod rules;
+
+/// The ways a pair of adjacent levels can break the safety rules
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ViolationKind {
+    /// The levels changed by more than three
+    StepTooLarge,
+    /// The levels did not change at all
+    NoChange,
+    /// The levels changed in the opposite direction of the report
+    DirectionChange,
+}
+
+impl ViolationKind {
+    /// Gets the name of the kind of violation
+    pub fn name(&self) -> &'static str {
+        match self {
+            ViolationKind::StepTooLarge => "step_too_large",
+            ViolationKind::NoChange => "no_change",
+            ViolationKind::DirectionChange => "direction_change",
+        }
+    }
+}
+
+/// A violation of the safety rules between the level at the index and the next one
+#[derive(Clone, Copy, Debug)]
+pub struct Violation {
+    pub index: usize,
+    pub kind: ViolationKind,
+}
+
+/// The result of checking a report against the safety rules
+pub struct SafetyCheck {
+    pub num_levels: usize,
+    /// The change from each level to the next one, if requested
+    pub diffs: Option<Vec<i64>>,
+    pub violations: Vec<Violation>,
+}
+
+impl SafetyCheck {
+    /// Checks whether the report had no violations
+    pub fn is_safe(&self) -> bool {
+        self.violations.is_empty()
+    }
+
+    /// Gets the number of violations of the given kind
+    pub fn count(&self, kind: ViolationKind) -> usize {
+        self.violations.iter().filter(|x| x.kind == kind).count()
+    }
+
+    /// Gets the number of levels at the start of the report that are safe on their own
+    pub fn longest_safe_prefix(&self) -> usize {
+        match self.violations.first() {
+            Some(violation) => violation.index + 1,
+            None => self.num_levels,
+        }
+    }
+}
+
+/// Checks the report against the safety rules, collecting every violation between adjacent levels
+pub fn check_report(report: &[u64], rules: &RuleSet) -> SafetyCheck {
+    check_levels(report, rules, false)
+}
+
+/// Checks the report against the safety rules like `check_report`, also collecting the change
+/// from each level to the next one
+pub fn check_report_with_diffs(report: &[u64], rules: &RuleSet) -> SafetyCheck {
+    check_levels(report, rules, true)
+}
+
+/// Checks the report against the safety rules, collecting every violation between adjacent
+/// levels, along with the change between them if requested
+fn check_levels(report: &[u64], rules: &RuleSet, with_diffs: bool) -> SafetyCheck {
+    // Create lists for storing violations and, if requested, differences
+    let mut violations = Vec::new();
+    let mut diffs = with_diffs.then(Vec::new);
+
+    // The direction of the report is set by the first pair of levels
+    let increasing = report.len() > 1 && report[1] > report[0];
+
+    // Iterate through adjacent pairs of levels and check for violations
+    for (index, pair) in report.windows(2).enumerate() {
+        let (previous_entry, entry) = (pair[0], pair[1]);
+        if let Some(kind) = rules.check(previous_entry, entry, increasing) {
+            violations.push(Violation { index, kind });
+        }
+        if let Some(diffs) = &mut diffs {
+            diffs.push(entry as i64 - previous_entry as i64);
+        }
+    }
+
+    // Return the results of the check
+    SafetyCheck {
+        num_levels: report.len(),
+        diffs,
+        violations,
+    }
+}
+
+/// Gets the indices of the fewest levels that must be removed from the report to make it safe
+///
+/// This finds the longest subsequence of levels that is safe in either direction, where each
+/// level can follow any earlier level that the rules allow it to follow in that direction.
+/// Every level not in that subsequence is removed.
+pub fn repair(report: &[u64], rules: &RuleSet) -> Vec<usize> {
+    // Get the longest safe subsequence in either direction, as the indices of its levels
+    let mut longest: Vec<usize> = Vec::new();
+    for increasing in [true, false] {
+        // Get the length of the longest safe subsequence ending at each level, and the level
+        // before it in that subsequence
+        let mut lengths = vec![1; report.len()];
+        let mut previous: Vec<Option<usize>> = vec![None; report.len()];
+        for index in 0..report.len() {
+            for previous_index in 0..index {
+                let (previous_entry, entry) = (report[previous_index], report[index]);
+                if rules.check(previous_entry, entry, increasing).is_none()
+                    && lengths[previous_index] + 1 > lengths[index]
+                {
+                    lengths[index] = lengths[previous_index] + 1;
+                    previous[index] = Some(previous_index);
+                }
+            }
+        }
+
+        // Walk back from the end of the longest subsequence to get its levels
+        let Some(end) = (0..report.len()).max_by_key(|x| (lengths[*x], std::cmp::Reverse(*x)))
+        else {
+            continue;
+        };
+        if lengths[end] > longest.len() {
+            let mut kept = vec![end];
+            while let Some(index) = previous[*kept.last().unwrap()] {
+                kept.push(index);
+            }
+            kept.reverse();
+            longest = kept;
+        }
+    }
+
+    // Every level not in the longest safe subsequence must be removed
+    (0..report.len()).filter(|x| !longest.contains(x)).collect()
+}
+
+/// Gets the minimum number of levels that must be removed from the report to make it safe
+pub fn min_removals(report: &[u64], rules: &RuleSet) -> usize {
+    repair(report, rules).len()
+}
+
+/// Parses the reports from the input text, one per line, failing with the line number of any
+/// level that isn't a number
+pub fn parse_reports(contents: &str) -> Result<Vec<Vec<u64>>, String> {
+    // For each line in the supplied text, split the string and parse the numbers
+    let mut data = Vec::new();
+    for (index, line) in contents.lines().enumerate() {
+        let report = line
+            .split(' ')
+            .filter(|x| !x.is_empty())
+            .map(|level| {
+                level.parse::<u64>().map_err(|_| {
+                    format!("Line {}: could not parse {level:?} as a level", index + 1)
+                })
+            })
+            .collect::<Result<Vec<u64>, String>>()?;
+        data.push(report);
+    }
+
+    // Return the list
+    Ok(data)
+}
+
+/// Gets the number of reports that are safe
+pub fn count_safe(reports: &[Vec<u64>], rules: &RuleSet) -> u64 {
+    // Initialize the number of safe reports as 0
+    let mut safe_report_count: u64 = 0;
+
+    // Iterate through all reports in the data
+    for report in reports {
+        // If the current report is not safe, check the next one
+        if !check_report(report, rules).is_safe() {
+            continue;
+        }
+
+        // Add to the number of safe reports found
+        safe_report_count += 1;
+    }
+
+    // Return the number of safe reports
+    safe_report_count
+}
+
+/// Gets the number of reports that are safe once at most a single level is removed
+pub fn count_dampened_safe(reports: &[Vec<u64>], rules: &RuleSet) -> u64 {
+    // Initialize the number of safe reports as 0
+    let mut safe_report_count: u64 = 0;
+
+    // Iterate through all reports in the data
+    'report_check: for report in reports {
+        // Iterate through report, removing each entry until a safe report is detected
+        for index in 0..report.len() {
+            // Create a report with an single point removed
+            // Already safe reports will still pass regardless!
+            let mut modified_report = report.clone();
+            modified_report.remove(index);
+
+            // Check whether the modified report is safe, try to remove
+            // a different entry if it's not.
+            if check_report(&modified_report, rules).is_safe() {
+                safe_report_count += 1;
+                continue 'report_check;
+            }
+        }
+    }
+
+    // Return the number of safe reports
+    safe_report_count
+}
+
+/// Solves part one for the puzzle input, getting the number of safe reports under the
+/// puzzle's rules
+pub fn part1(input: &str) -> Result<u64, String> {
+    let reports = parse_reports(input)?;
+    Ok(count_safe(&reports, &RuleSet::new(3, false, false)))
+}
+
+/// Solves part two for the puzzle input, getting the number of reports that are safe under
+/// the puzzle's rules once at most a single level is removed
+pub fn part2(input: &str) -> Result<u64, String> {
+    let reports = parse_reports(input)?;
+    Ok(count_dampened_safe(
+        &reports,
+        &RuleSet::new(3, false, false),
+    ))
+}
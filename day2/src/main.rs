@@ -1,135 +1,229 @@
-use std::fs;
+use std::{collections::BTreeMap, fs};
 
 use clap::Parser;
+use common::{read_input, About, Failure, Format};
+use day2::{
+    check_report, check_report_with_diffs, count_dampened_safe, count_safe, min_removals,
+    parse_reports, repair, rules::RuleSet, ViolationKind,
+};
 
 #[derive(Parser)]
 struct CliArgs {
     part: u64,
     filepath: String,
+    /// Print the longest safe prefix and number of violations for each report
+    #[arg(long)]
+    stats: bool,
+    /// Print the number of safe reports when up to K levels can be removed, for K from zero up to
+    /// the given maximum
+    #[arg(long)]
+    dampener_table: Option<usize>,
+    /// Print the fewest levels to remove from each report that's unsafe even with the dampener,
+    /// and the distribution of those repair costs
+    #[arg(long)]
+    repairs: bool,
+    /// Write the differences between adjacent levels of each report, along with its
+    /// violations, to the given file as JSON for plotting
+    #[arg(long, value_name = "FILE")]
+    diffs: Option<String>,
+    /// The largest change allowed between adjacent levels
+    #[arg(long, default_value_t = 3)]
+    max_step: u64,
+    /// Allow adjacent levels to be the same
+    #[arg(long)]
+    allow_plateaus: bool,
+    /// Allow the levels to change direction within a report
+    #[arg(long)]
+    allow_reversals: bool,
+    /// Parse the input and print statistics about its structure instead of solving it
+    #[arg(long)]
+    parse_only: bool,
+    /// The format in which errors are reported
+    #[arg(long, value_enum, default_value_t = Format::Text)]
+    format: Format,
 }
 
+/// Description of the puzzle solved by this binary
+const ABOUT: About = About {
+    day: 2,
+    title: "Red-Nosed Reports",
+    input: "One report per line, each a list of space-separated levels",
+    parts: &[1, 2],
+    features: &[],
+};
+
 fn main() {
+    // Describe the puzzle instead if requested
+    common::handle_about::<CliArgs>(&ABOUT);
+
     // Parse CLI arguments
     let cli = CliArgs::parse();
 
+    // Report errors in the requested format
+    common::init(cli.format);
+
+    // Only parse the input if requested
+    if cli.parse_only {
+        report_structure(&cli.filepath);
+    }
+
+    // Get the safety rules to check the reports against
+    let rules = RuleSet::new(cli.max_step, cli.allow_plateaus, cli.allow_reversals);
+
     // Run the code for the desired challenge part
+    if cli.stats {
+        print_stats(&cli.filepath, &rules);
+    }
+    if let Some(max_removals) = cli.dampener_table {
+        print_dampener_table(&cli.filepath, max_removals, &rules);
+    }
+    if cli.repairs {
+        print_repairs(&cli.filepath, &rules);
+    }
+    if let Some(output_filepath) = &cli.diffs {
+        write_diffs(&cli.filepath, output_filepath, &rules);
+    }
     match cli.part {
-        1 => main_part_one(cli.filepath),
-        2 => main_part_two(cli.filepath),
+        1 => main_part_one(cli.filepath, &rules),
+        2 => main_part_two(cli.filepath, &rules),
         _ => panic!("Invalid selection part selection!"),
     }
 }
 
 // Function to create sorted lists of numbers based on the input text file
 fn create_list(filepath: String) -> Vec<Vec<u64>> {
-    let contents = fs::read_to_string(filepath).expect("Could not read file");
-
-    // Create empty, mutable lists
-    let mut data: Vec<Vec<u64>> = Vec::new();
-
-    // For each line in the supplied text, split the string and parse the number, and add to the list
-    for line in contents.lines() {
-        let report: Vec<u64> = line
-            .split(" ")
-            .filter(|x| !x.is_empty())
-            .map(|y| str::parse::<u64>(y).unwrap())
-            .collect();
-        data.push(report.clone());
-    }
-
-    // Return the list
-    data
+    let contents = read_input(filepath);
+    parse_reports(&contents).unwrap_or_else(|error| common::fail(Failure::Parse(error)))
 }
 
-fn is_safe_report(report: &Vec<u64>) -> bool {
-    // Iterate through entries and see if change is within the safe margin
-    let mut previous_entry = *report.first().unwrap();
-    let mut first_entry = true;
-    for entry in report {
-        let entry_diff = entry.abs_diff(previous_entry);
-        if entry_diff > 3 || (entry_diff == 0 && !first_entry) {
-            return false;
-        }
-        previous_entry = *entry;
-        first_entry = false;
-    }
-    true
+/// Parses the reports from the input, then prints the number of reports and levels and the
+/// lengths of the shortest and longest reports and exits
+fn report_structure(filepath: &str) -> ! {
+    let data = create_list(filepath.to_owned());
+    let lengths = data.iter().map(|x| x.len());
+    common::report_parse(&[
+        ("reports", data.len()),
+        ("levels", lengths.clone().sum()),
+        ("shortest report", lengths.clone().min().unwrap_or(0)),
+        ("longest report", lengths.max().unwrap_or(0)),
+    ]);
 }
 
-fn is_list_sorted(report: &Vec<u64>) -> bool {
-    // Create a clone of an ascending sorted report
-    let mut asc_sorted_report = report.clone();
-    asc_sorted_report.sort();
+/// Prints a table of the number of safe reports when up to K levels can be removed from each
+/// report, for K from zero up to the given maximum
+fn print_dampener_table(filepath: &str, max_removals: usize, rules: &RuleSet) {
+    // Parse the file contents for the lists
+    let data = create_list(filepath.to_owned());
 
-    // Create a clone of a descending sorted report
-    let mut desc_sorted_report = asc_sorted_report.clone();
-    desc_sorted_report.reverse();
+    // Get the minimum number of removals needed for each report
+    let removals: Vec<usize> = data.iter().map(|x| min_removals(x, rules)).collect();
 
-    // Immediately disqualify report for safety if array not sorted
-    if *report == asc_sorted_report || *report == desc_sorted_report {
-        return true;
+    // Print the number of reports that are safe for each number of allowed removals
+    println!("K\tsafe");
+    for allowed in 0..=max_removals {
+        let num_safe = removals.iter().filter(|x| **x <= allowed).count();
+        println!("{allowed}\t{num_safe}");
     }
-
-    false
 }
 
-fn main_part_one(filepath: String) {
+/// Prints the fewest levels to remove from each report that's still unsafe when one level can
+/// be removed, as CSV, followed by the number of those reports for each number of removals
+fn print_repairs(filepath: &str, rules: &RuleSet) {
     // Parse the file contents for the lists
-    let data = create_list(filepath);
-
-    // Initialize the number of safe reports as 0
-    let mut safe_report_count: u64 = 0;
-
-    // Iterate through all reports in the data
-    for report in data {
-        // Check whether the list is sorted
-        if !is_list_sorted(&report) {
+    let data = create_list(filepath.to_owned());
+
+    // Print a set of levels to remove from each report that needs more than one removed
+    let mut distribution: BTreeMap<usize, usize> = BTreeMap::new();
+    println!("report,removals,removed");
+    for (index, report) in data.iter().enumerate() {
+        let removed = repair(report, rules);
+        if removed.len() <= 1 {
             continue;
         }
+        let removed_str: Vec<String> = removed.iter().map(|x| x.to_string()).collect();
+        println!("{index},{},{}", removed.len(), removed_str.join(" "));
+        *distribution.entry(removed.len()).or_default() += 1;
+    }
 
-        // If the current report is not safe, check the next one
-        if !is_safe_report(&report) {
-            continue;
-        }
+    // Print the number of reports needing each number of removals
+    println!("removals,reports");
+    for (num_removals, num_reports) in distribution {
+        println!("{num_removals},{num_reports}");
+    }
+}
+
+/// Writes the differences between adjacent levels of each report to the given file as a JSON
+/// array, with an object per report also holding its levels, whether it's safe, and the index
+/// and kind of each of its violations
+fn write_diffs(filepath: &str, output_filepath: &str, rules: &RuleSet) {
+    // Parse the file contents for the lists
+    let data = create_list(filepath.to_owned());
+
+    // Describe each report as a JSON object
+    let objects: Vec<String> = data
+        .iter()
+        .enumerate()
+        .map(|(index, report)| {
+            let check = check_report_with_diffs(report, rules);
+            let levels: Vec<String> = report.iter().map(|x| x.to_string()).collect();
+            let diffs: Vec<String> = check.diffs.iter().flatten().map(|x| x.to_string()).collect();
+            let violations: Vec<String> = check
+                .violations
+                .iter()
+                .map(|x| format!("{{\"index\":{},\"kind\":\"{}\"}}", x.index, x.kind.name()))
+                .collect();
+            format!(
+                "{{\"report\":{index},\"levels\":[{}],\"diffs\":[{}],\"safe\":{},\"violations\":[{}]}}",
+                levels.join(","),
+                diffs.join(","),
+                check.is_safe(),
+                violations.join(",")
+            )
+        })
+        .collect();
+
+    // Write the objects to the file
+    let json = format!("[\n{}\n]\n", objects.join(",\n"));
+    if let Err(error) = fs::write(output_filepath, json) {
+        common::fail(Failure::Io(format!(
+            "Could not write {output_filepath}: {error}"
+        )));
+    }
+}
 
-        // Add to the number of safe reports found
-        safe_report_count += 1;
+fn print_stats(filepath: &str, rules: &RuleSet) {
+    // Parse the file contents for the lists
+    let data = create_list(filepath.to_owned());
+
+    // Print the statistics for each report
+    for (index, report) in data.iter().enumerate() {
+        let check = check_report(report, rules);
+        let prefix = check.longest_safe_prefix();
+        let num_violations = check.violations.len();
+        let num_too_large = check.count(ViolationKind::StepTooLarge);
+        let num_no_change = check.count(ViolationKind::NoChange);
+        let num_direction = check.count(ViolationKind::DirectionChange);
+        println!(
+            "Report {index}: longest safe prefix {prefix}, violations {num_violations} \
+            ({num_too_large} too large, {num_no_change} no change, {num_direction} direction change)"
+        );
     }
+}
+
+fn main_part_one(filepath: String, rules: &RuleSet) {
+    // Parse the file contents for the lists
+    let data = create_list(filepath);
 
     // Print the number of safe reports
+    let safe_report_count = count_safe(&data, rules);
     println!("{safe_report_count}");
 }
 
-fn main_part_two(filepath: String) {
+fn main_part_two(filepath: String, rules: &RuleSet) {
     // Parse the file contents for the lists
     let data = create_list(filepath);
 
-    // Initialize the number of safe reports as 0
-    let mut safe_report_count: u64 = 0;
-
-    // Iterate through all reports in the data
-    'report_check: for report in data {
-        // Iterate through report, removing each entry until a safe report is detected
-        'removal_check: for index in 0..report.len() {
-            // Create a report with an single point removed
-            // Already safe reports will still pass regardless!
-            let mut modified_report = report.clone();
-            modified_report.remove(index);
-
-            // Check whether the modified list is sorted, try to remove
-            // a different entry if it's not.
-            if !is_list_sorted(&modified_report) {
-                continue 'removal_check;
-            }
-
-            // Check whether the modified report is safe
-            if is_safe_report(&modified_report) {
-                safe_report_count += 1;
-                continue 'report_check;
-            }
-        }
-    }
-
-    // Print the number of safe reports
+    // Print the number of reports that are safe with at most a single level removed
+    let safe_report_count = count_dampened_safe(&data, rules);
     println!("{safe_report_count}");
 }
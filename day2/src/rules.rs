@@ -0,0 +1,72 @@
+use crate::ViolationKind;
+
+/// A rule that every pair of adjacent levels in a safe report must follow
+pub trait SafetyRule {
+    /// Checks the pair of adjacent levels, given the direction of the report, returning the
+    /// kind of violation if the rule is broken
+    fn check(&self, previous_entry: u64, entry: u64, increasing: bool) -> Option<ViolationKind>;
+}
+
+/// Adjacent levels must not differ by more than the given amount
+pub struct MaxStep(pub u64);
+
+impl SafetyRule for MaxStep {
+    fn check(&self, previous_entry: u64, entry: u64, _increasing: bool) -> Option<ViolationKind> {
+        (entry.abs_diff(previous_entry) > self.0).then_some(ViolationKind::StepTooLarge)
+    }
+}
+
+/// Adjacent levels must change in the same direction as the report
+///
+/// Levels that don't change are left to `NoRepeats`.
+pub struct MonotonicDirection;
+
+impl SafetyRule for MonotonicDirection {
+    fn check(&self, previous_entry: u64, entry: u64, increasing: bool) -> Option<ViolationKind> {
+        let changed_direction = entry != previous_entry && (entry > previous_entry) != increasing;
+        changed_direction.then_some(ViolationKind::DirectionChange)
+    }
+}
+
+/// Adjacent levels must not be the same
+pub struct NoRepeats;
+
+impl SafetyRule for NoRepeats {
+    fn check(&self, previous_entry: u64, entry: u64, _increasing: bool) -> Option<ViolationKind> {
+        (entry == previous_entry).then_some(ViolationKind::NoChange)
+    }
+}
+
+/// The set of rules that reports are checked against, in the order they are checked
+pub struct RuleSet {
+    rules: Vec<Box<dyn SafetyRule>>,
+}
+
+impl RuleSet {
+    /// Creates the rule set, where levels may change by up to the given maximum step, and
+    /// optionally stay the same or change direction
+    pub fn new(max_step: u64, allow_plateaus: bool, allow_reversals: bool) -> Self {
+        let mut rules: Vec<Box<dyn SafetyRule>> = Vec::new();
+        if !allow_plateaus {
+            rules.push(Box::new(NoRepeats));
+        }
+        if !allow_reversals {
+            rules.push(Box::new(MonotonicDirection));
+        }
+        rules.push(Box::new(MaxStep(max_step)));
+        Self { rules }
+    }
+
+    /// Checks the pair of adjacent levels against each rule in turn, returning the kind of
+    /// violation of the first rule broken
+    pub fn check(
+        &self,
+        previous_entry: u64,
+        entry: u64,
+        increasing: bool,
+    ) -> Option<ViolationKind> {
+        self.rules
+            .iter()
+            .find_map(|rule| rule.check(previous_entry, entry, increasing))
+    }
+}
@@ -0,0 +1,68 @@
+use day2::{check_report, check_report_with_diffs, rules::RuleSet, ViolationKind};
+
+const EXAMPLE: &str = include_str!("../inputs/example.txt");
+
+/// Parses the reports from the input
+fn reports(input: &str) -> Vec<Vec<u64>> {
+    input
+        .lines()
+        .map(|line| {
+            line.split_whitespace()
+                .map(|x| x.parse().unwrap())
+                .collect()
+        })
+        .collect()
+}
+
+#[test]
+fn diffs_only_when_requested() {
+    let rules = RuleSet::new(3, false, false);
+    assert_eq!(check_report(&[1, 2, 4], &rules).diffs, None);
+    assert_eq!(
+        check_report_with_diffs(&[1, 2, 4], &rules).diffs,
+        Some(vec![1, 2])
+    );
+}
+
+#[test]
+fn diffs_of_example() {
+    let rules = RuleSet::new(3, false, false);
+    let diffs: Vec<Vec<i64>> = reports(EXAMPLE)
+        .iter()
+        .map(|x| check_report_with_diffs(x, &rules).diffs.unwrap())
+        .collect();
+    assert_eq!(
+        diffs,
+        vec![
+            vec![-1, -2, -2, -1],
+            vec![1, 5, 1, 1],
+            vec![-2, -1, -4, -1],
+            vec![2, -1, 2, 1],
+            vec![-2, -2, 0, -3],
+            vec![2, 3, 1, 2],
+        ]
+    );
+}
+
+#[test]
+fn diffs_keep_the_same_violations() {
+    let rules = RuleSet::new(3, false, false);
+    for report in reports(EXAMPLE) {
+        let with_diffs = check_report_with_diffs(&report, &rules);
+        let without_diffs = check_report(&report, &rules);
+        let kinds = |violations: &[day2::Violation]| -> Vec<(usize, ViolationKind)> {
+            violations.iter().map(|x| (x.index, x.kind)).collect()
+        };
+        assert_eq!(
+            kinds(&with_diffs.violations),
+            kinds(&without_diffs.violations)
+        );
+    }
+}
+
+#[test]
+fn diffs_of_short_reports_are_empty() {
+    let rules = RuleSet::new(3, false, false);
+    assert_eq!(check_report_with_diffs(&[], &rules).diffs, Some(vec![]));
+    assert_eq!(check_report_with_diffs(&[7], &rules).diffs, Some(vec![]));
+}
@@ -0,0 +1,81 @@
+use day2::{check_report, min_removals, repair, rules::RuleSet};
+
+const EXAMPLE: &str = include_str!("../inputs/example.txt");
+
+/// Parses the reports from the input
+fn reports(input: &str) -> Vec<Vec<u64>> {
+    input
+        .lines()
+        .map(|line| {
+            line.split_whitespace()
+                .map(|x| x.parse().unwrap())
+                .collect()
+        })
+        .collect()
+}
+
+/// Gets the report with the levels at the given indices removed
+fn without(report: &[u64], removed: &[usize]) -> Vec<u64> {
+    report
+        .iter()
+        .enumerate()
+        .filter(|(index, _)| !removed.contains(index))
+        .map(|(_, level)| *level)
+        .collect()
+}
+
+#[test]
+fn already_safe_needs_no_removals() {
+    let rules = RuleSet::new(3, false, false);
+    assert!(repair(&[7, 6, 4, 2, 1], &rules).is_empty());
+    assert!(repair(&[1, 3, 6, 7, 9], &rules).is_empty());
+    assert!(repair(&[], &rules).is_empty());
+    assert!(repair(&[4], &rules).is_empty());
+}
+
+#[test]
+fn one_removal() {
+    let rules = RuleSet::new(3, false, false);
+    for report in [[1, 3, 2, 4, 5], [8, 6, 4, 4, 1], [9, 1, 2, 3, 4]] {
+        let removed = repair(&report, &rules);
+        assert_eq!(removed.len(), 1, "{report:?}");
+        assert!(check_report(&without(&report, &removed), &rules).is_safe());
+    }
+    assert_eq!(repair(&[9, 1, 2, 3], &rules), vec![0]);
+}
+
+#[test]
+fn several_removals() {
+    let rules = RuleSet::new(3, false, false);
+    assert_eq!(repair(&[1, 2, 7, 8, 9], &rules).len(), 2);
+    assert_eq!(repair(&[9, 7, 6, 2, 1], &rules).len(), 2);
+    assert_eq!(repair(&[1, 9, 2, 8, 3, 7], &rules), vec![1, 3, 5]);
+}
+
+#[test]
+fn unrepairable_report_keeps_one_level() {
+    // No two levels can be next to each other, so only one of them can be kept
+    let rules = RuleSet::new(3, false, false);
+    assert_eq!(repair(&[5, 5, 5, 5], &rules), vec![1, 2, 3]);
+    assert_eq!(repair(&[1, 10, 20, 30], &rules).len(), 3);
+}
+
+#[test]
+fn repaired_reports_are_safe() {
+    let rules = RuleSet::new(3, false, false);
+    for report in reports(EXAMPLE) {
+        let removed = repair(&report, &rules);
+        assert!(check_report(&without(&report, &removed), &rules).is_safe());
+        assert_eq!(min_removals(&report, &rules), removed.len());
+    }
+}
+
+#[test]
+fn example_dampener_counts() {
+    let rules = RuleSet::new(3, false, false);
+    let removals: Vec<usize> = reports(EXAMPLE)
+        .iter()
+        .map(|x| min_removals(x, &rules))
+        .collect();
+    assert_eq!(removals, vec![0, 2, 2, 1, 1, 0]);
+}
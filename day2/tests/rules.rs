@@ -0,0 +1,103 @@
+use day2::{check_report, rules::RuleSet, ViolationKind};
+
+const EXAMPLE: &str = include_str!("../inputs/example.txt");
+
+/// Checks the report the way it was checked before the rules were configurable, returning the
+/// index and kind of each violation
+fn fused_check(report: &[u64]) -> Vec<(usize, ViolationKind)> {
+    let increasing = report.len() > 1 && report[1] > report[0];
+    let mut violations = Vec::new();
+    for (index, pair) in report.windows(2).enumerate() {
+        let (previous_entry, entry) = (pair[0], pair[1]);
+        let kind = if entry == previous_entry {
+            Some(ViolationKind::NoChange)
+        } else if (entry > previous_entry) != increasing {
+            Some(ViolationKind::DirectionChange)
+        } else if entry.abs_diff(previous_entry) > 3 {
+            Some(ViolationKind::StepTooLarge)
+        } else {
+            None
+        };
+        if let Some(kind) = kind {
+            violations.push((index, kind));
+        }
+    }
+    violations
+}
+
+/// Checks the report against the default rules, returning the index and kind of each violation
+fn default_check(report: &[u64]) -> Vec<(usize, ViolationKind)> {
+    let rules = RuleSet::new(3, false, false);
+    check_report(report, &rules)
+        .violations
+        .iter()
+        .map(|x| (x.index, x.kind))
+        .collect()
+}
+
+/// Parses the reports from the input
+fn reports(input: &str) -> Vec<Vec<u64>> {
+    input
+        .lines()
+        .map(|line| {
+            line.split_whitespace()
+                .map(|x| x.parse().unwrap())
+                .collect()
+        })
+        .collect()
+}
+
+#[test]
+fn default_rules_match_fused_check_on_example() {
+    for report in reports(EXAMPLE) {
+        assert_eq!(default_check(&report), fused_check(&report), "{report:?}");
+    }
+}
+
+#[test]
+fn default_rules_match_fused_check_on_edge_cases() {
+    let edge_cases: [&[u64]; 8] = [
+        &[],
+        &[5],
+        &[5, 5],
+        &[5, 5, 6, 7],
+        &[5, 5, 4, 3],
+        &[1, 5, 4, 4, 9],
+        &[9, 8, 12, 12, 1],
+        &[3, 3, 3, 3],
+    ];
+    for report in edge_cases {
+        assert_eq!(default_check(report), fused_check(report), "{report:?}");
+    }
+}
+
+#[test]
+fn default_rules_count_example_safe_reports() {
+    let rules = RuleSet::new(3, false, false);
+    let safe = reports(EXAMPLE)
+        .iter()
+        .filter(|x| check_report(x, &rules).is_safe())
+        .count();
+    assert_eq!(safe, 2);
+}
+
+#[test]
+fn first_pair_sets_direction() {
+    // A report starting with a plateau is treated as decreasing, so the rise after it is a
+    // change of direction
+    assert_eq!(
+        default_check(&[5, 5, 6]),
+        vec![
+            (0, ViolationKind::NoChange),
+            (1, ViolationKind::DirectionChange)
+        ]
+    );
+}
+
+#[test]
+fn relaxed_rules() {
+    assert!(check_report(&[1, 2, 2], &RuleSet::new(3, true, false)).is_safe());
+    assert!(check_report(&[1, 3, 2], &RuleSet::new(3, false, true)).is_safe());
+    assert!(check_report(&[1, 6], &RuleSet::new(5, false, false)).is_safe());
+    assert!(!check_report(&[1, 6], &RuleSet::new(4, false, false)).is_safe());
+}
@@ -0,0 +1,407 @@
+use std::ops::Range;
+
+use parse::{conditional_regions, toggled_regions};
+
+/// A single instruction found in the corrupted memory
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Instruction {
+    /// Multiply the two numbers together
+    Mul(u64, u64),
+    /// Enable future multiplications
+    Do,
+    /// Disable future multiplications
+    Dont,
+}
+
+/// Policy used by the interpreter to decide which instructions get executed
+///
+/// New conditional semantics can be added by implementing this trait rather
+/// than modifying the instruction stream itself.
+pub trait Policy {
+    /// Observes the next instruction in the stream, returning whether it should be executed
+    fn observe(&mut self, instruction: &Instruction) -> bool;
+}
+
+/// Policy where every multiplication is executed, ignoring all conditionals
+pub struct Unconditional;
+
+impl Policy for Unconditional {
+    fn observe(&mut self, _instruction: &Instruction) -> bool {
+        true
+    }
+}
+
+/// Policy for where the tokenizer resumes scanning after finding an instruction
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Overlap {
+    /// Resume just after the start of the instruction, so instructions may be found within
+    /// text already consumed by a previous one
+    Allow,
+    /// Resume after the end of the instruction, so consumed text is never scanned again
+    Deny,
+}
+
+/// A contiguous region of the corrupted memory in which multiplications are either all
+/// enabled or all disabled
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Span {
+    /// The byte range of the region
+    pub range: Range<usize>,
+    /// Whether multiplications in the region are enabled
+    pub enabled: bool,
+}
+
+/// Parses the corrupted memory into the stream of instructions it contains, scanning it one
+/// position at a time and resuming after each instruction according to the overlap policy
+///
+/// None of the instructions can begin inside the text of another, so both policies currently
+/// produce the same stream; denying overlap simply avoids rescanning consumed text.
+pub fn parse_instructions(contents: &str, overlap: Overlap) -> Vec<Instruction> {
+    parse_located_instructions(contents, overlap)
+        .into_iter()
+        .map(|(_, instruction)| instruction)
+        .collect()
+}
+
+/// Parses the corrupted memory into the stream of instructions it contains, along with the
+/// byte range of the text of each instruction
+pub fn parse_located_instructions(
+    contents: &str,
+    overlap: Overlap,
+) -> Vec<(Range<usize>, Instruction)> {
+    let mut tokenizer = Tokenizer::new(overlap);
+    tokenizer.push(contents);
+    tokenizer.instructions
+}
+
+/// Tokenizer that parses the corrupted memory as it arrives in pieces, which can be saved
+/// between pieces and resumed later
+///
+/// Text at the end of the memory that could be the start of an instruction is held back until
+/// more text arrives, so the instructions found are the same no matter how the memory is split.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Tokenizer {
+    /// Where the tokenizer resumes scanning after finding an instruction
+    overlap: Overlap,
+    /// The byte offset within the memory of the start of the pending text
+    offset: usize,
+    /// The text at the end of the memory that hasn't been scanned yet
+    pending: String,
+    /// The instructions found so far, along with the byte range of the text of each
+    instructions: Vec<(Range<usize>, Instruction)>,
+}
+
+impl Tokenizer {
+    /// Creates a tokenizer that hasn't seen any of the memory yet
+    pub fn new(overlap: Overlap) -> Self {
+        Self {
+            overlap,
+            offset: 0,
+            pending: String::new(),
+            instructions: Vec::new(),
+        }
+    }
+
+    /// Gets the policy for where the tokenizer resumes scanning after finding an instruction
+    pub fn overlap(&self) -> Overlap {
+        self.overlap
+    }
+
+    /// Gets the instructions found so far, along with the byte range of the text of each
+    pub fn instructions(&self) -> &[(Range<usize>, Instruction)] {
+        &self.instructions
+    }
+
+    /// Gets the length of the memory seen so far, including the text still pending
+    pub fn memory_length(&self) -> usize {
+        self.offset + self.pending.len()
+    }
+
+    /// Adds the given text to the end of the memory, scanning it position by position for
+    /// instructions and resuming after each according to the overlap policy
+    pub fn push(&mut self, text: &str) {
+        // Scan the pending text along with the new text
+        self.pending.push_str(text);
+        let bytes = self.pending.as_bytes();
+        let mut cursor = 0;
+        while cursor < bytes.len() {
+            // If there's no instruction at the cursor, move on to the next position unless more
+            // text could still complete one there
+            let Some((instruction, length)) = match_instruction(&bytes[cursor..]) else {
+                if is_partial_instruction(&bytes[cursor..]) {
+                    break;
+                }
+                cursor += 1;
+                continue;
+            };
+
+            // Add the instruction and move the cursor according to the overlap policy
+            let start = self.offset + cursor;
+            self.instructions.push((start..start + length, instruction));
+            cursor += match self.overlap {
+                Overlap::Allow => 1,
+                Overlap::Deny => length,
+            };
+        }
+
+        // Keep only the text that hasn't been scanned yet
+        self.pending.drain(..cursor);
+        self.offset += cursor;
+    }
+
+    /// Gets the state of the tokenizer as text, with a line each for the overlap policy, the
+    /// offset and text still pending, followed by a line for each instruction found so far
+    pub fn to_text(&self) -> String {
+        let overlap = match self.overlap {
+            Overlap::Allow => "allow",
+            Overlap::Deny => "deny",
+        };
+        let mut text = format!(
+            "overlap {overlap}\noffset {}\npending {}\n",
+            self.offset, self.pending
+        );
+        for (range, instruction) in &self.instructions {
+            let instruction = match instruction {
+                Instruction::Mul(x, y) => format!("mul {x} {y}"),
+                Instruction::Do => String::from("do"),
+                Instruction::Dont => String::from("dont"),
+            };
+            text.push_str(&format!("{} {} {instruction}\n", range.start, range.end));
+        }
+        text
+    }
+
+    /// Parses the state of a tokenizer from text created by `to_text()`
+    pub fn from_text(text: &str) -> Result<Self, String> {
+        // Parse the header lines
+        let mut lines = text.lines();
+        let mut header = |name: &str| {
+            lines
+                .next()
+                .and_then(|x| x.strip_prefix(name))
+                .and_then(|x| x.strip_prefix(' '))
+                .ok_or_else(|| format!("Missing {name} line"))
+        };
+        let overlap = match header("overlap")? {
+            "allow" => Overlap::Allow,
+            "deny" => Overlap::Deny,
+            other => return Err(format!("Unknown overlap policy {other:?}")),
+        };
+        let offset = header("offset")?
+            .parse::<usize>()
+            .map_err(|_| String::from("Could not parse offset"))?;
+        let pending = String::from(header("pending")?);
+
+        // Parse the instructions on the remaining lines
+        let mut instructions = Vec::new();
+        for line in lines {
+            let fields: Vec<&str> = line.split(' ').collect();
+            let parse = |field: &str| {
+                field
+                    .parse::<u64>()
+                    .map_err(|_| format!("Could not parse instruction {line:?}"))
+            };
+            let (range, instruction) = match fields[..] {
+                [start, end, "mul", x, y] => (start..end, Instruction::Mul(parse(x)?, parse(y)?)),
+                [start, end, "do"] => (start..end, Instruction::Do),
+                [start, end, "dont"] => (start..end, Instruction::Dont),
+                _ => return Err(format!("Could not parse instruction {line:?}")),
+            };
+            let range = parse(range.start)? as usize..parse(range.end)? as usize;
+            instructions.push((range, instruction));
+        }
+
+        // Return the tokenizer
+        Ok(Self {
+            overlap,
+            offset,
+            pending,
+            instructions,
+        })
+    }
+}
+
+/// Checks whether the text is the start of an instruction that more text could complete
+fn is_partial_instruction(text: &[u8]) -> bool {
+    // Check for the start of the conditional instructions
+    if b"do()".starts_with(text) || b"don't()".starts_with(text) {
+        return true;
+    }
+
+    // Check for the start of a multiplication, which can end partway through either number
+    if text.len() < 4 {
+        return b"mul(".starts_with(text);
+    }
+    let Some(rest) = text.strip_prefix(b"mul(") else {
+        return false;
+    };
+    let length_one = rest.iter().take_while(|x| x.is_ascii_digit()).count();
+    if length_one > 3 {
+        return false;
+    }
+    let Some(rest) = rest[length_one..].strip_prefix(b",") else {
+        return length_one == rest.len();
+    };
+    let length_two = rest.iter().take_while(|x| x.is_ascii_digit()).count();
+    length_one > 0 && length_two <= 3 && length_two == rest.len()
+}
+
+/// Matches an instruction at the start of the text, returning it along with the length
+/// of text it consumed
+fn match_instruction(text: &[u8]) -> Option<(Instruction, usize)> {
+    // Check for the conditional instructions
+    if text.starts_with(b"do()") {
+        return Some((Instruction::Do, 4));
+    }
+    if text.starts_with(b"don't()") {
+        return Some((Instruction::Dont, 7));
+    }
+
+    // Check for a multiplication of two numbers of up to three digits each
+    let rest = text.strip_prefix(b"mul(")?;
+    let (factor_one, length_one) = match_number(rest)?;
+    let rest = rest[length_one..].strip_prefix(b",")?;
+    let (factor_two, length_two) = match_number(rest)?;
+    rest[length_two..].strip_prefix(b")")?;
+    let length = 4 + length_one + 1 + length_two + 1;
+    Some((Instruction::Mul(factor_one, factor_two), length))
+}
+
+/// Matches a number of one to three digits at the start of the text, returning it along
+/// with the number of digits
+fn match_number(text: &[u8]) -> Option<(u64, usize)> {
+    let length = text
+        .iter()
+        .take(3)
+        .take_while(|x| x.is_ascii_digit())
+        .count();
+    if length == 0 {
+        return None;
+    }
+    let number = std::str::from_utf8(&text[..length])
+        .ok()?
+        .parse::<u64>()
+        .ok()?;
+    Some((number, length))
+}
+
+/// Gets the byte ranges of the regions of the memory of the given length that are enabled by
+/// the `do()` and `don't()` instructions among the given ones
+///
+/// These are the same regions as found in the text of the memory by
+/// `parse::conditional_regions()`, so this can be used when only the instructions are known.
+fn enabled_regions(
+    instructions: &[(Range<usize>, Instruction)],
+    length: usize,
+) -> Vec<Range<usize>> {
+    let toggles = instructions
+        .iter()
+        .filter_map(|(range, instruction)| match instruction {
+            Instruction::Do => Some((range.clone(), true)),
+            Instruction::Dont => Some((range.clone(), false)),
+            Instruction::Mul(..) => None,
+        });
+    toggled_regions(length, toggles).collect()
+}
+
+/// Splits the corrupted memory into regions where multiplications are enabled or disabled
+///
+/// Memory starts enabled, and each `don't()` that changes the state disables it from the start
+/// of its own text until the end of the next `do()`.  The regions cover the whole memory
+/// without gaps.
+pub fn enabled_spans(contents: &str, overlap: Overlap) -> Vec<Span> {
+    // Create a list for storing the regions
+    let mut spans = Vec::new();
+    let mut start = 0;
+
+    // Add each enabled region, preceded by the disabled region before it if there is one
+    let instructions = parse_located_instructions(contents, overlap);
+    for range in enabled_regions(&instructions, contents.len()) {
+        if start < range.start {
+            spans.push(Span {
+                range: start..range.start,
+                enabled: false,
+            });
+        }
+        start = range.end;
+        spans.push(Span {
+            range,
+            enabled: true,
+        });
+    }
+
+    // Add the disabled region at the end of the memory if there is one, or an empty enabled
+    // region if the memory is empty
+    if start < contents.len() {
+        spans.push(Span {
+            range: start..contents.len(),
+            enabled: false,
+        });
+    }
+    if spans.is_empty() {
+        spans.push(Span {
+            range: 0..0,
+            enabled: true,
+        });
+    }
+
+    // Return the regions
+    spans
+}
+
+/// Gets the instructions whose text lies within the regions of the memory of the given
+/// length that are enabled by the `do()` and `don't()` instructions among them
+pub fn enabled_instructions(
+    instructions: &[(Range<usize>, Instruction)],
+    length: usize,
+) -> Vec<Instruction> {
+    let regions = enabled_regions(instructions, length);
+    instructions
+        .iter()
+        .filter(|(range, _)| {
+            regions
+                .iter()
+                .any(|x| x.start <= range.start && range.end <= x.end)
+        })
+        .map(|(_, instruction)| *instruction)
+        .collect()
+}
+
+/// Runs the instructions using the given policy, returning the multiplication total
+pub fn interpret(instructions: &[Instruction], policy: &mut impl Policy) -> u64 {
+    // Initialize multiplication total
+    let mut total = 0;
+
+    // Iterate through the instructions, executing them if the policy allows it
+    for instruction in instructions {
+        if !policy.observe(instruction) {
+            continue;
+        }
+        if let Instruction::Mul(factor_one, factor_two) = instruction {
+            total += factor_one * factor_two;
+        }
+    }
+
+    // Return the total
+    total
+}
+
+/// Solves part one for the puzzle input, getting the total of every multiplication in the
+/// memory
+pub fn part1(input: &str) -> Result<u64, String> {
+    let instructions = parse_instructions(input, Overlap::Deny);
+    Ok(interpret(&instructions, &mut Unconditional))
+}
+
+/// Solves part two for the puzzle input, getting the total of the multiplications in the
+/// enabled regions of the memory
+pub fn part2(input: &str) -> Result<u64, String> {
+    Ok(conditional_regions(input, "do()", "don't()")
+        .map(|region| {
+            interpret(
+                &parse_instructions(&input[region], Overlap::Deny),
+                &mut Unconditional,
+            )
+        })
+        .sum())
+}
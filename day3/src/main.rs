@@ -1,100 +1,209 @@
-use std::fs;
+use clap::{Parser, ValueEnum};
+use std::{fs, io::ErrorKind};
 
-use clap::Parser;
-use regex::Regex;
+use common::{read_input, read_input_bytes, About, Failure, Format};
+use day3::{
+    enabled_instructions, enabled_spans, interpret, parse_instructions, Instruction, Overlap,
+    Tokenizer, Unconditional,
+};
+use parse::conditional_regions;
 
 #[derive(Parser)]
 struct CliArgs {
     part: u64,
     filepath: String,
+    /// Whether instructions may be found within text consumed by a previous instruction
+    #[arg(long, value_enum, default_value_t = OverlapPolicy::Deny)]
+    overlap: OverlapPolicy,
+    /// Print the byte ranges of the enabled and disabled regions of the memory before the answer
+    #[arg(long)]
+    spans: bool,
+    /// Read the memory as arbitrary bytes, which may not be valid UTF-8, such as a raw memory
+    /// dump
+    #[arg(long)]
+    binary: bool,
+    /// Treat the input file as text appended to the memory parsed so far, whose parsed state
+    /// is loaded from (if it exists) and saved back to the given file
+    #[arg(long, value_name = "FILE", conflicts_with = "spans")]
+    append_from: Option<String>,
+    /// Parse the input and print statistics about its structure instead of solving it
+    #[arg(long)]
+    parse_only: bool,
+    /// The format in which errors are reported
+    #[arg(long, value_enum, default_value_t = Format::Text)]
+    format: Format,
+}
+
+/// Description of the puzzle solved by this binary
+const ABOUT: About = About {
+    day: 3,
+    title: "Mull It Over",
+    input: "Corrupted memory containing mul(X,Y), do() and don't() instructions",
+    parts: &[1, 2],
+    features: &[],
+};
+
+/// Policies for where the tokenizer resumes scanning after finding an instruction
+#[derive(Clone, Copy, ValueEnum)]
+enum OverlapPolicy {
+    /// Resume just after the start of the instruction
+    Allow,
+    /// Resume after the end of the instruction
+    Deny,
+}
+
+impl From<OverlapPolicy> for Overlap {
+    fn from(policy: OverlapPolicy) -> Self {
+        match policy {
+            OverlapPolicy::Allow => Overlap::Allow,
+            OverlapPolicy::Deny => Overlap::Deny,
+        }
+    }
 }
 
 fn main() {
+    // Describe the puzzle instead if requested
+    common::handle_about::<CliArgs>(&ABOUT);
+
     // Parse CLI arguments
     let cli = CliArgs::parse();
 
+    // Report errors in the requested format
+    common::init(cli.format);
+
+    // Only parse the input if requested
+    if cli.parse_only {
+        report_structure(&cli);
+    }
+
     // Run the code for the desired challenge part
     match cli.part {
-        1 => main_part_one(cli.filepath),
-        2 => main_part_two(cli.filepath),
+        1 => main_part_one(&cli),
+        2 => main_part_two(&cli),
         _ => panic!("Invalid selection part selection!"),
     }
 }
 
-fn find_multiplications(contents: String) -> u64 {
-    // Initialize multiplication total
-    let mut total: u64 = 0;
-
-    // Create regex to parse the input string
-    let re = Regex::new(r"mul\((\d{1,3}),(\d{1,3})\)").unwrap();
-
-    // Iterate though the regex matches and multiple
-    for (_, [factor_one_str, factor_two_str]) in re.captures_iter(&contents).map(|x| x.extract()) {
-        let factor_one = factor_one_str.parse::<u64>().unwrap();
-        let factor_two = factor_two_str.parse::<u64>().unwrap();
-        total += factor_one * factor_two;
+/// Reads the corrupted memory from the input file
+///
+/// When reading it as arbitrary bytes, every byte that isn't ASCII is replaced by a single
+/// `?`, which can't be part of an instruction, so the byte offsets still match the file.
+fn read_memory(cli: &CliArgs) -> String {
+    if !cli.binary {
+        return read_input(&cli.filepath);
     }
+    read_input_bytes(&cli.filepath)
+        .into_iter()
+        .map(|x| if x.is_ascii() { x as char } else { '?' })
+        .collect()
+}
 
-    // Return the total
-    total
+/// Parses the instructions in the memory, then prints the length of the memory and the number
+/// of each kind of instruction and exits
+fn report_structure(cli: &CliArgs) -> ! {
+    let contents = read_memory(cli);
+    let instructions = parse_instructions(&contents, cli.overlap.into());
+    let count = |kind: fn(&Instruction) -> bool| instructions.iter().filter(|x| kind(x)).count();
+    common::report_parse(&[
+        ("bytes", contents.len()),
+        ("instructions", instructions.len()),
+        ("mul", count(|x| matches!(x, Instruction::Mul(_, _)))),
+        ("do", count(|x| matches!(x, Instruction::Do))),
+        ("don't", count(|x| matches!(x, Instruction::Dont))),
+    ]);
 }
 
-fn create_instructioned_string(contents: String) -> String {
-    // Initialize flagss for modifying the input string
-    let mut delete_mode = false;
-    let mut start_index: usize = 0;
-    let mut deletions = Vec::new();
-
-    // Create the regex for parsing the input string
-    let re = Regex::new(r"(do(n't)?\(\))").unwrap();
-
-    // Iterate through the regex matches
-    for keyword_match in re.captures_iter(&contents) {
-        // Matches the keyword found
-        match keyword_match.get(1).unwrap().as_str() {
-            "do()" => {
-                // If in delete mode, turn off delete mode, get the final bounds of
-                // deletion, and save it to the list
-                if delete_mode {
-                    delete_mode = false;
-                    let end_index = keyword_match.get(1).unwrap().start();
-                    deletions.push((start_index, end_index));
-                }
-            }
-            "don't()" => {
-                // If not in delete mode, turn off delete mode and save the start index
-                // of the deletion for later use
-                if !delete_mode {
-                    delete_mode = true;
-                    start_index = keyword_match.get(1).unwrap().start();
-                }
-            }
-            _e => panic!("Found {_e} - something unexpected!"),
-        }
+/// Parses the input file as text appended to the memory whose parsed state is saved in the
+/// given file, saving the updated state back to it, and gets the updated state
+fn append_memory(cli: &CliArgs, state_filepath: &str) -> Tokenizer {
+    // Load the parsed state, starting afresh only if there is no file for it yet
+    let mut tokenizer = match fs::read_to_string(state_filepath) {
+        Ok(text) => Tokenizer::from_text(&text).unwrap_or_else(|error| {
+            common::fail(Failure::Parse(format!(
+                "Could not parse {state_filepath}: {error}"
+            )))
+        }),
+        Err(error) if error.kind() == ErrorKind::NotFound => Tokenizer::new(cli.overlap.into()),
+        Err(error) => common::fail(Failure::Io(format!(
+            "Could not read {state_filepath}: {error}"
+        ))),
+    };
+    if tokenizer.overlap() != Overlap::from(cli.overlap) {
+        common::fail(Failure::Parse(format!(
+            "{state_filepath} was parsed with a different overlap policy"
+        )));
     }
 
-    // The list needs to be operated in reverse to preserve index values
-    deletions.reverse();
-
-    // Create a mutable string based on the input, and replace the saved index slices with nothing ("")
-    let mut modified_contents = contents;
-    for (start_index, end_index) in deletions {
-        modified_contents.replace_range(start_index..end_index, "");
+    // Parse the appended text and save the updated state
+    tokenizer.push(&read_memory(cli));
+    if let Err(error) = fs::write(state_filepath, tokenizer.to_text()) {
+        common::fail(Failure::Io(format!(
+            "Could not write {state_filepath}: {error}"
+        )));
     }
 
-    // Return the modified multiplication string
-    modified_contents
+    // Return the updated state
+    tokenizer
 }
 
-fn main_part_one(filepath: String) {
-    let contents = fs::read_to_string(filepath).expect("Invalid filepath");
-    let total = find_multiplications(contents);
+/// Prints the enabled and disabled regions of the memory as CSV, followed by the fraction
+/// of the memory that is enabled
+fn print_spans(contents: &str, overlap: Overlap) {
+    let spans = enabled_spans(contents, overlap);
+    println!("start,end,state");
+    for span in &spans {
+        let state = if span.enabled { "enabled" } else { "disabled" };
+        println!("{},{},{state}", span.range.start, span.range.end);
+    }
+    let enabled_length: usize = spans
+        .iter()
+        .filter(|x| x.enabled)
+        .map(|x| x.range.len())
+        .sum();
+    let fraction = enabled_length as f64 / contents.len().max(1) as f64;
+    println!("The enabled fraction of the memory is {fraction:.4}");
+}
+
+fn main_part_one(cli: &CliArgs) {
+    if let Some(state_filepath) = &cli.append_from {
+        let tokenizer = append_memory(cli, state_filepath);
+        let instructions: Vec<Instruction> =
+            tokenizer.instructions().iter().map(|(_, x)| *x).collect();
+        let total = interpret(&instructions, &mut Unconditional);
+        println!("The multiplication total is {total}");
+        return;
+    }
+    let overlap = Overlap::from(cli.overlap);
+    let contents = read_memory(cli);
+    if cli.spans {
+        print_spans(&contents, overlap);
+    }
+    let instructions = parse_instructions(&contents, overlap);
+    let total = interpret(&instructions, &mut Unconditional);
     println!("The multiplication total is {total}");
 }
 
-fn main_part_two(filepath: String) {
-    let contents = fs::read_to_string(filepath).expect("Invalid filepath");
-    let modified_contents = create_instructioned_string(contents);
-    let total = find_multiplications(modified_contents);
+fn main_part_two(cli: &CliArgs) {
+    // The appended memory is only available as instructions, so the enabled regions are found
+    // from where the conditional instructions are rather than from the text
+    if let Some(state_filepath) = &cli.append_from {
+        let tokenizer = append_memory(cli, state_filepath);
+        let instructions =
+            enabled_instructions(tokenizer.instructions(), tokenizer.memory_length());
+        let total = interpret(&instructions, &mut Unconditional);
+        println!("The conditional multiplication total is {total}");
+        return;
+    }
+    let overlap = Overlap::from(cli.overlap);
+    let contents = read_memory(cli);
+    if cli.spans {
+        print_spans(&contents, overlap);
+    }
+    let total: u64 = conditional_regions(&contents, "do()", "don't()")
+        .map(|region| {
+            let instructions = parse_instructions(&contents[region], overlap);
+            interpret(&instructions, &mut Unconditional)
+        })
+        .sum();
     println!("The conditional multiplication total is {total}");
 }
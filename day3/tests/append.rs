@@ -0,0 +1,92 @@
+use day3::{
+    enabled_instructions, interpret, parse_instructions, parse_located_instructions, Instruction,
+    Overlap, Tokenizer, Unconditional,
+};
+use parse::conditional_regions;
+
+const EXAMPLE_TWO: &str =
+    "xmul(2,4)&mul[3,7]!^don't()_mul(5,5)+mul(32,64](mul(11,8)undo()?mul(8,5))";
+
+#[test]
+fn split_anywhere_matches_whole() {
+    for overlap in [Overlap::Allow, Overlap::Deny] {
+        let whole = parse_located_instructions(EXAMPLE_TWO, overlap);
+        for split in 0..=EXAMPLE_TWO.len() {
+            let mut tokenizer = Tokenizer::new(overlap);
+            tokenizer.push(&EXAMPLE_TWO[..split]);
+            tokenizer.push(&EXAMPLE_TWO[split..]);
+            assert_eq!(tokenizer.instructions(), whole, "split at {split}");
+        }
+    }
+}
+
+#[test]
+fn byte_by_byte_matches_whole() {
+    let whole = parse_located_instructions(EXAMPLE_TWO, Overlap::Deny);
+    let mut tokenizer = Tokenizer::new(Overlap::Deny);
+    for index in 0..EXAMPLE_TWO.len() {
+        tokenizer.push(&EXAMPLE_TWO[index..index + 1]);
+    }
+    assert_eq!(tokenizer.instructions(), whole);
+}
+
+#[test]
+fn number_split_across_pushes() {
+    let mut tokenizer = Tokenizer::new(Overlap::Deny);
+    tokenizer.push("mul(12");
+    assert!(tokenizer.instructions().is_empty());
+    tokenizer.push("3,4)");
+    assert_eq!(
+        tokenizer.instructions(),
+        parse_located_instructions("mul(123,4)", Overlap::Deny)
+    );
+}
+
+#[test]
+fn state_round_trips_through_text() {
+    for split in 0..=EXAMPLE_TWO.len() {
+        let mut tokenizer = Tokenizer::new(Overlap::Deny);
+        tokenizer.push(&EXAMPLE_TWO[..split]);
+        let mut resumed = Tokenizer::from_text(&tokenizer.to_text()).unwrap();
+        assert_eq!(resumed, tokenizer, "split at {split}");
+        resumed.push(&EXAMPLE_TWO[split..]);
+        tokenizer.push(&EXAMPLE_TWO[split..]);
+        assert_eq!(resumed, tokenizer, "split at {split}");
+    }
+}
+
+#[test]
+fn malformed_state() {
+    assert!(Tokenizer::from_text("").is_err());
+    assert!(Tokenizer::from_text("overlap sideways\noffset 0\npending \n").is_err());
+    assert!(Tokenizer::from_text("overlap deny\noffset 0\npending \n0 8 mul 2\n").is_err());
+}
+
+#[test]
+fn enabled_instructions_match_regions() {
+    // Pushing the memory in pieces finds the same enabled instructions as splitting the text
+    let mut tokenizer = Tokenizer::new(Overlap::Deny);
+    for piece in EXAMPLE_TWO.as_bytes().chunks(5) {
+        tokenizer.push(std::str::from_utf8(piece).unwrap());
+    }
+    let instructions = enabled_instructions(tokenizer.instructions(), tokenizer.memory_length());
+    let expected: Vec<Instruction> = conditional_regions(EXAMPLE_TWO, "do()", "don't()")
+        .flat_map(|region| parse_instructions(&EXAMPLE_TWO[region], Overlap::Deny))
+        .collect();
+    assert_eq!(instructions, expected);
+    assert_eq!(interpret(&instructions, &mut Unconditional), 48);
+}
+
+#[test]
+fn enabled_instructions_with_pending_text() {
+    // The conditional instructions themselves lie outside the enabled regions, and the text
+    // still pending counts towards the length of the memory
+    let mut tokenizer = Tokenizer::new(Overlap::Deny);
+    tokenizer.push("mul(2,3)don't()mul(4,5)do()mul(1,1)don't()mul(");
+    let instructions = enabled_instructions(tokenizer.instructions(), tokenizer.memory_length());
+    assert_eq!(
+        instructions,
+        vec![Instruction::Mul(2, 3), Instruction::Mul(1, 1)]
+    );
+    assert_eq!(tokenizer.memory_length(), 46);
+}
@@ -0,0 +1,52 @@
+use day3::{
+    enabled_instructions, interpret, parse_instructions, parse_located_instructions, Instruction,
+    Overlap, Unconditional,
+};
+
+const EXAMPLE_ONE: &str = "xmul(2,4)%&mul[3,7]!@^do_not_mul(5,5)+mul(32,64]then(mul(11,8)mul(8,5))";
+const EXAMPLE_TWO: &str =
+    "xmul(2,4)&mul[3,7]!^don't()_mul(5,5)+mul(32,64](mul(11,8)undo()?mul(8,5))";
+
+#[test]
+fn examples() {
+    for overlap in [Overlap::Allow, Overlap::Deny] {
+        let instructions = parse_instructions(EXAMPLE_ONE, overlap);
+        assert_eq!(interpret(&instructions, &mut Unconditional), 161);
+        let located = parse_located_instructions(EXAMPLE_TWO, overlap);
+        let instructions = enabled_instructions(&located, EXAMPLE_TWO.len());
+        assert_eq!(interpret(&instructions, &mut Unconditional), 48);
+    }
+}
+
+/// Instructions can't begin inside the text of another instruction, so even inputs crafted
+/// around nested and adjacent instructions produce the same stream under both policies
+#[test]
+fn crafted_inputs_match_between_policies() {
+    let crafted = [
+        "mul(mul(2,3))",
+        "mul(2,mul(3,4))",
+        "mmul(2,3)mul(4,5)",
+        "don't()do()don't(do())",
+        "mul(1234,5)mul(123,45)",
+        "mul(2,3mul(4,5)",
+    ];
+    for text in crafted {
+        assert_eq!(
+            parse_instructions(text, Overlap::Allow),
+            parse_instructions(text, Overlap::Deny),
+            "{text}"
+        );
+    }
+}
+
+#[test]
+fn number_limits() {
+    let instructions = parse_instructions("mul(1234,5)mul(123,45)mul(,1)", Overlap::Deny);
+    assert_eq!(instructions, vec![Instruction::Mul(123, 45)]);
+}
+
+#[test]
+fn nested_instructions() {
+    let instructions = parse_instructions("mul(2,mul(3,4))don't(do())", Overlap::Deny);
+    assert_eq!(instructions, vec![Instruction::Mul(3, 4), Instruction::Do]);
+}
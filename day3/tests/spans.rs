@@ -0,0 +1,72 @@
+use day3::{enabled_spans, Overlap, Span};
+use parse::conditional_regions;
+
+const EXAMPLE_TWO: &str =
+    "xmul(2,4)&mul[3,7]!^don't()_mul(5,5)+mul(32,64](mul(11,8)undo()?mul(8,5))";
+
+#[test]
+fn example() {
+    let spans = enabled_spans(EXAMPLE_TWO, Overlap::Deny);
+    assert_eq!(
+        spans,
+        vec![
+            Span {
+                range: 0..20,
+                enabled: true
+            },
+            Span {
+                range: 20..63,
+                enabled: false
+            },
+            Span {
+                range: 63..EXAMPLE_TWO.len(),
+                enabled: true
+            },
+        ]
+    );
+}
+
+#[test]
+fn repeated_conditionals_do_not_split() {
+    let spans = enabled_spans("do()xdon't()ydon't()zdo()", Overlap::Deny);
+    let states: Vec<(usize, usize, bool)> = spans
+        .iter()
+        .map(|x| (x.range.start, x.range.end, x.enabled))
+        .collect();
+    assert_eq!(states, vec![(0, 5, true), (5, 25, false)]);
+}
+
+#[test]
+fn spans_match_conditional_regions() {
+    let enabled: Vec<_> = enabled_spans(EXAMPLE_TWO, Overlap::Deny)
+        .into_iter()
+        .filter(|x| x.enabled)
+        .map(|x| x.range)
+        .collect();
+    let regions: Vec<_> = conditional_regions(EXAMPLE_TWO, "do()", "don't()").collect();
+    assert_eq!(enabled, regions);
+}
+
+#[test]
+fn disabled_from_the_start() {
+    let spans = enabled_spans("don't()mul(2,3)", Overlap::Deny);
+    assert_eq!(
+        spans,
+        vec![Span {
+            range: 0..15,
+            enabled: false
+        }]
+    );
+}
+
+#[test]
+fn empty_memory() {
+    let spans = enabled_spans("", Overlap::Deny);
+    assert_eq!(
+        spans,
+        vec![Span {
+            range: 0..0,
+            enabled: true
+        }]
+    );
+}
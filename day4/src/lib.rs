@@ -0,0 +1,327 @@
+use std::ops::Range;
+
+use rayon::prelude::*;
+
+/// The eight directions in which a word can be read, as (row, column) steps
+pub const DIRECTIONS: [(i64, i64); 8] = [
+    (0, 1),
+    (1, 1),
+    (1, 0),
+    (1, -1),
+    (0, -1),
+    (-1, -1),
+    (-1, 0),
+    (-1, 1),
+];
+
+/// The names of the eight directions, in the same order as the steps
+pub const DIRECTION_NAMES: [&str; 8] = [
+    "east",
+    "southeast",
+    "south",
+    "southwest",
+    "west",
+    "northwest",
+    "north",
+    "northeast",
+];
+
+/// How the edges of the grid are treated when walking in a direction
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Edges {
+    /// Walks stop at the edges of the grid
+    Bounded,
+    /// Walks wrap around to the opposite edge of the grid
+    Toroidal,
+}
+
+impl Edges {
+    /// Gets the character at the given row and column, which may be outside the grid
+    ///
+    /// Returns None if the location is outside the grid and walks stop at the edges, or if the
+    /// walk wraps around into an empty row.
+    pub fn get(&self, matrix: &[&[u8]], row: i64, col: i64) -> Option<u8> {
+        let (row, col) = match self {
+            Edges::Bounded => {
+                if row < 0 || col < 0 {
+                    return None;
+                }
+                (row as usize, col as usize)
+            }
+            Edges::Toroidal => {
+                let row = row.checked_rem_euclid(matrix.len() as i64)? as usize;
+                let col = col.checked_rem_euclid(matrix[row].len() as i64)? as usize;
+                (row, col)
+            }
+        };
+        matrix.get(row).and_then(|x| x.get(col)).copied()
+    }
+}
+
+/// Parses the grid of letters from the input text, one row per line, failing if it's empty or
+/// its rows differ in width
+pub fn parse_grid(contents: &str) -> Result<Vec<&[u8]>, String> {
+    let matrix: Vec<&[u8]> = contents.lines().map(|x| x.as_bytes()).collect();
+    let width = matrix.first().map_or(0, |x| x.len());
+    if width == 0 {
+        return Err(String::from("The grid is empty"));
+    }
+    if let Some(row_index) = matrix.iter().position(|x| x.len() != width) {
+        let line = row_index + 1;
+        return Err(format!(
+            "Line {line} has a different width than the first row"
+        ));
+    }
+    Ok(matrix)
+}
+
+/// Gets the number of directions, from the start of the directions, in which the word is
+/// counted, so that each occurrence of it is only counted once
+///
+/// A palindrome read in one of the last four directions covers the same letters as one read in
+/// the opposite direction, so it's only counted in the first four, and a single letter is only
+/// counted reading east.
+pub fn counted_directions(word: &[u8]) -> usize {
+    if word.len() <= 1 {
+        1
+    } else if word.iter().eq(word.iter().rev()) {
+        DIRECTIONS.len() / 2
+    } else {
+        DIRECTIONS.len()
+    }
+}
+
+/// Counts the occurrences of each of the words that start within the given range of rows,
+/// walking from each starting character in every direction
+///
+/// The walks are allowed to leave the range of rows, so each range of rows can be
+/// scanned independently of the others without missing diagonal or vertical matches.
+/// Each walk is shared by all of the words, going as far as the longest one.  The counts are
+/// returned per word, and per direction in the same order as the directions, with palindromes
+/// and single letters only counted in the directions given by `counted_directions()`.
+pub fn count_words_in_rows(
+    matrix: &[&[u8]],
+    words: &[&[u8]],
+    rows: Range<usize>,
+    edges: Edges,
+) -> Vec<[u64; 8]> {
+    // Initialize the count of matches for each word and direction
+    let mut counts = vec![[0; 8]; words.len()];
+
+    // Get the length of the longest walk needed, with space to store the characters walked
+    let max_length = words.iter().map(|x| x.len()).max().unwrap_or(0);
+    let mut walked = Vec::with_capacity(max_length);
+    let num_directions: Vec<usize> = words.iter().map(|x| counted_directions(x)).collect();
+
+    // Iterate through the starting locations in the given rows
+    for row_index in rows {
+        for col_index in 0..matrix[row_index].len() {
+            // Walk in every direction, collecting the characters until the longest word is
+            // covered or the walk leaves the grid
+            for (direction, (row_step, col_step)) in DIRECTIONS.into_iter().enumerate() {
+                walked.clear();
+                for index in 0..max_length {
+                    let row = row_index as i64 + row_step * index as i64;
+                    let col = col_index as i64 + col_step * index as i64;
+                    match edges.get(matrix, row, col) {
+                        Some(character) => walked.push(character),
+                        None => break,
+                    }
+                }
+
+                // Check the characters walked against each of the words
+                let counted = words.iter().zip(&num_directions).zip(counts.iter_mut());
+                for ((word, num_directions), word_counts) in counted {
+                    if direction < *num_directions && walked.starts_with(word) {
+                        word_counts[direction] += 1;
+                    }
+                }
+            }
+        }
+    }
+
+    // Return the number of matches for each word in each direction
+    counts
+}
+
+/// Counts the occurrences of each of the words in each direction by splitting the rows of the
+/// grid into ranges, one per worker, and scanning them in parallel
+pub fn count_words_parallel(
+    matrix: &[&[u8]],
+    words: &[&[u8]],
+    jobs: usize,
+    edges: Edges,
+) -> Vec<[u64; 8]> {
+    // Split the rows into (nearly) equally sized ranges for each of the workers
+    let jobs = jobs.max(1);
+    let chunk_size = matrix.len().div_ceil(jobs).max(1);
+    let ranges: Vec<Range<usize>> = (0..matrix.len())
+        .step_by(chunk_size)
+        .map(|start| start..(start + chunk_size).min(matrix.len()))
+        .collect();
+
+    // Scan the ranges of rows using the requested number of workers
+    let pool = rayon::ThreadPoolBuilder::new()
+        .num_threads(jobs)
+        .build()
+        .expect("Could not create the worker pool");
+    pool.install(|| {
+        ranges
+            .into_par_iter()
+            .map(|rows| count_words_in_rows(matrix, words, rows, edges))
+            .reduce(
+                || vec![[0; 8]; words.len()],
+                |mut totals, counts| {
+                    for (word_totals, word_counts) in totals.iter_mut().zip(counts) {
+                        word_totals
+                            .iter_mut()
+                            .zip(word_counts)
+                            .for_each(|(x, y)| *x += y);
+                    }
+                    totals
+                },
+            )
+    })
+}
+
+/// The character that matches any character in a stencil
+pub const WILDCARD: u8 = b'.';
+
+/// The stencil of the X-MAS, which is found in any of its rotations or reflections
+pub const CROSS_MAS: &str = "M.M\n.A.\nS.S\n";
+
+/// The names of the X-MAS orientations, by the side of the cross on which both M's are found,
+/// in the same order as the variants of its stencil
+pub const ORIENTATION_NAMES: [&str; 4] = ["top", "right", "bottom", "left"];
+
+/// A small pattern of characters to search for, where None matches any character
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Stencil {
+    cells: Vec<Vec<Option<u8>>>,
+}
+
+impl Stencil {
+    /// Parses the stencil from the given text, padding shorter lines with wildcards, failing if
+    /// it has no characters
+    pub fn parse(text: &str) -> Result<Self, String> {
+        // Get the lines of the stencil, which must not be empty
+        let lines: Vec<&[u8]> = text.lines().map(|x| x.as_bytes()).collect();
+        let width = lines.iter().map(|x| x.len()).max().unwrap_or(0);
+        if width == 0 {
+            return Err(String::from("The stencil is empty"));
+        }
+
+        // Convert the characters into cells, padding each line to the same width
+        let cells = lines
+            .iter()
+            .map(|line| {
+                (0..width)
+                    .map(|index| line.get(index).copied().filter(|x| *x != WILDCARD))
+                    .collect()
+            })
+            .collect();
+
+        // Return the stencil
+        Ok(Self { cells })
+    }
+
+    /// Gets the stencil rotated clockwise by a quarter turn
+    pub fn rotate(&self) -> Self {
+        let height = self.cells.len();
+        let width = self.cells[0].len();
+        let cells = (0..width)
+            .map(|row| {
+                (0..height)
+                    .map(|col| self.cells[height - 1 - col][row])
+                    .collect()
+            })
+            .collect();
+        Self { cells }
+    }
+
+    /// Gets the stencil reflected over its vertical center
+    pub fn reflect(&self) -> Self {
+        let cells = self
+            .cells
+            .iter()
+            .map(|x| x.iter().rev().copied().collect())
+            .collect();
+        Self { cells }
+    }
+
+    /// Gets the distinct rotations and reflections of the stencil, so that symmetric stencils
+    /// aren't counted more than once at the same location
+    ///
+    /// The variants are the quarter turns of the stencil, clockwise from the stencil itself,
+    /// followed by those of its reflection.
+    pub fn variants(&self) -> Vec<Stencil> {
+        let mut variants: Vec<Stencil> = Vec::new();
+        for start in [self.clone(), self.reflect()] {
+            let mut variant = start;
+            for _ in 0..4 {
+                let next = variant.rotate();
+                if !variants.contains(&variant) {
+                    variants.push(variant);
+                }
+                variant = next;
+            }
+        }
+        variants
+    }
+
+    /// Checks whether the stencil matches the grid with its top left corner at the given
+    /// location
+    pub fn matches_at(&self, matrix: &[&[u8]], row_index: usize, col_index: usize) -> bool {
+        self.cells.iter().enumerate().all(|(row_offset, cells)| {
+            cells.iter().enumerate().all(|(col_offset, cell)| {
+                let character = matrix
+                    .get(row_index + row_offset)
+                    .and_then(|x| x.get(col_index + col_offset));
+                match (cell, character) {
+                    (_, None) => false,
+                    (None, Some(_)) => true,
+                    (Some(expected), Some(actual)) => expected == actual,
+                }
+            })
+        })
+    }
+}
+
+/// Counts the occurrences of each variant of the stencil in the grid, in the same order as the
+/// variants
+pub fn count_stencil_by_variant(matrix: &[&[u8]], stencil: &Stencil) -> Vec<u64> {
+    // Count the matches of each of the variants of the stencil at every location in the grid
+    stencil
+        .variants()
+        .iter()
+        .map(|variant| {
+            let mut count = 0;
+            for row_index in 0..matrix.len() {
+                for col_index in 0..matrix[row_index].len() {
+                    if variant.matches_at(matrix, row_index, col_index) {
+                        count += 1;
+                    }
+                }
+            }
+            count
+        })
+        .collect()
+}
+
+/// Counts the occurrences of the stencil in the grid, in any of its rotations or reflections
+pub fn count_stencil(matrix: &[&[u8]], stencil: &Stencil) -> u64 {
+    count_stencil_by_variant(matrix, stencil).iter().sum()
+}
+
+/// Solves part one for the puzzle input, getting the number of times XMAS appears in the grid
+pub fn part1(input: &str) -> Result<u64, String> {
+    let matrix = parse_grid(input)?;
+    let counts = count_words_parallel(&matrix, &[b"XMAS"], 1, Edges::Bounded);
+    Ok(counts.iter().flatten().sum())
+}
+
+/// Solves part two for the puzzle input, getting the number of X-MAS crosses in the grid
+pub fn part2(input: &str) -> Result<u64, String> {
+    let matrix = parse_grid(input)?;
+    Ok(count_stencil(&matrix, &Stencil::parse(CROSS_MAS)?))
+}
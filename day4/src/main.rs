@@ -1,26 +1,115 @@
-use std::{collections::HashSet, fs};
-
-use clap::Parser;
+use clap::{builder::NonEmptyStringValueParser, Parser};
+use common::{cached, fail, read_input, About, Failure, Feature, Format};
+use day4::{
+    count_stencil, count_stencil_by_variant, count_words_parallel, parse_grid, Edges, Stencil,
+    CROSS_MAS, DIRECTION_NAMES, ORIENTATION_NAMES,
+};
 use regex::Regex;
 
+/// The version of the grid transformations, to be changed whenever they change so that
+/// transformations cached by older builds aren't used
+const TRANSFORM_VERSION: u32 = 1;
+
 #[derive(Parser)]
 struct CliArgs {
     part: u64,
     filepath: String,
+    /// Scan the grid with the given number of parallel workers, splitting it by row ranges
+    #[arg(long)]
+    jobs: Option<usize>,
+    /// Allow words to wrap around the edges of the grid (implies the direction scanner)
+    #[arg(long)]
+    wrap: bool,
+    /// Print the number of matches found in each direction (or orientation) before the answer
+    /// (implies the direction scanner)
+    #[arg(long)]
+    breakdown: bool,
+    /// Search for the given word instead of XMAS, which can be given multiple times to count
+    /// each word from a single scan of the grid (implies the direction scanner), counting a
+    /// palindrome or single letter only once at each location rather than in every direction
+    #[arg(long, value_parser = NonEmptyStringValueParser::new())]
+    word: Vec<String>,
+    /// Search for the pattern in the given file instead of the X-MAS, in any of its rotations or
+    /// reflections, where '.' matches any character
+    #[arg(long, conflicts_with = "breakdown")]
+    stencil: Option<String>,
+    /// Cache the transposed and diagonal versions of the grid made when counting XMAS with the
+    /// string transforms, so later runs on the same grid can skip making them
+    #[arg(long, conflicts_with_all = ["jobs", "wrap", "breakdown", "word"])]
+    cache: bool,
+    /// Parse the input and print statistics about its structure instead of solving it
+    #[arg(long)]
+    parse_only: bool,
+    /// The format in which errors are reported
+    #[arg(long, value_enum, default_value_t = Format::Text)]
+    format: Format,
 }
 
+/// Description of the puzzle solved by this binary
+const ABOUT: About = About {
+    day: 4,
+    title: "Ceres Search",
+    input: "A grid of letters for the word search",
+    parts: &[1, 2],
+    features: &[Feature::Parallel],
+};
+
 fn main() {
+    // Describe the puzzle instead if requested
+    common::handle_about::<CliArgs>(&ABOUT);
+
     // Parse CLI arguments
     let cli = CliArgs::parse();
 
+    // Report errors in the requested format
+    common::init(cli.format);
+
+    // Only parse the input if requested
+    if cli.parse_only {
+        report_structure(&cli.filepath);
+    }
+
     // Run the code for the desired challenge part
     match cli.part {
-        1 => main_part_one(cli.filepath),
-        2 => main_part_two(cli.filepath),
+        1 => main_part_one(
+            cli.filepath,
+            cli.jobs,
+            cli.wrap,
+            cli.breakdown,
+            cli.word,
+            cli.cache,
+        ),
+        2 => main_part_two(cli.filepath, cli.breakdown, cli.stencil),
         _ => panic!("Invalid selection part selection!"),
     }
 }
 
+/// Parses the grid of letters from the file contents, failing if it's malformed
+fn read_grid(contents: &str) -> Vec<&[u8]> {
+    parse_grid(contents).unwrap_or_else(|error| fail(Failure::Parse(error)))
+}
+
+/// Parses the stencil from the text, failing if it's empty
+fn read_stencil(text: &str) -> Stencil {
+    Stencil::parse(text).unwrap_or_else(|error| fail(Failure::Parse(error)))
+}
+
+/// Parses the grid of letters, failing if its rows differ in width, then prints its dimensions
+/// and the number of distinct letters in it and exits
+fn report_structure(filepath: &str) -> ! {
+    let contents = read_input(filepath);
+    let matrix = read_grid(&contents);
+    let width = matrix[0].len();
+    let mut letters: Vec<u8> = matrix.concat();
+    letters.sort();
+    letters.dedup();
+    common::report_parse(&[
+        ("rows", matrix.len()),
+        ("columns", width),
+        ("distinct letters", letters.len()),
+    ]);
+}
+
 fn transpose(input: &str) -> String {
     // Initialize an empty matrix to use for eventually building the string
     let mut output_matrix: Vec<Vec<char>> = Vec::new();
@@ -127,88 +216,66 @@ fn check_for_xmas(x: &str) -> u64 {
     count as u64
 }
 
-fn get_diagonal_characters(
-    input_matrix: &[&str],
-    row_index: usize,
-    col_index: usize,
-    forward_dir: bool,
-) -> HashSet<char> {
-    // Create a hash set for storing the found characters
-    let mut slash_characters: HashSet<char> = HashSet::new();
-
-    // Get the column modifier with checking for forward slashes or backslashes
-    let col_modifier = if forward_dir { 1 } else { -1 };
-
-    // Get the column indices for upper and lower character access
-    let col_upper_index = (col_index as i32 + col_modifier) as usize;
-    let col_lower_index = (col_index as i32 - col_modifier) as usize;
-
-    // Get the upper and lower characters
-    let upper_char = input_matrix[row_index + 1]
-        .chars()
-        .nth(col_upper_index)
-        .unwrap();
-    let lower_char = input_matrix[row_index - 1]
-        .chars()
-        .nth(col_lower_index)
-        .unwrap();
-
-    // Add the characters to the empty hashset
-    slash_characters.insert(upper_char);
-    slash_characters.insert(lower_char);
-
-    // Return the hash set
-    slash_characters
+/// Prints the number of matches for each of the named categories, as CSV
+fn print_breakdown(header: &str, names: &[&str], counts: &[u64]) {
+    println!("{header},count");
+    for (name, count) in names.iter().zip(counts) {
+        println!("{name},{count}");
+    }
 }
 
-fn check_for_cross_mas(input_matrix: &[&str]) -> u64 {
-    // Initialize the count of found X-MAS
-    let mut count = 0;
-
-    // Create a hash set representing the target hashset to compare against when 'A' is found
-    let mut target_hashset = HashSet::new();
-    target_hashset.insert('M');
-    target_hashset.insert('S');
-
-    // Iterate along the matrix row-by-row to look for matches
-    for (row_index, line) in input_matrix.iter().enumerate() {
-        // Skip the first and last rows, where matches will never be found (and avoid access issues)
-        if row_index == 0 || row_index == (input_matrix.len() - 1) {
-            continue;
-        }
-
-        // Iterate along the row character-by-character to look for matches
-        for (col_index, character) in line.chars().enumerate() {
-            // Skip the first and last column, where matches will never be found (and avoid access issues)
-            if col_index == 0 || col_index == (line.len() - 1) {
-                continue;
-            }
-
-            // If 'A' is found, check for 'M' and 'S'
-            if character == 'A' {
-                // Get the forward slash characters as a hashset
-                let fslash_characters =
-                    get_diagonal_characters(input_matrix, row_index, col_index, true);
-
-                // Get the backslash characters as a hashset
-                let bslash_characters =
-                    get_diagonal_characters(input_matrix, row_index, col_index, false);
-
-                // If the forward slash and backslash characters are both 'M' and 'S', add to the running tally
-                if fslash_characters == target_hashset && bslash_characters == target_hashset {
-                    count += 1;
+fn main_part_one(
+    filepath: String,
+    jobs: Option<usize>,
+    wrap: bool,
+    breakdown: bool,
+    words: Vec<String>,
+    cache: bool,
+) {
+    // Read the contents of the file, checking that it's a grid
+    let contents = read_input(filepath);
+    let matrix = read_grid(&contents);
+
+    // If workers are requested, words may wrap, a breakdown is requested, or other words are
+    // searched for, scan the rows of the grid in each direction, in parallel if requested
+    if jobs.is_some() || wrap || breakdown || !words.is_empty() {
+        let edges = if wrap {
+            Edges::Toroidal
+        } else {
+            Edges::Bounded
+        };
+
+        // Count the matches for each of the words, defaulting to XMAS
+        let words = if words.is_empty() {
+            vec![String::from("XMAS")]
+        } else {
+            words
+        };
+        let word_bytes: Vec<&[u8]> = words.iter().map(|x| x.as_bytes()).collect();
+        let counts = count_words_parallel(&matrix, &word_bytes, jobs.unwrap_or(1), edges);
+
+        // Print the number of matches in each direction if requested, and for each word if
+        // there are several
+        if breakdown && words.len() > 1 {
+            println!("word,direction,count");
+            for (word, word_counts) in words.iter().zip(&counts) {
+                for (direction, count) in DIRECTION_NAMES.iter().zip(word_counts) {
+                    println!("{word},{direction},{count}");
                 }
             }
+        } else if breakdown {
+            print_breakdown("direction", &DIRECTION_NAMES, &counts[0]);
+        } else if words.len() > 1 {
+            let word_names: Vec<&str> = words.iter().map(|x| x.as_str()).collect();
+            let word_totals: Vec<u64> = counts.iter().map(|x| x.iter().sum()).collect();
+            print_breakdown("word", &word_names, &word_totals);
         }
-    }
-
-    // Return the number of found matches
-    count
-}
 
-fn main_part_one(filepath: String) {
-    // Read the contents of the file
-    let contents = fs::read_to_string(filepath).expect("Invalid filepath");
+        // Print the total number of matches for all of the words
+        let count: u64 = counts.iter().flatten().sum();
+        println!("{count}");
+        return;
+    }
 
     // Initialize the count of matches
     let mut count = 0;
@@ -216,32 +283,55 @@ fn main_part_one(filepath: String) {
     // Check for horizontal matches
     count += check_for_xmas(&contents);
 
+    // Make the transformations of the grid, using the cache if requested
+    let transform = |name: &str, function: fn(&str) -> String| {
+        if cache {
+            cached(name, TRANSFORM_VERSION, contents.as_bytes(), || {
+                function(&contents)
+            })
+        } else {
+            function(&contents)
+        }
+    };
+
     // Check for vertical matches
-    let transposed = transpose(&contents);
+    let transposed = transform("day4-transposed", transpose);
     count += check_for_xmas(&transposed);
 
     // Check for forward slash matches
-    let fslash = get_diagonal_representation(&contents);
+    let fslash = transform("day4-fslash", get_diagonal_representation);
     count += check_for_xmas(&fslash);
 
     // Check for back slash matches
-    let mirrored = mirror(&contents);
-    let bslash = get_diagonal_representation(&mirrored);
+    let bslash = transform("day4-bslash", |x| get_diagonal_representation(&mirror(x)));
     count += check_for_xmas(&bslash);
 
     // Print the result
     println!("{count}");
 }
 
-fn main_part_two(filepath: String) {
-    // Read the contents of the file
-    let contents = fs::read_to_string(filepath).expect("Invalid filepath");
+fn main_part_two(filepath: String, breakdown: bool, stencil: Option<String>) {
+    // Read the contents of the file, checking that it's a grid
+    let contents = read_input(filepath);
+    let matrix = read_grid(&contents);
+
+    // If a breakdown is requested, count the matches of each orientation of the X-MAS instead
+    if breakdown {
+        let counts = count_stencil_by_variant(&matrix, &read_stencil(CROSS_MAS));
+        print_breakdown("orientation", &ORIENTATION_NAMES, &counts);
+        let count: u64 = counts.iter().sum();
+        println!("{count}");
+        return;
+    }
 
-    // Get the contents of the file as a list of strings
-    let matrix: Vec<&str> = contents.lines().collect();
+    // Get the stencil to search for, which is the X-MAS unless another is given
+    let stencil = match stencil {
+        Some(stencil_filepath) => read_stencil(&read_input(stencil_filepath)),
+        None => read_stencil(CROSS_MAS),
+    };
 
-    // Get the number of X-MAS in the matrix
-    let count = check_for_cross_mas(&matrix);
+    // Get the number of matches of the stencil in the matrix
+    let count = count_stencil(&matrix, &stencil);
 
     // Print the result
     println!("{count}");
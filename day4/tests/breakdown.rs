@@ -0,0 +1,80 @@
+use day4::{count_words_in_rows, Edges, DIRECTIONS, DIRECTION_NAMES};
+
+const EXAMPLE: &str = include_str!("../inputs/example.txt");
+
+/// Gets the rows of the grid
+fn matrix(input: &str) -> Vec<&[u8]> {
+    input.lines().map(|x| x.as_bytes()).collect()
+}
+
+/// Counts the matches of the word in each direction across the whole grid
+fn count_by_direction(input: &str, word: &[u8]) -> [u64; 8] {
+    let matrix = matrix(input);
+    count_words_in_rows(&matrix, &[word], 0..matrix.len(), Edges::Bounded)[0]
+}
+
+#[test]
+fn example_breakdown() {
+    let counts = count_by_direction(EXAMPLE, b"XMAS");
+    assert_eq!(counts, [3, 1, 1, 1, 2, 4, 2, 4]);
+    assert_eq!(counts.iter().sum::<u64>(), 18);
+}
+
+#[test]
+fn reversed_word_matches_in_opposite_directions() {
+    let forward = count_by_direction(EXAMPLE, b"XMAS");
+    let backward = count_by_direction(EXAMPLE, b"SAMX");
+    for direction in 0..8 {
+        assert_eq!(
+            forward[direction],
+            backward[(direction + 4) % 8],
+            "{}",
+            DIRECTION_NAMES[direction]
+        );
+    }
+}
+
+#[test]
+fn names_match_steps() {
+    // Lay the word out along each direction from the center of an empty grid, and check that
+    // only that direction is counted
+    for (direction, (row_step, col_step)) in DIRECTIONS.into_iter().enumerate() {
+        let mut grid = vec![vec![b'.'; 7]; 7];
+        for (index, character) in b"XMAS".iter().enumerate() {
+            let row = 3 + row_step * index as i64;
+            let col = 3 + col_step * index as i64;
+            grid[row as usize][col as usize] = *character;
+        }
+        let input: String = grid
+            .iter()
+            .map(|x| String::from_utf8(x.clone()).unwrap() + "\n")
+            .collect();
+        let mut expected = [0; 8];
+        expected[direction] = 1;
+        assert_eq!(
+            count_by_direction(&input, b"XMAS"),
+            expected,
+            "{}",
+            DIRECTION_NAMES[direction]
+        );
+    }
+}
+
+#[test]
+fn wrapped_matches_keep_their_direction() {
+    // The word only reads east by wrapping around the end of the row
+    let matrix = matrix("ASXM\n");
+    let bounded = count_words_in_rows(&matrix, &[b"XMAS"], 0..1, Edges::Bounded);
+    let toroidal = count_words_in_rows(&matrix, &[b"XMAS"], 0..1, Edges::Toroidal);
+    assert_eq!(bounded, vec![[0; 8]]);
+    assert_eq!(toroidal[0][0], 1);
+    assert_eq!(toroidal[0][4], 0);
+}
+
+#[test]
+fn wrapping_stops_at_empty_rows() {
+    // Walks can wrap into an empty row, which ends them instead of failing
+    let matrix: Vec<&[u8]> = vec![b"XMAS", b"", b"XMAS"];
+    let counts = count_words_in_rows(&matrix, &[b"XMAS"], 0..3, Edges::Toroidal);
+    assert_eq!(counts[0].iter().sum::<u64>(), 2);
+}
@@ -0,0 +1,48 @@
+use day4::{count_words_in_rows, count_words_parallel, Edges};
+
+const EXAMPLE: &str = include_str!("../inputs/example.txt");
+
+/// Gets the rows of the grid
+fn matrix(input: &str) -> Vec<&[u8]> {
+    input.lines().map(|x| x.as_bytes()).collect()
+}
+
+#[test]
+fn parallel_matches_sequential() {
+    let matrix = matrix(EXAMPLE);
+    let words: [&[u8]; 2] = [b"XMAS", b"MAS"];
+    for edges in [Edges::Bounded, Edges::Toroidal] {
+        let sequential = count_words_in_rows(&matrix, &words, 0..matrix.len(), edges);
+        for jobs in [0, 1, 2, 3, 4, 10, 64] {
+            assert_eq!(
+                count_words_parallel(&matrix, &words, jobs, edges),
+                sequential,
+                "{jobs} jobs with {edges:?} edges"
+            );
+        }
+    }
+}
+
+#[test]
+fn parallel_counts_example() {
+    let matrix = matrix(EXAMPLE);
+    let counts = count_words_parallel(&matrix, &[b"XMAS"], 3, Edges::Bounded);
+    assert_eq!(counts[0].iter().sum::<u64>(), 18);
+}
+
+#[test]
+fn empty_grid() {
+    let counts = count_words_parallel(&[], &[b"XMAS"], 4, Edges::Bounded);
+    assert_eq!(counts, vec![[0; 8]]);
+}
+
+#[test]
+fn single_row() {
+    let matrix = matrix("XMASAMXMAS\n");
+    let counts = count_words_parallel(&matrix, &[b"XMAS"], 4, Edges::Bounded);
+    assert_eq!(counts, vec![[2, 0, 0, 0, 1, 0, 0, 0]]);
+    assert_eq!(
+        counts,
+        count_words_in_rows(&matrix, &[b"XMAS"], 0..1, Edges::Bounded)
+    );
+}
@@ -0,0 +1,84 @@
+use day4::{count_stencil, count_stencil_by_variant, Stencil, CROSS_MAS};
+
+const EXAMPLE: &str = include_str!("../inputs/example.txt");
+
+/// Gets the rows of the grid
+fn matrix(input: &str) -> Vec<&[u8]> {
+    input.lines().map(|x| x.as_bytes()).collect()
+}
+
+#[test]
+fn parse_pads_with_wildcards() {
+    assert_eq!(
+        Stencil::parse("AB\nC\n.D\n").unwrap(),
+        Stencil::parse("AB\nC.\n.D\n").unwrap()
+    );
+}
+
+#[test]
+fn parse_rejects_empty_stencil() {
+    let empty = Err(String::from("The stencil is empty"));
+    assert_eq!(Stencil::parse("\n\n"), empty);
+    assert_eq!(Stencil::parse(""), empty);
+}
+
+#[test]
+fn rotate_turns_clockwise() {
+    let stencil = Stencil::parse("AB\nCD\n").unwrap();
+    assert_eq!(stencil.rotate(), Stencil::parse("CA\nDB\n").unwrap());
+    assert_eq!(stencil.rotate().rotate().rotate().rotate(), stencil);
+}
+
+#[test]
+fn reflect_flips_columns() {
+    assert_eq!(
+        Stencil::parse("AB.\n").unwrap().reflect(),
+        Stencil::parse(".BA\n").unwrap()
+    );
+}
+
+#[test]
+fn variants_skip_symmetric_duplicates() {
+    assert_eq!(Stencil::parse("A").unwrap().variants().len(), 1);
+    assert_eq!(Stencil::parse("AA").unwrap().variants().len(), 2);
+    assert_eq!(Stencil::parse("XMAS").unwrap().variants().len(), 4);
+    assert_eq!(Stencil::parse("AB\nC.\n").unwrap().variants().len(), 8);
+    assert_eq!(Stencil::parse(CROSS_MAS).unwrap().variants().len(), 4);
+}
+
+#[test]
+fn matches_at_stays_inside_grid() {
+    let matrix = matrix("XMAS\nMMMM\n");
+    let stencil = Stencil::parse("MAS").unwrap();
+    assert!(stencil.matches_at(&matrix, 0, 1));
+    assert!(!stencil.matches_at(&matrix, 0, 2));
+    assert!(!stencil.matches_at(&matrix, 1, 2));
+    assert!(!stencil.matches_at(&matrix, 2, 0));
+}
+
+#[test]
+fn counts_example_cross_mas() {
+    let matrix = matrix(EXAMPLE);
+    assert_eq!(
+        count_stencil(&matrix, &Stencil::parse(CROSS_MAS).unwrap()),
+        9
+    );
+}
+
+#[test]
+fn breaks_down_example_cross_mas_by_orientation() {
+    // The counts are for the M's on the top, right, bottom and left
+    let matrix = matrix(EXAMPLE);
+    assert_eq!(
+        count_stencil_by_variant(&matrix, &Stencil::parse(CROSS_MAS).unwrap()),
+        vec![1, 1, 5, 2]
+    );
+}
+
+#[test]
+fn counts_straight_xmas_in_example() {
+    // Only the horizontal and vertical XMAS are found by the straight stencil, leaving the 10
+    // diagonal ones of the 18 in total
+    let matrix = matrix(EXAMPLE);
+    assert_eq!(count_stencil(&matrix, &Stencil::parse("XMAS").unwrap()), 8);
+}
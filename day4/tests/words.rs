@@ -0,0 +1,61 @@
+use day4::{count_words_in_rows, count_words_parallel, Edges};
+
+const EXAMPLE: &str = include_str!("../inputs/example.txt");
+
+/// Gets the rows of the grid
+fn matrix(input: &str) -> Vec<&[u8]> {
+    input.lines().map(|x| x.as_bytes()).collect()
+}
+
+/// Counts the total matches of each of the words from a single scan of the whole grid
+fn count_totals(input: &str, words: &[&[u8]]) -> Vec<u64> {
+    let matrix = matrix(input);
+    count_words_in_rows(&matrix, words, 0..matrix.len(), Edges::Bounded)
+        .iter()
+        .map(|x| x.iter().sum())
+        .collect()
+}
+
+#[test]
+fn example_words() {
+    let words: [&[u8]; 5] = [b"XMAS", b"MAS", b"SAMX", b"A", b"XMASX"];
+    assert_eq!(count_totals(EXAMPLE, &words), vec![18, 38, 18, 24, 5]);
+}
+
+#[test]
+fn single_scan_matches_separate_scans() {
+    let matrix = matrix(EXAMPLE);
+    let words: [&[u8]; 4] = [b"XMAS", b"MAS", b"MM", b"XMASAMX"];
+    let together = count_words_parallel(&matrix, &words, 2, Edges::Toroidal);
+    for (word, counts) in words.iter().zip(&together) {
+        assert_eq!(
+            count_words_parallel(&matrix, &[word], 2, Edges::Toroidal)[0],
+            *counts,
+            "{}",
+            String::from_utf8_lossy(word)
+        );
+    }
+}
+
+#[test]
+fn repeated_words_are_counted_separately() {
+    assert_eq!(count_totals(EXAMPLE, &[b"XMAS", b"XMAS"]), vec![18, 18]);
+}
+
+#[test]
+fn words_longer_than_the_grid_never_match() {
+    assert_eq!(count_totals("XM\nAS\n", &[b"XMASX", b"XM"]), vec![0, 1]);
+}
+
+#[test]
+fn no_words() {
+    assert!(count_totals(EXAMPLE, &[]).is_empty());
+}
+
+#[test]
+fn palindromes_are_counted_once() {
+    // Reading a palindrome backwards covers the same letters, and a single letter is the same
+    // match in every direction
+    let words: [&[u8]; 3] = [b"SAS", b"A", b"SA"];
+    assert_eq!(count_totals("SAS\nAAA\nSAS\n", &words), vec![6, 5, 12]);
+}
@@ -0,0 +1,37 @@
+use std::collections::HashMap;
+
+use crate::Rules;
+
+/// Infers a set of rules from updates that are all known to follow the rules, by mining the
+/// order in which each pair of pages appears
+///
+/// A rule that one page comes before another is inferred when the pages appear in that order
+/// in at least `min_support` updates, and never appear in the opposite order, so every one of
+/// the updates still follows the inferred rules.  Pairs of pages that never appear together
+/// can't be inferred, so the rules may be fewer than the original ones.
+pub fn infer_rules(updates: &[Vec<u16>], min_support: usize) -> Rules {
+    // Count the number of updates in which each page comes before each other page
+    let mut before_counts: HashMap<(u16, u16), usize> = HashMap::new();
+    for update in updates {
+        for (index, leading_page) in update.iter().enumerate() {
+            for following_page in &update[index + 1..] {
+                if leading_page != following_page {
+                    *before_counts
+                        .entry((*leading_page, *following_page))
+                        .or_default() += 1;
+                }
+            }
+        }
+    }
+
+    // Add a rule for each order seen often enough that's never contradicted
+    let mut rules = Rules::new();
+    for (&(leading_page, following_page), &count) in &before_counts {
+        if count >= min_support && !before_counts.contains_key(&(following_page, leading_page)) {
+            rules.add_rule(leading_page, following_page);
+        }
+    }
+
+    // Return the inferred rules
+    rules
+}
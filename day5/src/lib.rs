@@ -0,0 +1,335 @@
+use std::collections::{HashMap, HashSet};
+
+pub mod infer;
+pub mod wildcards;
+
+/// Rules for the page ordering, specifically rules that indicate an
+/// ordering is NOT in the correct order.  That means for any given page,
+/// a set of other pages is available that CANNOT come before it.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Rules {
+    rule_breaks: HashMap<u16, HashSet<u16>>,
+}
+
+impl Rules {
+    /// Creates an empty set of rules, to be built up one rule at a time
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds the rule that the leading page must come before the following page
+    ///
+    /// Returns whether the rule was newly added.
+    pub fn add_rule(&mut self, leading_page: u16, following_page: u16) -> bool {
+        self.rule_breaks
+            .entry(following_page)
+            .or_default()
+            .insert(leading_page)
+    }
+
+    /// Removes the rule that the leading page must come before the following page
+    ///
+    /// Returns whether the rule was present.
+    pub fn remove_rule(&mut self, leading_page: u16, following_page: u16) -> bool {
+        // Remove the leading page from the set of pages that cannot follow
+        let Some(leading_pages) = self.rule_breaks.get_mut(&following_page) else {
+            return false;
+        };
+        let removed = leading_pages.remove(&leading_page);
+
+        // Don't keep empty sets around, as pages without rules are skipped entirely
+        if leading_pages.is_empty() {
+            self.rule_breaks.remove(&following_page);
+        }
+
+        // Return whether the rule was removed
+        removed
+    }
+
+    /// Gets the set of pages that cannot come after the given page
+    pub fn get(&self, page: &u16) -> Option<&HashSet<u16>> {
+        self.rule_breaks.get(page)
+    }
+
+    /// Gets the rules in the `a|b` format, one per line, ordered by the leading page and then
+    /// the following page
+    pub fn to_text(&self) -> String {
+        let mut pairs: Vec<(u16, u16)> = self
+            .rule_breaks
+            .iter()
+            .flat_map(|(following_page, leading_pages)| {
+                leading_pages.iter().map(|x| (*x, *following_page))
+            })
+            .collect();
+        pairs.sort();
+        pairs
+            .iter()
+            .map(|(leading_page, following_page)| format!("{leading_page}|{following_page}\n"))
+            .collect()
+    }
+
+    /// Gets the number of rules
+    pub fn num_rules(&self) -> usize {
+        self.rule_breaks.values().map(|x| x.len()).sum()
+    }
+
+    /// Gets every page mentioned by the rules
+    pub fn pages(&self) -> HashSet<u16> {
+        let mut pages: HashSet<u16> = self.rule_breaks.keys().copied().collect();
+        pages.extend(self.rule_breaks.values().flatten());
+        pages
+    }
+}
+
+/// Parse a single page number of a rule or update
+fn parse_page(page_text: &str) -> Result<u16, String> {
+    page_text
+        .trim()
+        .parse::<u16>()
+        .map_err(|_| format!("could not parse {page_text:?} as a page"))
+}
+
+/// Parse a single rule of the form `a|b` into the leading and following page
+pub fn parse_rule(rule_text: &str) -> Result<(u16, u16), String> {
+    let (leading_text, following_text) = rule_text
+        .trim()
+        .split_once('|')
+        .ok_or_else(|| format!("expected a rule like 47|53, got {rule_text:?}"))?;
+    Ok((parse_page(leading_text)?, parse_page(following_text)?))
+}
+
+/// Generate the rules for the page ordering from the provided text, failing with the line
+/// number of any rule that can't be parsed
+pub fn generate_rules(rules_text: &str) -> Result<Rules, String> {
+    // Create an empty set of rules to populate
+    let mut rules = Rules::new();
+
+    // Iterate through the rules text line by line, adding each rule
+    for (index, line) in rules_text.lines().enumerate() {
+        let (leading_page, following_page) =
+            parse_rule(line).map_err(|error| format!("Line {}: {error}", index + 1))?;
+        rules.add_rule(leading_page, following_page);
+    }
+
+    // Return the rules
+    Ok(rules)
+}
+
+/// A set of updates along with whether each one currently follows the rules
+///
+/// The validity is cached so that changing a single rule only requires
+/// rechecking the updates that contain both pages of that rule.
+pub struct ValidatedUpdates {
+    updates: Vec<Vec<u16>>,
+    valid: Vec<bool>,
+}
+
+impl ValidatedUpdates {
+    /// Validates all of the updates against the given rules
+    pub fn new(updates: Vec<Vec<u16>>, rules: &Rules) -> Self {
+        let valid = updates
+            .iter()
+            .map(|x| !check_for_rule_break(x, rules))
+            .collect();
+        Self { updates, valid }
+    }
+
+    /// Revalidates only the updates affected by a change to the rule between the two pages
+    pub fn revalidate(&mut self, rules: &Rules, leading_page: u16, following_page: u16) {
+        for (update, valid) in self.updates.iter().zip(self.valid.iter_mut()) {
+            if update.contains(&leading_page) && update.contains(&following_page) {
+                *valid = !check_for_rule_break(update, rules);
+            }
+        }
+    }
+
+    /// Iterates through the updates that follow the rules
+    pub fn correct(&self) -> impl Iterator<Item = &Vec<u16>> {
+        self.updates
+            .iter()
+            .zip(&self.valid)
+            .filter(|(_, valid)| **valid)
+            .map(|(update, _)| update)
+    }
+
+    /// Iterates through the updates that break the rules
+    pub fn incorrect(&self) -> impl Iterator<Item = &Vec<u16>> {
+        self.updates
+            .iter()
+            .zip(&self.valid)
+            .filter(|(_, valid)| !**valid)
+            .map(|(update, _)| update)
+    }
+}
+
+/// Generate the list of updates from the provided text, failing with the (1-based) index of
+/// any update that can't be parsed
+pub fn generate_updates(updates_text: &str) -> Result<Vec<Vec<u16>>, String> {
+    // Create a new vector to populate
+    let mut all_updates = Vec::new();
+
+    // For each line in the text:
+    // 1. Split by commas
+    // 2. Parse each number into a u16
+    // 3. Collect the list into a vector
+    // 4. Push it to the previously created vector
+    for (index, line) in updates_text
+        .lines()
+        .map(|x| x.trim())
+        .filter(|x| !x.is_empty())
+        .enumerate()
+    {
+        let update = line
+            .split(",")
+            .map(parse_page)
+            .collect::<Result<Vec<u16>, String>>()
+            .map_err(|error| format!("Update {}: {error}", index + 1))?;
+        all_updates.push(update);
+    }
+
+    // Return the vector
+    Ok(all_updates)
+}
+
+/// Convenience function for creating both the rules and updates from the provided text
+pub fn generate_rules_and_updates(input: &str) -> Result<(Rules, Vec<Vec<u16>>), String> {
+    // Split the text by the double newline
+    let (rules_str, updates_str) = split_rules_and_updates(input)?;
+
+    // Get the rules and updates from their respective parts
+    let rules = generate_rules(rules_str)?;
+    let updates = generate_updates(updates_str)?;
+
+    // Return both the rules and updates
+    Ok((rules, updates))
+}
+
+/// Splits the provided text into the rules text and the updates text at the blank line
+/// between them
+pub fn split_rules_and_updates(input: &str) -> Result<(&str, &str), String> {
+    input
+        .split_once("\n\n")
+        .ok_or_else(|| String::from("Missing the blank line between the rules and the updates"))
+}
+
+/// Checks an update if any rules (rule breaks) apply
+pub fn check_for_rule_break(update: &[u16], rules: &Rules) -> bool {
+    // Iterate through the update page by page
+    for (index, page) in update.iter().enumerate() {
+        // Create a hash set from the remaining pages after it in the update
+        let following_pages: HashSet<u16> = HashSet::from_iter(update[index..].iter().cloned());
+
+        // Get the applicable rule breaks for the given pages
+        match rules.get(page) {
+            // There are rules that must be checked for this page
+            Some(forbidden_following_pages) => {
+                // Get the intersection of rules that indicate bad ordering and remaining pages
+                // in the ordering
+                let all_found_forbidden_pages: HashSet<&u16> = following_pages
+                    .intersection(forbidden_following_pages)
+                    .collect();
+
+                // If the intersection is not empty, rules have be broken, so early return true
+                // to the caller
+                if !all_found_forbidden_pages.is_empty() {
+                    return true;
+                }
+            }
+            // No rules can be broken, so check the next page in the update
+            None => continue,
+        }
+    }
+
+    // No rules were ever broken, return false
+    false
+}
+
+/// Calculates the order score for a given page
+///
+/// This is intended to be used for comparing pages for ordering.
+pub fn calculate_order_score(
+    page: &u16,
+    update: &Vec<u16>,
+    rules: &Rules,
+    previously_calculated: &mut HashMap<u16, u64>,
+) -> u64 {
+    // Checking for scores recursively for large lists can take a long time,
+    // so if a score is previously calculated and stored, we can skip recursively
+    // calculating it again
+    if previously_calculated.contains_key(page) {
+        return *previously_calculated.get(page).unwrap();
+    }
+
+    // Initialize the default score at 1
+    let mut score = 1;
+
+    // Get the remaining pages to check (other than the current one)
+    let mut remaining_pages = HashSet::from_iter(update.iter().copied());
+    remaining_pages.remove(page);
+
+    // Caclculate the order score for the page
+    if let Some(possible_rules) = rules.get(page) {
+        // Get the intersection of pages that have ordering rules and remaining pages,
+        // and recursively add their own scores to this one
+        let applicable_rules: HashSet<u16> = possible_rules
+            .intersection(&remaining_pages)
+            .copied()
+            .collect();
+        for applicable_rule in applicable_rules {
+            score += calculate_order_score(&applicable_rule, update, rules, previously_calculated)
+        }
+    }
+
+    // Store the caclulated score for the page for faster calculations for the rest of the pages
+    previously_calculated.insert(*page, score);
+
+    // Return the order score
+    score
+}
+
+/// Sorts an incorrectly ordered update so that it follows the rules
+pub fn reorder_update(update: &Vec<u16>, rules: &Rules) -> Vec<u16> {
+    // Create a new hash map for storing previously discovered score of given pages,
+    // which greatly improves the speed at which the sorting later on takes
+    let mut saved_scores: HashMap<u16, u64> = HashMap::new();
+
+    // Create a clone of the update
+    let mut ordered_update = update.clone();
+
+    // Sort the cloned copy of the update
+    ordered_update.sort_by(|x, y| {
+        calculate_order_score(x, update, rules, &mut saved_scores).cmp(&calculate_order_score(
+            y,
+            update,
+            rules,
+            &mut saved_scores,
+        ))
+    });
+
+    // Return the sorted update
+    ordered_update
+}
+
+/// Gets the middle page of the update
+fn middle_page(update: &[u16]) -> u64 {
+    update[(update.len() - 1) / 2] as u64
+}
+
+/// Solves part one for the puzzle input, getting the sum of the middle pages of the correctly
+/// ordered updates
+pub fn part1(input: &str) -> Result<u64, String> {
+    let (rules, updates) = generate_rules_and_updates(input)?;
+    let updates = ValidatedUpdates::new(updates, &rules);
+    Ok(updates.correct().map(|x| middle_page(x)).sum())
+}
+
+/// Solves part two for the puzzle input, getting the sum of the middle pages of the
+/// incorrectly ordered updates once they're reordered
+pub fn part2(input: &str) -> Result<u64, String> {
+    let (rules, updates) = generate_rules_and_updates(input)?;
+    let updates = ValidatedUpdates::new(updates, &rules);
+    Ok(updates
+        .incorrect()
+        .map(|x| middle_page(&reorder_update(x, &rules)))
+        .sum())
+}
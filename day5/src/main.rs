@@ -1,278 +1,345 @@
-use std::{
-    collections::{HashMap, HashSet},
-    fs,
+use std::fs;
+
+use clap::{Parser, Subcommand, ValueEnum};
+use common::{fail, read_input, About, Failure, Format};
+use day5::{
+    generate_rules, generate_rules_and_updates, generate_updates,
+    infer::infer_rules,
+    parse_rule, reorder_update, split_rules_and_updates,
+    wildcards::{complete_update, parse_partial_update},
+    Rules, ValidatedUpdates,
 };
 
-use clap::Parser;
-
 #[derive(Parser)]
+#[command(subcommand_negates_reqs = true)]
 struct CliArgs {
-    part: u64,
-    filepath: String,
+    #[command(subcommand)]
+    command: Option<Command>,
+    #[arg(required = true)]
+    part: Option<u64>,
+    #[arg(required = true)]
+    filepath: Option<String>,
+    /// Additional rules (`a|b`) to add before checking the updates
+    #[arg(long)]
+    add_rule: Vec<String>,
+    /// Rules (`a|b`) to remove before checking the updates
+    #[arg(long)]
+    remove_rule: Vec<String>,
+    /// Which page of each update to sum
+    #[arg(long, value_enum, default_value_t = PagePick::Middle)]
+    pick: PagePick,
+    /// Print the number of valid and invalid updates before the answer
+    #[arg(long)]
+    counts: bool,
+    /// Parse the input and print statistics about its structure instead of solving it
+    #[arg(long)]
+    parse_only: bool,
+    /// The format in which errors are reported
+    #[arg(long, value_enum, default_value_t = Format::Text)]
+    format: Format,
+}
+
+/// Commands that can be run instead of solving a part
+#[derive(Subcommand)]
+enum Command {
+    /// Print the rule-compliant ordering of a list of pages, using the rules from the file
+    Sort {
+        filepath: String,
+        /// The comma-separated pages to order (e.g. 75,47,61,53,29)
+        #[arg(long, value_delimiter = ',', required = true)]
+        pages: Vec<u16>,
+    },
+    /// Check whether each update, where pages can be wildcards (`?`), can be completed to follow
+    /// the rules, printing the middle page when every completion has the same one
+    Wildcards { filepath: String },
+    /// Infer rules (`a|b`) from updates that all follow them, such as when the rules are lost,
+    /// using only the updates after the last blank line of the file
+    Infer {
+        filepath: String,
+        /// The number of updates a pair of pages must appear in, in the same order, to infer a
+        /// rule for them
+        #[arg(long, default_value_t = 1)]
+        min_support: usize,
+        /// Write the inferred rules to the given file instead of printing them
+        #[arg(long)]
+        output: Option<String>,
+    },
+}
+
+/// Description of the puzzle solved by this binary
+const ABOUT: About = About {
+    day: 5,
+    title: "Print Queue",
+    input: "Page ordering rules (X|Y), a blank line, then comma-separated updates",
+    parts: &[1, 2],
+    features: &[],
+};
+
+/// Pages that can be picked out of an update for summing
+#[derive(Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum PagePick {
+    /// The first page of the update
+    First,
+    /// The middle page of the update
+    Middle,
+    /// The last page of the update
+    Last,
+}
+
+impl PagePick {
+    /// Picks the page out of the given update
+    fn pick(&self, update: &[u16]) -> u16 {
+        match self {
+            PagePick::First => update[0],
+            PagePick::Middle => update[(update.len() - 1) / 2],
+            PagePick::Last => update[update.len() - 1],
+        }
+    }
 }
 
 fn main() {
+    // Describe the puzzle instead if requested
+    common::handle_about::<CliArgs>(&ABOUT);
+
     // Parse CLI arguments
     let cli = CliArgs::parse();
 
-    // Run the code for the desired challenge part
-    match cli.part {
-        1 => main_part_one(cli.filepath),
-        2 => main_part_two(cli.filepath),
-        _ => panic!("Invalid selection part selection!"),
-    }
-}
+    // Report errors in the requested format
+    common::init(cli.format);
 
-/// Generate the rules for the page ordering, specifically rules that
-/// indicate an ordering is NOT in the correct order.  That means for
-/// any given page, a set of other pages is available that CANNOT come
-/// before it.
-fn generate_rules(rules_text: &str) -> HashMap<u16, HashSet<u16>> {
-    // Create an empty hash map to populate with rules
-    let mut all_rule_breaks: HashMap<u16, HashSet<u16>> = HashMap::new();
-
-    // Iterate through the rules text line by line
-    for line in rules_text.lines().map(|x| x.trim()) {
-        // Get the pages in the rule
-        let page_split: Vec<&str> = line.split("|").collect();
-        let leading_page = page_split[0].parse::<u16>().unwrap();
-        let following_page = page_split[1].parse::<u16>().unwrap();
-
-        // Either create a new set of pages for each entry (page), or add to
-        // the existing one
-        match all_rule_breaks.get_mut(&following_page) {
-            Some(entry) => {
-                entry.insert(leading_page);
-            }
-            None => {
-                let mut leading_page_set = HashSet::new();
-                leading_page_set.insert(leading_page);
-                all_rule_breaks.insert(following_page, leading_page_set);
-            }
+    // Run the requested command instead, if given
+    match &cli.command {
+        Some(Command::Sort { filepath, pages }) => {
+            main_sort(filepath, pages, &cli);
+            return;
         }
+        Some(Command::Wildcards { filepath }) => {
+            main_wildcards(filepath, &cli);
+            return;
+        }
+        Some(Command::Infer {
+            filepath,
+            min_support,
+            output,
+        }) => {
+            main_infer(filepath, *min_support, output.as_deref());
+            return;
+        }
+        None => {}
     }
 
-    // Return the rules
-    all_rule_breaks
-}
-
-/// Generate the list of updates from the provided text
-fn generate_updates(updates_text: &str) -> Vec<Vec<u16>> {
-    // Create a new vector to populate
-    let mut all_updates = Vec::new();
-
-    // For each line in the text:
-    // 1. Split by commas
-    // 2. Parse each number into a u16
-    // 3. Collect the list into a vector
-    // 4. Push it to the previously created vector
-    for line in updates_text.lines().map(|x| x.trim()) {
-        all_updates.push(line.split(",").map(|x| x.parse::<u16>().unwrap()).collect());
+    // Only parse the input if requested
+    let filepath = cli.filepath.as_ref().expect("Missing filepath");
+    if cli.parse_only {
+        report_structure(filepath);
     }
 
-    // Return the vector
-    all_updates
+    // Run the code for the desired challenge part
+    match cli.part {
+        Some(1) => main_part_one(filepath, &cli),
+        Some(2) => main_part_two(filepath, &cli),
+        _ => panic!("Invalid selection part selection!"),
+    }
 }
 
-// Convenience function for creating both the rules and updates from the provided text
-fn generate_rules_and_updates(input: &str) -> (HashMap<u16, HashSet<u16>>, Vec<Vec<u16>>) {
-    // Split the text by the double newline
-    let input_split: Vec<&str> = input.split("\n\n").collect();
-    let rules_str = input_split[0];
-    let updates_str = input_split[1];
+/// Fails with the given parse error
+fn parse_failure<T>(error: String) -> T {
+    fail(Failure::Parse(error))
+}
 
-    // Get the rules and updates from their respective parts
-    let rules = generate_rules(rules_str);
-    let updates = generate_updates(updates_str);
+/// Parses a rule given on the command line, failing if it's malformed
+fn read_rule(rule_text: &str) -> (u16, u16) {
+    parse_rule(rule_text).unwrap_or_else(parse_failure)
+}
 
-    // Return both the rules and updates
-    (rules, updates)
+/// Parses the rules and updates from the file contents, failing if they're malformed
+fn read_rules_and_updates(contents: &str) -> (Rules, Vec<Vec<u16>>) {
+    generate_rules_and_updates(contents).unwrap_or_else(parse_failure)
 }
 
-/// Checks an update if any rules (rule breaks) apply
-fn check_for_rule_break(update: &[u16], rules: &HashMap<u16, HashSet<u16>>) -> bool {
-    // Iterate through the update page by page
-    for (index, page) in update.iter().enumerate() {
-        // Create a hash set from the remaining pages after it in the update
-        let following_pages: HashSet<u16> = HashSet::from_iter(update[index..].iter().cloned());
-
-        // Get the applicable rule breaks for the given pages
-        match rules.get(page) {
-            // There are rules that must be checked for this page
-            Some(forbidden_following_pages) => {
-                // Get the intersection of rules that indicate bad ordering and remaining pages
-                // in the ordering
-                let all_found_forbidden_pages: HashSet<&u16> = following_pages
-                    .intersection(forbidden_following_pages)
-                    .collect();
-
-                // If the intersection is not empty, rules have be broken, so early return true
-                // to the caller
-                if !all_found_forbidden_pages.is_empty() {
-                    return true;
-                }
-            }
-            // No rules can be broken, so check the next page in the update
-            None => continue,
-        }
+/// Applies the requested rule changes to the rules
+fn apply_rule_changes(rules: &mut Rules, cli: &CliArgs) {
+    for rule_text in &cli.add_rule {
+        let (leading_page, following_page) = read_rule(rule_text);
+        rules.add_rule(leading_page, following_page);
+    }
+    for rule_text in &cli.remove_rule {
+        let (leading_page, following_page) = read_rule(rule_text);
+        rules.remove_rule(leading_page, following_page);
     }
-
-    // No rules were ever broken, return false
-    false
 }
 
-/// Calculates the order score for a given page
-///
-/// This is intended to be used for comparing pages for ordering.
-fn calculate_order_score(
-    page: &u16,
-    update: &Vec<u16>,
-    rules: &HashMap<u16, HashSet<u16>>,
-    previously_calculated: &mut HashMap<u16, u64>,
-) -> u64 {
-    // Checking for scores recursively for large lists can take a long time,
-    // so if a score is previously calculated and stored, we can skip recursively
-    // calculating it again
-    if previously_calculated.contains_key(page) {
-        return *previously_calculated.get(page).unwrap();
-    }
+/// Parses the rules and updates from the file, then prints the number of rules, updates and
+/// pages within the updates and exits
+fn report_structure(filepath: &str) -> ! {
+    let (rules, updates) = read_rules_and_updates(&read_input(filepath));
+    common::report_parse(&[
+        ("rules", rules.num_rules()),
+        ("updates", updates.len()),
+        ("pages", updates.iter().map(|x| x.len()).sum()),
+    ]);
+}
 
-    // Initialize the default score at 1
-    let mut score = 1;
-
-    // Get the remaining pages to check (other than the current one)
-    let mut remaining_pages = HashSet::from_iter(update.iter().copied());
-    remaining_pages.remove(page);
-
-    // Caclculate the order score for the page
-    if let Some(possible_rules) = rules.get(page) {
-        // Get the intersection of pages that have ordering rules and remaining pages,
-        // and recursively add their own scores to this one
-        let applicable_rules: HashSet<u16> = possible_rules
-            .intersection(&remaining_pages)
-            .copied()
-            .collect();
-        for applicable_rule in applicable_rules {
-            score += calculate_order_score(&applicable_rule, update, rules, previously_calculated)
-        }
+/// Reduces either the valid or invalid updates into a single value using the given reducer
+fn reduce_updates<T>(
+    updates: &ValidatedUpdates,
+    valid: bool,
+    init: T,
+    reducer: impl FnMut(T, &Vec<u16>) -> T,
+) -> T {
+    if valid {
+        updates.correct().fold(init, reducer)
+    } else {
+        updates.incorrect().fold(init, reducer)
     }
+}
 
-    // Store the caclulated score for the page for faster calculations for the rest of the pages
-    previously_calculated.insert(*page, score);
+/// Sums the picked page of either the valid or the invalid updates, where invalid updates
+/// are first reordered to follow the rules
+fn sum_picked_pages(updates: &ValidatedUpdates, rules: &Rules, valid: bool, pick: PagePick) -> u64 {
+    reduce_updates(updates, valid, 0, |sum, update| {
+        let page = if valid {
+            pick.pick(update)
+        } else {
+            pick.pick(&reorder_update(update, rules))
+        };
+        sum + page as u64
+    })
+}
 
-    // Return the order score
-    score
+/// Prints the number of valid and invalid updates
+fn print_counts(updates: &ValidatedUpdates) {
+    let num_valid = reduce_updates(updates, true, 0, |count, _| count + 1);
+    let num_invalid = reduce_updates(updates, false, 0, |count, _| count + 1);
+    println!("valid: {num_valid}");
+    println!("invalid: {num_invalid}");
 }
 
-/// Gets the list of middle pages for INCORRECTLY ordered pages
-///
-/// This functions finds incorrect orderings, sorts them, and returns middle pages for them
-/// as a vector.
-fn get_incorrectly_ordered_middles(
-    updates: &Vec<Vec<u16>>,
-    rules: &HashMap<u16, HashSet<u16>>,
-) -> Vec<u16> {
-    // Create a new list of middle pages to populate
-    let mut middle_pages = Vec::new();
-
-    // Iterate through the page updates
-    for update in updates {
-        // Create a new hash map for storing previously discovered score of given pages,
-        // which greatly improves the speed at which the sorting later on takes
-        let mut saved_scores: HashMap<u16, u64> = HashMap::new();
-
-        // If the update follows all the rules, skip it
-        if !check_for_rule_break(update, rules) {
-            continue;
+/// Reads the rules and validated updates from the file, applying any rule changes requested
+fn load_rules_and_updates(filepath: &str, cli: &CliArgs) -> (Rules, ValidatedUpdates) {
+    // Read the contents of the file
+    let contents = read_input(filepath);
+
+    // Get the rules and updates, and validate the updates
+    let (mut rules, updates) = read_rules_and_updates(&contents);
+    let mut validated_updates = ValidatedUpdates::new(updates, &rules);
+
+    // Add any additional rules, revalidating only the affected updates
+    for rule_text in &cli.add_rule {
+        let (leading_page, following_page) = read_rule(rule_text);
+        if rules.add_rule(leading_page, following_page) {
+            validated_updates.revalidate(&rules, leading_page, following_page);
         }
+    }
 
-        // Create a clone of the update
-        let mut ordered_update = update.clone();
-
-        // Sort the cloned copy of the update
-        ordered_update.sort_by(|x, y| {
-            calculate_order_score(x, update, rules, &mut saved_scores).cmp(&calculate_order_score(
-                y,
-                update,
-                rules,
-                &mut saved_scores,
-            ))
-        });
-
-        // Get the middle page of the newly sorted vector
-        let update_length = ordered_update.len();
-        let middle_page_index = (update_length - 1) / 2;
-        let middle_page = ordered_update[middle_page_index];
-
-        // Add the middle page to the vector
-        middle_pages.push(middle_page);
+    // Remove any requested rules, revalidating only the affected updates
+    for rule_text in &cli.remove_rule {
+        let (leading_page, following_page) = read_rule(rule_text);
+        if rules.remove_rule(leading_page, following_page) {
+            validated_updates.revalidate(&rules, leading_page, following_page);
+        }
     }
 
-    // Return all of the middle pages
-    middle_pages
+    // Return the rules and the validated updates
+    (rules, validated_updates)
 }
 
-/// Gets the list of middle pages for CORRECTLY ordered pages
-///
-/// This functions finds correct orderings and returns middle pages for them
-/// as a vector.
-fn get_correctly_ordered_middles(
-    updates: &Vec<Vec<u16>>,
-    rules: &HashMap<u16, HashSet<u16>>,
-) -> Vec<u16> {
-    // Create a new list of middle pages to populate
-    let mut middle_pages = Vec::new();
-
-    // Iterate through the page updates
-    for update_pages in updates {
-        // If the update doesn;t follow all the rules, skip it
-        if check_for_rule_break(update_pages, rules) {
-            continue;
-        }
-
-        // Update is valid, so get the middle page
-        let update_length = update_pages.len();
-        let middle_page_index = (update_length - 1) / 2;
-        let middle_page = update_pages[middle_page_index];
+fn main_part_one(filepath: &str, cli: &CliArgs) {
+    // Get the rules and validated updates
+    let (rules, updates) = load_rules_and_updates(filepath, cli);
 
-        // Add the middle page to the vector
-        middle_pages.push(middle_page);
+    // Print the number of valid and invalid updates if requested
+    if cli.counts {
+        print_counts(&updates);
     }
 
-    // Return all of the middle pages
-    middle_pages
-}
+    // Get the sum of the picked pages of correctly ordered updates
+    let sum_valid_pages = sum_picked_pages(&updates, &rules, true, cli.pick);
 
-fn main_part_one(filepath: String) {
-    // Read the contents of the file
-    let contents = fs::read_to_string(filepath).expect("Invalid filepath");
+    // Announce the sum
+    println!("{sum_valid_pages}");
+}
 
-    // Get the rules and updates
-    let (rules, updates) = generate_rules_and_updates(&contents);
+fn main_part_two(filepath: &str, cli: &CliArgs) {
+    // Get the rules and validated updates
+    let (rules, updates) = load_rules_and_updates(filepath, cli);
 
-    // Get the middle pages of correctly ordered updates
-    let valid_middle_pages: Vec<u16> = get_correctly_ordered_middles(&updates, &rules);
+    // Print the number of valid and invalid updates if requested
+    if cli.counts {
+        print_counts(&updates);
+    }
 
-    // Get the sum of the middle pages
-    let sum_valid_middle_pages: u64 = valid_middle_pages.iter().map(|x| *x as u64).sum();
+    // Get the sum of the picked pages of the reordered incorrectly ordered updates
+    let sum_reordered_pages = sum_picked_pages(&updates, &rules, false, cli.pick);
 
     // Announce the sum
-    println!("{sum_valid_middle_pages}");
+    println!("{sum_reordered_pages}");
 }
 
-fn main_part_two(filepath: String) {
-    // Read the contents of the file
-    let contents = fs::read_to_string(filepath).expect("Invalid filepath");
+/// Prints the rule-compliant ordering of the given pages
+fn main_sort(filepath: &str, pages: &[u16], cli: &CliArgs) {
+    // Get the rules, applying any requested changes to them
+    let (rules, _) = load_rules_and_updates(filepath, cli);
 
-    // Get the rules and updates
-    let (rules, updates) = generate_rules_and_updates(&contents);
+    // Reorder the pages to follow the rules
+    let ordered_pages = reorder_update(&pages.to_vec(), &rules);
 
-    // Get the intended middle pages of incorrectly ordered updates
-    let reordered_middle_pages = get_incorrectly_ordered_middles(&updates, &rules);
+    // Announce the ordering in the same format as the updates
+    let ordered_text: Vec<String> = ordered_pages.iter().map(|x| x.to_string()).collect();
+    println!("{}", ordered_text.join(","));
+}
 
-    // Get the sum of the middle pages
-    let sum_reordered_middle_pages: u64 = reordered_middle_pages.iter().map(|x| *x as u64).sum();
+/// Prints, for each update with wildcards, whether it can be completed to follow the rules and
+/// its middle page if it is forced, as CSV
+fn main_wildcards(filepath: &str, cli: &CliArgs) {
+    // Read the contents of the file, and split off the updates from the rules
+    let contents = read_input(filepath);
+    let (rules_text, updates_text) =
+        split_rules_and_updates(&contents).unwrap_or_else(parse_failure);
+
+    // Get the rules, applying any requested changes to them
+    let mut rules = generate_rules(rules_text).unwrap_or_else(parse_failure);
+    apply_rule_changes(&mut rules, cli);
+
+    // Complete each update as it is read
+    println!("update,consistent,middle");
+    for (index, line) in updates_text
+        .lines()
+        .filter(|x| !x.trim().is_empty())
+        .enumerate()
+    {
+        let update = parse_partial_update(line)
+            .unwrap_or_else(|error| parse_failure(format!("Update {}: {error}", index + 1)));
+        let completion = complete_update(&update, &rules);
+        let middle = completion.middle.map_or(String::new(), |x| x.to_string());
+        println!("{},{},{middle}", index + 1, completion.consistent);
+    }
+}
 
-    // Announce the sum
-    println!("{sum_reordered_middle_pages}");
+/// Infers the rules from the updates in the given file, which all follow them, writing the
+/// rules to the given file (printing the number of them) or printing them if none is given
+fn main_infer(filepath: &str, min_support: usize, output: Option<&str>) {
+    // Read the contents of the file, using only the updates after any rules
+    let contents = read_input(filepath);
+    let updates_text = contents
+        .trim()
+        .rsplit_once("\n\n")
+        .map_or(contents.trim(), |(_, x)| x);
+    let updates = generate_updates(updates_text).unwrap_or_else(parse_failure);
+
+    // Infer the rules from the order of the pages in the updates
+    let rules = infer_rules(&updates, min_support);
+    let rules_text = rules.to_text();
+
+    // Print the rules, or write them to the given file
+    let Some(output_filepath) = output else {
+        print!("{rules_text}");
+        return;
+    };
+    fs::write(output_filepath, rules_text).unwrap_or_else(|error| {
+        fail(Failure::Io(format!(
+            "Could not write {output_filepath}: {error}"
+        )))
+    });
+    println!("{}", rules.num_rules());
 }
@@ -0,0 +1,162 @@
+use std::collections::HashSet;
+
+use crate::Rules;
+
+/// Whether an update with wildcards can be completed to follow the rules, and the middle page
+/// of it if every completion agrees on it
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Completion {
+    /// Whether any completion of the update follows the rules
+    pub consistent: bool,
+    /// The middle page of the update, if every completion has the same one
+    pub middle: Option<u16>,
+}
+
+/// Parse an update where pages can be left as wildcards (`?`), which can be any page
+pub fn parse_partial_update(line: &str) -> Result<Vec<Option<u16>>, String> {
+    line.trim()
+        .split(',')
+        .map(|x| match x.trim() {
+            "?" => Ok(None),
+            page => page
+                .parse::<u16>()
+                .map(Some)
+                .map_err(|_| format!("could not parse {page:?} as a page")),
+        })
+        .collect()
+}
+
+/// Checks whether the first page is allowed to come before the second one
+fn allowed_before(rules: &Rules, first: u16, second: u16) -> bool {
+    // The first page can't come before a page that must come before it
+    rules.get(&first).is_none_or(|x| !x.contains(&second))
+}
+
+/// Checks whether the two pages can be at the given positions in the same update
+fn compatible(
+    rules: &Rules,
+    position: usize,
+    page: u16,
+    other_position: usize,
+    other_page: u16,
+) -> bool {
+    if page == other_page {
+        return false;
+    }
+    if position < other_position {
+        allowed_before(rules, page, other_page)
+    } else {
+        allowed_before(rules, other_page, page)
+    }
+}
+
+/// Removes pages from the possibilities of each position until every page left has a
+/// compatible page at every other position, returning false if a position runs out of pages
+fn propagate(rules: &Rules, domains: &mut [Vec<u16>]) -> bool {
+    let mut changed = true;
+    while changed {
+        changed = false;
+        for position in 0..domains.len() {
+            for other_position in 0..domains.len() {
+                if position == other_position {
+                    continue;
+                }
+
+                // Keep only the pages that are supported by some page at the other position
+                let before = domains[position].len();
+                let others = domains[other_position].clone();
+                domains[position].retain(|page| {
+                    others
+                        .iter()
+                        .any(|other| compatible(rules, position, *page, other_position, *other))
+                });
+
+                // If nothing is left, no completion exists
+                if domains[position].is_empty() {
+                    return false;
+                }
+                changed |= domains[position].len() != before;
+            }
+        }
+    }
+    true
+}
+
+/// Searches for a completion picking a page for each position in order, consistent with the
+/// pages already picked
+fn search(rules: &Rules, domains: &[Vec<u16>], picked: &mut Vec<u16>) -> bool {
+    // If every position has a page, the completion is found
+    let position = picked.len();
+    if position == domains.len() {
+        return true;
+    }
+
+    // Try each page for the position that fits with the pages already picked
+    for page in &domains[position] {
+        let fits = picked.iter().enumerate().all(|(other_position, other)| {
+            compatible(rules, position, *page, other_position, *other)
+        });
+        if fits {
+            picked.push(*page);
+            if search(rules, domains, picked) {
+                return true;
+            }
+            picked.pop();
+        }
+    }
+
+    // No page fits at this position
+    false
+}
+
+/// Checks whether the update can be completed, and finds the middle page if it is forced
+///
+/// Wildcards can be any page mentioned in the rules that isn't already in the update.
+pub fn complete_update(update: &[Option<u16>], rules: &Rules) -> Completion {
+    // Get the pages that each position could be
+    let known: HashSet<u16> = update.iter().flatten().copied().collect();
+    let free_pages: Vec<u16> = rules.pages().difference(&known).copied().collect();
+    let mut domains: Vec<Vec<u16>> = update
+        .iter()
+        .map(|page| match page {
+            Some(page) => vec![*page],
+            None => free_pages.clone(),
+        })
+        .collect();
+
+    // Narrow down the possibilities using the rules between each pair of positions
+    let inconsistent = Completion {
+        consistent: false,
+        middle: None,
+    };
+    if !propagate(rules, &mut domains) {
+        return inconsistent;
+    }
+
+    // Try each remaining possibility for the middle page, stopping once two of them work
+    let middle_position = (update.len() - 1) / 2;
+    let mut middles = Vec::new();
+    for page in domains[middle_position].clone() {
+        let mut fixed = domains.clone();
+        fixed[middle_position] = vec![page];
+        if propagate(rules, &mut fixed) && search(rules, &fixed, &mut Vec::new()) {
+            middles.push(page);
+            if middles.len() > 1 {
+                break;
+            }
+        }
+    }
+
+    // Return whether any completion exists, and the middle page if only one is possible
+    match middles.as_slice() {
+        [] => inconsistent,
+        [middle] => Completion {
+            consistent: true,
+            middle: Some(*middle),
+        },
+        _ => Completion {
+            consistent: true,
+            middle: None,
+        },
+    }
+}
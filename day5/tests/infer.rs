@@ -0,0 +1,84 @@
+use day5::{
+    check_for_rule_break, generate_rules, generate_rules_and_updates, infer::infer_rules, Rules,
+    ValidatedUpdates,
+};
+
+const EXAMPLE: &str = include_str!("../inputs/example.txt");
+
+/// Gets the rules of the example and the updates that follow them
+fn example_correct_updates() -> (Rules, Vec<Vec<u16>>) {
+    let (rules, updates) = generate_rules_and_updates(EXAMPLE).unwrap();
+    let correct = ValidatedUpdates::new(updates, &rules)
+        .correct()
+        .cloned()
+        .collect();
+    (rules, correct)
+}
+
+/// Gets the rules as (leading page, following page) pairs, in order
+fn pairs(rules: &Rules) -> Vec<(u16, u16)> {
+    rules
+        .to_text()
+        .lines()
+        .map(|line| {
+            let (leading_page, following_page) = line.split_once('|').unwrap();
+            (
+                leading_page.parse().unwrap(),
+                following_page.parse().unwrap(),
+            )
+        })
+        .collect()
+}
+
+#[test]
+fn infers_rules_from_example_updates() {
+    let (rules, correct) = example_correct_updates();
+    let inferred = infer_rules(&correct, 1);
+    assert_eq!(inferred.num_rules(), 18);
+
+    // Every inferred rule is one of the original rules
+    let original = pairs(&rules);
+    for pair in pairs(&inferred) {
+        assert!(original.contains(&pair), "{pair:?}");
+    }
+
+    // The updates follow the inferred rules
+    for update in &correct {
+        assert!(!check_for_rule_break(update, &inferred), "{update:?}");
+    }
+}
+
+#[test]
+fn min_support_drops_rare_orders() {
+    let (_, correct) = example_correct_updates();
+    assert_eq!(
+        pairs(&infer_rules(&correct, 2)),
+        vec![(29, 13), (53, 29), (61, 29), (61, 53), (75, 29)]
+    );
+    assert_eq!(infer_rules(&correct, 4).num_rules(), 0);
+}
+
+#[test]
+fn contradicted_orders_are_not_inferred() {
+    let updates = vec![vec![1, 2, 3], vec![2, 1, 3]];
+    assert_eq!(pairs(&infer_rules(&updates, 1)), vec![(1, 3), (2, 3)]);
+}
+
+#[test]
+fn to_text_round_trip() {
+    let (rules, _) = generate_rules_and_updates(EXAMPLE).unwrap();
+    let text = rules.to_text();
+    assert_eq!(text.lines().count(), 21);
+    assert_eq!(generate_rules(&text), Ok(rules));
+
+    // The text holds the same rules as the example, ordered by leading then following page
+    let mut expected: Vec<&str> = EXAMPLE.split("\n\n").next().unwrap().lines().collect();
+    expected.sort_by_key(|x| pairs(&generate_rules(x).unwrap()));
+    assert_eq!(text.lines().collect::<Vec<&str>>(), expected);
+}
+
+#[test]
+fn to_text_of_no_rules_is_empty() {
+    assert_eq!(Rules::new().to_text(), "");
+    assert_eq!(generate_rules(""), Ok(Rules::new()));
+}
@@ -0,0 +1,48 @@
+use day5::{generate_rules_and_updates, generate_updates, parse_rule};
+
+const EXAMPLE: &str = include_str!("../inputs/example.txt");
+
+#[test]
+fn parses_example() {
+    let (rules, updates) = generate_rules_and_updates(EXAMPLE).unwrap();
+    assert_eq!(rules.num_rules(), 21);
+    assert_eq!(updates.len(), 6);
+    assert_eq!(updates[0], vec![75, 47, 61, 53, 29]);
+}
+
+#[test]
+fn parses_rule() {
+    assert_eq!(parse_rule(" 47|53\n"), Ok((47, 53)));
+}
+
+#[test]
+fn rejects_malformed_rules() {
+    assert_eq!(
+        parse_rule("47-53"),
+        Err("expected a rule like 47|53, got \"47-53\"".to_string())
+    );
+    assert_eq!(
+        parse_rule("47|x"),
+        Err("could not parse \"x\" as a page".to_string())
+    );
+    assert_eq!(
+        generate_rules_and_updates("47|53\nfoo\n\n47,53\n"),
+        Err("Line 2: expected a rule like 47|53, got \"foo\"".to_string())
+    );
+}
+
+#[test]
+fn rejects_malformed_updates() {
+    assert_eq!(
+        generate_updates("75,47\n\n75,,47\n"),
+        Err("Update 2: could not parse \"\" as a page".to_string())
+    );
+}
+
+#[test]
+fn rejects_missing_updates() {
+    assert_eq!(
+        generate_rules_and_updates("47|53\n"),
+        Err("Missing the blank line between the rules and the updates".to_string())
+    );
+}
@@ -0,0 +1,66 @@
+use day5::{check_for_rule_break, generate_rules_and_updates, Rules, ValidatedUpdates};
+
+const EXAMPLE: &str = include_str!("../inputs/example.txt");
+
+/// Gets the updates that currently follow the rules
+fn correct(updates: &ValidatedUpdates) -> Vec<Vec<u16>> {
+    updates.correct().cloned().collect()
+}
+
+#[test]
+fn builds_rules_incrementally() {
+    let mut rules = Rules::new();
+    assert_eq!(rules.num_rules(), 0);
+    assert!(rules.add_rule(47, 53));
+    assert!(rules.add_rule(97, 53));
+    assert!(!rules.add_rule(47, 53));
+    assert_eq!(rules.num_rules(), 2);
+    assert!(check_for_rule_break(&[53, 47], &rules));
+    assert!(!check_for_rule_break(&[47, 97, 53], &rules));
+
+    // Removing the rules should leave no trace of them
+    assert!(rules.remove_rule(47, 53));
+    assert!(!rules.remove_rule(47, 53));
+    assert!(rules.remove_rule(97, 53));
+    assert_eq!(rules, Rules::new());
+    assert!(!check_for_rule_break(&[53, 47], &rules));
+}
+
+#[test]
+fn validates_example() {
+    let (rules, updates) = generate_rules_and_updates(EXAMPLE).unwrap();
+    let validated = ValidatedUpdates::new(updates, &rules);
+    assert_eq!(
+        correct(&validated),
+        vec![
+            vec![75, 47, 61, 53, 29],
+            vec![97, 61, 53, 29, 13],
+            vec![75, 29, 13],
+        ]
+    );
+    assert_eq!(validated.incorrect().count(), 3);
+}
+
+#[test]
+fn revalidates_only_changed_rules() {
+    let (mut rules, updates) = generate_rules_and_updates(EXAMPLE).unwrap();
+    let mut validated = ValidatedUpdates::new(updates.clone(), &rules);
+
+    // Removing the only rule broken by 75,97,47,61,53 makes it valid
+    assert!(rules.remove_rule(97, 75));
+    validated.revalidate(&rules, 97, 75);
+    assert!(correct(&validated).contains(&vec![75, 97, 47, 61, 53]));
+    assert_eq!(
+        correct(&validated),
+        correct(&ValidatedUpdates::new(updates.clone(), &rules))
+    );
+
+    // Adding a rule broken by 75,29,13 makes it invalid
+    assert!(rules.add_rule(13, 75));
+    validated.revalidate(&rules, 13, 75);
+    assert!(!correct(&validated).contains(&vec![75, 29, 13]));
+    assert_eq!(
+        correct(&validated),
+        correct(&ValidatedUpdates::new(updates, &rules))
+    );
+}
@@ -0,0 +1,50 @@
+use day5::{check_for_rule_break, generate_rules_and_updates, reorder_update};
+
+const EXAMPLE: &str = include_str!("../inputs/example.txt");
+
+#[test]
+fn sorts_incorrect_example_updates() {
+    let (rules, _) = generate_rules_and_updates(EXAMPLE).unwrap();
+    assert_eq!(
+        reorder_update(&vec![75, 97, 47, 61, 53], &rules),
+        vec![97, 75, 47, 61, 53]
+    );
+    assert_eq!(reorder_update(&vec![61, 13, 29], &rules), vec![61, 29, 13]);
+    assert_eq!(
+        reorder_update(&vec![97, 13, 75, 29, 47], &rules),
+        vec![97, 75, 47, 29, 13]
+    );
+}
+
+#[test]
+fn keeps_correct_order() {
+    let (rules, _) = generate_rules_and_updates(EXAMPLE).unwrap();
+    let pages = vec![75, 47, 61, 53, 29];
+    assert_eq!(reorder_update(&pages, &rules), pages);
+}
+
+#[test]
+fn sorts_ad_hoc_pages() {
+    // Pages in an order not found in any of the updates
+    let (rules, _) = generate_rules_and_updates(EXAMPLE).unwrap();
+    for pages in [
+        vec![29, 75, 53],
+        vec![13, 97],
+        vec![53, 13, 61, 47, 97, 75, 29],
+    ] {
+        let ordered = reorder_update(&pages, &rules);
+        assert!(!check_for_rule_break(&ordered, &rules), "{pages:?}");
+        let mut sorted_pages = pages.clone();
+        sorted_pages.sort();
+        let mut sorted_ordered = ordered.clone();
+        sorted_ordered.sort();
+        assert_eq!(sorted_ordered, sorted_pages);
+    }
+    assert_eq!(reorder_update(&vec![29, 75, 53], &rules), vec![75, 53, 29]);
+}
+
+#[test]
+fn single_page_is_unchanged() {
+    let (rules, _) = generate_rules_and_updates(EXAMPLE).unwrap();
+    assert_eq!(reorder_update(&vec![47], &rules), vec![47]);
+}
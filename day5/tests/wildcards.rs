@@ -0,0 +1,86 @@
+use day5::{
+    generate_rules_and_updates,
+    wildcards::{complete_update, parse_partial_update, Completion},
+};
+
+const EXAMPLE: &str = include_str!("../inputs/example.txt");
+
+/// Completes the update with wildcards using the rules from the example
+fn complete(line: &str) -> Completion {
+    let (rules, _) = generate_rules_and_updates(EXAMPLE).unwrap();
+    complete_update(&parse_partial_update(line).unwrap(), &rules)
+}
+
+#[test]
+fn parses_wildcards() {
+    assert_eq!(
+        parse_partial_update("75, ?,61,?\n"),
+        Ok(vec![Some(75), None, Some(61), None])
+    );
+}
+
+#[test]
+fn rejects_invalid_pages() {
+    assert_eq!(
+        parse_partial_update("75,x"),
+        Err("could not parse \"x\" as a page".to_string())
+    );
+}
+
+#[test]
+fn wildcard_resolved_by_propagation() {
+    // Only 61 fits between 47 and 53
+    assert_eq!(
+        complete("75,47,?,53,29"),
+        Completion {
+            consistent: true,
+            middle: Some(61),
+        }
+    );
+
+    // The middle page is forced even though the other wildcards could each be two pages
+    assert_eq!(
+        complete("?,47,?,53,?"),
+        Completion {
+            consistent: true,
+            middle: Some(61),
+        }
+    );
+}
+
+#[test]
+fn wildcard_left_ambiguous() {
+    assert_eq!(
+        complete("75,?,29"),
+        Completion {
+            consistent: true,
+            middle: None,
+        }
+    );
+    assert_eq!(
+        complete("?,?,?"),
+        Completion {
+            consistent: true,
+            middle: None,
+        }
+    );
+}
+
+#[test]
+fn inconsistent_update() {
+    // Nothing can come before 97
+    assert_eq!(
+        complete("?,97,?,?,13"),
+        Completion {
+            consistent: false,
+            middle: None,
+        }
+    );
+    assert_eq!(
+        complete("13,?,97"),
+        Completion {
+            consistent: false,
+            middle: None,
+        }
+    );
+}
@@ -0,0 +1,55 @@
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use day6::{analyze_guard_route, parse_game, play_game};
+
+/// Generates a square map of the given size with obstructions scattered over it and the guard
+/// in the middle, similar to the puzzle inputs
+fn generate_map(size: usize) -> String {
+    let mut state: u64 = 0x2545_f491_4f6c_dd1d;
+    let mut map = String::new();
+    for y in 0..size {
+        for x in 0..size {
+            // Step a xorshift generator to decide whether the cell is obstructed
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            let character = if (x, y) == (size / 2, size / 2) {
+                '^'
+            } else if state.is_multiple_of(50) {
+                '#'
+            } else {
+                '.'
+            };
+            map.push(character);
+        }
+        map.push('\n');
+    }
+    map
+}
+
+fn patrol(c: &mut Criterion) {
+    let mut group = c.benchmark_group("patrol");
+    group.sample_size(10);
+
+    // Parse the map and walk the guard's route, as for part one
+    let map = generate_map(130);
+    group.bench_function("part one", |b| {
+        b.iter(|| {
+            let (mut guard, map) = parse_game(black_box(&map)).unwrap();
+            play_game(&mut guard, &map);
+            guard.history.len()
+        })
+    });
+
+    // Also try an obstruction at every step of the route, as for part two
+    group.bench_function("part two", |b| {
+        b.iter(|| {
+            let (mut guard, map) = parse_game(black_box(&map)).unwrap();
+            play_game(&mut guard, &map);
+            analyze_guard_route(&guard, &map)
+        })
+    });
+    group.finish();
+}
+
+criterion_group!(benches, patrol);
+criterion_main!(benches);
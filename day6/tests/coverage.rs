@@ -0,0 +1,43 @@
+use day6::{parse_game, patrol_coverage, Coverage};
+
+const EXAMPLE: &str = include_str!("../inputs/example.txt");
+
+/// Gets the coverage of the guard's patrol for the given map
+fn coverage(input: &str) -> Coverage {
+    let (mut guard, map) = parse_game(input).unwrap();
+    patrol_coverage(&mut guard, &map)
+}
+
+#[test]
+fn example_coverage() {
+    let coverage = coverage(EXAMPLE);
+    assert_eq!(coverage.reachable, 92);
+    assert_eq!(coverage.visited, 41);
+    assert_eq!(coverage.unvisited.len(), 51);
+    assert_eq!(coverage.unvisited[..3], [(0, 0), (1, 0), (2, 0)]);
+    assert_eq!(coverage.unvisited.last(), Some(&(9, 9)));
+}
+
+#[test]
+fn visited_cells_are_never_unvisited() {
+    let coverage = coverage(EXAMPLE);
+    assert!(!coverage.unvisited.contains(&(4, 6)));
+    assert_eq!(
+        coverage.reachable,
+        coverage.visited + coverage.unvisited.len()
+    );
+}
+
+#[test]
+fn enclosed_cells_are_not_reachable() {
+    // The top left cell is walled off, and the guard leaves after a single turn
+    let input = ".#.\n#..\n.^.\n";
+    assert_eq!(
+        coverage(input),
+        Coverage {
+            reachable: 6,
+            visited: 3,
+            unvisited: vec![(2, 0), (0, 2), (2, 2)],
+        }
+    );
+}
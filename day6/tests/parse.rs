@@ -0,0 +1,39 @@
+use day6::parse_game;
+
+const EXAMPLE: &str = include_str!("../inputs/example.txt");
+
+/// Gets the error from parsing the given map
+fn parse_error(input: &str) -> String {
+    parse_game(input).err().expect("The map was parsed")
+}
+
+#[test]
+fn parses_example() {
+    let (guard, map) = parse_game(EXAMPLE).unwrap();
+    let start = guard.location.unwrap();
+    assert_eq!((start.x, start.y), (4, 6));
+    assert_eq!(map.start_location, Some(start));
+    assert_eq!((map.space_map.width(), map.space_map.height()), (10, 10));
+}
+
+#[test]
+fn rejects_unexpected_characters() {
+    assert_eq!(
+        parse_error("..^\n.x.\n"),
+        "Line 2: unexpected character 'x'"
+    );
+}
+
+#[test]
+fn rejects_missing_or_extra_guards() {
+    assert_eq!(parse_error("...\n.#.\n"), "The map has no guard");
+    assert_eq!(parse_error("^..\n..^\n"), "Line 2: found a second guard");
+}
+
+#[test]
+fn rejects_ragged_maps() {
+    assert_eq!(
+        parse_error("..^\n..\n"),
+        "Row 1 has 2 cells, but expected 3"
+    );
+}
@@ -0,0 +1,65 @@
+use std::collections::HashSet;
+
+use criterion::{criterion_group, criterion_main, Criterion};
+use day7::{parse_data, Operation, PossibleEquation, Solver};
+
+/// The example equations from the puzzle, along with some longer unsolvable ones that make
+/// both solvers try every combination of operations
+const EQUATIONS: &str = "190: 10 19
+3267: 81 40 27
+83: 17 5
+156: 15 6
+7290: 6 8 6 15
+161011: 16 10 13
+192: 17 8 14
+21037: 9 7 18 13
+292: 11 6 16 20
+999999999999: 1 2 3 4 5 6 7 8 9 1 2 3
+888888888888: 9 8 7 6 5 4 3 2 1 9 8 7
+";
+
+/// Counts how many of the equations are solvable with the given operations and solver
+fn count_solvable(
+    equations: &[PossibleEquation],
+    operations: &HashSet<Operation>,
+    solver: Solver,
+) -> usize {
+    equations
+        .iter()
+        .filter(|x| x.is_solvable(operations, solver))
+        .count()
+}
+
+fn solvers(c: &mut Criterion) {
+    let equations = parse_data(EQUATIONS, false).unwrap();
+    let operation_sets = [
+        (
+            "part one",
+            HashSet::from([Operation::Addition, Operation::Multiplication]),
+        ),
+        (
+            "part two",
+            HashSet::from([
+                Operation::Addition,
+                Operation::Multiplication,
+                Operation::Concatenation,
+            ]),
+        ),
+    ];
+
+    for (name, operations) in &operation_sets {
+        let mut group = c.benchmark_group(*name);
+        for (solver_name, solver) in [
+            ("recursive", Solver::Recursive),
+            ("enumeration", Solver::Enumeration),
+        ] {
+            group.bench_function(solver_name, |b| {
+                b.iter(|| count_solvable(&equations, operations, solver))
+            });
+        }
+        group.finish();
+    }
+}
+
+criterion_group!(benches, solvers);
+criterion_main!(benches);
@@ -0,0 +1,507 @@
+use std::{
+    collections::{HashMap, HashSet, VecDeque},
+    ops::RangeInclusive,
+};
+
+use clap::ValueEnum;
+
+/// The largest number of inputs for which the automatic solver enumerates every combination
+/// of operations instead of searching recursively
+pub const MAX_ENUMERATED_INPUTS: usize = 16;
+
+/// Possible operations that can be performed
+#[derive(PartialEq, Eq, Hash, Clone, Copy, Debug)]
+pub enum Operation {
+    Addition,
+    Multiplication,
+    Concatenation,
+}
+
+impl Operation {
+    /// Performs the operation on the two given numbers
+    pub fn apply(&self, x: i64, y: i64) -> i64 {
+        match self {
+            Operation::Multiplication => x * y,
+            Operation::Addition => x + y,
+            Operation::Concatenation => combine_numbers(x, y),
+        }
+    }
+}
+
+/// Methods of checking whether an equation is solvable
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum)]
+pub enum Solver {
+    /// Enumerate the combinations of operations for equations with few enough inputs, and
+    /// search recursively otherwise
+    #[default]
+    Auto,
+    /// Search recursively, pruning once the running value is too large
+    Recursive,
+    /// Enumerate every combination of operations
+    Enumeration,
+}
+
+/// Possible equations representation, including results and inputs
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct PossibleEquation {
+    pub result: i64,
+    pub inputs: VecDeque<i64>,
+    /// The text of the inputs, as originally written or as produced by previous operations
+    pub tokens: VecDeque<String>,
+    /// Whether concatenation uses the text of the inputs rather than their values
+    pub strict_concat: bool,
+    // operations: Vec<Operation>,
+}
+
+impl PossibleEquation {
+    /// Checks whether the equation is solvable using the given solver
+    pub fn is_solvable(&self, operations_allowed: &HashSet<Operation>, solver: Solver) -> bool {
+        match solver {
+            Solver::Recursive => self.is_solvable_recursively(operations_allowed),
+            Solver::Enumeration => self.is_solvable_by_enumeration(operations_allowed),
+            Solver::Auto if self.inputs.len() <= MAX_ENUMERATED_INPUTS => {
+                self.is_solvable_by_enumeration(operations_allowed)
+            }
+            Solver::Auto => self.is_solvable_recursively(operations_allowed),
+        }
+    }
+
+    /// Checks whether the equation is solvable by trying every combination of operations in
+    /// turn, counting through them in base 3 (or base 2 without concatenation)
+    ///
+    /// This evaluates each combination in a tight loop, without the recursion and copying of
+    /// the inputs done by `is_solvable_recursively()`.  If there are too many combinations to
+    /// count through, it falls back to solving recursively instead.
+    pub fn is_solvable_by_enumeration(&self, operations_allowed: &HashSet<Operation>) -> bool {
+        // Get the allowed operations in a fixed order, so each digit of the counter is one
+        let operations: Vec<Operation> = [
+            Operation::Addition,
+            Operation::Multiplication,
+            Operation::Concatenation,
+        ]
+        .into_iter()
+        .filter(|x| operations_allowed.contains(x))
+        .collect();
+        let base = operations.len() as u64;
+
+        // Get the inputs, along with the factor each one shifts the running value by when
+        // concatenated onto it (using the text of the input if strict)
+        let inputs: Vec<i64> = self.inputs.iter().copied().collect();
+        let shifts: Vec<i64> = self
+            .tokens
+            .iter()
+            .zip(&inputs)
+            .map(|(token, input)| {
+                if self.strict_concat {
+                    10_i64.pow(token.len() as u32)
+                } else {
+                    reverse_factor_for(*input)
+                }
+            })
+            .collect();
+
+        // Count through every combination of operations between the inputs, solving
+        // recursively if the number of combinations doesn't fit in the counter
+        let Some(num_combinations) = base.checked_pow(inputs.len() as u32 - 1) else {
+            return self.is_solvable_recursively(operations_allowed);
+        };
+        'combinations: for combination in 0..num_combinations {
+            // Evaluate the combination left to right, using each digit of the counter as the
+            // operation to apply
+            let mut counter = combination;
+            let mut value = inputs[0];
+            for index in 1..inputs.len() {
+                let y = inputs[index];
+                value = match operations[(counter % base) as usize] {
+                    Operation::Addition => value + y,
+                    Operation::Multiplication => value * y,
+                    Operation::Concatenation => value * shifts[index] + y,
+                };
+                counter /= base;
+
+                // The value can only grow, so stop once it's too large
+                if value > self.result {
+                    continue 'combinations;
+                }
+            }
+
+            // Check whether the combination gives the result
+            if value == self.result {
+                return true;
+            }
+        }
+
+        // No combination of operations gave the result
+        false
+    }
+
+    /// Checks whether the equation is solvable, recursively if needed
+    pub fn is_solvable_recursively(&self, operations_allowed: &HashSet<Operation>) -> bool {
+        // Get the first two inputs at the top of the inputs list
+        let mut inputs = self.inputs.clone();
+        let x = inputs.pop_front().expect("Missing first number");
+        let y = inputs.pop_front().expect("Missing second nunber");
+
+        // If there are no remaining inputs other than these two, operate on them directly
+        if inputs.is_empty() {
+            // If they are valid via multiplication or addition, return true
+            let mult = x * y == self.result;
+            let add = x + y == self.result;
+            if mult || add {
+                return true;
+            }
+
+            // If concatenation is not allowed, return false
+            if !operations_allowed.contains(&Operation::Concatenation) {
+                return false;
+            }
+
+            // Check the actual result of trying to concatenate the numbers
+            return self.as_if_next_operation(Operation::Concatenation).inputs[0] == self.result;
+        }
+
+        // Recursively check possible values for multiplication, assuming the answer is still even possible
+        let z_mult = x * y;
+        if z_mult <= self.result
+            && self
+                .as_if_next_operation(Operation::Multiplication)
+                .is_solvable_recursively(operations_allowed)
+        {
+            return true;
+        }
+
+        // Recursively check possible values for addition, assuming the answer is still even possible
+        let z_add = x + y;
+        if z_add <= self.result
+            && self
+                .as_if_next_operation(Operation::Addition)
+                .is_solvable_recursively(operations_allowed)
+        {
+            return true;
+        }
+
+        // If concatenation is not allowed, return false
+        if !operations_allowed.contains(&Operation::Concatenation) {
+            return false;
+        }
+
+        // Recursively check the actual results of trying to concatenate the numbers, assuming
+        // the answer is still even possible
+        let concatenated = self.as_if_next_operation(Operation::Concatenation);
+        concatenated.inputs[0] <= self.result
+            && concatenated.is_solvable_recursively(operations_allowed)
+    }
+
+    /// Creates a new equation from an existing one, where the given operation is performed
+    /// to the leading inputs
+    fn as_if_next_operation(&self, operation: Operation) -> Self {
+        // Get the first two inputs, along with their text
+        let mut inputs = self.inputs.clone();
+        let x = inputs.pop_front().expect("Could not get first number");
+        let y = inputs.pop_front().expect("Could not get second number");
+        let mut tokens = self.tokens.clone();
+        let x_text = tokens.pop_front().expect("Could not get first token");
+        let y_text = tokens.pop_front().expect("Could not get second token");
+
+        // Get the result of the operation on the two numbers, using the text of the numbers
+        // for concatenation if strict
+        let (z, z_text) = if self.strict_concat && operation == Operation::Concatenation {
+            let z_text = x_text + &y_text;
+            let z = z_text
+                .parse::<i64>()
+                .expect("Could not parse concatenation");
+            (z, z_text)
+        } else {
+            let z = operation.apply(x, y);
+            (z, z.to_string())
+        };
+
+        // Create and return a new equation with the new inputs
+        inputs.push_front(z);
+        tokens.push_front(z_text);
+        Self {
+            result: self.result,
+            inputs,
+            tokens,
+            strict_concat: self.strict_concat,
+        }
+    }
+}
+
+/// Equation whose result can be anywhere within a range of values
+#[derive(Clone, Debug)]
+pub struct RangeEquation {
+    pub target: RangeInclusive<i64>,
+    pub inputs: Vec<i64>,
+}
+
+/// Outcome of searching for the operations that bring an equation closest to its target range
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct RangeOutcome {
+    /// Whether any combination of operations gives a value within the range
+    pub hit: bool,
+    /// The value closest to the range that any combination of operations gives, which is
+    /// within the range if it was hit, with ties going to the lower value
+    pub closest: i64,
+}
+
+impl RangeEquation {
+    /// Searches the combinations of operations for the value closest to the target range
+    ///
+    /// Like the other solvers, this assumes the inputs are positive, so the running value can
+    /// only grow.  Once it's past the end of the range by at least as much as the closest value
+    /// found so far, the rest of the combination can't get any closer and is skipped.
+    pub fn closest_to_range(&self, operations_allowed: &HashSet<Operation>) -> RangeOutcome {
+        // Get the allowed operations in a fixed order
+        let operations: Vec<Operation> = [
+            Operation::Addition,
+            Operation::Multiplication,
+            Operation::Concatenation,
+        ]
+        .into_iter()
+        .filter(|x| operations_allowed.contains(x))
+        .collect();
+
+        // Search from the first input for the closest value
+        let first = *self.inputs.first().expect("Missing first number");
+        let mut closest = None;
+        self.search_range(1, first, &operations, &mut closest);
+        let closest = closest.expect("No combination of operations was evaluated");
+
+        // Return whether the range was hit, along with the closest value
+        RangeOutcome {
+            hit: self.target.contains(&closest),
+            closest,
+        }
+    }
+
+    /// Gets how far the given value is from the target range, which is zero if within it
+    fn distance_from(&self, value: i64) -> i64 {
+        if value < *self.target.start() {
+            self.target.start() - value
+        } else {
+            value.saturating_sub(*self.target.end()).max(0)
+        }
+    }
+
+    /// Searches the combinations of operations for the remaining inputs starting from the given
+    /// index and running value, updating the closest value found so far
+    fn search_range(
+        &self,
+        index: usize,
+        value: i64,
+        operations: &[Operation],
+        closest: &mut Option<i64>,
+    ) {
+        // Get how far the closest value found so far is from the range
+        let best = closest.map(|x| self.distance_from(x));
+
+        // Once all of the inputs are used, keep the value if it's closer than the closest so far
+        if index == self.inputs.len() {
+            let distance = self.distance_from(value);
+            let is_closer = match (best, *closest) {
+                (Some(best), Some(current)) => (distance, value) < (best, current),
+                _ => true,
+            };
+            if is_closer {
+                *closest = Some(value);
+            }
+            return;
+        }
+
+        // The value can only grow, so stop once it's past the range by at least as much as the
+        // closest value so far
+        if best.is_some_and(|best| value > *self.target.end() && self.distance_from(value) >= best)
+        {
+            return;
+        }
+
+        // Try each of the operations with the next input, stopping if the range is hit
+        let y = self.inputs[index];
+        for operation in operations {
+            let next_value = match operation {
+                Operation::Addition => value.saturating_add(y),
+                Operation::Multiplication => value.saturating_mul(y),
+                Operation::Concatenation => value
+                    .saturating_mul(reverse_factor_for(y))
+                    .saturating_add(y),
+            };
+            self.search_range(index + 1, next_value, operations, closest);
+            if closest.is_some_and(|x| self.target.contains(&x)) {
+                return;
+            }
+        }
+    }
+}
+
+/// Gets the "reverse factor" for a given number
+///
+/// I originally implemented this using logarithm base 10 and the ceiling
+/// operation, but I was a little concerned about using floats given that
+/// Rust says it's non-deterministic, and this method is also much simpler
+/// to program
+fn reverse_factor_for(x: i64) -> i64 {
+    let mut factor = 10;
+    while x % factor != x {
+        factor *= 10;
+    }
+    factor
+}
+
+/// Combines two numbers (concatenation)
+pub fn combine_numbers(x: i64, y: i64) -> i64 {
+    let mult = reverse_factor_for(y);
+    x * mult + y
+}
+
+/// Splits a line of the input at the colon into the text before and after it
+pub fn split_equation(line: &str) -> Result<(&str, &str), String> {
+    line.split_once(':')
+        .ok_or_else(|| String::from("missing the colon after the test value"))
+}
+
+/// Parses a number in an equation, describing what it is if it can't be parsed
+pub fn parse_number(text: &str, what: &str) -> Result<i64, String> {
+    text.parse::<i64>()
+        .map_err(|_| format!("could not parse {text:?} as {what}"))
+}
+
+/// Gets the lines of the input that aren't empty, along with their line numbers
+pub fn numbered_lines(input: &str) -> impl Iterator<Item = (usize, &str)> {
+    input
+        .lines()
+        .enumerate()
+        .map(|(index, line)| (index + 1, line))
+        .filter(|(_, line)| !line.is_empty())
+}
+
+/// Parse a string input into a list of possible equations, failing with the line number of any
+/// equation that can't be parsed
+pub fn parse_data(input: &str, strict_concat: bool) -> Result<Vec<PossibleEquation>, String> {
+    // Create a list of possible equations to populate;
+    let mut equations = Vec::new();
+
+    // Iterate through the input string line by line
+    for (line_number, line) in numbered_lines(input) {
+        let with_line = |error: String| format!("Line {line_number}: {error}");
+
+        // Split the line by the colon to get and parse the result on the left
+        let (result_text, inputs_text) = split_equation(line).map_err(with_line)?;
+        let result = parse_number(result_text, "a test value").map_err(with_line)?;
+
+        // Split the right by spaces to get the tokens, and parse them to get the inputs
+        let tokens: VecDeque<String> = inputs_text.trim().split(' ').map(String::from).collect();
+        let inputs = tokens
+            .iter()
+            .map(|x| parse_number(x, "an input").map_err(with_line))
+            .collect::<Result<_, String>>()?;
+
+        // Create the possible equation and add it to the list of equations
+        let equation = PossibleEquation {
+            result,
+            inputs,
+            tokens,
+            strict_concat,
+        };
+        equations.push(equation);
+    }
+
+    // Return the list of possible equations
+    Ok(equations)
+}
+
+/// Collapses identical equations into one, returning each distinct equation along with the
+/// number of times it appears, in order of first appearance
+pub fn count_duplicates(equations: Vec<PossibleEquation>) -> Vec<(PossibleEquation, usize)> {
+    // Keep track of where each distinct equation is in the list
+    let mut positions: HashMap<PossibleEquation, usize> = HashMap::new();
+    let mut counted: Vec<(PossibleEquation, usize)> = Vec::new();
+
+    // Count each equation against its first appearance
+    for equation in equations {
+        match positions.get(&equation) {
+            Some(position) => counted[*position].1 += 1,
+            None => {
+                positions.insert(equation.clone(), counted.len());
+                counted.push((equation, 1));
+            }
+        }
+    }
+
+    // Return the distinct equations with their counts
+    counted
+}
+
+/// Parse a string input into a list of equations with target ranges, each written as `min..max`
+/// (or `min..=max` to include `max`) in place of the result, failing with the line number of any
+/// equation that can't be parsed or has an empty range
+pub fn parse_range_data(input: &str) -> Result<Vec<RangeEquation>, String> {
+    // Create a list of equations to populate
+    let mut equations = Vec::new();
+
+    // Iterate through the input string line by line
+    for (line_number, line) in numbered_lines(input) {
+        let with_line = |error: String| format!("Line {line_number}: {error}");
+
+        // Split the line by the colon to get the target range on the left
+        let (target_text, inputs_text) = split_equation(line).map_err(with_line)?;
+        let (min_text, max_text) = target_text
+            .split_once("..")
+            .ok_or_else(|| with_line(format!("expected a range like 1..5, got {target_text:?}")))?;
+
+        // Parse the ends of the range, adjusting the end if it's not included
+        let min = parse_number(min_text, "a range start").map_err(with_line)?;
+        let max = match max_text.strip_prefix('=') {
+            Some(max_text) => parse_number(max_text, "a range end").map_err(with_line)?,
+            None => parse_number(max_text, "a range end").map_err(with_line)? - 1,
+        };
+        if min > max {
+            return Err(with_line(format!("the range {target_text} is empty")));
+        }
+
+        // Split the right by spaces to get and parse the inputs
+        let inputs = inputs_text
+            .split_whitespace()
+            .map(|x| parse_number(x, "an input").map_err(with_line))
+            .collect::<Result<_, String>>()?;
+
+        // Create the equation and add it to the list of equations
+        equations.push(RangeEquation {
+            target: min..=max,
+            inputs,
+        });
+    }
+
+    // Return the list of equations
+    Ok(equations)
+}
+
+/// Gets the sum of the results of the equations in the input text that can be solved with
+/// the given operations
+fn solvable_total(input: &str, operations_list: &[Operation]) -> Result<i64, String> {
+    let operations = HashSet::from_iter(operations_list.iter().copied());
+    Ok(parse_data(input, false)?
+        .iter()
+        .filter(|x| x.is_solvable(&operations, Solver::Auto))
+        .map(|x| x.result)
+        .sum())
+}
+
+/// Solves part one for the puzzle input, getting the total of the equations that can be
+/// solved by adding and multiplying
+pub fn part1(input: &str) -> Result<i64, String> {
+    solvable_total(input, &[Operation::Multiplication, Operation::Addition])
+}
+
+/// Solves part two for the puzzle input, getting the total of the equations that can be
+/// solved by adding, multiplying and concatenating
+pub fn part2(input: &str) -> Result<i64, String> {
+    solvable_total(
+        input,
+        &[
+            Operation::Multiplication,
+            Operation::Addition,
+            Operation::Concatenation,
+        ],
+    )
+}
@@ -1,205 +1,461 @@
 use std::{
-    collections::{HashSet, VecDeque},
+    collections::{HashMap, HashSet},
     fs,
+    iter::Peekable,
+    str::Chars,
 };
 
 use clap::Parser;
+use common::{fail, read_input, About, Failure, Format};
+use day7::{
+    count_duplicates, numbered_lines, parse_data, parse_number, parse_range_data, split_equation,
+    Operation, PossibleEquation, Solver,
+};
 
 #[derive(Parser)]
 struct CliArgs {
     part: u64,
     filepath: String,
+    /// Verify explicit expressions (operators and parentheses) instead of searching for operators
+    #[arg(long)]
+    evaluate: bool,
+    /// Treat the result of each equation as a range (`min..max`, or `min..=max` to include
+    /// `max`), printing whether each range can be hit and the closest value to it, followed by
+    /// the number of ranges hit
+    #[arg(long, conflicts_with_all = ["evaluate", "strict_concat", "partition"])]
+    ranges: bool,
+    /// Concatenate inputs using their original text, so leading zeros are kept (e.g. 1 || 05 = 105)
+    #[arg(long)]
+    strict_concat: bool,
+    /// Write the solvable and unsolvable equations to the two given files, in the input format
+    #[arg(long, num_args = 2, value_names = ["SOLVABLE", "UNSOLVABLE"])]
+    partition: Option<Vec<String>>,
+    /// How to search for operators that solve each equation
+    #[arg(long, value_enum, default_value_t = Solver::Auto)]
+    solver: Solver,
+    /// Solve each distinct equation only once, counting its result once for every time it
+    /// appears, and print the number of duplicates found before the answer
+    #[arg(long, conflicts_with = "evaluate")]
+    dedupe: bool,
+    /// Parse the input and print statistics about its structure instead of solving it
+    #[arg(long)]
+    parse_only: bool,
+    /// The format in which errors are reported
+    #[arg(long, value_enum, default_value_t = Format::Text)]
+    format: Format,
 }
 
-/// Possible operations that can be performed
-#[derive(PartialEq, Eq, Hash, Clone, Copy)]
-enum Operation {
-    Addition,
-    Multiplication,
-    Concatenation,
+/// Description of the puzzle solved by this binary
+const ABOUT: About = About {
+    day: 7,
+    title: "Bridge Repair",
+    input: "One equation per line, as a test value, a colon, and space-separated numbers",
+    parts: &[1, 2],
+    features: &[],
+};
+
+/// Explicit expression representation, where operations are evaluated left to right
+/// unless grouped using parentheses
+#[derive(Clone, Debug)]
+enum Expression {
+    Value(i64),
+    Operation(Box<Expression>, Operation, Box<Expression>),
 }
 
-/// Possible equations representation, including results and inputs
+impl Expression {
+    /// Evaluates the expression recursively
+    fn evaluate(&self) -> i64 {
+        match self {
+            Expression::Value(value) => *value,
+            Expression::Operation(left, operation, right) => {
+                operation.apply(left.evaluate(), right.evaluate())
+            }
+        }
+    }
+
+    /// Checks whether the expression only uses the allowed operations
+    fn uses_only(&self, operations_allowed: &HashSet<Operation>) -> bool {
+        match self {
+            Expression::Value(_) => true,
+            Expression::Operation(left, operation, right) => {
+                operations_allowed.contains(operation)
+                    && left.uses_only(operations_allowed)
+                    && right.uses_only(operations_allowed)
+            }
+        }
+    }
+}
+
+/// Explicit equation representation, including the result and the expression to verify
 #[derive(Clone, Debug)]
-struct PossibleEquation {
+struct ExplicitEquation {
     result: i64,
-    inputs: VecDeque<i64>,
-    // operations: Vec<Operation>,
+    expression: Expression,
+}
+
+impl ExplicitEquation {
+    /// Checks whether the expression uses only allowed operations and evaluates to the result
+    fn is_verified(&self, operations_allowed: &HashSet<Operation>) -> bool {
+        self.expression.uses_only(operations_allowed) && self.expression.evaluate() == self.result
+    }
 }
 
 /// Main entry function
 fn main() {
+    // Describe the puzzle instead if requested
+    common::handle_about::<CliArgs>(&ABOUT);
+
     // Parse CLI arguments
     let cli = CliArgs::parse();
 
+    // Report errors in the requested format
+    common::init(cli.format);
+
+    // Only parse the input if requested
+    if cli.parse_only {
+        report_structure(&cli);
+    }
+
+    // Solve the equations with target ranges instead if requested
+    if cli.ranges {
+        main_ranges(cli.filepath, cli.part);
+        return;
+    }
+
     // Run the code for the desired challenge part
     match cli.part {
-        1 => main_part_one(cli.filepath),
-        2 => main_part_two(cli.filepath),
+        1 => main_part_one(
+            cli.filepath,
+            cli.evaluate,
+            cli.strict_concat,
+            cli.partition,
+            cli.solver,
+            cli.dedupe,
+        ),
+        2 => main_part_two(
+            cli.filepath,
+            cli.evaluate,
+            cli.strict_concat,
+            cli.partition,
+            cli.solver,
+            cli.dedupe,
+        ),
+        _ => panic!("Invalid selection part selection!"),
+    }
+}
+
+/// Fails with the given parse error
+fn parse_failure<T>(error: String) -> T {
+    fail(Failure::Parse(error))
+}
+
+/// Parses the equations in the format selected by the command line arguments, then prints the
+/// number of equations and inputs and the most inputs in one equation and exits
+///
+/// Explicit expressions are only counted, since their inputs are nested within them.
+fn report_structure(cli: &CliArgs) -> ! {
+    let contents = read_input(&cli.filepath);
+    if cli.evaluate {
+        common::report_parse(&[(
+            "equations",
+            parse_explicit_data(&contents)
+                .unwrap_or_else(parse_failure)
+                .len(),
+        )]);
+    }
+    let input_lengths: Vec<usize> = if cli.ranges {
+        parse_range_data(&contents)
+            .unwrap_or_else(parse_failure)
+            .iter()
+            .map(|x| x.inputs.len())
+            .collect()
+    } else {
+        parse_data(&contents, cli.strict_concat)
+            .unwrap_or_else(parse_failure)
+            .iter()
+            .map(|x| x.inputs.len())
+            .collect()
+    };
+    common::report_parse(&[
+        ("equations", input_lengths.len()),
+        ("inputs", input_lengths.iter().sum()),
+        (
+            "most inputs",
+            input_lengths.iter().max().copied().unwrap_or(0),
+        ),
+    ]);
+}
+
+/// Finds whether the target range of each equation can be hit using the operations of the given
+/// part, printing the outcome for each as CSV followed by the number of ranges hit
+fn main_ranges(filepath: String, part: u64) {
+    // Get the operations allowed for the part
+    let operations: HashSet<Operation> = match part {
+        1 => HashSet::from([Operation::Multiplication, Operation::Addition]),
+        2 => HashSet::from([
+            Operation::Multiplication,
+            Operation::Addition,
+            Operation::Concatenation,
+        ]),
         _ => panic!("Invalid selection part selection!"),
+    };
+
+    // Read the contents of the file
+    let contents = read_input(filepath);
+
+    // Print the outcome for each of the equations
+    println!("min,max,hit,closest");
+    let mut num_hit = 0;
+    for equation in parse_range_data(&contents).unwrap_or_else(parse_failure) {
+        let outcome = equation.closest_to_range(&operations);
+        let (min, max) = equation.target.into_inner();
+        println!("{min},{max},{},{}", outcome.hit, outcome.closest);
+        if outcome.hit {
+            num_hit += 1;
+        }
     }
+
+    // Print the number of ranges hit
+    println!("{num_hit}");
 }
 
 /// Runs the main functions with the specified operations
-fn run_main_with_operations(filepath: String, operations: &HashSet<Operation>) {
+fn run_main_with_operations(
+    filepath: String,
+    operations: &HashSet<Operation>,
+    evaluate: bool,
+    strict_concat: bool,
+    partition: Option<Vec<String>>,
+    solver: Solver,
+    dedupe: bool,
+) {
     // Read the contents of the file
-    let contents = fs::read_to_string(filepath).expect("Invalid filepath");
+    let contents = read_input(filepath);
 
-    // Get the list of possible equations
-    let equations = parse_data(&contents);
+    // Get whether the equation on each line is valid, along with the result of each distinct
+    // equation, whether it is valid, and the number of times it appears, verifying explicit
+    // expressions instead of searching if they are provided
+    let (line_validity, outcomes): (Vec<bool>, Vec<(i64, bool, usize)>) = if evaluate {
+        let outcomes: Vec<(i64, bool, usize)> = parse_explicit_data(&contents)
+            .unwrap_or_else(parse_failure)
+            .iter()
+            .map(|x| (x.result, x.is_verified(operations), 1))
+            .collect();
+        (
+            outcomes.iter().map(|(_, valid, _)| *valid).collect(),
+            outcomes,
+        )
+    } else {
+        // Collapse identical equations first if requested
+        let equations = parse_data(&contents, strict_concat).unwrap_or_else(parse_failure);
+        let counted = if dedupe {
+            count_duplicates(equations.clone())
+        } else {
+            equations.iter().map(|x| (x.clone(), 1)).collect()
+        };
 
-    // Get the sum of the valid equations
-    let solvable_total: i64 = equations
+        // Solve each of the equations, and look up the outcome for each line
+        let validity: HashMap<&PossibleEquation, bool> = counted
+            .iter()
+            .map(|(x, _)| (x, x.is_solvable(operations, solver)))
+            .collect();
+        let outcomes = counted
+            .iter()
+            .map(|(x, count)| (x.result, validity[x], *count))
+            .collect();
+        (equations.iter().map(|x| validity[x]).collect(), outcomes)
+    };
+
+    // Write the equations to separate files based on whether they are valid, if requested
+    if let Some(partition) = partition {
+        write_partition(
+            &contents,
+            line_validity.into_iter(),
+            &partition[0],
+            &partition[1],
+        );
+    }
+
+    // Print the number of duplicates collapsed if requested
+    if dedupe {
+        let num_duplicates: usize = outcomes.iter().map(|(_, _, count)| count - 1).sum();
+        println!("duplicates: {num_duplicates}");
+    }
+
+    // Get the sum of the valid equations, counting each once for every time it appears
+    let solvable_total: i64 = outcomes
         .iter()
-        .filter(|x| x.is_solvable(operations))
-        .map(|x| x.result)
+        .filter(|(_, valid, _)| *valid)
+        .map(|(result, _, count)| result * *count as i64)
         .sum();
     println!("{solvable_total}");
 }
 
+/// Writes the lines of the input to one of two files depending on whether the equation
+/// on that line is valid
+fn write_partition(
+    input: &str,
+    valid: impl Iterator<Item = bool>,
+    solvable_filepath: &str,
+    unsolvable_filepath: &str,
+) {
+    // Sort the lines into the solvable and unsolvable lists, keeping their original text
+    let mut solvable = String::new();
+    let mut unsolvable = String::new();
+    for (line, is_valid) in input.lines().filter(|x| !x.is_empty()).zip(valid) {
+        let text = if is_valid {
+            &mut solvable
+        } else {
+            &mut unsolvable
+        };
+        text.push_str(line);
+        text.push('\n');
+    }
+
+    // Write both lists to their respective files
+    for (filepath, text) in [
+        (solvable_filepath, solvable),
+        (unsolvable_filepath, unsolvable),
+    ] {
+        fs::write(filepath, text).unwrap_or_else(|error| {
+            fail(Failure::Io(format!("Could not write {filepath}: {error}")))
+        });
+    }
+}
+
 /// Runs part one
-fn main_part_one(filepath: String) {
+fn main_part_one(
+    filepath: String,
+    evaluate: bool,
+    strict_concat: bool,
+    partition: Option<Vec<String>>,
+    solver: Solver,
+    dedupe: bool,
+) {
     let operations_list = [Operation::Multiplication, Operation::Addition];
     let operations = HashSet::from_iter(operations_list.iter().copied());
-    run_main_with_operations(filepath, &operations);
+    run_main_with_operations(
+        filepath,
+        &operations,
+        evaluate,
+        strict_concat,
+        partition,
+        solver,
+        dedupe,
+    );
 }
 
 // Runs part two
-fn main_part_two(filepath: String) {
+fn main_part_two(
+    filepath: String,
+    evaluate: bool,
+    strict_concat: bool,
+    partition: Option<Vec<String>>,
+    solver: Solver,
+    dedupe: bool,
+) {
     let operations_list = [
         Operation::Multiplication,
         Operation::Addition,
         Operation::Concatenation,
     ];
     let operations = HashSet::from_iter(operations_list.iter().copied());
-    run_main_with_operations(filepath, &operations);
-}
-
-impl PossibleEquation {
-    /// Checks whether the equation is solvable, recursively if needed
-    fn is_solvable(&self, operations_allowed: &HashSet<Operation>) -> bool {
-        // Get the first two inputs at the top of the inputs list
-        let mut inputs = self.inputs.clone();
-        let x = inputs.pop_front().expect("Missing first number");
-        let y = inputs.pop_front().expect("Missing second nunber");
-
-        // If there are no remaining inputs other than these two, operate on them directly
-        if inputs.is_empty() {
-            // If they are valid via multiplication or addition, return true
-            let mult = x * y == self.result;
-            let add = x + y == self.result;
-            if mult || add {
-                return true;
-            }
-
-            // If concatenation is not allowed, return false
-            if !operations_allowed.contains(&Operation::Concatenation) {
-                return false;
-            }
+    run_main_with_operations(
+        filepath,
+        &operations,
+        evaluate,
+        strict_concat,
+        partition,
+        solver,
+        dedupe,
+    );
+}
 
-            // Check the actual result of trying to concatenate the numbers
-            return combine_numbers(x, y) == self.result;
-        }
+/// Parse a string input into a list of explicit equations, failing with the line number of any
+/// equation that can't be parsed
+fn parse_explicit_data(input: &str) -> Result<Vec<ExplicitEquation>, String> {
+    // Create a list of explicit equations to populate
+    let mut equations = Vec::new();
 
-        // Recursively check possible values for multiplication, assuming the answer is still even possible
-        let z_mult = x * y;
-        if z_mult <= self.result
-            && self
-                .as_if_next_operation(Operation::Multiplication)
-                .is_solvable(operations_allowed)
-        {
-            return true;
-        }
+    // Iterate through the input string line by line
+    for (line_number, line) in numbered_lines(input) {
+        let with_line = |error: String| format!("Line {line_number}: {error}");
 
-        // Recursively check possible values for addition, assuming the answer is still even possible
-        let z_add = x + y;
-        if z_add <= self.result
-            && self
-                .as_if_next_operation(Operation::Addition)
-                .is_solvable(operations_allowed)
-        {
-            return true;
-        }
+        // Split the line by the colon to get and parse the result on the left
+        let (result_text, expression_text) = split_equation(line).map_err(with_line)?;
+        let result = parse_number(result_text, "a test value").map_err(with_line)?;
 
-        // If concatenation is not allowed, return false
-        if !operations_allowed.contains(&Operation::Concatenation) {
-            return false;
+        // Parse the expression on the right, which should consume the entire text
+        let mut chars = expression_text.chars().peekable();
+        let expression = parse_expression(&mut chars).map_err(with_line)?;
+        if chars.next().is_some() {
+            return Err(with_line(String::from("unmatched closing parenthesis")));
         }
 
-        // Recursively check the actual results of trying to concatenate the numbers
-        self.as_if_next_operation(Operation::Concatenation)
-            .is_solvable(operations_allowed)
+        // Create the explicit equation and add it to the list of equations
+        let equation = ExplicitEquation { result, expression };
+        equations.push(equation);
     }
 
-    /// Creates a new equation from an existing one, where the given operation is performed
-    /// to the leading inputs
-    fn as_if_next_operation(&self, operation: Operation) -> Self {
-        // Get the first two inputs
-        let mut inputs = self.inputs.clone();
-        let x = inputs.pop_front().expect("Could not get first number");
-        let y = inputs.pop_front().expect("Could not get second number");
+    // Return the list of explicit equations
+    Ok(equations)
+}
 
-        // Get the result of the operation on the two numbers
-        let z = match operation {
-            Operation::Multiplication => x * y,
-            Operation::Addition => x + y,
-            Operation::Concatenation => combine_numbers(x, y),
-        };
+/// Parse an expression, stopping at the end of the text or at a closing parenthesis
+fn parse_expression(chars: &mut Peekable<Chars>) -> Result<Expression, String> {
+    // Get the leading operand
+    let mut expression = parse_operand(chars)?;
 
-        // Create and return a new equation with the new inputs
-        inputs.push_front(z);
-        Self {
-            result: self.result,
-            inputs,
+    // Keep folding operations into the expression left to right
+    loop {
+        skip_whitespace(chars);
+        if matches!(chars.peek(), None | Some(')')) {
+            return Ok(expression);
         }
+        let operation = parse_operator(chars)?;
+        let operand = parse_operand(chars)?;
+        expression = Expression::Operation(Box::new(expression), operation, Box::new(operand));
     }
 }
 
-/// Gets the "reverse factor" for a given number
-///
-/// I originally implemented this using logarithm base 10 and the ceiling
-/// operation, but I was a little concerned about using floats given that
-/// Rust says it's non-deterministic, and this method is also much simpler
-/// to program
-fn reverse_factor_for(x: i64) -> i64 {
-    let mut factor = 10;
-    while x % factor != x {
-        factor *= 10;
+/// Parse an operand, which is either a number or a parenthesized expression
+fn parse_operand(chars: &mut Peekable<Chars>) -> Result<Expression, String> {
+    skip_whitespace(chars);
+
+    // If this is a parenthesized expression, parse it and consume the closing parenthesis
+    if chars.next_if_eq(&'(').is_some() {
+        let expression = parse_expression(chars)?;
+        chars
+            .next_if_eq(&')')
+            .ok_or_else(|| String::from("missing closing parenthesis"))?;
+        return Ok(expression);
     }
-    factor
-}
 
-/// Combines two numbers (concatenation)
-fn combine_numbers(x: i64, y: i64) -> i64 {
-    let mult = reverse_factor_for(y);
-    x * mult + y
+    // Otherwise, gather the digits of the number and parse it
+    let mut digits = String::new();
+    while let Some(digit) = chars.next_if(|x| x.is_ascii_digit()) {
+        digits.push(digit);
+    }
+    Ok(Expression::Value(parse_number(&digits, "an input")?))
 }
 
-/// Parse a string input into a list of possible equations
-fn parse_data(input: &str) -> Vec<PossibleEquation> {
-    // Create a list of possible equations to populate;
-    let mut equations = Vec::new();
-
-    // Iterate through the input string line by line
-    for line in input.lines().filter(|x| !x.is_empty()) {
-        // Split the line by the colon to get and parse the result on the left
-        let result_split: Vec<&str> = line.split(':').collect();
-        let result = result_split[0]
-            .parse::<i64>()
-            .expect("Could not parse result");
-
-        // Split the right by spaces and parse to get the inputs
-        let inputs = result_split[1]
-            .trim()
-            .split(' ')
-            .map(|x| x.parse::<i64>().expect("Could not parse input"))
-            .collect();
-
-        // Create the possible equation and add it to the list of equations
-        let equation = PossibleEquation { result, inputs };
-        equations.push(equation);
+/// Parse an operator symbol into its operation
+fn parse_operator(chars: &mut Peekable<Chars>) -> Result<Operation, String> {
+    match chars.next() {
+        Some('+') => Ok(Operation::Addition),
+        Some('*') => Ok(Operation::Multiplication),
+        Some('|') => {
+            chars
+                .next_if_eq(&'|')
+                .ok_or_else(|| String::from("incomplete concatenation operator"))?;
+            Ok(Operation::Concatenation)
+        }
+        Some(character) => Err(format!("found {character:?}, expected an operator")),
+        None => Err(String::from("expected an operator")),
     }
+}
 
-    // Return the list of possible equations
-    equations
+/// Skip any whitespace at the front of the characters
+fn skip_whitespace(chars: &mut Peekable<Chars>) {
+    while chars.next_if(|x| x.is_whitespace()).is_some() {}
 }
@@ -0,0 +1,29 @@
+use day7::{count_duplicates, parse_data};
+
+#[test]
+fn counts_identical_equations() {
+    let equations = parse_data(
+        "190: 10 19\n83: 17 5\n190: 10 19\n190: 19 10\n190: 10 19\n",
+        false,
+    )
+    .unwrap();
+    let counted = count_duplicates(equations);
+    let summary: Vec<(i64, Vec<i64>, usize)> = counted
+        .iter()
+        .map(|(x, count)| (x.result, x.inputs.iter().copied().collect(), *count))
+        .collect();
+    assert_eq!(
+        summary,
+        [
+            (190, vec![10, 19], 3),
+            (83, vec![17, 5], 1),
+            (190, vec![19, 10], 1)
+        ]
+    );
+}
+
+#[test]
+fn strict_concat_keeps_leading_zeros_distinct() {
+    let input = "105: 1 05\n105: 1 5\n";
+    assert_eq!(count_duplicates(parse_data(input, true).unwrap()).len(), 2);
+}
@@ -0,0 +1,41 @@
+use day7::{parse_data, parse_range_data};
+
+#[test]
+fn parses_equations() {
+    let equations = parse_data("190: 10 19\n\n3267: 81 40 27\n", false).unwrap();
+    assert_eq!(equations.len(), 2);
+    assert_eq!(equations[1].result, 3267);
+    assert_eq!(equations[1].inputs, [81, 40, 27]);
+}
+
+#[test]
+fn rejects_malformed_equations() {
+    let error = |input: &str| parse_data(input, false).err().unwrap();
+    assert_eq!(
+        error("190: 10 19\n83 17 5\n"),
+        "Line 2: missing the colon after the test value"
+    );
+    assert_eq!(
+        error("x: 10 19\n"),
+        "Line 1: could not parse \"x\" as a test value"
+    );
+    // Blank lines are skipped, but still counted
+    assert_eq!(
+        error("190: 10 19\n\n83: 17 y\n"),
+        "Line 3: could not parse \"y\" as an input"
+    );
+}
+
+#[test]
+fn rejects_malformed_ranges() {
+    let error = |input: &str| parse_range_data(input).err().unwrap();
+    assert_eq!(
+        error("190: 10 19\n"),
+        "Line 1: expected a range like 1..5, got \"190\""
+    );
+    assert_eq!(error("5..5: 1 4\n"), "Line 1: the range 5..5 is empty");
+    assert_eq!(
+        error("1..=x: 1 4\n"),
+        "Line 1: could not parse \"x\" as a range end"
+    );
+}
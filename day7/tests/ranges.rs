@@ -0,0 +1,131 @@
+use std::collections::HashSet;
+
+use day7::{parse_range_data, Operation, RangeOutcome};
+
+/// Gets the outcome for each of the equations using the given operations
+fn outcomes(input: &str, operations: &[Operation]) -> Vec<RangeOutcome> {
+    let operations: HashSet<Operation> = operations.iter().copied().collect();
+    parse_range_data(input)
+        .unwrap()
+        .iter()
+        .map(|x| x.closest_to_range(&operations))
+        .collect()
+}
+
+#[test]
+fn hits_ranges() {
+    // 10 + 19 = 29 and 10 * 19 = 190
+    let input = "25..30: 10 19\n190..=190: 10 19\n";
+    let results = outcomes(input, &[Operation::Addition, Operation::Multiplication]);
+    assert_eq!(
+        results,
+        vec![
+            RangeOutcome {
+                hit: true,
+                closest: 29
+            },
+            RangeOutcome {
+                hit: true,
+                closest: 190
+            },
+        ]
+    );
+}
+
+#[test]
+fn finds_closest_value_when_missed() {
+    // The values are 29 and 190, so 29 is closest to the range above it and 190 below it
+    let input = "40..50: 10 19\n200..300: 10 19\n";
+    let results = outcomes(input, &[Operation::Addition, Operation::Multiplication]);
+    assert_eq!(
+        results[0],
+        RangeOutcome {
+            hit: false,
+            closest: 29
+        }
+    );
+    assert_eq!(
+        results[1],
+        RangeOutcome {
+            hit: false,
+            closest: 190
+        }
+    );
+}
+
+#[test]
+fn exclusive_end_is_not_hit() {
+    let input = "20..29: 10 19\n";
+    let results = outcomes(input, &[Operation::Addition, Operation::Multiplication]);
+    assert_eq!(
+        results[0],
+        RangeOutcome {
+            hit: false,
+            closest: 29
+        }
+    );
+}
+
+#[test]
+fn concatenation_reaches_range() {
+    // 15 || 6 = 156, which is only reachable with concatenation
+    let input = "150..160: 15 6\n";
+    let without = outcomes(input, &[Operation::Addition, Operation::Multiplication]);
+    assert_eq!(
+        without[0],
+        RangeOutcome {
+            hit: false,
+            closest: 90
+        }
+    );
+    let with = outcomes(
+        input,
+        &[
+            Operation::Addition,
+            Operation::Multiplication,
+            Operation::Concatenation,
+        ],
+    );
+    assert_eq!(
+        with[0],
+        RangeOutcome {
+            hit: true,
+            closest: 156
+        }
+    );
+}
+
+#[test]
+fn pruned_search_matches_exhaustive() {
+    // 2 3 4 5 gives 14, 15, 25, 29, 45, 50, 100, and 120
+    let input = "1..2: 2 3 4 5\n60..70: 2 3 4 5\n45..=45: 2 3 4 5\n1000..2000: 2 3 4 5\n";
+    let results = outcomes(input, &[Operation::Addition, Operation::Multiplication]);
+    assert_eq!(
+        results[0],
+        RangeOutcome {
+            hit: false,
+            closest: 14
+        }
+    );
+    assert_eq!(
+        results[1],
+        RangeOutcome {
+            hit: false,
+            closest: 50
+        }
+    );
+    assert_eq!(
+        results[2],
+        RangeOutcome {
+            hit: true,
+            closest: 45
+        }
+    );
+    assert_eq!(
+        results[3],
+        RangeOutcome {
+            hit: false,
+            closest: 120
+        }
+    );
+}
@@ -0,0 +1,73 @@
+use std::collections::HashSet;
+
+use day7::{parse_data, Operation, Solver};
+
+const EXAMPLE: &str = "190: 10 19
+3267: 81 40 27
+83: 17 5
+156: 15 6
+7290: 6 8 6 15
+161011: 16 10 13
+192: 17 8 14
+21037: 9 7 18 13
+292: 11 6 16 20
+";
+
+/// Gets the sum of the results of the solvable equations
+fn solvable_total(
+    input: &str,
+    operations: &[Operation],
+    strict_concat: bool,
+    solver: Solver,
+) -> i64 {
+    let operations: HashSet<Operation> = operations.iter().copied().collect();
+    parse_data(input, strict_concat)
+        .unwrap()
+        .iter()
+        .filter(|x| x.is_solvable(&operations, solver))
+        .map(|x| x.result)
+        .sum()
+}
+
+const PART_ONE: [Operation; 2] = [Operation::Addition, Operation::Multiplication];
+const PART_TWO: [Operation; 3] = [
+    Operation::Addition,
+    Operation::Multiplication,
+    Operation::Concatenation,
+];
+
+#[test]
+fn solvers_agree_on_example() {
+    for solver in [Solver::Auto, Solver::Recursive, Solver::Enumeration] {
+        assert_eq!(solvable_total(EXAMPLE, &PART_ONE, false, solver), 3749);
+        assert_eq!(solvable_total(EXAMPLE, &PART_TWO, false, solver), 11387);
+    }
+}
+
+#[test]
+fn enumeration_respects_strict_concat() {
+    let input = "105: 1 05\n";
+    assert_eq!(
+        solvable_total(input, &PART_TWO, false, Solver::Enumeration),
+        0
+    );
+    assert_eq!(
+        solvable_total(input, &PART_TWO, true, Solver::Enumeration),
+        105
+    );
+    assert_eq!(
+        solvable_total(input, &PART_TWO, true, Solver::Recursive),
+        105
+    );
+}
+
+#[test]
+fn enumeration_falls_back_on_long_lines() {
+    // Too many inputs for every combination of three operations to fit in the counter
+    let inputs = vec!["2"; 45].join(" ");
+    let input = format!("90: {inputs}\n91: {inputs}\n");
+    assert_eq!(
+        solvable_total(&input, &PART_TWO, false, Solver::Enumeration),
+        90
+    );
+}
@@ -0,0 +1,416 @@
+use std::{
+    collections::{HashMap, HashSet},
+    hash::{Hash, Hasher},
+};
+
+use common::escape_json;
+use itertools::Itertools;
+use pathfinding::{flood_fill, Visit};
+
+/// Representation of a given coordinate on a map, and whether an
+/// antenna of a given frequency is at that location
+#[derive(Clone, Copy, Eq)]
+pub struct Coordinate {
+    pub x: i64,
+    pub y: i64,
+    pub antenna: Option<char>,
+}
+
+impl Coordinate {
+    /// Get the coordinate on the given game map at the given multiple (harmonic) of the
+    /// distance of this coordinate from a given coordinate, projected beyond this coordinate
+    pub fn get_harmonic_for(
+        &self,
+        coordinate: &Coordinate,
+        harmonic: u64,
+        map: &GameMap,
+    ) -> Option<Coordinate> {
+        let (x_diff, y_diff) = self.get_distance_from(coordinate);
+        let new_x = self.x + x_diff * harmonic as i64;
+        let new_y = self.y + y_diff * harmonic as i64;
+        map.at(new_x, new_y)
+    }
+
+    /// Gets the distance of this coordinate from another coordinate
+    pub fn get_distance_from(&self, coordinate: &Coordinate) -> (i64, i64) {
+        let x_diff = self.x - coordinate.x;
+        let y_diff = self.y - coordinate.y;
+        (x_diff, y_diff)
+    }
+}
+
+impl PartialEq for Coordinate {
+    fn eq(&self, other: &Coordinate) -> bool {
+        self.x == other.x && self.y == other.y
+    }
+}
+
+impl Hash for Coordinate {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.x.hash(state);
+        self.y.hash(state);
+    }
+}
+
+/// An antinode along with the pair of antennas that produced it
+#[derive(Clone, Copy)]
+pub struct Antinode {
+    /// The location of the antinode
+    pub location: Coordinate,
+    /// The antenna the antinode was projected beyond, or either antenna of the pair for
+    /// antinodes anywhere on the line through it
+    pub base: Coordinate,
+    /// The other antenna of the pair
+    pub paired: Coordinate,
+    /// The multiple of the separation between the pair the antinode is beyond the base
+    /// antenna, or None for antinodes anywhere on the line through it
+    pub harmonic: Option<u64>,
+}
+
+impl Antinode {
+    /// Gets the source of the antinode as JSON
+    pub fn source_to_json(&self) -> String {
+        let frequency = escape_json(&self.base.antenna.map_or(String::new(), String::from));
+        let harmonic = self.harmonic.map_or("null".to_string(), |x| x.to_string());
+        format!(
+            "{{\"frequency\":\"{frequency}\",\"antennas\":[[{},{}],[{},{}]],\"harmonic\":{harmonic}}}",
+            self.base.x, self.base.y, self.paired.x, self.paired.y
+        )
+    }
+}
+
+/// The multiples (harmonics) of the separation between a pair of antennas that count as
+/// antinodes, where harmonic 0 is the antenna itself
+pub enum Harmonics {
+    /// Only the given harmonics
+    Only(Vec<u64>),
+    /// Every harmonic within the bounds of the map
+    All,
+}
+
+impl Harmonics {
+    /// Gets the harmonics counted by the given challenge part
+    pub fn for_part(part: u64) -> Self {
+        match part {
+            1 => Self::Only(vec![1]),
+            2 => Self::All,
+            _ => panic!("Invalid selection part selection!"),
+        }
+    }
+
+    /// Checks whether the given harmonic counts as an antinode
+    pub fn includes(&self, harmonic: u64) -> bool {
+        match self {
+            Self::Only(harmonics) => harmonics.contains(&harmonic),
+            Self::All => true,
+        }
+    }
+
+    /// Gets the highest harmonic that counts as an antinode, or None if there is no limit
+    pub fn highest(&self) -> Option<u64> {
+        match self {
+            Self::Only(harmonics) => Some(harmonics.iter().copied().max().unwrap_or(0)),
+            Self::All => None,
+        }
+    }
+}
+
+/// Representation of the game map
+#[derive(Default)]
+pub struct GameMap {
+    pub spaces: Vec<Vec<Coordinate>>,
+}
+
+impl GameMap {
+    /// Create a new blank game map
+    pub fn new() -> Self {
+        Self { spaces: Vec::new() }
+    }
+
+    /// Get the coordinate at a given X, Y coordinate
+    ///
+    /// Returns the Coordinate with the given location, or None if it's
+    /// outside the bounds of the map
+    pub fn at(&self, x: i64, y: i64) -> Option<Coordinate> {
+        if x < 0 || y < 0 {
+            return None;
+        }
+
+        match self.spaces.get(y as usize) {
+            Some(row) => row.get(x as usize).copied(),
+            None => None,
+        }
+    }
+
+    /// Gets all the antennas from the map, grouped by frequency (label) in a hash map
+    pub fn get_antennas(&self) -> HashMap<char, HashSet<Coordinate>> {
+        // Create a hash map for storing the antenna information
+        let mut locations: HashMap<char, HashSet<Coordinate>> = HashMap::new();
+
+        // Iterate through the map inspecting coordinates
+        for row in &self.spaces {
+            for coord in row {
+                // If the coordinate has an antenna, add it to the stored hash set (adding one if
+                // this is the first access)
+                if let Some(label) = coord.antenna {
+                    let set = locations.entry(label).or_default();
+                    set.insert(*coord);
+                }
+            }
+        }
+
+        // Return the hash map of all the antenna locations
+        locations
+    }
+}
+
+/// Parse the string to build a game map
+pub fn parse_map(input: &str) -> GameMap {
+    // Create a new map
+    let mut map = GameMap::new();
+
+    // Iterate through the file contents line by line
+    for (row_index, line) in input.lines().filter(|x| !x.is_empty()).enumerate() {
+        // Start a new row for the given line of data
+        let mut row = Vec::new();
+
+        // Iterate through the line character by character
+        for (col_index, character) in line.chars().enumerate() {
+            // Initialize a coordinate for the map
+            let mut coordinate = Coordinate {
+                x: col_index as i64,
+                y: row_index as i64,
+                antenna: None,
+            };
+
+            // If the character is an antenna, save it
+            if character != '.' {
+                coordinate.antenna = Some(character);
+            }
+
+            // Add the coordinate to the row
+            row.push(coordinate);
+        }
+
+        // Add the row to the map space
+        map.spaces.push(row);
+    }
+
+    // Return the completed map
+    map
+}
+
+/// Gets the antinodes at the given harmonics for a given set of antennas of the same frequency,
+/// along with the pair that produced each
+///
+/// The same location is listed once for each pair that produces it.
+pub fn get_antinodes(
+    antennas: &HashSet<Coordinate>,
+    map: &GameMap,
+    harmonics: &Harmonics,
+) -> Vec<Antinode> {
+    // Create a new list for store antinodes that are found
+    let mut antinodes = Vec::new();
+
+    // Iterate through all the permutations of the given set of antennas
+    for antenna_pair in antennas.iter().permutations(2) {
+        // Get the base antenna and paired antenna (paired <--> base <--> antinode)
+        let base_antenna = antenna_pair[0];
+        let paired_antenna = antenna_pair[1];
+
+        // Project the pair out along the line, up to the highest harmonic or the edge of the map
+        let mut harmonic = 0;
+        while let Some(antinode) = base_antenna.get_harmonic_for(paired_antenna, harmonic, map) {
+            if harmonics
+                .highest()
+                .is_some_and(|highest| harmonic > highest)
+            {
+                break;
+            }
+
+            // If the harmonic is counted, add the antinode to the list
+            if harmonics.includes(harmonic) {
+                antinodes.push(Antinode {
+                    location: antinode,
+                    base: *base_antenna,
+                    paired: *paired_antenna,
+                    harmonic: Some(harmonic),
+                });
+            }
+            harmonic += 1;
+        }
+    }
+
+    // Return all antinodes found
+    antinodes
+}
+
+/// Gets every location on the map on the line through each pair of the given set of antennas
+/// of the same frequency, along with the pair that produced each
+///
+/// The same location is listed once for each pair that produces it.  The separation between
+/// each pair is reduced by its greatest common divisor to get the smallest step between
+/// locations on the line, so locations between the multiples of the separation are included
+/// too.
+pub fn get_line_antinodes(antennas: &HashSet<Coordinate>, map: &GameMap) -> Vec<Antinode> {
+    // Create a new list for store locations that are found
+    let mut antinodes = Vec::new();
+
+    // Iterate through all the combinations of the given set of antennas
+    for antenna_pair in antennas.iter().combinations(2) {
+        let (base_antenna, paired_antenna) = (antenna_pair[0], antenna_pair[1]);
+
+        // Get the smallest step along the line through the pair
+        let (x_diff, y_diff) = base_antenna.get_distance_from(paired_antenna);
+        let divisor = gcd(x_diff, y_diff);
+        let (x_step, y_step) = (x_diff / divisor, y_diff / divisor);
+
+        // Walk the line in both directions until leaving the map, starting the walk backward
+        // one step from the base antenna so it's only added once
+        for (start, (x_dir, y_dir)) in [(0, (x_step, y_step)), (1, (-x_step, -y_step))] {
+            let (mut x, mut y) = (
+                base_antenna.x + start * x_dir,
+                base_antenna.y + start * y_dir,
+            );
+            while let Some(location) = map.at(x, y) {
+                antinodes.push(Antinode {
+                    location,
+                    base: *base_antenna,
+                    paired: *paired_antenna,
+                    harmonic: None,
+                });
+                x += x_dir;
+                y += y_dir;
+            }
+        }
+    }
+
+    // Return all locations found
+    antinodes
+}
+
+/// Gets the greatest common divisor of two numbers
+pub fn gcd(a: i64, b: i64) -> i64 {
+    if b == 0 {
+        a.abs()
+    } else {
+        gcd(b, a % b)
+    }
+}
+
+/// Counts the distinct locations of the given antinodes
+pub fn count_locations(antinodes: &[Antinode]) -> usize {
+    antinodes
+        .iter()
+        .map(|x| x.location)
+        .collect::<HashSet<Coordinate>>()
+        .len()
+}
+
+/// Groups the given antennas (of the same frequency) into clusters, where antennas are in the
+/// same cluster if they can be reached by hopping between antennas within the given
+/// (Manhattan) distance of each other
+///
+/// Only the area within the bounds of the given map is searched around each antenna, so a
+/// radius beyond the size of the map costs no more than one spanning it.
+pub fn get_clusters(
+    antennas: &HashSet<Coordinate>,
+    map: &GameMap,
+    radius: i64,
+) -> Vec<HashSet<Coordinate>> {
+    // Get the antennas by their position for lookups during the flood fill
+    let positions: HashMap<(i64, i64), Coordinate> =
+        antennas.iter().map(|x| ((x.x, x.y), *x)).collect();
+
+    // Create a list of clusters and a set of antennas already clustered
+    let mut clusters = Vec::new();
+    let mut clustered = HashSet::new();
+
+    // Iterate through the antennas in a stable order, starting a cluster with any that
+    // haven't been clustered yet
+    for antenna in antennas.iter().sorted_by_key(|x| (x.y, x.x)) {
+        if clustered.contains(antenna) {
+            continue;
+        }
+
+        // Hop between antennas, flood filling the area of the map within the radius of each one
+        // to find the others nearby
+        let mut cluster = HashSet::from([*antenna]);
+        let mut to_search = vec![*antenna];
+        while let Some(center) = to_search.pop() {
+            flood_fill(
+                [(center.x, center.y)],
+                |_, (x, y)| {
+                    map.at(x, y).is_some() && (x - center.x).abs() + (y - center.y).abs() <= radius
+                },
+                |point, _| {
+                    if let Some(nearby) = positions.get(&point) {
+                        if cluster.insert(*nearby) {
+                            to_search.push(*nearby);
+                        }
+                    }
+                    Visit::Continue
+                },
+            );
+        }
+
+        // Add the cluster to the list
+        clustered.extend(cluster.iter().copied());
+        clusters.push(cluster);
+    }
+
+    // Return the list of clusters
+    clusters
+}
+
+/// Describes the antinodes as a JSON array, with an object per location holding its
+/// coordinates and the sources of every antinode there, ordered by row then column
+pub fn antinodes_to_json(antinodes: &[Antinode]) -> String {
+    // Group the sources of the antinodes by their location, in a stable order
+    let mut sources: HashMap<Coordinate, Vec<&Antinode>> = HashMap::new();
+    for antinode in antinodes {
+        sources.entry(antinode.location).or_default().push(antinode);
+    }
+
+    // Create a JSON object for each of the locations, one per line
+    let objects: Vec<String> = sources
+        .iter()
+        .sorted_by_key(|(location, _)| (location.y, location.x))
+        .map(|(location, location_sources)| {
+            let location_sources = location_sources
+                .iter()
+                .map(|x| x.source_to_json())
+                .sorted()
+                .join(",");
+            format!(
+                "{{\"x\":{},\"y\":{},\"sources\":[{location_sources}]}}",
+                location.x, location.y
+            )
+        })
+        .collect();
+
+    // Return the objects as a JSON array
+    format!("[\n{}\n]\n", objects.join(",\n"))
+}
+
+/// Counts the distinct locations of the antinodes on the map in the input text at the given
+/// harmonics
+fn count_antinodes(input: &str, harmonics: &Harmonics) -> usize {
+    let map = parse_map(input);
+    let mut all_antinodes = Vec::new();
+    for (_label, antenna_set) in map.get_antennas() {
+        all_antinodes.extend(get_antinodes(&antenna_set, &map, harmonics));
+    }
+    count_locations(&all_antinodes)
+}
+
+/// Solves part one for the puzzle input, getting the number of locations with an antinode
+pub fn part1(input: &str) -> Result<usize, String> {
+    Ok(count_antinodes(input, &Harmonics::for_part(1)))
+}
+
+/// Solves part two for the puzzle input, getting the number of locations with an antinode at
+/// any harmonic
+pub fn part2(input: &str) -> Result<usize, String> {
+    Ok(count_antinodes(input, &Harmonics::for_part(2)))
+}
@@ -1,238 +1,250 @@
-use std::{
-    collections::{HashMap, HashSet},
-    fs,
-    hash::{Hash, Hasher},
-};
+use std::{collections::HashSet, fs};
 
 use clap::Parser;
+use common::{fail, read_input, About, Failure, Format};
+use day8::{
+    antinodes_to_json, count_locations, gcd, get_antinodes, get_clusters, get_line_antinodes,
+    parse_map, Antinode, Harmonics,
+};
 use itertools::Itertools;
 
 #[derive(Parser)]
 struct CliArgs {
     part: u64,
     filepath: String,
+    /// Print the geometry of every pair of antennas with the same frequency
+    #[arg(long)]
+    geometry: bool,
+    /// Group antennas of the same frequency within the given (Manhattan) distance of each other
+    /// into clusters, and print the antinodes each cluster contributes
+    #[arg(long)]
+    clusters: Option<i64>,
+    /// Only count the given multiples of the separation between each pair of antennas beyond
+    /// the pair as antinodes, instead of those of the selected part (1 for part one, all for
+    /// part two)
+    #[arg(long, value_delimiter = ',')]
+    harmonics: Option<Vec<u64>>,
+    /// Count every location on the line through each pair of antennas as an antinode, instead
+    /// of only those at a multiple of the separation between the pair
+    #[arg(long, conflicts_with = "harmonics")]
+    line_fill: bool,
+    /// Write each antinode counted to the given JSON file, along with the frequency and
+    /// positions of every pair of antennas that produced it
+    #[arg(long)]
+    export: Option<String>,
+    /// Parse the input and print statistics about its structure instead of solving it
+    #[arg(long)]
+    parse_only: bool,
+    /// The format in which errors are reported
+    #[arg(long, value_enum, default_value_t = Format::Text)]
+    format: Format,
 }
 
-/// Representation of a given coordinate on a map, and whether an
-/// antenna of a given frequency is at that location
-#[derive(Clone, Copy, Eq)]
-struct Coordinate {
-    x: i64,
-    y: i64,
-    antenna: Option<char>,
-}
+/// Description of the puzzle solved by this binary
+const ABOUT: About = About {
+    day: 8,
+    title: "Resonant Collinearity",
+    input: "A map of antennas, each labelled by its frequency",
+    parts: &[1, 2],
+    features: &[],
+};
 
-impl Coordinate {
-    /// Get the coordinate on the given game map representing an antinode location for this
-    /// coordinate based on the location of a given coordinate
-    fn get_antinode_for(&self, coordinate: &Coordinate, map: &GameMap) -> Option<Coordinate> {
-        let (x_diff, y_diff) = self.get_distance_from(coordinate);
-        let new_x = self.x + x_diff;
-        let new_y = self.y + y_diff;
-        map.at(new_x, new_y)
-    }
+/// Main entry function
+fn main() {
+    // Describe the puzzle instead if requested
+    common::handle_about::<CliArgs>(&ABOUT);
 
-    /// Gets the distance of this coordinate from another coordinate
-    fn get_distance_from(&self, coordinate: &Coordinate) -> (i64, i64) {
-        let x_diff = self.x - coordinate.x;
-        let y_diff = self.y - coordinate.y;
-        (x_diff, y_diff)
-    }
-}
+    // Parse CLI arguments
+    let cli = CliArgs::parse();
 
-impl PartialEq for Coordinate {
-    fn eq(&self, other: &Coordinate) -> bool {
-        self.x == other.x && self.y == other.y
-    }
-}
+    // Report errors in the requested format
+    common::init(cli.format);
 
-impl Hash for Coordinate {
-    fn hash<H: Hasher>(&self, state: &mut H) {
-        self.x.hash(state);
-        self.y.hash(state);
+    // Only parse the input if requested
+    if cli.parse_only {
+        report_structure(&cli.filepath);
     }
-}
-
-/// Representation of the game map
-struct GameMap {
-    spaces: Vec<Vec<Coordinate>>,
-}
 
-impl GameMap {
-    /// Create a new blank game map
-    fn new() -> Self {
-        Self { spaces: Vec::new() }
+    // Print the antenna pair geometry if requested
+    if cli.geometry {
+        print_geometry(&cli.filepath);
     }
 
-    /// Get the coordinate at a given X, Y coordinate
-    ///
-    /// Returns the Coordinate with the given location, or None if it's
-    /// outside the bounds of the map
-    fn at(&self, x: i64, y: i64) -> Option<Coordinate> {
-        if x < 0 || y < 0 {
-            return None;
-        }
-
-        match self.spaces.get(y as usize) {
-            Some(row) => return row.get(x as usize).copied(),
-            None => None,
-        }
+    // Print the antenna clusters if requested
+    if let Some(radius) = cli.clusters {
+        print_clusters(&cli.filepath, radius);
     }
 
-    /// Gets all the antennas from the map, grouped by frequency (label) in a hash map
-    fn get_antennas(&self) -> HashMap<char, HashSet<Coordinate>> {
-        // Create a hash map for storing the antenna information
-        let mut locations: HashMap<char, HashSet<Coordinate>> = HashMap::new();
-
-        // Iterate through the map inspecting coordinates
-        for row in &self.spaces {
-            for coord in row {
-                // If the coordinate has an antenna, add it to the stored hash set (adding one if
-                // this is the first access)
-                if let Some(label) = coord.antenna {
-                    let set = locations.entry(label).or_default();
-                    set.insert(*coord);
-                }
-            }
-        }
+    // Get the harmonics counted as antinodes, defaulting to those of the desired challenge part
+    let harmonics = match cli.harmonics {
+        Some(harmonics) => Harmonics::Only(harmonics),
+        None => Harmonics::for_part(cli.part),
+    };
 
-        // Return the hash map of all the antenna locations
-        locations
+    // Count every location on the lines through the antennas instead if requested
+    if cli.line_fill {
+        main_count_line_antinodes(cli.filepath, cli.export);
+        return;
     }
-}
-
-/// Main entry function
-fn main() {
-    // Parse CLI arguments
-    let cli = CliArgs::parse();
 
     // Run the code for the desired challenge part
     match cli.part {
-        1 => main_part_one(cli.filepath),
-        2 => main_part_two(cli.filepath),
+        1 | 2 => main_count_antinodes(cli.filepath, &harmonics, cli.export),
         _ => panic!("Invalid selection part selection!"),
     }
 }
 
-/// Runs part one
-fn main_part_one(filepath: String) {
+/// Parses the map, then prints its dimensions and the number of antennas and frequencies on it
+/// and exits
+fn report_structure(filepath: &str) -> ! {
+    let map = parse_map(&read_input(filepath));
+    let antennas = map.get_antennas();
+    common::report_parse(&[
+        ("rows", map.spaces.len()),
+        ("columns", map.spaces.first().map_or(0, |x| x.len())),
+        ("antennas", antennas.values().map(|x| x.len()).sum()),
+        ("frequencies", antennas.len()),
+    ]);
+}
+
+/// Runs either part, counting the antinodes at the given harmonics
+fn main_count_antinodes(filepath: String, harmonics: &Harmonics, export: Option<String>) {
     // Read the contents of the file
-    let contents = fs::read_to_string(filepath).expect("Invalid filepath");
+    let contents = read_input(filepath);
 
     // Parse the game map
     let map = parse_map(&contents);
 
-    // Get all the antinodes for the antennas
-    let mut all_antinodes: HashSet<Coordinate> = HashSet::new();
+    // Get all the antinodes for the antennas and add them to a running list
+    let mut all_antinodes = Vec::new();
     for (_label, antenna_set) in map.get_antennas() {
-        let antenna_set_antinodes = get_antinodes(&antenna_set, &map);
-        all_antinodes.extend(&antenna_set_antinodes);
+        let antenna_set_antinodes = get_antinodes(&antenna_set, &map, harmonics);
+        all_antinodes.extend(antenna_set_antinodes);
+    }
+
+    // Export the antinodes if requested
+    if let Some(export_filepath) = export {
+        write_antinodes(&all_antinodes, export_filepath);
     }
 
-    // Print the number of valid antinodes calculated
-    let num_antinodes = all_antinodes.len();
+    // Print the number of distinct antinodes calculated
+    let num_antinodes = count_locations(&all_antinodes);
     println!("{num_antinodes}");
 }
 
-// Runs part two
-fn main_part_two(filepath: String) {
+/// Counts every location on the lines through each pair of antennas with the same frequency
+fn main_count_line_antinodes(filepath: String, export: Option<String>) {
     // Read the contents of the file
-    let contents = fs::read_to_string(filepath).expect("Invalid filepath");
+    let contents = read_input(filepath);
 
     // Parse the game map
     let map = parse_map(&contents);
 
-    // Get all the antinodes for the antennas and add them to a running hash set
-    let mut all_antinodes: HashSet<Coordinate> = HashSet::new();
+    // Get all the locations on the lines and add them to a running list
+    let mut all_antinodes = Vec::new();
     for (_label, antenna_set) in map.get_antennas() {
-        let antenna_set_antinodes = get_resonant_antinodes(&antenna_set, &map);
-        all_antinodes.extend(&antenna_set_antinodes);
+        all_antinodes.extend(get_line_antinodes(&antenna_set, &map));
     }
 
-    // Print the number of valid antinodes calculated
-    let num_antinodes = all_antinodes.len();
+    // Export the antinodes if requested
+    if let Some(export_filepath) = export {
+        write_antinodes(&all_antinodes, export_filepath);
+    }
+
+    // Print the number of distinct locations on the lines
+    let num_antinodes = count_locations(&all_antinodes);
     println!("{num_antinodes}");
 }
 
-/// Parse the string to build a game map
-fn parse_map(input: &str) -> GameMap {
-    // Create a new map
-    let mut map = GameMap::new();
-
-    // Iterate through the file contents line by line
-    for (row_index, line) in input.lines().filter(|x| !x.is_empty()).enumerate() {
-        // Start a new row for the given line of data
-        let mut row = Vec::new();
-
-        // Iterate through the line character by character
-        for (col_index, character) in line.chars().enumerate() {
-            // Initialize a coordinate for the map
-            let mut coordinate = Coordinate {
-                x: col_index as i64,
-                y: row_index as i64,
-                antenna: None,
-            };
-
-            // If the character is an antenna, save it
-            if character != '.' {
-                coordinate.antenna = Some(character);
-            }
-
-            // Add the coordinate to the row
-            row.push(coordinate);
-        }
-
-        // Add the row to the map space
-        map.spaces.push(row);
-    }
-
-    // Return the completed map
-    map
+/// Writes the given antinodes to the given JSON file as a list of objects with the position
+/// of each distinct antinode and the sources that produced it, ordered by their position on the
+/// map
+fn write_antinodes(antinodes: &[Antinode], export_filepath: String) {
+    // Describe the antinodes as JSON
+    let json = antinodes_to_json(antinodes);
+
+    // Write the antinodes to the given file
+    fs::write(&export_filepath, json).unwrap_or_else(|error| {
+        fail(Failure::Io(format!(
+            "Could not write {export_filepath}: {error}"
+        )))
+    });
 }
 
-/// Gets the antinodes for a given set of antennas of the same frequency
-fn get_antinodes(antennas: &HashSet<Coordinate>, map: &GameMap) -> HashSet<Coordinate> {
-    // Create a new hash set for store antinodes that are found
-    let mut antinodes = HashSet::new();
+/// Prints, for each pair of antennas with the same frequency, the separation vector between
+/// them, its reduced direction, and the number of antinodes and resonant antinodes the pair
+/// contributes within the bounds of the map
+fn print_geometry(filepath: &str) {
+    // Read the contents of the file
+    let contents = read_input(filepath);
 
-    // Iterate through all the permutations of the given set of antennas
-    for antenna_pair in antennas.iter().permutations(2) {
-        // Get the base antenna and paired antenna (paired <--> base <--> antinode)
-        let base_antenna = antenna_pair[0];
-        let paired_antenna = antenna_pair[1];
+    // Parse the game map
+    let map = parse_map(&contents);
 
-        // If an antinode can be found, add it to the hash set
-        if let Some(antinode) = base_antenna.get_antinode_for(paired_antenna, map) {
-            antinodes.insert(antinode);
+    // Iterate through the frequencies in a stable order
+    let antennas = map.get_antennas();
+    for label in antennas.keys().sorted() {
+        // Sort the antennas of the frequency by their position on the map
+        let antenna_set = &antennas[label];
+        let ordered = antenna_set.iter().sorted_by_key(|x| (x.y, x.x));
+
+        // Iterate through each pair of antennas
+        for antenna_pair in ordered.combinations(2) {
+            let (first, second) = (antenna_pair[0], antenna_pair[1]);
+
+            // Get the separation between the pair and the reduced direction of it
+            let (x_diff, y_diff) = second.get_distance_from(first);
+            let divisor = gcd(x_diff, y_diff);
+            let (x_dir, y_dir) = (x_diff / divisor, y_diff / divisor);
+
+            // Get the antinodes contributed by the pair alone
+            let pair_set = HashSet::from([*first, *second]);
+            let num_antinodes =
+                count_locations(&get_antinodes(&pair_set, &map, &Harmonics::for_part(1)));
+            let num_resonant = count_locations(&get_antinodes(&pair_set, &map, &Harmonics::All));
+
+            // Print the pair geometry
+            println!(
+                "{label}: ({}, {}) <-> ({}, {}) separation ({x_diff}, {y_diff}) direction ({x_dir}, {y_dir}) antinodes {num_antinodes} resonant {num_resonant}",
+                first.x, first.y, second.x, second.y
+            );
         }
     }
-
-    // Return all antinodes found
-    antinodes
 }
 
-/// Gets the resonant antinodes for a given set of antennas of the same frequency
-fn get_resonant_antinodes(antennas: &HashSet<Coordinate>, map: &GameMap) -> HashSet<Coordinate> {
-    // Create a new hash set for store antinodes that are found
-    let mut antinodes = HashSet::new();
-
-    // Iterate through all the permutations of the given set of antennas
-    for antenna_pair in antennas.iter().permutations(2) {
-        // Get the original base antenna and paired antenna (paired <-> base <--> antinode)
-        let mut base_antenna = *antenna_pair[0];
-        let mut paired_antenna = *antenna_pair[1];
-
-        // While antinodes can be found, add them to the hash set, and move down then line to detect again
-        while let Some(antinode) = base_antenna.get_antinode_for(&paired_antenna, map) {
-            antinodes.insert(antinode);
-            paired_antenna = base_antenna;
-            base_antenna = antinode;
-        }
+/// Prints, for each cluster of antennas with the same frequency, the antennas in it and the
+/// number of antinodes and resonant antinodes the cluster contributes within the bounds of the
+/// map
+fn print_clusters(filepath: &str, radius: i64) {
+    // Read the contents of the file
+    let contents = read_input(filepath);
 
-        // If there are two antennas, both are also antinodes, so add them to the hash set
-        antinodes.extend(antenna_pair);
-    }
+    // Parse the game map
+    let map = parse_map(&contents);
 
-    // Return the completed list of antinodes
-    antinodes
+    // Iterate through the frequencies in a stable order
+    let antennas = map.get_antennas();
+    for label in antennas.keys().sorted() {
+        // Iterate through the clusters of the frequency
+        for (index, cluster) in get_clusters(&antennas[label], &map, radius)
+            .iter()
+            .enumerate()
+        {
+            // Get the antinodes contributed by the cluster alone
+            let num_antinodes =
+                count_locations(&get_antinodes(cluster, &map, &Harmonics::for_part(1)));
+            let num_resonant = count_locations(&get_antinodes(cluster, &map, &Harmonics::All));
+
+            // Print the cluster and its contributions
+            let members = cluster
+                .iter()
+                .sorted_by_key(|x| (x.y, x.x))
+                .map(|x| format!("({}, {})", x.x, x.y))
+                .join(" ");
+            let num_antennas = cluster.len();
+            println!(
+                "{label} cluster {index}: {num_antennas} antennas {members} antinodes {num_antinodes} resonant {num_resonant}"
+            );
+        }
+    }
 }
@@ -0,0 +1,56 @@
+use day8::{get_clusters, parse_map};
+
+const EXAMPLE: &str = include_str!("../inputs/example.txt");
+
+/// Gets the positions of the antennas in each cluster of the frequency, in order
+fn cluster_positions(input: &str, frequency: char, radius: i64) -> Vec<Vec<(i64, i64)>> {
+    let map = parse_map(input);
+    let antennas = map.get_antennas();
+    get_clusters(&antennas[&frequency], &map, radius)
+        .iter()
+        .map(|cluster| {
+            let mut positions: Vec<(i64, i64)> = cluster.iter().map(|x| (x.x, x.y)).collect();
+            positions.sort_by_key(|&(x, y)| (y, x));
+            positions
+        })
+        .collect()
+}
+
+#[test]
+fn clusters_within_radius() {
+    assert_eq!(
+        cluster_positions(EXAMPLE, 'A', 4),
+        vec![vec![(6, 5)], vec![(8, 8), (9, 9)]]
+    );
+    assert_eq!(
+        cluster_positions(EXAMPLE, '0', 4),
+        vec![vec![(8, 1), (5, 2), (7, 3), (4, 4)]]
+    );
+}
+
+#[test]
+fn radius_zero_separates_every_antenna() {
+    assert_eq!(
+        cluster_positions(EXAMPLE, 'A', 0),
+        vec![vec![(6, 5)], vec![(8, 8)], vec![(9, 9)]]
+    );
+}
+
+#[test]
+fn huge_radius_is_bounded_by_the_map() {
+    assert_eq!(
+        cluster_positions(EXAMPLE, 'A', i64::MAX / 2),
+        vec![vec![(6, 5), (8, 8), (9, 9)]]
+    );
+}
+
+#[test]
+fn clusters_join_at_exactly_the_radius() {
+    // The antennas are a (Manhattan) distance of 4 apart
+    let input = "a..\n...\n..a\n";
+    assert_eq!(cluster_positions(input, 'a', 4), vec![vec![(0, 0), (2, 2)]]);
+    assert_eq!(
+        cluster_positions(input, 'a', 3),
+        vec![vec![(0, 0)], vec![(2, 2)]]
+    );
+}
@@ -0,0 +1,58 @@
+use day8::{antinodes_to_json, get_antinodes, parse_map, Antinode, Harmonics};
+use serde_json::Value;
+
+const EXAMPLE: &str = include_str!("../inputs/example.txt");
+
+/// Gets the antinodes of every frequency in the input at the harmonics of the given part
+fn example_antinodes(part: u64) -> Vec<Antinode> {
+    let map = parse_map(EXAMPLE);
+    let mut antinodes = Vec::new();
+    for antennas in map.get_antennas().values() {
+        antinodes.extend(get_antinodes(antennas, &map, &Harmonics::for_part(part)));
+    }
+    antinodes
+}
+
+#[test]
+fn export_parses_as_json() {
+    let antinodes = example_antinodes(1);
+    let json: Value = serde_json::from_str(&antinodes_to_json(&antinodes)).unwrap();
+    let locations = json.as_array().unwrap();
+
+    // There is one object per distinct location, ordered by row then column
+    assert_eq!(locations.len(), 14);
+    let positions: Vec<(i64, i64)> = locations
+        .iter()
+        .map(|x| (x["y"].as_i64().unwrap(), x["x"].as_i64().unwrap()))
+        .collect();
+    let mut sorted = positions.clone();
+    sorted.sort();
+    sorted.dedup();
+    assert_eq!(positions, sorted);
+
+    // Every antinode is listed as a source of its location
+    let num_sources: usize = locations
+        .iter()
+        .map(|x| x["sources"].as_array().unwrap().len())
+        .sum();
+    assert_eq!(num_sources, antinodes.len());
+}
+
+#[test]
+fn export_describes_sources() {
+    let json: Value = serde_json::from_str(&antinodes_to_json(&example_antinodes(1))).unwrap();
+    for location in json.as_array().unwrap() {
+        for source in location["sources"].as_array().unwrap() {
+            let frequency = source["frequency"].as_str().unwrap();
+            assert!(frequency == "0" || frequency == "A", "{frequency}");
+            assert_eq!(source["antennas"].as_array().unwrap().len(), 2);
+            assert_eq!(source["harmonic"], 1);
+        }
+    }
+}
+
+#[test]
+fn export_of_no_antinodes_is_empty_array() {
+    let json: Value = serde_json::from_str(&antinodes_to_json(&[])).unwrap();
+    assert_eq!(json, Value::Array(Vec::new()));
+}
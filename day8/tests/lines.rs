@@ -0,0 +1,63 @@
+use std::collections::HashSet;
+
+use day8::{count_locations, get_antinodes, get_line_antinodes, parse_map, Antinode, Harmonics};
+
+const EXAMPLE: &str = include_str!("../inputs/example.txt");
+
+/// Gets the antinodes on the lines through the pairs of antennas of every frequency in the input
+fn line_antinodes(input: &str) -> Vec<Antinode> {
+    let map = parse_map(input);
+    let mut antinodes = Vec::new();
+    for antennas in map.get_antennas().values() {
+        antinodes.extend(get_line_antinodes(antennas, &map));
+    }
+    antinodes
+}
+
+/// Gets the positions of the given antinodes, without duplicates
+fn positions(antinodes: &[Antinode]) -> HashSet<(i64, i64)> {
+    antinodes
+        .iter()
+        .map(|x| (x.location.x, x.location.y))
+        .collect()
+}
+
+#[test]
+fn example_line_fill_matches_part_two() {
+    assert_eq!(count_locations(&line_antinodes(EXAMPLE)), 34);
+}
+
+#[test]
+fn line_fill_matches_resonant_harmonics_on_example() {
+    let map = parse_map(EXAMPLE);
+    let mut resonant = Vec::new();
+    for antennas in map.get_antennas().values() {
+        resonant.extend(get_antinodes(antennas, &map, &Harmonics::All));
+    }
+    assert_eq!(positions(&line_antinodes(EXAMPLE)), positions(&resonant));
+}
+
+#[test]
+fn line_fill_includes_locations_between_antennas() {
+    // The antennas are two steps apart along the line, so the location between them is on it
+    let input = "a....\n.....\n.....\n.....\n..a..\n";
+    let antinodes = line_antinodes(input);
+    assert_eq!(
+        positions(&antinodes),
+        HashSet::from([(0, 0), (1, 2), (2, 4)])
+    );
+    assert!(antinodes.iter().all(|x| x.harmonic.is_none()));
+}
+
+#[test]
+fn each_pair_lists_its_locations() {
+    // Each of the three pairs on the row covers the whole row
+    let antinodes = line_antinodes("a.a.a\n");
+    assert_eq!(antinodes.len(), 15);
+    assert_eq!(count_locations(&antinodes), 5);
+}
+
+#[test]
+fn lone_antenna_has_no_line() {
+    assert!(line_antinodes("...\n.a.\n...\n").is_empty());
+}
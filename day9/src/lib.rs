@@ -0,0 +1,409 @@
+use std::{
+    fmt::{self, Display, Formatter},
+    ops::Range,
+};
+
+use common::paint;
+
+/// Representation of a contiguous block of memory
+#[derive(Clone, Copy, Debug)]
+pub struct MemoryBlock {
+    /// The ID of the memory block
+    ///
+    /// This is Some(id) if it is data, and None if it is empty space
+    pub id: Option<usize>,
+
+    /// The size/length of the memory block
+    pub size: usize,
+}
+
+impl MemoryBlock {
+    /// Reduces the size of the memory block by a specified amount
+    ///
+    /// Returns whether the memory was reduced.
+    pub fn reduce(&mut self, amount: usize) -> bool {
+        if amount > self.size {
+            return false;
+        }
+        self.size -= amount;
+        true
+    }
+
+    /// Creates an empty memory block of a certain size
+    pub fn as_empty(size: usize) -> Self {
+        Self { id: None, size }
+    }
+
+    /// Checks whether the memory block is considered free
+    pub fn is_free(&self) -> bool {
+        self.id.is_none()
+    }
+
+    /// Gets the memory block as a vector of bytes
+    pub fn as_byte_list(&self) -> Vec<Option<usize>> {
+        vec![self.id; self.size]
+    }
+}
+
+/// A list of memory blocks making up the disk, which is displayed with one character per
+/// "byte", shading used and free space and coloring each file by its ID
+///
+/// The drawing wraps after the width given to the formatter, if any (e.g. `{:80}`).
+pub struct BlockList(pub Vec<MemoryBlock>);
+
+impl BlockList {
+    /// Creates the list of memory blocks from a list of "bytes", joining runs of the same ID
+    pub fn from_bytes(data: &[Option<usize>]) -> Self {
+        let mut blocks: Vec<MemoryBlock> = Vec::new();
+        for id in data {
+            match blocks.last_mut() {
+                Some(block) if block.id == *id => block.size += 1,
+                _ => blocks.push(MemoryBlock { id: *id, size: 1 }),
+            }
+        }
+        Self(blocks)
+    }
+
+    /// Gets the ranges of "bytes" taken up by each contiguous stretch of free space, in order
+    ///
+    /// Adjacent free memory blocks are combined into a single stretch.
+    pub fn free_extents(&self) -> Vec<Range<usize>> {
+        let mut extents: Vec<Range<usize>> = Vec::new();
+        let mut start = 0;
+        for block in &self.0 {
+            let end = start + block.size;
+            if block.is_free() && block.size > 0 {
+                match extents.last_mut() {
+                    Some(extent) if extent.end == start => extent.end = end,
+                    _ => extents.push(start..end),
+                }
+            }
+            start = end;
+        }
+        extents
+    }
+}
+
+impl Display for BlockList {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        let width = f.width();
+        let mut column = 0;
+        for block in &self.0 {
+            // Get the shading for the block, colored by the file ID if it is used
+            let shade = match block.id {
+                Some(id) => paint("█", id),
+                None => "░".to_string(),
+            };
+
+            // Draw the block one "byte" at a time, wrapping at the width if given
+            for _ in 0..block.size {
+                if width.is_some_and(|width| column == width) {
+                    writeln!(f)?;
+                    column = 0;
+                }
+                write!(f, "{shade}")?;
+                column += 1;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Count of the work done while compacting the disk, used to spot inputs for which compacting
+/// takes time quadratic in the size of the disk
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Work {
+    /// The number of elements inspected while searching the disk
+    pub scanned: usize,
+    /// The number of elements moved to make room for, or close up after, an insertion or
+    /// removal
+    pub shifted: usize,
+}
+
+impl Work {
+    /// Gets the total number of element operations performed
+    pub fn total(&self) -> usize {
+        self.scanned + self.shifted
+    }
+
+    /// Checks whether more than the given factor of operations were performed per element of a
+    /// disk of the given size
+    pub fn exceeds(&self, size: usize, factor: usize) -> bool {
+        self.total() > size.max(1).saturating_mul(factor)
+    }
+}
+
+/// Filter for which files are eligible to be moved when compacting whole files
+pub struct FileFilter {
+    /// The range of IDs of files that can be moved, or all files if None
+    pub ids: Option<Range<usize>>,
+    /// The minimum size of files that can be moved
+    pub min_size: usize,
+}
+
+impl FileFilter {
+    /// Checks whether the given file is eligible to be moved
+    pub fn allows(&self, file: &MemoryBlock) -> bool {
+        let in_range = match (&self.ids, file.id) {
+            (Some(ids), Some(id)) => ids.contains(&id),
+            _ => true,
+        };
+        in_range && file.size >= self.min_size
+    }
+}
+
+/// Parses a range of IDs in the form `a..b` or `a..=b`, where either end can be left out
+pub fn parse_id_range(text: &str) -> Result<Range<usize>, String> {
+    // Split the text into the start and end of the range
+    let (start_text, end_text) = text
+        .split_once("..")
+        .ok_or_else(|| format!("Expected a range like 2..5, got {text:?}"))?;
+    let (end_text, inclusive) = match end_text.strip_prefix('=') {
+        Some(end_text) => (end_text, true),
+        None => (end_text, false),
+    };
+
+    // Parse the ends of the range, defaulting to all IDs when left out
+    let parse_bound = |bound: &str, default: usize| {
+        if bound.is_empty() {
+            Ok(default)
+        } else {
+            bound
+                .parse::<usize>()
+                .map_err(|_| format!("Could not parse {bound:?} as an ID"))
+        }
+    };
+    let start = parse_bound(start_text, 0)?;
+    let end = parse_bound(end_text, usize::MAX)?;
+
+    // Return the range, adjusting the end if inclusive
+    if inclusive {
+        Ok(start..end.saturating_add(1))
+    } else {
+        Ok(start..end)
+    }
+}
+
+/// Parses the sizes of the files and free spaces in the disk map, which are single digits,
+/// failing with the position of any other character
+pub fn parse_disk_map(input: &str) -> Result<Vec<usize>, String> {
+    input
+        .trim()
+        .chars()
+        .enumerate()
+        .map(|(index, character)| {
+            character.to_digit(10).map(|x| x as usize).ok_or_else(|| {
+                let position = index + 1;
+                format!("Position {position}: could not parse {character:?} as a digit")
+            })
+        })
+        .collect()
+}
+
+/// Creates a list of memory blocks based on the input string, failing if it isn't a disk map
+pub fn create_block_list(input: &str) -> Result<Vec<MemoryBlock>, String> {
+    // Create a new list for storing byte data
+    let mut data = Vec::new();
+
+    // Keep track of the mode (data or empty)
+    let mut data_mode = true;
+    let mut id = 0;
+
+    // Iterate through the sizes in the disk map
+    for num_spaces in parse_disk_map(input)? {
+        // Prepare the ID of the memory block depending on whether it is data or empty space
+        let assignable_id = if data_mode { Some(id) } else { None };
+
+        // Create and add the memory block to the list
+        let memory_block = MemoryBlock {
+            id: assignable_id,
+            size: num_spaces,
+        };
+        data.push(memory_block);
+
+        // If just performed the action in data mode, increase the ID by 1
+        if data_mode {
+            id += 1;
+        }
+
+        // Switch between data and empty mode
+        data_mode = !data_mode
+    }
+
+    // Return the data list
+    Ok(data)
+}
+
+/// Defragment the data at the "memory block" level, only moving the files allowed by the
+/// filter, returning the work done along the way
+pub fn defragment_data_blockwise(data: &mut Vec<MemoryBlock>, filter: &FileFilter) -> Work {
+    // Get the number of IDs to be iterated over
+    let num_id = data.iter().filter_map(|x| x.id).count();
+    let mut work = Work::default();
+
+    // Iterate through the IDs in descending order
+    for id in (0..num_id).rev() {
+        // Get the files original position and remove it from the list
+        let file_position = data.iter().position(|x| x.id == Some(id)).unwrap();
+        work.scanned += file_position + 1;
+
+        // If the file isn't eligible to be moved, leave it where it is
+        if !filter.allows(&data[file_position]) {
+            continue;
+        }
+        let file_memory = data[file_position];
+
+        // Look for a free memory block of at least the same size ahead of the original position
+        let free_position = data[..file_position]
+            .iter()
+            .position(|x| x.is_free() && x.size >= file_memory.size);
+        work.scanned += free_position.map_or(file_position, |x| x + 1);
+        if let Some(free_position) = free_position {
+            // Replace the file with an empty memory block the same size at its original position
+            data[file_position] = MemoryBlock::as_empty(file_memory.size);
+
+            // Get the free memory and reduce it's size by the file size
+            let free_memory = data.get_mut(free_position).unwrap();
+            free_memory.reduce(file_memory.size);
+
+            // Insert the file ahead of the free memory, which shifts the original position
+            data.insert(free_position, file_memory);
+            work.shifted += data.len() - free_position - 1;
+
+            // Combine the empty memory with any free memory around it
+            coalesce_free_blocks(data, file_position + 1, &mut work);
+
+            // Remove the free memory if the file used all of it
+            if data[free_position + 1].is_free() && data[free_position + 1].size == 0 {
+                data.remove(free_position + 1);
+                work.shifted += data.len() - free_position - 1;
+            }
+        }
+    }
+
+    data.retain_mut(|x| x.size > 0);
+    work
+}
+
+/// Combines the free memory block at the given position with any free memory blocks directly
+/// before or after it, so that free space isn't left split across several blocks, adding the
+/// elements shifted to the given work
+fn coalesce_free_blocks(data: &mut Vec<MemoryBlock>, mut position: usize, work: &mut Work) {
+    // Only free memory can be combined
+    if !data[position].is_free() {
+        return;
+    }
+
+    // Absorb the free memory blocks after the position
+    while data.get(position + 1).is_some_and(|x| x.is_free()) {
+        data[position].size += data.remove(position + 1).size;
+        work.shifted += data.len() - position - 1;
+    }
+
+    // Absorb the position into the free memory blocks before it
+    while position > 0 && data[position - 1].is_free() {
+        data[position - 1].size += data.remove(position).size;
+        work.shifted += data.len() - position;
+        position -= 1;
+    }
+}
+
+/// Creates a list of numbers based on the input string
+///
+/// IDs are placed in their respective locations, with None being
+/// used to signify empty spaces.  Fails if the input isn't a disk map.
+pub fn create_byte_list(input: &str) -> Result<Vec<Option<usize>>, String> {
+    // Create a new list for storing byte data
+    let mut data = Vec::new();
+
+    // Keep track of the mode (data or empty)
+    let mut data_mode = true;
+    let mut id = 0;
+
+    // Iterate through the sizes in the disk map
+    for num_spaces in parse_disk_map(input)? {
+        // Perform the action for the number of spaces
+        for _x in 0..num_spaces {
+            // If data mode, push IDs to the list; otherwise push empty space
+            if data_mode {
+                data.push(Some(id));
+            } else {
+                data.push(None);
+            }
+        }
+
+        // If just performed the action in data mode, increase the ID by 1
+        if data_mode {
+            id += 1;
+        }
+
+        // Switch between data and empty mode
+        data_mode = !data_mode
+    }
+
+    // Return the data list
+    Ok(data)
+}
+
+/// Defragment the data at the "byte" level, moving the last byte into the first empty space
+/// until none is left, in linear time by walking inward from both ends at once, returning the
+/// work done
+pub fn defragment_data_bytewise_linear(data: &mut Vec<Option<usize>>) -> Work {
+    let mut work = Work::default();
+
+    // Move the last byte into the first empty space until the two meet
+    let mut free = 0;
+    let mut last = data.len();
+    loop {
+        // Find the next empty space from the front and the next byte from the back
+        while free < last && data[free].is_some() {
+            free += 1;
+            work.scanned += 1;
+        }
+        while last > free && data[last - 1].is_none() {
+            last -= 1;
+            work.scanned += 1;
+        }
+        if free >= last {
+            break;
+        }
+
+        // Move the byte into the empty space
+        data.swap(free, last - 1);
+    }
+
+    // Remove the empty space left at the end
+    data.truncate(last);
+    work
+}
+
+/// Calculate the checksum for an array of data bytes
+pub fn calculate_checksum(data: &[Option<usize>]) -> usize {
+    let mut checksum = 0;
+    for (index, id) in data.iter().enumerate() {
+        if let Some(value) = id {
+            checksum += index * value
+        }
+    }
+    checksum
+}
+
+/// Solves part one for the puzzle input, getting the checksum once the blocks are compacted
+/// one at a time
+pub fn part1(input: &str) -> Result<usize, String> {
+    let mut data = create_byte_list(input)?;
+    defragment_data_bytewise_linear(&mut data);
+    Ok(calculate_checksum(&data))
+}
+
+/// Solves part two for the puzzle input, getting the checksum once whole files are compacted
+pub fn part2(input: &str) -> Result<usize, String> {
+    let mut blocks = create_block_list(input)?;
+    let filter = FileFilter {
+        ids: None,
+        min_size: 0,
+    };
+    defragment_data_blockwise(&mut blocks, &filter);
+    let data: Vec<Option<usize>> = blocks.iter().flat_map(|x| x.as_byte_list()).collect();
+    Ok(calculate_checksum(&data))
+}
@@ -1,238 +1,275 @@
-use std::fs;
+use std::ops::Range;
 
-use clap::Parser;
+use clap::{Parser, ValueEnum};
+use common::{fail, read_input, About, Failure, Feature, Format};
+use day9::{
+    calculate_checksum, create_block_list, create_byte_list, defragment_data_blockwise,
+    defragment_data_bytewise_linear, parse_id_range, BlockList, FileFilter, MemoryBlock, Work,
+};
+
+mod reference;
 
 #[derive(Parser)]
 struct CliArgs {
     part: u64,
     filepath: String,
+    /// Verify the checksum against the one calculated by the reference defragmenter
+    #[arg(long, conflicts_with_all = ["only_ids", "min_size"])]
+    verify: bool,
+    /// Only move files with IDs in the given range (`a..b`, or `a..=b` to include `b`) when
+    /// compacting whole files
+    #[arg(long, value_parser = parse_id_range)]
+    only_ids: Option<Range<usize>>,
+    /// Only move files of at least the given size when compacting whole files
+    #[arg(long, default_value_t = 0)]
+    min_size: usize,
+    /// Draw the disk after compacting it, shading used and free space and coloring each file
+    #[arg(long)]
+    render: bool,
+    /// Wrap the drawing of the disk after the given number of blocks
+    #[arg(long, requires = "render")]
+    render_width: Option<usize>,
+    /// Watch for compaction doing more than the given number of element operations per element
+    /// of the disk, which means it is taking time quadratic in the size of the disk
+    #[arg(long)]
+    guard: Option<usize>,
+    /// What to do when compaction goes over the limit set by the guard
+    #[arg(long, value_enum, default_value_t = GuardAction::Warn, requires = "guard")]
+    on_quadratic: GuardAction,
+    /// Parse the input and print statistics about its structure instead of solving it
+    #[arg(long)]
+    parse_only: bool,
+    /// The format in which errors are reported
+    #[arg(long, value_enum, default_value_t = Format::Text)]
+    format: Format,
 }
 
-/// Representation of a contiguous block of memory
-#[derive(Clone, Copy, Debug)]
-struct MemoryBlock {
-    /// The ID of the memory block
-    ///
-    /// This is Some(id) if it is data, and None if it is empty space
-    id: Option<usize>,
+/// Description of the puzzle solved by this binary
+const ABOUT: About = About {
+    day: 9,
+    title: "Disk Fragmenter",
+    input: "A single line dense disk map of alternating file and free space lengths",
+    parts: &[1, 2],
+    features: &[Feature::Visualization],
+};
+
+/// Actions to take when compaction goes over the limit set by the guard
+#[derive(Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum GuardAction {
+    /// Report the work done once compaction finishes
+    Warn,
+    /// Stop compacting and start over using a strategy that takes linear time, if there is one,
+    /// reporting the work done either way
+    Switch,
+}
 
-    /// The size/length of the memory block
-    size: usize,
+/// Limit on the work done while compacting, along with the action to take when it is exceeded
+#[derive(Clone, Copy)]
+struct Guard {
+    /// The number of element operations allowed per element of the disk
+    factor: usize,
+    /// The action to take when the limit is exceeded
+    action: GuardAction,
 }
 
-impl MemoryBlock {
-    /// Reduces the size of the memory block by a specified amount
-    ///
-    /// Returns whether the memory was reduced.
-    fn reduce(&mut self, amount: usize) -> bool {
-        if amount > self.size {
-            return false;
+impl Guard {
+    /// Prints a warning to stderr if the given work on a disk with the given number of elements
+    /// exceeds the limit
+    fn check(&self, work: &Work, size: usize) {
+        if work.exceeds(size, self.factor) {
+            let total = work.total();
+            let factor = self.factor;
+            eprintln!(
+                "Warning: compaction did {total} element operations ({} scanned, {} shifted) for {size} elements, more than {factor} per element, which suggests it is quadratic for this input",
+                work.scanned, work.shifted
+            );
         }
-        self.size -= amount;
-        true
-    }
-
-    /// Creates an empty memory block of a certain size
-    fn as_empty(size: usize) -> Self {
-        Self { id: None, size }
-    }
-
-    /// Checks whether the memory block is considered free
-    fn is_free(&self) -> bool {
-        self.id.is_none()
-    }
-
-    /// Gets the memory block as a vector of bytes
-    fn as_byte_list(&self) -> Vec<Option<usize>> {
-        vec![self.id; self.size]
     }
 }
 
 /// Main entry function
 fn main() {
+    // Describe the puzzle instead if requested
+    common::handle_about::<CliArgs>(&ABOUT);
+
     // Parse CLI arguments
     let cli = CliArgs::parse();
 
+    // Report errors in the requested format
+    common::init(cli.format);
+
+    // Only parse the input if requested
+    if cli.parse_only {
+        report_structure(&cli.filepath);
+    }
+
+    // Get the width to wrap the drawing of the disk at, if it should be drawn
+    let render = cli.render.then_some(cli.render_width.unwrap_or(0));
+
+    // Get the limit on the work done while compacting, if it should be watched
+    let guard = cli.guard.map(|factor| Guard {
+        factor,
+        action: cli.on_quadratic,
+    });
+
     // Run the code for the desired challenge part
     match cli.part {
-        1 => main_part_one(cli.filepath),
-        2 => main_part_two(cli.filepath),
+        1 => main_part_one(cli.filepath, cli.verify, render, guard),
+        2 => {
+            let filter = FileFilter {
+                ids: cli.only_ids,
+                min_size: cli.min_size,
+            };
+            main_part_two(cli.filepath, cli.verify, &filter, render, guard)
+        }
         _ => panic!("Invalid selection part selection!"),
     }
 }
 
+/// Fails with the given parse error
+fn parse_failure<T>(error: String) -> T {
+    fail(Failure::Parse(error))
+}
+
+/// Parses the disk map, then prints the number of files and free spaces and the number of
+/// blocks used by each and exits
+fn report_structure(filepath: &str) -> ! {
+    let blocks = create_block_list(&read_input(filepath)).unwrap_or_else(parse_failure);
+    let (files, spaces): (Vec<MemoryBlock>, Vec<MemoryBlock>) =
+        blocks.iter().partition(|x| !x.is_free());
+    common::report_parse(&[
+        ("files", files.len()),
+        ("file blocks", files.iter().map(|x| x.size).sum()),
+        ("free spaces", spaces.len()),
+        ("free blocks", spaces.iter().map(|x| x.size).sum()),
+    ]);
+}
+
 /// Runs part one
-fn main_part_one(filepath: String) {
+fn main_part_one(filepath: String, verify: bool, render: Option<usize>, guard: Option<Guard>) {
     // Read the contents of the file
-    let contents = fs::read_to_string(filepath).expect("Invalid filepath");
+    let contents = read_input(filepath);
 
     // Parse the input
-    let mut data = create_byte_list(&contents);
+    let mut data = create_byte_list(&contents).unwrap_or_else(parse_failure);
+    let size = data.len();
+
+    // Defragment the data, stopping early if over the limit and switching strategies
+    let budget = guard
+        .filter(|x| x.action == GuardAction::Switch)
+        .map(|x| size.max(1).saturating_mul(x.factor));
+    let mut work = defragment_data_bytewise(&mut data, budget);
+    if let Some(guard) = guard {
+        guard.check(&work, size);
+        if budget.is_some_and(|x| work.total() > x) {
+            eprintln!("Switching to linear compaction");
+            data = create_byte_list(&contents).unwrap_or_else(parse_failure);
+            work = defragment_data_bytewise_linear(&mut data);
+            guard.check(&work, size);
+        }
+    }
 
-    // Defragment the data
-    defragment_data_bytewise(&mut data);
+    // Draw the defragmented data if requested
+    if let Some(width) = render {
+        print_blocks(&BlockList::from_bytes(&data), width);
+    }
 
     // Caclulate and print the checksum
     let checksum = calculate_checksum(&data);
+    if verify {
+        verify_checksum(&contents, false, checksum);
+    }
     println!("{checksum}");
 }
 
-/// Runs part one
-fn main_part_two(filepath: String) {
+/// Runs part two
+fn main_part_two(
+    filepath: String,
+    verify: bool,
+    filter: &FileFilter,
+    render: Option<usize>,
+    guard: Option<Guard>,
+) {
     // Read the contents of the file
-    let contents = fs::read_to_string(filepath).expect("Invalid filepath");
+    let contents = read_input(filepath);
 
     // Parse the input
-    let mut blocks = create_block_list(&contents);
+    let mut blocks = create_block_list(&contents).unwrap_or_else(parse_failure);
+    let size = blocks.len();
+
+    // Defragment the data, warning if it was over the limit since there is no other strategy
+    // for compacting whole files to switch to
+    let work = defragment_data_blockwise(&mut blocks, filter);
+    if let Some(guard) = guard {
+        guard.check(&work, size);
+    }
 
-    // Defragment the data
-    defragment_data_blockwise(&mut blocks);
+    // Draw the defragmented data if requested
+    let blocks = BlockList(blocks);
+    if let Some(width) = render {
+        print_blocks(&blocks, width);
+    }
 
     // Create the newly defragmented data in bytes format
     let mut data = Vec::new();
-    blocks.iter().for_each(|x| data.extend(x.as_byte_list()));
+    blocks.0.iter().for_each(|x| data.extend(x.as_byte_list()));
 
     // Caclulate and print the checksum
     let checksum = calculate_checksum(&data);
+    if verify {
+        verify_checksum(&contents, true, checksum);
+    }
     println!("{checksum}");
 }
 
-/// Creates a list of numbers based on the input string
-///
-/// IDs are placed in their respective locations, with None being
-/// used to signify empty spaces.
-fn create_byte_list(input: &str) -> Vec<Option<usize>> {
-    // Create a new list for storing byte data
-    let mut data = Vec::new();
-
-    // Keep track of the mode (data or empty)
-    let mut data_mode = true;
-    let mut id = 0;
-
-    // Iterate through the characters of the input
-    for character in input.trim().chars() {
-        // Get the number of spaces by reading and parsing the digit
-        let num_spaces = character.to_digit(10).expect("Could not parse the digit");
-
-        // Perform the action for the number of spaces
-        for _x in 0..num_spaces {
-            // If data mode, push IDs to the list; otherwise push empty space
-            if data_mode {
-                data.push(Some(id));
-            } else {
-                data.push(None);
-            }
-        }
-
-        // If just performed the action in data mode, increase the ID by 1
-        if data_mode {
-            id += 1;
-        }
-
-        // Switch between data and empty mode
-        data_mode = !data_mode
+/// Prints the drawing of the memory blocks, wrapping after the given width unless it is zero
+fn print_blocks(blocks: &BlockList, width: usize) {
+    if width == 0 {
+        println!("{blocks}");
+    } else {
+        println!("{blocks:width$}");
     }
-
-    // Return the data list
-    data
 }
 
-/// Creates a list of memory blocks based on the input string
-fn create_block_list(input: &str) -> Vec<MemoryBlock> {
-    // Create a new list for storing byte data
-    let mut data = Vec::new();
-
-    // Keep track of the mode (data or empty)
-    let mut data_mode = true;
-    let mut id = 0;
-
-    // Iterate through the characters of the input
-    for character in input.trim().chars() {
-        // Get the number of spaces by reading and parsing the digit
-        let num_spaces = character.to_digit(10).expect("Could not parse the digit");
-
-        // Prepare the ID of the memory block depending on whether it is data or empty space
-        let assignable_id = if data_mode { Some(id) } else { None };
-
-        // Create and add the memory block to the list
-        let memory_block = MemoryBlock {
-            id: assignable_id,
-            size: num_spaces as usize,
-        };
-        data.push(memory_block);
+/// Defragment the data at the "byte" level, returning the work done along the way
+///
+/// Each move searches for the first empty space from the start of the data again, so this
+/// stops early once the work done exceeds the budget, if given.
+fn defragment_data_bytewise(data: &mut Vec<Option<usize>>, budget: Option<usize>) -> Work {
+    let mut work = Work::default();
 
-        // If just performed the action in data mode, increase the ID by 1
-        if data_mode {
-            id += 1;
+    // While empty space is still detected in the data...
+    while let Some(first_free) = data.iter().position(|x| x.is_none()) {
+        work.scanned += first_free + 1;
+        if budget.is_some_and(|x| work.total() > x) {
+            return work;
         }
 
-        // Switch between data and empty mode
-        data_mode = !data_mode
-    }
-
-    // Return the data list
-    data
-}
-
-/// Defragment the data at the "byte" level
-fn defragment_data_bytewise(data: &mut Vec<Option<usize>>) {
-    // While empty space is still detected in the data...
-    while data.contains(&None) {
         // Remove trailing empty space
         while data.last().expect("Data vector is empty").is_none() {
             data.pop();
+            work.scanned += 1;
         }
 
         // If an additional empty space is detected, move the last byte into it's location
         if let Some(pos) = data.iter().position(|x| x.is_none()) {
+            work.scanned += pos + 1;
             let last = data.pop().expect("Vector is empty!").unwrap();
             data[pos] = Some(last)
         }
     }
-}
-
-/// Defragment the data at the "memory block" level
-fn defragment_data_blockwise(data: &mut Vec<MemoryBlock>) {
-    // Get the number of IDs to be iterated over
-    let num_id = data.iter().filter_map(|x| x.id).count();
-
-    // Iterate through the IDs in descending order
-    for id in (0..num_id).rev() {
-        // Get the files original position and remove it from the list
-        let file_position = data.iter().position(|x| x.id == Some(id)).unwrap();
-        let file_memory = data.remove(file_position);
-
-        // Look for a free memory block of at least the same size ahead of the original position
-        if let Some(free_position) = data
-            .iter()
-            .enumerate()
-            .position(|(i, x)| x.is_free() && x.size >= file_memory.size && i < file_position)
-        {
-            // Get the free memory and reduce it's size by the file size
-            let free_memory = data.get_mut(free_position).unwrap();
-            free_memory.reduce(file_memory.size);
-
-            // Insert the file ahead of the free memory
-            data.insert(free_position, file_memory);
-
-            // Insert an empty memory block the same size as the file memory at the file's original position
-            data.insert(file_position, MemoryBlock::as_empty(file_memory.size));
-        } else {
-            // No free space was found, re-insert the file in it's original postion
-            data.insert(file_position, file_memory);
-        }
-    }
 
-    data.retain_mut(|x| x.size > 0)
+    // Account for the final search finding no empty space
+    work.scanned += data.len();
+    work
 }
 
-/// Calculate the checksum for an array of data bytes
-fn calculate_checksum(data: &[Option<usize>]) -> usize {
-    let mut checksum = 0;
-    for (index, id) in data.iter().enumerate() {
-        if let Some(value) = id {
-            checksum += index * value
-        }
+/// Verifies the checksum against the one calculated by the reference defragmenter
+///
+/// Panics if the checksums do not match.
+fn verify_checksum(input: &str, whole_files: bool, checksum: usize) {
+    let expected = reference::checksum(input, whole_files);
+    if checksum != expected {
+        panic!("Checksum {checksum} does not match the reference checksum {expected}!");
     }
-    checksum
 }
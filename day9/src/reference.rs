@@ -0,0 +1,68 @@
+//! Reference defragmenter, which is deliberately simple (and slow) so that it is obviously
+//! correct, used to verify the checksums produced by the main implementation
+
+/// Expands the disk map into a list of bytes, where each byte is either the ID of the file
+/// occupying it or None for empty space
+fn expand(input: &str) -> Vec<Option<usize>> {
+    let mut disk = Vec::new();
+    for (index, character) in input.trim().chars().enumerate() {
+        let size = character.to_digit(10).expect("Could not parse the digit");
+        let id = if index % 2 == 0 {
+            Some(index / 2)
+        } else {
+            None
+        };
+        for _x in 0..size {
+            disk.push(id);
+        }
+    }
+    disk
+}
+
+/// Moves individual bytes from the end of the disk into the leftmost empty space
+fn compact_bytes(disk: &mut [Option<usize>]) {
+    for from in (0..disk.len()).rev() {
+        if disk[from].is_none() {
+            continue;
+        }
+        if let Some(to) = (0..from).find(|x| disk[*x].is_none()) {
+            disk.swap(from, to);
+        }
+    }
+}
+
+/// Moves whole files, in order of decreasing ID, into the leftmost span of empty space that
+/// is large enough to hold them and is to the left of the file
+fn compact_files(disk: &mut [Option<usize>]) {
+    let max_id = disk.iter().flatten().max().copied().unwrap_or(0);
+    for id in (0..=max_id).rev() {
+        // Find where the file is and how large it is
+        let Some(start) = disk.iter().position(|x| *x == Some(id)) else {
+            continue;
+        };
+        let size = disk.iter().filter(|x| **x == Some(id)).count();
+
+        // Check every position to the left of the file for a large enough span of empty space
+        let target = (0..start).find(|to| (*to..*to + size).all(|x| disk[x].is_none()));
+        if let Some(to) = target {
+            for offset in 0..size {
+                disk.swap(start + offset, to + offset);
+            }
+        }
+    }
+}
+
+/// Calculates the checksum of the disk map after defragmenting it, either byte by byte or
+/// by whole files
+pub fn checksum(input: &str, whole_files: bool) -> usize {
+    let mut disk = expand(input);
+    if whole_files {
+        compact_files(&mut disk);
+    } else {
+        compact_bytes(&mut disk);
+    }
+    disk.iter()
+        .enumerate()
+        .map(|(index, id)| index * id.unwrap_or(0))
+        .sum()
+}
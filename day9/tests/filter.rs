@@ -0,0 +1,98 @@
+use std::ops::Range;
+
+use day9::{
+    create_block_list, defragment_data_blockwise, parse_disk_map, parse_id_range, FileFilter,
+};
+
+const EXAMPLE: &str = include_str!("../inputs/example.txt");
+
+/// Compacts the example disk by moving whole files allowed by the filter, returning the
+/// layout of the bytes and the checksum
+fn compact_example(ids: Option<Range<usize>>, min_size: usize) -> (String, usize) {
+    let mut blocks = create_block_list(EXAMPLE).unwrap();
+    defragment_data_blockwise(&mut blocks, &FileFilter { ids, min_size });
+    let bytes: Vec<Option<usize>> = blocks.iter().flat_map(|x| x.as_byte_list()).collect();
+    let layout = bytes
+        .iter()
+        .map(|x| x.map_or('.', |id| char::from_digit(id as u32, 10).unwrap()))
+        .collect();
+    let checksum = bytes
+        .iter()
+        .enumerate()
+        .map(|(index, x)| index * x.unwrap_or(0))
+        .sum();
+    (layout, checksum)
+}
+
+#[test]
+fn no_filter_moves_every_file() {
+    assert_eq!(
+        compact_example(None, 0),
+        (
+            "00992111777.44.333....5555.6666.....8888..".to_string(),
+            2858
+        )
+    );
+}
+
+#[test]
+fn id_range_moves_only_those_files() {
+    assert_eq!(
+        compact_example(Some(9..10), 0),
+        (
+            "0099.111...2...333.44.5555.6666.777.8888..".to_string(),
+            3432
+        )
+    );
+}
+
+#[test]
+fn empty_id_range_moves_nothing() {
+    let (layout, checksum) = compact_example(Some(0..0), 0);
+    assert_eq!(layout, "00...111...2...333.44.5555.6666.777.888899");
+    assert_eq!(checksum, 4116);
+}
+
+#[test]
+fn min_size_moves_only_large_files() {
+    // The files of size 4 have no free space large enough ahead of them
+    assert_eq!(compact_example(None, 4).1, 4116);
+    assert_ne!(compact_example(None, 3).1, 4116);
+}
+
+#[test]
+fn filters_combine() {
+    // File 9 is too small to move, even though it is in the range
+    assert_eq!(compact_example(Some(9..10), 3).1, 4116);
+}
+
+#[test]
+fn parses_id_ranges() {
+    assert_eq!(parse_id_range("2..5"), Ok(2..5));
+    assert_eq!(parse_id_range("2..=5"), Ok(2..6));
+    assert_eq!(parse_id_range("..3"), Ok(0..3));
+    assert_eq!(parse_id_range("4.."), Ok(4..usize::MAX));
+    assert_eq!(parse_id_range("..="), Ok(0..usize::MAX));
+}
+
+#[test]
+fn rejects_invalid_id_ranges() {
+    assert_eq!(
+        parse_id_range("5"),
+        Err("Expected a range like 2..5, got \"5\"".to_string())
+    );
+    assert_eq!(
+        parse_id_range("a..3"),
+        Err("Could not parse \"a\" as an ID".to_string())
+    );
+    assert!(parse_id_range("1..=x").is_err());
+}
+
+#[test]
+fn rejects_malformed_disk_maps() {
+    assert_eq!(parse_disk_map("2333\n"), Ok(vec![2, 3, 3, 3]));
+    assert_eq!(
+        create_block_list("23x3\n").err(),
+        Some("Position 3: could not parse 'x' as a digit".to_string())
+    );
+}
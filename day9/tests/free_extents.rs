@@ -0,0 +1,35 @@
+use day9::{create_block_list, defragment_data_blockwise, BlockList, FileFilter};
+
+const EXAMPLE: &str = include_str!("../inputs/example.txt");
+
+/// Compacts the example disk by moving whole files
+fn compacted_example() -> BlockList {
+    let mut blocks = create_block_list(EXAMPLE).unwrap();
+    let filter = FileFilter {
+        ids: None,
+        min_size: 0,
+    };
+    defragment_data_blockwise(&mut blocks, &filter);
+    BlockList(blocks)
+}
+
+#[test]
+fn free_extents_of_compacted_example() {
+    // 00992111777.44.333....5555.6666.....8888..
+    let extents = compacted_example().free_extents();
+    assert_eq!(
+        extents,
+        vec![11..12, 14..15, 18..22, 26..27, 31..36, 40..42]
+    );
+}
+
+#[test]
+fn free_blocks_are_coalesced() {
+    let blocks = compacted_example();
+    assert!(blocks
+        .0
+        .windows(2)
+        .all(|x| !(x[0].is_free() && x[1].is_free())));
+    let num_free = blocks.0.iter().filter(|x| x.is_free()).count();
+    assert_eq!(num_free, blocks.free_extents().len());
+}
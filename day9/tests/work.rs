@@ -0,0 +1,35 @@
+use day9::{create_block_list, defragment_data_blockwise, FileFilter, Work};
+
+/// Compacts the disk with the given number of files which have no room to move, returning the
+/// work done
+fn compact_packed_disk(num_files: usize) -> Work {
+    let mut blocks = create_block_list(&"10".repeat(num_files)).unwrap();
+    let filter = FileFilter {
+        ids: None,
+        min_size: 0,
+    };
+    defragment_data_blockwise(&mut blocks, &filter)
+}
+
+#[test]
+fn packed_disk_scans_quadratically() {
+    // Every file searches all of the blocks before it for free space that isn't there
+    let small = compact_packed_disk(100);
+    let large = compact_packed_disk(1000);
+    assert_eq!(small.shifted, 0);
+    assert!(large.total() > 50 * small.total());
+    assert!(!small.exceeds(200, 200));
+    assert!(large.exceeds(2000, 200));
+}
+
+#[test]
+fn example_work() {
+    let mut blocks = create_block_list(include_str!("../inputs/example.txt")).unwrap();
+    let filter = FileFilter {
+        ids: None,
+        min_size: 0,
+    };
+    let work = defragment_data_blockwise(&mut blocks, &filter);
+    assert!(work.scanned > 0 && work.shifted > 0);
+    assert_eq!(work.total(), work.scanned + work.shifted);
+}
@@ -0,0 +1,82 @@
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use grid::Grid;
+
+/// The size of the (square) map, which is much larger than the puzzle inputs so that it
+/// doesn't fit in the cache
+const SIZE: usize = 2000;
+
+/// Gets the value of each cell of the map, which varies so that the sums can't be folded away
+fn cell(x: usize, y: usize) -> u32 {
+    ((x * 31 + y * 17) % 10) as u32
+}
+
+/// Sums each cell with its neighbors in the cardinal directions, looking them up by position
+/// the same way the days look up neighbors
+fn neighbor_sum(get: impl Fn(i64, i64) -> Option<u32>) -> u64 {
+    let mut total = 0;
+    for y in 0..SIZE as i64 {
+        for x in 0..SIZE as i64 {
+            for (dx, dy) in [(0, 0), (1, 0), (-1, 0), (0, 1), (0, -1)] {
+                total += get(x + dx, y + dy).unwrap_or(0) as u64;
+            }
+        }
+    }
+    total
+}
+
+fn locality(c: &mut Criterion) {
+    // Build the same map as nested rows and as a flat grid
+    let nested: Vec<Vec<u32>> = (0..SIZE)
+        .map(|y| (0..SIZE).map(|x| cell(x, y)).collect())
+        .collect();
+    let flat = Grid::from_rows(nested.clone()).unwrap();
+
+    // Compare looking up every cell and its neighbors in each
+    let mut group = c.benchmark_group("neighbor sum");
+    group.sample_size(10);
+    group.bench_function("nested rows", |b| {
+        b.iter(|| {
+            let nested = black_box(&nested);
+            neighbor_sum(|x, y| {
+                if x < 0 || y < 0 {
+                    return None;
+                }
+                nested.get(y as usize)?.get(x as usize).copied()
+            })
+        })
+    });
+    group.bench_function("flat grid", |b| {
+        b.iter(|| {
+            let flat = black_box(&flat);
+            neighbor_sum(|x, y| flat.get_signed(x, y).copied())
+        })
+    });
+    group.finish();
+
+    // Compare walking every cell, row by row
+    let mut group = c.benchmark_group("scan");
+    group.sample_size(10);
+    group.bench_function("nested rows", |b| {
+        b.iter(|| {
+            black_box(&nested)
+                .iter()
+                .flatten()
+                .map(|x| *x as u64)
+                .sum::<u64>()
+        })
+    });
+    group.bench_function("flat grid", |b| {
+        b.iter(|| black_box(&flat).iter().map(|x| *x as u64).sum::<u64>())
+    });
+    group.finish();
+
+    // Compare copying the whole map, as done for each trial obstacle on day 6
+    let mut group = c.benchmark_group("clone");
+    group.sample_size(10);
+    group.bench_function("nested rows", |b| b.iter(|| black_box(&nested).clone()));
+    group.bench_function("flat grid", |b| b.iter(|| black_box(&flat).clone()));
+    group.finish();
+}
+
+criterion_group!(benches, locality);
+criterion_main!(benches);
@@ -0,0 +1,176 @@
+use std::{
+    ops::{Index, IndexMut},
+    slice,
+};
+
+/// A rectangular grid of cells stored row by row in a single allocation
+///
+/// Cells are addressed by their X (column) and Y (row) position, so neighboring cells in a row
+/// are next to each other in memory and rows follow one after another.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    /// Creates a grid of the given size with every cell set to the given value
+    pub fn new(width: usize, height: usize, value: T) -> Self
+    where
+        T: Clone,
+    {
+        Self {
+            width,
+            height,
+            cells: vec![value; width * height],
+        }
+    }
+
+    /// Creates a grid from the given rows, failing if they aren't all the same length
+    pub fn from_rows(rows: Vec<Vec<T>>) -> Result<Self, String> {
+        // Get the size of the grid from the rows
+        let height = rows.len();
+        let width = rows.first().map_or(0, |x| x.len());
+
+        // Copy each row into the cells, checking that it fits the grid
+        let mut cells = Vec::with_capacity(width * height);
+        for (index, row) in rows.into_iter().enumerate() {
+            if row.len() != width {
+                return Err(format!(
+                    "Row {index} has {} cells, but expected {width}",
+                    row.len()
+                ));
+            }
+            cells.extend(row);
+        }
+
+        // Return the grid
+        Ok(Self {
+            width,
+            height,
+            cells,
+        })
+    }
+
+    /// Gets the number of columns in the grid
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    /// Gets the number of rows in the grid
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// Gets the position of the cell at the given X, Y position within the cells, or None if
+    /// it's outside the grid
+    pub fn index_of(&self, x: usize, y: usize) -> Option<usize> {
+        (x < self.width && y < self.height).then_some(y * self.width + x)
+    }
+
+    /// Gets the cell at the given X, Y position, or None if it's outside the grid
+    pub fn get(&self, x: usize, y: usize) -> Option<&T> {
+        if x >= self.width || y >= self.height {
+            return None;
+        }
+        self.cells.get(y * self.width + x)
+    }
+
+    /// Gets the cell at the given X, Y position mutably, or None if it's outside the grid
+    pub fn get_mut(&mut self, x: usize, y: usize) -> Option<&mut T> {
+        if x >= self.width || y >= self.height {
+            return None;
+        }
+        self.cells.get_mut(y * self.width + x)
+    }
+
+    /// Gets the cell at the given X, Y position, which may be negative, or None if it's
+    /// outside the grid
+    pub fn get_signed(&self, x: i64, y: i64) -> Option<&T> {
+        if x < 0 || y < 0 {
+            return None;
+        }
+        self.get(x as usize, y as usize)
+    }
+
+    /// Gets the cell at the given X, Y position mutably, which may be negative, or None if it's
+    /// outside the grid
+    pub fn get_signed_mut(&mut self, x: i64, y: i64) -> Option<&mut T> {
+        if x < 0 || y < 0 {
+            return None;
+        }
+        self.get_mut(x as usize, y as usize)
+    }
+
+    /// Gets all of the cells, row by row
+    pub fn cells(&self) -> &[T] {
+        &self.cells
+    }
+
+    /// Gets the row at the given Y position
+    ///
+    /// Panics if the row is outside the grid.
+    pub fn row(&self, y: usize) -> &[T] {
+        assert!(y < self.height, "Row {y} is outside the grid");
+        &self.cells[y * self.width..(y + 1) * self.width]
+    }
+
+    /// Gets the rows of the grid, from top to bottom
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        let width = self.width;
+        (0..self.height).map(move |y| &self.cells[y * width..(y + 1) * width])
+    }
+
+    /// Gets an iterator over all of the cells, row by row
+    pub fn iter(&self) -> slice::Iter<'_, T> {
+        self.cells.iter()
+    }
+
+    /// Gets a mutable iterator over all of the cells, row by row
+    pub fn iter_mut(&mut self) -> slice::IterMut<'_, T> {
+        self.cells.iter_mut()
+    }
+}
+
+impl<T> Default for Grid<T> {
+    /// Creates an empty grid
+    fn default() -> Self {
+        Self {
+            width: 0,
+            height: 0,
+            cells: Vec::new(),
+        }
+    }
+}
+
+impl<T> Index<(usize, usize)> for Grid<T> {
+    type Output = T;
+
+    /// Gets the cell at the given X, Y position, panicking if it's outside the grid
+    fn index(&self, (x, y): (usize, usize)) -> &T {
+        let index = self
+            .index_of(x, y)
+            .unwrap_or_else(|| panic!("{x},{y} is outside the grid"));
+        &self.cells[index]
+    }
+}
+
+impl<T> IndexMut<(usize, usize)> for Grid<T> {
+    /// Gets the cell at the given X, Y position mutably, panicking if it's outside the grid
+    fn index_mut(&mut self, (x, y): (usize, usize)) -> &mut T {
+        let index = self
+            .index_of(x, y)
+            .unwrap_or_else(|| panic!("{x},{y} is outside the grid"));
+        &mut self.cells[index]
+    }
+}
+
+impl<'a, T> IntoIterator for &'a Grid<T> {
+    type Item = &'a T;
+    type IntoIter = slice::Iter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
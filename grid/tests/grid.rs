@@ -0,0 +1,54 @@
+use grid::Grid;
+
+/// Gets a grid of the numbers 0 to 5, three wide and two tall
+fn numbered() -> Grid<u32> {
+    Grid::from_rows(vec![vec![0, 1, 2], vec![3, 4, 5]]).unwrap()
+}
+
+#[test]
+fn addresses_cells_by_position() {
+    let grid = numbered();
+    assert_eq!(grid.width(), 3);
+    assert_eq!(grid.height(), 2);
+    assert_eq!(grid.get(2, 0), Some(&2));
+    assert_eq!(grid.get(0, 1), Some(&3));
+    assert_eq!(grid[(1, 1)], 4);
+    assert_eq!(grid.get(3, 0), None);
+    assert_eq!(grid.get(0, 2), None);
+    assert_eq!(grid.get_signed(-1, 0), None);
+    assert_eq!(grid.get_signed(2, 1), Some(&5));
+}
+
+#[test]
+fn stores_rows_contiguously() {
+    let grid = numbered();
+    assert_eq!(grid.cells(), &[0, 1, 2, 3, 4, 5]);
+    assert_eq!(grid.row(1), &[3, 4, 5]);
+    let rows: Vec<&[u32]> = grid.rows().collect();
+    assert_eq!(rows, vec![&[0, 1, 2][..], &[3, 4, 5][..]]);
+    assert_eq!(grid.index_of(1, 1), Some(4));
+}
+
+#[test]
+fn modifies_cells() {
+    let mut grid = Grid::new(2, 2, 0);
+    grid[(1, 0)] = 7;
+    *grid.get_mut(0, 1).unwrap() = 8;
+    *grid.get_signed_mut(1, 1).unwrap() = 9;
+    assert_eq!(grid.cells(), &[0, 7, 8, 9]);
+}
+
+#[test]
+fn rejects_ragged_rows() {
+    assert_eq!(
+        Grid::from_rows(vec![vec![0, 1, 2], vec![3, 4]]),
+        Err("Row 1 has 2 cells, but expected 3".to_string())
+    );
+}
+
+#[test]
+fn default_is_empty() {
+    let grid: Grid<u32> = Grid::default();
+    assert_eq!((grid.width(), grid.height()), (0, 0));
+    assert_eq!(Grid::from_rows(Vec::new()), Ok(grid));
+}
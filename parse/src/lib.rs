@@ -0,0 +1,85 @@
+use std::ops::Range;
+
+/// Iterator over the enabled regions of a sequence, where an "off" toggle disables the
+/// sequence from its start until the end of the next "on" toggle enables it again
+///
+/// Created by `conditional_regions()` or `toggled_regions()`.
+#[derive(Clone, Debug)]
+pub struct ConditionalRegions<I> {
+    toggles: I,
+    length: usize,
+    /// Whether the sequence is enabled after the toggles seen so far
+    enabled: bool,
+    /// The start of the current enabled region if enabled, otherwise the end of the toggle
+    /// that disabled it, before which no toggle can change the state
+    from: usize,
+}
+
+impl<I: Iterator<Item = (Range<usize>, bool)>> Iterator for ConditionalRegions<I> {
+    type Item = Range<usize>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        // Go through the toggles that change the state, returning each enabled region as it's
+        // ended by an "off" toggle unless it's empty
+        for (range, on) in self.toggles.by_ref() {
+            if range.start < self.from || on == self.enabled {
+                continue;
+            }
+            let start = self.from;
+            self.enabled = on;
+            self.from = range.end;
+            if !on && start < range.start {
+                return Some(start..range.start);
+            }
+        }
+
+        // End the final region at the end of the sequence if it's still enabled
+        if !self.enabled || self.from >= self.length {
+            return None;
+        }
+        self.enabled = false;
+        Some(self.from..self.length)
+    }
+}
+
+/// Gets the ranges of the regions of a sequence of the given length that are enabled, in
+/// order, given the range of each toggle in it and whether it's an "on" toggle
+///
+/// The sequence starts enabled, and each "off" toggle disables it from the start of the toggle
+/// until the end of the next "on" toggle.  Toggles that don't change the state are part of the
+/// surrounding region, as are toggles that begin inside the toggle that last changed it.
+pub fn toggled_regions<T>(length: usize, toggles: T) -> ConditionalRegions<T::IntoIter>
+where
+    T: IntoIterator<Item = (Range<usize>, bool)>,
+{
+    ConditionalRegions {
+        toggles: toggles.into_iter(),
+        length,
+        enabled: true,
+        from: 0,
+    }
+}
+
+/// Gets the byte ranges of the regions of the text that are enabled, in order
+///
+/// The text starts enabled, and each `off_token` disables it from the start of the token
+/// until the end of the next `on_token`.  Tokens that don't change the state are part of the
+/// surrounding region.
+pub fn conditional_regions(
+    text: &str,
+    on_token: &str,
+    off_token: &str,
+) -> ConditionalRegions<std::vec::IntoIter<(Range<usize>, bool)>> {
+    // Find every occurrence of either token, in order
+    let mut toggles = Vec::new();
+    for (index, _) in text.char_indices() {
+        for (token, on) in [(on_token, true), (off_token, false)] {
+            if !token.is_empty() && text[index..].starts_with(token) {
+                toggles.push((index..index + token.len(), on));
+            }
+        }
+    }
+
+    // Get the regions enabled by the tokens
+    toggled_regions(text.len(), toggles)
+}
@@ -0,0 +1,55 @@
+use parse::{conditional_regions, toggled_regions};
+
+#[test]
+fn whole_text_enabled_without_tokens() {
+    let regions: Vec<_> = conditional_regions("abc", "on", "off").collect();
+    assert_eq!(regions, vec![0..3]);
+}
+
+#[test]
+fn off_token_disables_until_on_token() {
+    let text = "abcOFFdefONghiONjkOFFlm";
+    let regions: Vec<&str> = conditional_regions(text, "ON", "OFF")
+        .map(|x| &text[x])
+        .collect();
+    assert_eq!(regions, vec!["abc", "ghiONjk"]);
+}
+
+#[test]
+fn empty_regions_are_skipped() {
+    let text = "OFFaONOFFbONc";
+    let regions: Vec<&str> = conditional_regions(text, "ON", "OFF")
+        .map(|x| &text[x])
+        .collect();
+    assert_eq!(regions, vec!["c"]);
+}
+
+#[test]
+fn text_stays_disabled_without_on_token() {
+    let regions: Vec<_> = conditional_regions("abOFFcd", "ON", "OFF").collect();
+    assert_eq!(regions, vec![0..2]);
+}
+
+#[test]
+fn example_memory() {
+    let text = "xmul(2,4)&mul[3,7]!^don't()_mul(5,5)+mul(32,64](mul(11,8)undo()?mul(8,5))";
+    let regions: Vec<&str> = conditional_regions(text, "do()", "don't()")
+        .map(|x| &text[x])
+        .collect();
+    assert_eq!(regions, vec!["xmul(2,4)&mul[3,7]!^", "?mul(8,5))"]);
+}
+
+#[test]
+fn toggles_with_ranges() {
+    // The "on" toggle starting inside the "off" toggle at 2..5 doesn't enable the sequence
+    let toggles = [(2..5, false), (4..6, true), (7..8, true), (9..10, false)];
+    let regions: Vec<_> = toggled_regions(12, toggles).collect();
+    assert_eq!(regions, vec![0..2, 8..9]);
+}
+
+#[test]
+fn toggles_ending_enabled() {
+    let regions: Vec<_> = toggled_regions(5, [(0..1, false), (1..2, true)]).collect();
+    assert_eq!(regions, vec![2..5]);
+    assert_eq!(toggled_regions(0, []).count(), 0);
+}
@@ -0,0 +1,217 @@
+use std::{
+    cmp::Reverse,
+    collections::{hash_map::Entry, BinaryHeap, HashMap, VecDeque},
+    hash::Hash,
+};
+
+/// An X, Y point on a grid
+pub type Point = (i64, i64);
+
+/// Whether a flood fill should continue after visiting a point
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Visit {
+    /// Keep searching outward
+    Continue,
+    /// Stop searching immediately
+    Stop,
+}
+
+/// Gets the neighboring points in the cardinal directions
+pub fn cardinals(point: Point) -> [Point; 4] {
+    let (x, y) = point;
+    [(x, y - 1), (x + 1, y), (x, y + 1), (x - 1, y)]
+}
+
+/// Flood fills outward from the given start points one step at a time in the cardinal
+/// directions, returning the number of steps to each point visited
+///
+/// The start points are always visited.  Each step from one point to the next is only taken
+/// if `passable` allows it, and `on_visit` is called with every point as it is visited, in
+/// order of distance, which can stop the flood fill early by returning `Visit::Stop`.
+pub fn flood_fill<P, V>(
+    start: impl IntoIterator<Item = Point>,
+    mut passable: P,
+    mut on_visit: V,
+) -> HashMap<Point, usize>
+where
+    P: FnMut(Point, Point) -> bool,
+    V: FnMut(Point, usize) -> Visit,
+{
+    // Create the map of distances and the queue, seeded with the start points
+    let mut distances = HashMap::new();
+    let mut queue = VecDeque::new();
+    for point in start {
+        if let Entry::Vacant(entry) = distances.entry(point) {
+            entry.insert(0);
+            queue.push_back(point);
+        }
+    }
+
+    // Visit the points in the queue, adding their unvisited passable neighbors to it
+    while let Some(point) = queue.pop_front() {
+        let distance = distances[&point];
+        if on_visit(point, distance) == Visit::Stop {
+            break;
+        }
+        for next_point in cardinals(point) {
+            if let Entry::Vacant(entry) = distances.entry(next_point) {
+                if passable(point, next_point) {
+                    entry.insert(distance + 1);
+                    queue.push_back(next_point);
+                }
+            }
+        }
+    }
+
+    // Return the distances found
+    distances
+}
+
+/// Finds the lowest cost of reaching every node reachable from the given seed nodes, which
+/// start with no cost, using Dijkstra's algorithm
+///
+/// The moves out of each node, along with the (non-negative) cost of each, are given by
+/// `moves`, so any graph can be searched, such as positions and headings on a weighted grid.
+pub fn dijkstra<N, M, I>(seeds: impl IntoIterator<Item = N>, mut moves: M) -> HashMap<N, u64>
+where
+    N: Copy + Eq + Hash + Ord,
+    M: FnMut(&N) -> I,
+    I: IntoIterator<Item = (N, u64)>,
+{
+    // Create the costs and queue, seeded with the given nodes
+    let mut costs = HashMap::new();
+    let mut queue = BinaryHeap::new();
+    for seed in seeds {
+        costs.insert(seed, 0);
+        queue.push(Reverse((0, seed)));
+    }
+
+    // Keep expanding the node with the lowest cost
+    while let Some(Reverse((cost, node))) = queue.pop() {
+        // If a lower cost was already found for this node, skip it
+        if costs.get(&node).is_some_and(|x| *x < cost) {
+            continue;
+        }
+
+        // Update the costs for the moves from this node where they improve
+        for (next_node, move_cost) in moves(&node) {
+            let next_cost = cost + move_cost;
+            if costs.get(&next_node).is_some_and(|x| *x <= next_cost) {
+                continue;
+            }
+            costs.insert(next_node, next_cost);
+            queue.push(Reverse((next_cost, next_node)));
+        }
+    }
+
+    // Return the lowest costs
+    costs
+}
+
+/// Gets the moves leading into the given node, along with the cost of each, from the moves
+/// leading out of the candidate nodes that may precede it
+///
+/// The moves out of each node are given by `moves`, as for `dijkstra`, so the moves found
+/// always agree with them.  The candidates only need to include every node with a move into
+/// the given node, which allows searching backwards from a goal without knowing every node.
+pub fn reverse_moves<N, C, M, I>(node: &N, candidates: C, mut moves: M) -> Vec<(N, u64)>
+where
+    N: Copy + Eq,
+    C: IntoIterator<Item = N>,
+    M: FnMut(&N) -> I,
+    I: IntoIterator<Item = (N, u64)>,
+{
+    let mut reversed = Vec::new();
+    for previous in candidates {
+        for (next, move_cost) in moves(&previous) {
+            if next == *node {
+                reversed.push((previous, move_cost));
+            }
+        }
+    }
+    reversed
+}
+
+/// Finds the lowest distinct costs of simple routes from the given seed nodes to any of the
+/// goal nodes, up to the given number of them, in increasing order
+///
+/// A route is simple if it never visits the same place twice, where `place` gets the place
+/// of each node, and it ends as soon as it reaches a goal.  Partial routes are searched best
+/// first, ordered by their cost plus `remaining`, which must never overestimate the cost of
+/// reaching a goal from a node (and gives None if no goal can be reached from it).  The more
+/// accurate the estimate, the fewer partial routes are searched.
+pub fn k_lowest_costs<N, K, M, I, G, P, R>(
+    seeds: impl IntoIterator<Item = N>,
+    mut moves: M,
+    mut is_goal: G,
+    mut place: P,
+    mut remaining: R,
+    k: usize,
+) -> Vec<u64>
+where
+    N: Copy + Eq + Hash + Ord,
+    K: Eq,
+    M: FnMut(&N) -> I,
+    I: IntoIterator<Item = (N, u64)>,
+    G: FnMut(&N) -> bool,
+    P: FnMut(&N) -> K,
+    R: FnMut(&N) -> Option<u64>,
+{
+    // Create the places visited by the partial routes, each linked to the previous place on
+    // its route, and the queue, seeded with the given nodes
+    let mut trail: Vec<(K, Option<usize>)> = Vec::new();
+    let mut queue = BinaryHeap::new();
+    for seed in seeds {
+        if let Some(estimate) = remaining(&seed) {
+            trail.push((place(&seed), None));
+            queue.push(Reverse((estimate, 0, seed, trail.len() - 1)));
+        }
+    }
+
+    // Keep extending the partial route with the lowest estimated cost, until enough goal
+    // costs are found
+    let mut goal_costs = Vec::new();
+    while let Some(Reverse((_, cost, node, index))) = queue.pop() {
+        if goal_costs.len() >= k {
+            break;
+        }
+
+        // If this is a goal, note the cost if it's new, and end the route here
+        if is_goal(&node) {
+            if goal_costs.last() != Some(&cost) {
+                goal_costs.push(cost);
+            }
+            continue;
+        }
+
+        // Queue the moves from this node to any place not already on the route that can
+        // still reach a goal
+        for (next_node, move_cost) in moves(&node) {
+            let next_place = place(&next_node);
+            let mut previous = Some(index);
+            while let Some(i) = previous {
+                if trail[i].0 == next_place {
+                    break;
+                }
+                previous = trail[i].1;
+            }
+            if previous.is_some() {
+                continue;
+            }
+            let Some(estimate) = remaining(&next_node) else {
+                continue;
+            };
+            let next_cost = cost + move_cost;
+            trail.push((next_place, Some(index)));
+            queue.push(Reverse((
+                next_cost + estimate,
+                next_cost,
+                next_node,
+                trail.len() - 1,
+            )));
+        }
+    }
+
+    // Return the lowest goal costs
+    goal_costs
+}
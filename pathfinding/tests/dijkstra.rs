@@ -0,0 +1,35 @@
+use pathfinding::dijkstra;
+
+/// Moves along a small weighted graph, where the direct edge from 0 to 3 is more expensive
+/// than going around through 1 and 2
+fn moves(node: &u8) -> Vec<(u8, u64)> {
+    match node {
+        0 => vec![(1, 2), (3, 10)],
+        1 => vec![(2, 3)],
+        2 => vec![(3, 1)],
+        _ => vec![],
+    }
+}
+
+#[test]
+fn finds_lowest_costs() {
+    let costs = dijkstra([0], moves);
+    assert_eq!(costs[&0], 0);
+    assert_eq!(costs[&1], 2);
+    assert_eq!(costs[&2], 5);
+    assert_eq!(costs[&3], 6);
+}
+
+#[test]
+fn searches_from_every_seed() {
+    let costs = dijkstra([0, 2], moves);
+    assert_eq!(costs[&2], 0);
+    assert_eq!(costs[&3], 1);
+}
+
+#[test]
+fn skips_unreachable_nodes() {
+    let costs = dijkstra([1], moves);
+    assert!(!costs.contains_key(&0));
+    assert_eq!(costs.len(), 3);
+}
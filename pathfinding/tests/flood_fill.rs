@@ -0,0 +1,65 @@
+use pathfinding::{flood_fill, Point, Visit};
+
+const GRID: &str = "...#\n.#.#\n.#..\n###.\n";
+
+/// Checks whether the given point is an open space on the grid
+fn is_open(point: Point) -> bool {
+    let (x, y) = point;
+    x >= 0
+        && y >= 0
+        && GRID
+            .lines()
+            .nth(y as usize)
+            .and_then(|row| row.chars().nth(x as usize))
+            == Some('.')
+}
+
+#[test]
+fn visits_reachable_points() {
+    let distances = flood_fill([(0, 0)], |_, to| is_open(to), |_, _| Visit::Continue);
+    assert_eq!(distances.len(), 9);
+    assert_eq!(distances[&(3, 3)], 6);
+    assert!(!distances.contains_key(&(1, 1)));
+}
+
+#[test]
+fn visits_in_order_of_distance() {
+    let mut visited = Vec::new();
+    flood_fill(
+        [(0, 0)],
+        |_, to| is_open(to),
+        |point, distance| {
+            visited.push((distance, point));
+            Visit::Continue
+        },
+    );
+    assert!(visited.windows(2).all(|pair| pair[0].0 <= pair[1].0));
+}
+
+#[test]
+fn stops_early() {
+    let mut num_visited = 0;
+    flood_fill(
+        [(0, 0)],
+        |_, to| is_open(to),
+        |point, _| {
+            num_visited += 1;
+            if point == (2, 1) {
+                Visit::Stop
+            } else {
+                Visit::Continue
+            }
+        },
+    );
+    assert_eq!(num_visited, 6);
+}
+
+#[test]
+fn starts_from_multiple_points() {
+    let distances = flood_fill(
+        [(0, 0), (3, 3)],
+        |_, to| is_open(to),
+        |_, _| Visit::Continue,
+    );
+    assert_eq!(distances[&(2, 2)], 2);
+}
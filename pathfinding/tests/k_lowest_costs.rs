@@ -0,0 +1,64 @@
+use pathfinding::k_lowest_costs;
+
+/// Moves along a small weighted graph with three routes from 0 to 3, costing 6, 6 (through 4
+/// instead of 2), and 10, with a cycle back from 2 to 0 costing 1
+fn moves(node: &u8) -> Vec<(u8, u64)> {
+    match node {
+        0 => vec![(1, 2), (3, 10)],
+        1 => vec![(2, 3), (4, 3)],
+        2 => vec![(3, 1), (0, 1)],
+        4 => vec![(3, 1)],
+        _ => vec![],
+    }
+}
+
+/// Finds the lowest costs from 0 with no estimate of the remaining cost
+fn lowest_costs(goals: &[u8], k: usize) -> Vec<u64> {
+    k_lowest_costs([0], moves, |x| goals.contains(x), |x| *x, |_| Some(0), k)
+}
+
+#[test]
+fn finds_distinct_costs_in_order() {
+    // The two routes costing 6 only count once, and the cycle through 0 isn't a route
+    assert_eq!(lowest_costs(&[3], 4), vec![6, 10]);
+}
+
+#[test]
+fn stops_at_the_requested_number() {
+    assert_eq!(lowest_costs(&[3], 1), vec![6]);
+    assert!(lowest_costs(&[3], 0).is_empty());
+}
+
+#[test]
+fn routes_end_at_the_first_goal() {
+    // Reaching 3 through the goal 2 isn't a separate route
+    assert_eq!(lowest_costs(&[2, 3], 4), vec![5, 6, 10]);
+}
+
+#[test]
+fn routes_never_revisit_a_place() {
+    // Nodes are (place, lap) pairs, where going from place 2 back to 0 starts a new lap, so
+    // routes that go around the cycle end at the same place with a different node
+    let laps = |(node, lap): &(u8, u8)| -> Vec<((u8, u8), u64)> {
+        moves(node)
+            .into_iter()
+            .map(|(next, cost)| ((next, lap + u8::from(next == 0)), cost))
+            .collect()
+    };
+    let costs = k_lowest_costs([(0, 0)], laps, |x| x.0 == 3, |x| x.0, |_| Some(0), 4);
+    assert_eq!(costs, vec![6, 10]);
+}
+
+#[test]
+fn skips_nodes_that_cannot_reach_a_goal() {
+    // Only 4 can reach the goal from 1, so the route through 2 is never searched
+    let remaining = |x: &u8| match x {
+        0 => Some(6),
+        1 => Some(4),
+        4 => Some(1),
+        3 => Some(0),
+        _ => None,
+    };
+    let costs = k_lowest_costs([0], moves, |x| *x == 3, |x| *x, remaining, 4);
+    assert_eq!(costs, vec![6, 10]);
+}
@@ -0,0 +1,34 @@
+use pathfinding::{dijkstra, reverse_moves};
+
+/// Moves along a small weighted graph, where the direct edge from 0 to 3 is more expensive
+/// than going around through 1 and 2
+fn moves(node: &u8) -> Vec<(u8, u64)> {
+    match node {
+        0 => vec![(1, 2), (3, 10)],
+        1 => vec![(2, 3)],
+        2 => vec![(3, 1)],
+        _ => vec![],
+    }
+}
+
+#[test]
+fn finds_moves_into_node() {
+    assert_eq!(reverse_moves(&3, 0..4, moves), vec![(0, 10), (2, 1)]);
+    assert_eq!(reverse_moves(&1, 0..4, moves), vec![(0, 2)]);
+    assert!(reverse_moves(&0, 0..4, moves).is_empty());
+}
+
+#[test]
+fn only_checks_candidates() {
+    assert_eq!(reverse_moves(&3, [2], moves), vec![(2, 1)]);
+    assert!(reverse_moves(&3, [1], moves).is_empty());
+}
+
+#[test]
+fn backward_search_finds_costs_to_goal() {
+    let costs = dijkstra([3], |node| reverse_moves(node, 0..4, moves));
+    assert_eq!(costs[&3], 0);
+    assert_eq!(costs[&2], 1);
+    assert_eq!(costs[&1], 4);
+    assert_eq!(costs[&0], 6);
+}
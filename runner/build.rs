@@ -0,0 +1,44 @@
+//! Embeds every day's example inputs into the `aoc-all` binary when the `bundle-examples`
+//! feature is enabled
+
+use std::{
+    env, fs,
+    path::{Path, PathBuf},
+};
+
+fn main() {
+    // Only embed the example inputs when they're being bundled
+    println!("cargo:rerun-if-changed=build.rs");
+    let out_dir = PathBuf::from(env::var("OUT_DIR").expect("OUT_DIR is not set"));
+    let mut examples = String::from("pub const EXAMPLES: &[(u8, &str, &str)] = &[\n");
+    if env::var_os("CARGO_FEATURE_BUNDLE_EXAMPLES").is_some() {
+        let root = Path::new(env!("CARGO_MANIFEST_DIR"))
+            .parent()
+            .expect("The runner is not within the repository")
+            .to_path_buf();
+        for number in 1..=25u8 {
+            // Embed the example inputs of each day that has them
+            let inputs = root.join(format!("day{number}")).join("inputs");
+            if !inputs.is_dir() {
+                continue;
+            }
+            println!("cargo:rerun-if-changed={}", inputs.display());
+            let mut entries: Vec<PathBuf> = fs::read_dir(&inputs)
+                .expect("Could not read the example inputs")
+                .map(|x| x.expect("Could not read the example inputs").path())
+                .filter(|x| x.is_file())
+                .collect();
+            entries.sort();
+            for path in entries {
+                let name = path.file_name().unwrap().to_string_lossy().into_owned();
+                examples.push_str(&format!(
+                    "    ({number}, {name:?}, include_str!({path:?})),\n"
+                ));
+            }
+        }
+    }
+    examples.push_str("];\n");
+
+    // Write the embedded inputs for the bundle to include
+    fs::write(out_dir.join("bundle.rs"), examples).expect("Could not write the bundle");
+}
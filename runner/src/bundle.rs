@@ -0,0 +1,103 @@
+//! A single binary linking every day's solution, built with the `bundle` feature
+//!
+//! Each day's library solves the part in-process with the puzzle's standard options, so the
+//! options specific to each day's own binary aren't available.  With the `bundle-examples`
+//! feature, an input like `example:example.txt` is replaced by the embedded example input of
+//! that name.
+
+use std::fmt::Display;
+
+use clap::Parser;
+use common::{fail, read_input, Failure, Format};
+
+include!(concat!(env!("OUT_DIR"), "/bundle.rs"));
+
+/// The prefix of inputs that refer to an embedded example input
+const EXAMPLE_PREFIX: &str = "example:";
+
+/// A function solving a part of a day's puzzle for the given input, returning the answer
+type Part = fn(&str) -> Result<String, String>;
+
+/// The solutions linked into the bundle, as the day and the functions solving each part
+const DAYS: &[(u8, Part, Part)] = &[
+    (1, |x| answer(day1::part1(x)), |x| answer(day1::part2(x))),
+    (2, |x| answer(day2::part1(x)), |x| answer(day2::part2(x))),
+    (3, |x| answer(day3::part1(x)), |x| answer(day3::part2(x))),
+    (4, |x| answer(day4::part1(x)), |x| answer(day4::part2(x))),
+    (5, |x| answer(day5::part1(x)), |x| answer(day5::part2(x))),
+    (6, |x| answer(day6::part1(x)), |x| answer(day6::part2(x))),
+    (7, |x| answer(day7::part1(x)), |x| answer(day7::part2(x))),
+    (8, |x| answer(day8::part1(x)), |x| answer(day8::part2(x))),
+    (9, |x| answer(day9::part1(x)), |x| answer(day9::part2(x))),
+    (10, |x| answer(day10::part1(x)), |x| answer(day10::part2(x))),
+    (11, |x| answer(day11::part1(x)), |x| answer(day11::part2(x))),
+    (12, |x| answer(day12::part1(x)), |x| answer(day12::part2(x))),
+    (13, |x| answer(day13::part1(x)), |x| answer(day13::part2(x))),
+    (14, |x| answer(day14::part1(x)), |x| answer(day14::part2(x))),
+    (15, |x| answer(day15::part1(x)), |x| answer(day15::part2(x))),
+    (16, |x| answer(day16::part1(x)), |x| answer(day16::part2(x))),
+    (17, |x| answer(day17::part1(x)), |x| answer(day17::part2(x))),
+    (18, |x| answer(day18::part1(x)), |x| answer(day18::part2(x))),
+    (19, |x| answer(day19::part1(x)), |x| answer(day19::part2(x))),
+];
+
+#[derive(Parser)]
+#[command(name = "aoc-all")]
+struct CliArgs {
+    /// The day to run
+    day: u8,
+    /// The part of the puzzle to solve
+    part: u64,
+    /// The input file, or `example:<name>` for an embedded example input
+    filepath: String,
+    /// The format in which errors are reported
+    #[arg(long, value_enum, default_value_t = Format::Text)]
+    format: Format,
+}
+
+fn main() {
+    // Parse CLI arguments
+    let cli = CliArgs::parse();
+
+    // Report errors in the requested format
+    common::init(cli.format);
+
+    // Get the linked solution for the part of the day
+    let Some((_, part1, part2)) = DAYS.iter().find(|(day, ..)| *day == cli.day) else {
+        fail(Failure::Parse(format!(
+            "There is no solution for day {}",
+            cli.day
+        )))
+    };
+    let solve = match cli.part {
+        1 => part1,
+        2 => part2,
+        part => fail(Failure::Parse(format!("There is no part {part}"))),
+    };
+
+    // Solve the part for the input, reading it from the embedded examples if requested
+    let contents = match cli.filepath.strip_prefix(EXAMPLE_PREFIX) {
+        Some(name) => String::from(example(cli.day, name)),
+        None => read_input(&cli.filepath),
+    };
+    let answer = solve(&contents).unwrap_or_else(|error| fail(Failure::Parse(error)));
+    println!("{answer}");
+}
+
+/// Converts the answer returned by a day's library into text
+fn answer(result: Result<impl Display, String>) -> Result<String, String> {
+    result.map(|x| x.to_string())
+}
+
+/// Gets the day's embedded example input with the given name
+fn example(day: u8, name: &str) -> &'static str {
+    let Some((_, _, contents)) = EXAMPLES
+        .iter()
+        .find(|(example_day, example_name, _)| *example_day == day && *example_name == name)
+    else {
+        fail(Failure::Parse(format!(
+            "There is no example input {name} for day {day}"
+        )))
+    };
+    contents
+}
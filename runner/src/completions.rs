@@ -0,0 +1,126 @@
+use std::{
+    env,
+    ffi::OsStr,
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+use clap::ValueEnum;
+use clap_complete::{
+    engine::{CompletionCandidate, PathCompleter, ValueCompleter},
+    env::{Bash, EnvCompleter, Fish, Zsh},
+};
+use common::{cache_dir, fail, Failure};
+
+use crate::day::{all_days, Day};
+
+/// Environment variable through which the shell asks the runner for completions
+pub const COMPLETE_VAR: &str = "COMPLETE";
+
+/// Shells that completion scripts can be generated for
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
+pub enum Shell {
+    Bash,
+    Zsh,
+    Fish,
+}
+
+/// Prints the script that registers completions for the runner with the given shell
+///
+/// The script calls back into the runner for every completion, so the days and input files
+/// offered are always current.
+pub fn completions(shell: Shell) {
+    let completer: &dyn EnvCompleter = match shell {
+        Shell::Bash => &Bash,
+        Shell::Zsh => &Zsh,
+        Shell::Fish => &Fish,
+    };
+    completer
+        .write_registration(COMPLETE_VAR, "aoc", "aoc", "aoc", &mut io::stdout())
+        .unwrap_or_else(|error| {
+            fail(Failure::Io(format!(
+                "Could not write the completion script: {error}"
+            )))
+        });
+}
+
+/// Gets every day with a solution as completion candidates
+pub fn day_candidates() -> Vec<CompletionCandidate> {
+    all_days()
+        .into_iter()
+        .map(|day| CompletionCandidate::new(day.number.to_string()))
+        .collect()
+}
+
+/// Gets the input files of the day given with `--day`, including those in the cache directory,
+/// and the files in the current directory matching what's been typed so far as completion
+/// candidates
+pub fn input_candidates(current: &OsStr) -> Vec<CompletionCandidate> {
+    // Offer the day's input files if the day has been given
+    let mut candidates: Vec<CompletionCandidate> = requested_day(env::args())
+        .map(|day| day_inputs(day, &current.to_string_lossy()))
+        .unwrap_or_default()
+        .into_iter()
+        .map(CompletionCandidate::new)
+        .collect();
+
+    // Offer the files in the current directory as usual
+    candidates.extend(PathCompleter::file().complete(current));
+    candidates
+}
+
+/// Gets the day given with `--day` in the command line being completed, if it has a solution
+pub fn requested_day(args: impl IntoIterator<Item = String>) -> Option<Day> {
+    let mut args = args.into_iter();
+    while let Some(arg) = args.next() {
+        let value = match arg.strip_prefix("--day") {
+            Some("") => args.next()?,
+            Some(value) => value.strip_prefix('=')?.to_string(),
+            None => continue,
+        };
+        return Day::parse(&value).ok();
+    }
+    None
+}
+
+/// Gets the paths of the files in the day's `inputs` directory, followed by those cached for the
+/// day in the cache directory, in order, where either the path or the name of the file starts
+/// with what's been typed so far
+pub fn day_inputs(day: Day, current: &str) -> Vec<String> {
+    day_inputs_in(day, &cache_dir(), current)
+}
+
+/// Gets the paths of the day's input files like `day_inputs()`, using the given cache directory
+///
+/// The files cached for the day are those whose names start with the day's name and a dash,
+/// like `day4-transposed-...txt`.
+pub fn day_inputs_in(day: Day, cache_directory: &Path, current: &str) -> Vec<String> {
+    let prefix = format!("{}-", day.name());
+    let mut paths = matching_files(&day.directory().join("inputs"), current, |_| true);
+    paths.extend(matching_files(cache_directory, current, |name| {
+        name.starts_with(&prefix)
+    }));
+    paths
+}
+
+/// Gets the paths of the files in the directory, in order, with names that are kept, where
+/// either the path or the name of the file starts with what's been typed so far
+fn matching_files(directory: &Path, current: &str, keep: impl Fn(&str) -> bool) -> Vec<String> {
+    let mut paths: Vec<PathBuf> = fs::read_dir(directory)
+        .into_iter()
+        .flatten()
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| path.is_file())
+        .filter(|path| {
+            let name = path.file_name().unwrap_or_default().to_string_lossy();
+            keep(&name)
+                && (path.to_string_lossy().starts_with(current) || name.starts_with(current))
+        })
+        .collect();
+    paths.sort();
+    paths
+        .iter()
+        .map(|path| path.display().to_string())
+        .collect()
+}
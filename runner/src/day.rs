@@ -0,0 +1,266 @@
+use std::{
+    path::{Path, PathBuf},
+    process::Command,
+    time::{Duration, Instant},
+};
+
+use common::{fail, Failure};
+use serde_json::Value;
+
+/// A single day's solution crate within the repository
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Day {
+    pub number: u8,
+}
+
+/// The result of running a day's solution once
+#[derive(Clone, Debug)]
+pub struct Run {
+    /// Whether the solution exited successfully
+    pub success: bool,
+    /// Everything the solution printed to standard output
+    pub output: String,
+    /// Everything the solution printed to standard error
+    pub errors: String,
+    /// How long the solution took to run, not including building it
+    pub elapsed: Duration,
+}
+
+impl Day {
+    /// Gets the day, or None if there is no crate for it in the repository
+    pub fn new(number: u8) -> Option<Self> {
+        let day = Self { number };
+        day.manifest().is_file().then_some(day)
+    }
+
+    /// Parses the number of a day with a solution, for use as a command line argument
+    pub fn parse(text: &str) -> Result<Self, String> {
+        let number = text
+            .parse::<u8>()
+            .map_err(|_| format!("{text:?} is not a day number"))?;
+        Self::new(number).ok_or_else(|| format!("There is no solution for day {number}"))
+    }
+
+    /// Gets the name of the day's crate, which is also the name of its binary
+    pub fn name(&self) -> String {
+        format!("day{}", self.number)
+    }
+
+    /// Gets the directory containing the day's crate
+    pub fn directory(&self) -> PathBuf {
+        repository_root().join(self.name())
+    }
+
+    /// Gets the path of the day's crate manifest
+    pub fn manifest(&self) -> PathBuf {
+        self.directory().join("Cargo.toml")
+    }
+
+    /// Gets the directories of the crates in the repository that the day is built from, which
+    /// are its own crate along with the shared crates it depends on, directly or not
+    pub fn local_crates(&self) -> Vec<PathBuf> {
+        let directory = self.directory();
+        let mut crates = vec![directory.canonicalize().unwrap_or(directory)];
+        let mut index = 0;
+        while let Some(directory) = crates.get(index).cloned() {
+            let package = describe_package(&directory.join("Cargo.toml")).unwrap_or_default();
+            for dependency in path_dependencies(&package) {
+                if !crates.contains(&dependency) {
+                    crates.push(dependency);
+                }
+            }
+            index += 1;
+        }
+        crates
+    }
+
+    /// Builds the day's release binary, returning its path, or the compiler errors if it fails
+    ///
+    /// The path is taken from cargo itself, since the crate names don't always match the days.
+    pub fn build(&self) -> Result<PathBuf, String> {
+        // Build the binary, asking cargo to describe what it built
+        let output = Command::new(env!("CARGO"))
+            .args(["build", "--release", "--quiet", "--message-format=json"])
+            .arg("--manifest-path")
+            .arg(self.manifest())
+            .output()
+            .unwrap_or_else(|error| fail(Failure::Io(format!("Could not run cargo: {error}"))));
+        if !output.status.success() {
+            return Err(String::from_utf8_lossy(&output.stderr).into_owned());
+        }
+
+        // Find the path of the executable among the built artifacts
+        String::from_utf8_lossy(&output.stdout)
+            .lines()
+            .filter_map(|line| serde_json::from_str::<Value>(line).ok())
+            .filter(|message| message["reason"] == "compiler-artifact")
+            .filter_map(|message| message["executable"].as_str().map(PathBuf::from))
+            .next_back()
+            .ok_or_else(|| format!("No binary was built for {}", self.name()))
+    }
+
+    /// Gets the metadata that the day's built binary describes about itself, along with the
+    /// features that can be told from its package, or a description of why it couldn't be read
+    pub fn metadata(&self, binary: &Path) -> Result<Metadata, String> {
+        let output = Command::new(binary)
+            .arg("--about")
+            .output()
+            .unwrap_or_else(|error| {
+                fail(Failure::Io(format!(
+                    "Could not run {}: {error}",
+                    self.name()
+                )))
+            });
+        let mut metadata = Metadata::from_about(&String::from_utf8_lossy(&output.stdout))?;
+
+        // Add the features that the binary doesn't describe but its dependencies imply
+        let package = describe_package(&self.manifest())?;
+        for feature in manifest_features(&package) {
+            if !metadata.features.contains(&feature) {
+                metadata.features.push(feature);
+            }
+        }
+        Ok(metadata)
+    }
+
+    /// Runs the day's built binary for the given part and input file, timing it
+    pub fn run(&self, binary: &Path, part: u64, input: &Path) -> Run {
+        let start = Instant::now();
+        let output = Command::new(binary)
+            .arg(part.to_string())
+            .arg(input)
+            .output()
+            .unwrap_or_else(|error| {
+                fail(Failure::Io(format!(
+                    "Could not run {}: {error}",
+                    self.name()
+                )))
+            });
+        let elapsed = start.elapsed();
+        Run {
+            success: output.status.success(),
+            output: String::from_utf8_lossy(&output.stdout).into_owned(),
+            errors: String::from_utf8_lossy(&output.stderr).into_owned(),
+            elapsed,
+        }
+    }
+}
+
+/// Gets all of the days that have a solution in the repository
+pub fn all_days() -> Vec<Day> {
+    (1..=25)
+        .map(|number| Day { number })
+        .filter(|day| day.manifest().is_file())
+        .collect()
+}
+
+/// Gets the root of the repository, which contains the crates for each of the days
+pub fn repository_root() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR"))
+        .parent()
+        .expect("The runner is not within the repository")
+        .to_path_buf()
+}
+
+/// Metadata that a day's solution describes about itself
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Metadata {
+    /// The title of the puzzle
+    pub title: String,
+    /// The parts of the puzzle that are implemented
+    pub parts: Vec<u64>,
+    /// The optional features that the solution supports
+    pub features: Vec<String>,
+}
+
+impl Metadata {
+    /// Parses the metadata out of the description printed by a solution's `--about`, failing
+    /// if it has no title or parts, or a part isn't a number
+    pub fn from_about(text: &str) -> Result<Self, String> {
+        let mut metadata = Self::default();
+        for line in text.lines() {
+            let Some((key, value)) = line.split_once(": ") else {
+                continue;
+            };
+            let list = || value.split(", ").map(|x| x.trim().to_string());
+            match key {
+                _ if key.starts_with("Day ") => metadata.title = value.to_string(),
+                "Parts" => {
+                    metadata.parts = list()
+                        .map(|x| x.parse::<u64>().map_err(|_| format!("Invalid part {x:?}")))
+                        .collect::<Result<_, _>>()?
+                }
+                "Features" => metadata.features = list().collect(),
+                _ => {}
+            }
+        }
+
+        // Make sure the description had what every solution describes
+        if metadata.title.is_empty() {
+            return Err("The description has no title".to_string());
+        }
+        if metadata.parts.is_empty() {
+            return Err("The description has no parts".to_string());
+        }
+        Ok(metadata)
+    }
+}
+
+/// Describes the package with the given manifest as cargo sees it, using `cargo metadata`, or
+/// gets why cargo couldn't describe it
+fn describe_package(manifest: &Path) -> Result<Value, String> {
+    let output = Command::new(env!("CARGO"))
+        .args([
+            "metadata",
+            "--format-version",
+            "1",
+            "--no-deps",
+            "--manifest-path",
+        ])
+        .arg(manifest)
+        .output()
+        .unwrap_or_else(|error| fail(Failure::Io(format!("Could not run cargo: {error}"))));
+    if !output.status.success() {
+        return Err(String::from_utf8_lossy(&output.stderr).into_owned());
+    }
+
+    // Find the package with the manifest among those cargo describes
+    let metadata: Value = serde_json::from_slice(&output.stdout)
+        .map_err(|error| format!("Could not parse the metadata from cargo: {error}"))?;
+    let manifest = manifest.canonicalize().unwrap_or(manifest.to_path_buf());
+    metadata["packages"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .find(|package| package["manifest_path"].as_str().map(Path::new) == Some(&manifest))
+        .cloned()
+        .ok_or_else(|| format!("Cargo did not describe {}", manifest.display()))
+}
+
+/// Gets the features that a package, as described by `cargo metadata`, shows it supports, which
+/// is currently only "parallel" for the packages that depend on rayon
+pub fn manifest_features(package: &Value) -> Vec<String> {
+    let mut features = Vec::new();
+    if dependencies(package).any(|dependency| dependency["name"] == "rayon") {
+        features.push("parallel".to_string());
+    }
+    features
+}
+
+/// Gets the directories of the dependencies that a package, as described by `cargo metadata`,
+/// gives by path, such as the shared crates in the repository
+pub fn path_dependencies(package: &Value) -> Vec<PathBuf> {
+    dependencies(package)
+        .filter_map(|dependency| dependency["path"].as_str().map(PathBuf::from))
+        .collect()
+}
+
+/// Gets the normal dependencies of a package as described by `cargo metadata`, leaving out its
+/// development and build dependencies
+fn dependencies(package: &Value) -> impl Iterator<Item = &Value> {
+    package["dependencies"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter(|dependency| dependency["kind"].is_null())
+}
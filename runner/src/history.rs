@@ -0,0 +1,98 @@
+use std::{
+    collections::HashMap,
+    fs::{self, OpenOptions},
+    io::Write,
+    path::PathBuf,
+    time::{Duration, SystemTime},
+};
+
+use common::{fail, Failure};
+
+use crate::day::{repository_root, Day};
+
+/// A single recorded run of a day's solution
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Entry {
+    /// The part of the day that was run
+    pub part: u64,
+    /// When the run happened, in seconds since the Unix epoch
+    pub timestamp: u64,
+    /// How long the run took
+    pub elapsed: Duration,
+}
+
+/// Gets the path of the history log, which is kept as CSV in the root of the repository
+fn history_path() -> PathBuf {
+    repository_root().join("aoc-history.csv")
+}
+
+/// Records a successful run of the day's solution in the history log
+pub fn record(day: &Day, part: u64, elapsed: Duration) {
+    // Open the history log, writing the header if it's new
+    let path = history_path();
+    let is_new = !path.exists();
+    let mut file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(&path)
+        .unwrap_or_else(|error| {
+            fail(Failure::Io(format!(
+                "Could not open {}: {error}",
+                path.display()
+            )))
+        });
+
+    // Append the run to the log
+    let timestamp = SystemTime::now()
+        .duration_since(SystemTime::UNIX_EPOCH)
+        .map_or(0, |x| x.as_secs());
+    let mut text = String::new();
+    if is_new {
+        text.push_str("day,part,timestamp,microseconds\n");
+    }
+    let microseconds = elapsed.as_micros();
+    text.push_str(&format!(
+        "{},{part},{timestamp},{microseconds}\n",
+        day.number
+    ));
+    file.write_all(text.as_bytes()).unwrap_or_else(|error| {
+        fail(Failure::Io(format!(
+            "Could not write {}: {error}",
+            path.display()
+        )))
+    });
+}
+
+/// Gets the most recently recorded run of each day from the history log
+pub fn last_runs() -> HashMap<u8, Entry> {
+    // Read the history log, which may not exist yet
+    let Ok(contents) = fs::read_to_string(history_path()) else {
+        return HashMap::new();
+    };
+
+    // Keep the latest run of each day, skipping the header and any malformed lines
+    let mut runs: HashMap<u8, Entry> = HashMap::new();
+    for line in contents.lines().skip(1) {
+        let fields: Vec<&str> = line.split(',').collect();
+        let [day, part, timestamp, microseconds] = fields[..] else {
+            continue;
+        };
+        let (Ok(day), Ok(part), Ok(timestamp), Ok(microseconds)) = (
+            day.parse::<u8>(),
+            part.parse::<u64>(),
+            timestamp.parse::<u64>(),
+            microseconds.parse::<u64>(),
+        ) else {
+            continue;
+        };
+        let entry = Entry {
+            part,
+            timestamp,
+            elapsed: Duration::from_micros(microseconds),
+        };
+        if runs.get(&day).is_none_or(|x| x.timestamp <= timestamp) {
+            runs.insert(day, entry);
+        }
+    }
+    runs
+}
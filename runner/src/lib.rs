@@ -0,0 +1,5 @@
+pub mod completions;
+pub mod day;
+pub mod history;
+pub mod list;
+pub mod watch;
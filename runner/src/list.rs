@@ -0,0 +1,39 @@
+use common::{fail, Failure};
+
+use crate::{day::all_days, history};
+
+/// Prints every day with a solution, along with its metadata and most recent timing, as CSV
+pub fn list() {
+    let last_runs = history::last_runs();
+
+    println!("day,title,parts,features,last_run");
+    for day in all_days() {
+        // Build the day so that it can describe itself
+        let binary = day.build().unwrap_or_else(|errors| {
+            fail(Failure::Io(format!(
+                "Could not build {}: {errors}",
+                day.name()
+            )))
+        });
+        let metadata = day.metadata(&binary).unwrap_or_else(|error| {
+            fail(Failure::Parse(format!(
+                "Could not describe {}: {error}",
+                day.name()
+            )))
+        });
+
+        // Describe the day, listing multiple values separated by spaces
+        let parts: Vec<String> = metadata.parts.iter().map(|x| x.to_string()).collect();
+        let last_run = last_runs.get(&day.number).map_or(String::new(), |x| {
+            let elapsed = x.elapsed.as_secs_f64() * 1000.0;
+            format!("part {} in {elapsed:.3}ms", x.part)
+        });
+        println!(
+            "{},{},{},{},{last_run}",
+            day.number,
+            metadata.title,
+            parts.join(" "),
+            metadata.features.join(" ")
+        );
+    }
+}
@@ -0,0 +1,64 @@
+use std::path::PathBuf;
+
+use clap::{CommandFactory, Parser, Subcommand};
+use clap_complete::{ArgValueCandidates, ArgValueCompleter, CompleteEnv};
+use common::Format;
+use runner::{
+    completions::{self, day_candidates, input_candidates, Shell, COMPLETE_VAR},
+    day::Day,
+    list, watch,
+};
+
+#[derive(Parser)]
+struct CliArgs {
+    #[command(subcommand)]
+    command: Command,
+    /// The format in which errors are reported
+    #[arg(long, value_enum, default_value_t = Format::Text)]
+    format: Format,
+}
+
+/// Commands supported by the runner
+#[derive(Subcommand)]
+enum Command {
+    /// Re-run a day's solution whenever its source or input file changes
+    Watch {
+        /// The day to run
+        #[arg(long, value_parser = Day::parse, add = ArgValueCandidates::new(day_candidates))]
+        day: Day,
+        /// The part of the day to run
+        #[arg(long)]
+        part: u64,
+        /// The input file to run the solution on
+        #[arg(long, add = ArgValueCompleter::new(input_candidates))]
+        input: PathBuf,
+    },
+    /// List every day with a solution, along with its parts, features, and last timing
+    List,
+    /// Print the script that registers completions for the runner with the given shell
+    Completions {
+        /// The shell to register completions with
+        #[arg(value_enum)]
+        shell: Shell,
+    },
+}
+
+fn main() {
+    // Answer the shell instead if it's asking for completions
+    CompleteEnv::with_factory(CliArgs::command)
+        .var(COMPLETE_VAR)
+        .complete();
+
+    // Parse CLI arguments
+    let cli = CliArgs::parse();
+
+    // Report errors in the requested format
+    common::init(cli.format);
+
+    // Run the requested command
+    match cli.command {
+        Command::Watch { day, part, input } => watch::watch(day, part, &input),
+        Command::List => list::list(),
+        Command::Completions { shell } => completions::completions(shell),
+    }
+}
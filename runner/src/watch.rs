@@ -0,0 +1,115 @@
+use std::{
+    path::{Path, PathBuf},
+    sync::mpsc,
+    time::{Duration, Instant},
+};
+
+use common::{fail, Failure};
+use notify::{EventKind, RecursiveMode, Watcher};
+
+use crate::{day::Day, history};
+
+/// How long to wait for a burst of file changes (such as an editor saving) to settle
+const SETTLE_TIME: Duration = Duration::from_millis(200);
+
+/// Runs the day's solution, then runs it again whenever its source, the source of the shared
+/// crates it depends on, or the input file changes
+pub fn watch(day: Day, part: u64, input: &Path) {
+    // Get the sources of the crates the day is built from, and the directory holding the input
+    // file, since editors often save by replacing the file, which would end a watch on it
+    let sources: Vec<PathBuf> = day
+        .local_crates()
+        .iter()
+        .map(|x| x.join("src"))
+        .filter(|x| x.is_dir())
+        .collect();
+    let input = input.canonicalize().unwrap_or_else(|error| {
+        let input = input.display();
+        fail(Failure::Io(format!("Could not read {input}: {error}")))
+    });
+    let input_directory = input.parent().unwrap_or(Path::new("/"));
+
+    // Watch the sources and the input file's directory for changes
+    let (sender, receiver) = mpsc::channel();
+    let mut watcher = notify::recommended_watcher(sender)
+        .unwrap_or_else(|error| fail(Failure::Io(format!("Could not watch files: {error}"))));
+    let watched = sources
+        .iter()
+        .map(|x| (x.as_path(), RecursiveMode::Recursive))
+        .chain([(input_directory, RecursiveMode::NonRecursive)]);
+    for (path, mode) in watched {
+        watcher.watch(path, mode).unwrap_or_else(|error| {
+            let path = path.display();
+            fail(Failure::Io(format!("Could not watch {path}: {error}")))
+        });
+    }
+    let input = input.as_path();
+
+    // Run the solution once up front, then again after every change
+    let mut previous = None;
+    rerun(&day, part, input, &mut previous);
+    while let Ok(event) = receiver.recv() {
+        // Only rerun when a source or the input file was actually changed, not just accessed,
+        // ignoring the other files in the input file's directory
+        let Ok(event) = event else {
+            continue;
+        };
+        if !matches!(
+            event.kind,
+            EventKind::Create(_) | EventKind::Modify(_) | EventKind::Remove(_)
+        ) {
+            continue;
+        }
+        let relevant =
+            |path: &PathBuf| path.as_path() == input || sources.iter().any(|x| path.starts_with(x));
+        if !event.paths.iter().any(relevant) {
+            continue;
+        }
+
+        // Let the burst of changes settle, discarding the rest of the events in it
+        let deadline = Instant::now() + SETTLE_TIME;
+        while let Some(remaining) = deadline.checked_duration_since(Instant::now()) {
+            if receiver.recv_timeout(remaining).is_err() {
+                break;
+            }
+        }
+
+        rerun(&day, part, input, &mut previous);
+    }
+}
+
+/// Builds and runs the solution, printing the answer along with how the timing changed since
+/// the previous successful run
+fn rerun(day: &Day, part: u64, input: &Path, previous: &mut Option<Duration>) {
+    println!("--- {} part {part}", day.name());
+
+    // Build the solution, reporting any compiler errors
+    let binary = match day.build() {
+        Ok(binary) => binary,
+        Err(errors) => {
+            print!("{errors}");
+            println!("Build failed");
+            return;
+        }
+    };
+
+    // Run the solution, reporting any errors
+    let run = day.run(&binary, part, input);
+    print!("{}", run.output);
+    if !run.success {
+        print!("{}", run.errors);
+        println!("Run failed");
+        return;
+    }
+
+    // Record the run, then report the timing along with the change since the previous run
+    history::record(day, part, run.elapsed);
+    let elapsed = run.elapsed.as_secs_f64() * 1000.0;
+    match previous.replace(run.elapsed) {
+        Some(previous) => {
+            let delta = elapsed - previous.as_secs_f64() * 1000.0;
+            println!("Took {elapsed:.3}ms ({delta:+.3}ms)");
+        }
+        None => println!("Took {elapsed:.3}ms"),
+    }
+}
@@ -0,0 +1,66 @@
+use std::fs;
+
+use runner::{
+    completions::{day_inputs, day_inputs_in, requested_day},
+    day::{repository_root, Day},
+};
+
+/// Gets the arguments of a command line being completed
+fn args(line: &str) -> Vec<String> {
+    line.split_whitespace().map(String::from).collect()
+}
+
+#[test]
+fn finds_requested_day() {
+    let day = Some(Day { number: 3 });
+    assert_eq!(requested_day(args("aoc -- aoc watch --day 3 --input")), day);
+    assert_eq!(requested_day(args("aoc -- aoc watch --day=3 --input")), day);
+}
+
+#[test]
+fn ignores_missing_day() {
+    assert_eq!(requested_day(args("aoc -- aoc watch --input")), None);
+    assert_eq!(requested_day(args("aoc -- aoc watch --day")), None);
+    assert_eq!(requested_day(args("aoc -- aoc watch --day 30")), None);
+}
+
+#[test]
+fn completes_day_inputs() {
+    let inputs = repository_root().join("day3").join("inputs");
+    let path = |name: &str| inputs.join(name).display().to_string();
+    let day = Day { number: 3 };
+    assert_eq!(
+        day_inputs(day, ""),
+        vec![path("example-part2.txt"), path("example.txt")]
+    );
+    assert_eq!(day_inputs(day, "example-"), vec![path("example-part2.txt")]);
+    assert_eq!(
+        day_inputs(day, &path("example.")),
+        vec![path("example.txt")]
+    );
+    assert!(day_inputs(day, "input").is_empty());
+}
+
+#[test]
+fn completes_cached_day_inputs() {
+    let directory = tempfile::tempdir().unwrap();
+    for name in [
+        "day3-transposed.txt",
+        "day4-transposed.txt",
+        ".day3-partial.tmp",
+    ] {
+        fs::write(directory.path().join(name), "").unwrap();
+    }
+    let inputs = repository_root().join("day3").join("inputs");
+    let cached = directory.path().join("day3-transposed.txt");
+    let day = Day { number: 3 };
+    assert_eq!(
+        day_inputs_in(day, directory.path(), "example."),
+        vec![inputs.join("example.txt").display().to_string()]
+    );
+    assert_eq!(
+        day_inputs_in(day, directory.path(), "day"),
+        vec![cached.display().to_string()]
+    );
+    assert_eq!(day_inputs_in(day, directory.path(), "").len(), 3);
+}
@@ -0,0 +1,53 @@
+use std::path::PathBuf;
+
+use runner::day::{path_dependencies, repository_root, Day};
+use serde_json::json;
+
+#[test]
+fn parses_existing_day() {
+    assert_eq!(Day::parse("8"), Ok(Day { number: 8 }));
+}
+
+#[test]
+fn rejects_missing_day() {
+    assert_eq!(
+        Day::parse("30"),
+        Err(String::from("There is no solution for day 30"))
+    );
+    assert_eq!(
+        Day::parse("eight"),
+        Err(String::from("\"eight\" is not a day number"))
+    );
+}
+
+#[test]
+fn local_crates_include_shared_crates() {
+    let root = repository_root().canonicalize().unwrap();
+    let crates = Day::parse("8").unwrap().local_crates();
+    for name in ["day8", "common", "pathfinding"] {
+        assert!(
+            crates
+                .iter()
+                .any(|x| x.ends_with(name) && x.starts_with(&root)),
+            "{name} missing from {crates:?}"
+        );
+    }
+    assert_eq!(crates.len(), 3);
+}
+
+#[test]
+fn path_dependencies_only_include_dependencies() {
+    let package = json!({
+        "name": "day6",
+        "dependencies": [
+            { "name": "clap", "kind": null, "path": null },
+            { "name": "common", "kind": null, "path": "/aoc/common" },
+            { "name": "grid", "kind": null, "path": "/aoc/grid" },
+            { "name": "day14", "kind": "dev", "path": "/aoc/day14" },
+        ],
+    });
+    assert_eq!(
+        path_dependencies(&package),
+        vec![PathBuf::from("/aoc/common"), PathBuf::from("/aoc/grid")]
+    );
+}
@@ -0,0 +1,112 @@
+//! Check of every day's library against the examples from the puzzle descriptions
+//!
+//! This links and solves every day, so it only runs with the `golden` feature enabled:
+//! `cargo test --features golden`.
+#![cfg(feature = "golden")]
+
+use std::{fs, path::PathBuf};
+
+use day14::GameMap;
+use day18::{Algorithm, ProgramMap};
+
+/// Gets the directory of the crate for the given day
+fn day_directory(day: u8) -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .parent()
+        .expect("The runner is not within the repository")
+        .join(format!("day{day}"))
+}
+
+/// Reads the given example input (relative to the day's crate) of the day
+fn example(day: u8, input: &str) -> String {
+    fs::read_to_string(day_directory(day).join(input)).expect("Could not read the example")
+}
+
+#[test]
+fn libraries_match_examples() {
+    // Days 14 and 18 are checked separately, as their libraries only solve the full-sized puzzle
+    let input = example(1, "inputs/example.txt");
+    assert_eq!(day1::part1(&input), Ok(11));
+    assert_eq!(day1::part2(&input), Ok(31));
+    let input = example(2, "inputs/example.txt");
+    assert_eq!(day2::part1(&input), Ok(2));
+    assert_eq!(day2::part2(&input), Ok(4));
+    assert_eq!(day3::part1(&example(3, "inputs/example.txt")), Ok(161));
+    assert_eq!(day3::part2(&example(3, "inputs/example-part2.txt")), Ok(48));
+    let input = example(4, "inputs/example.txt");
+    assert_eq!(day4::part1(&input), Ok(18));
+    assert_eq!(day4::part2(&input), Ok(9));
+    let input = example(5, "inputs/example.txt");
+    assert_eq!(day5::part1(&input), Ok(143));
+    assert_eq!(day5::part2(&input), Ok(123));
+    let input = example(6, "inputs/example.txt");
+    assert_eq!(day6::part1(&input), Ok(41));
+    assert_eq!(day6::part2(&input), Ok(6));
+    let input = example(7, "inputs/example.txt");
+    assert_eq!(day7::part1(&input), Ok(3749));
+    assert_eq!(day7::part2(&input), Ok(11387));
+    let input = example(8, "inputs/example.txt");
+    assert_eq!(day8::part1(&input), Ok(14));
+    assert_eq!(day8::part2(&input), Ok(34));
+    let input = example(9, "inputs/example.txt");
+    assert_eq!(day9::part1(&input), Ok(1928));
+    assert_eq!(day9::part2(&input), Ok(2858));
+    let input = example(10, "inputs/example.txt");
+    assert_eq!(day10::part1(&input), Ok(36));
+    assert_eq!(day10::part2(&input), Ok(81));
+    assert_eq!(day11::part1(&example(11, "inputs/example.txt")), Ok(55312));
+    let input = example(12, "inputs/example.txt");
+    assert_eq!(day12::part1(&input), Ok(1930));
+    assert_eq!(day12::part2(&input), Ok(1206));
+    assert_eq!(day13::part1(&example(13, "inputs/example.txt")), Ok(480));
+    let input = example(15, "inputs/example.txt");
+    assert_eq!(day15::part1(&input), Ok(10092));
+    assert_eq!(day15::part2(&input), Ok(9021));
+    let input = example(16, "inputs/example.txt");
+    assert_eq!(day16::part1(&input), Ok(7036));
+    assert_eq!(day16::part2(&input), Ok(45));
+    assert_eq!(
+        day17::part1(&example(17, "inputs/example.txt")),
+        Ok(String::from("4,6,3,5,6,3,5,2,1,0"))
+    );
+    assert_eq!(
+        day17::part2(&example(17, "inputs/example-part2.txt")),
+        Ok(117440)
+    );
+    let input = example(19, "inputs/example.txt");
+    assert_eq!(day19::part1(&input), Ok(6));
+    assert_eq!(day19::part2(&input), Ok(16));
+}
+
+#[test]
+fn day14_matches_example() {
+    let contents = example(14, "inputs/example.txt");
+    let mut gamemap = GameMap::parse(&contents, 11, 7).unwrap();
+    gamemap.extrapolate(100);
+    assert_eq!(gamemap.safety_factor(), 12);
+}
+
+#[test]
+fn day18_matches_example() {
+    let contents = example(18, "inputs/example.txt");
+
+    // Part one finds the shortest path after the first 12 bytes fall
+    let (mut program_map, _) = ProgramMap::try_from_string(&contents, 7, 7, false).unwrap();
+    program_map.presimulate_corruption(12);
+    program_map.search(Algorithm::Dijkstra);
+    let end = program_map.end;
+    assert_eq!(program_map.visited[&end].distance, 22);
+
+    // Part two finds the first byte that blocks the path, by removing bytes until it's open
+    let (mut program_map, _) = ProgramMap::try_from_string(&contents, 7, 7, false).unwrap();
+    let num_obstacles = program_map.planned_obstacles.len();
+    program_map.presimulate_corruption(num_obstacles);
+    program_map.search(Algorithm::Dijkstra);
+    while !program_map.visited.contains_key(&program_map.end) {
+        program_map.uncorrupt_next_space();
+        program_map.reset_visited();
+        program_map.search(Algorithm::Dijkstra);
+    }
+    let blocker = program_map.planned_obstacles.last().unwrap();
+    assert_eq!((blocker.x, blocker.y), (6, 1));
+}
@@ -0,0 +1,78 @@
+use runner::day::{manifest_features, Metadata};
+use serde_json::json;
+
+#[test]
+fn parses_about() {
+    let about = "Day 17: Chronospatial Computer\n\
+                 Input: The initial register values, a blank line, then the program\n\
+                 Parts: 1, 2\n\
+                 Features: visualization, parallel\n\
+                 Options:\n  --modulus <MODULUS>  The modulus of the machine\n";
+    let expected = Metadata {
+        title: "Chronospatial Computer".to_string(),
+        parts: vec![1, 2],
+        features: vec!["visualization".to_string(), "parallel".to_string()],
+    };
+    assert_eq!(Metadata::from_about(about), Ok(expected));
+}
+
+#[test]
+fn parses_about_without_features() {
+    let metadata = Metadata::from_about("Day 1: Historian Hysteria\nParts: 1\n").unwrap();
+    assert_eq!(metadata.parts, vec![1]);
+    assert!(metadata.features.is_empty());
+}
+
+#[test]
+fn rejects_invalid_part() {
+    let about = "Day 1: Historian Hysteria\nParts: 1, two\n";
+    assert_eq!(
+        Metadata::from_about(about),
+        Err("Invalid part \"two\"".to_string())
+    );
+}
+
+#[test]
+fn rejects_missing_title() {
+    assert_eq!(
+        Metadata::from_about("Parts: 1, 2\n"),
+        Err("The description has no title".to_string())
+    );
+}
+
+#[test]
+fn rejects_missing_parts() {
+    assert_eq!(
+        Metadata::from_about("Day 1: Historian Hysteria\n"),
+        Err("The description has no parts".to_string())
+    );
+}
+
+#[test]
+fn rejects_empty_output() {
+    assert!(Metadata::from_about("").is_err());
+}
+
+#[test]
+fn package_with_rayon_is_parallel() {
+    let package = json!({
+        "name": "day17",
+        "dependencies": [
+            { "name": "clap", "kind": null },
+            { "name": "rayon", "kind": null },
+        ],
+    });
+    assert_eq!(manifest_features(&package), vec!["parallel".to_string()]);
+}
+
+#[test]
+fn package_without_rayon_has_no_features() {
+    let package = json!({
+        "name": "rayon",
+        "dependencies": [
+            { "name": "common", "kind": null, "path": "/aoc/common" },
+            { "name": "rayon", "kind": "dev" },
+        ],
+    });
+    assert!(manifest_features(&package).is_empty());
+}
@@ -0,0 +1,220 @@
+//! End-to-end check of the `--parse-only` mode of every day against the examples
+//!
+//! This builds and runs every day, so it only runs with the `golden` feature enabled:
+//! `cargo test --features golden`.
+#![cfg(feature = "golden")]
+
+use std::{
+    fs,
+    path::PathBuf,
+    process::{Command, Output},
+};
+
+/// The statistics printed for each example, as the day, example input (relative to the day's
+/// crate), and the lines printed
+const STATISTICS: &[(u8, &str, &[&str])] = &[
+    (
+        1,
+        "inputs/example.txt",
+        &["entries: 6", "distinct first: 4", "distinct second: 4"],
+    ),
+    (
+        2,
+        "inputs/example.txt",
+        &[
+            "reports: 6",
+            "levels: 30",
+            "shortest report: 5",
+            "longest report: 5",
+        ],
+    ),
+    (
+        3,
+        "inputs/example-part2.txt",
+        &[
+            "bytes: 74",
+            "instructions: 6",
+            "mul: 4",
+            "do: 1",
+            "don't: 1",
+        ],
+    ),
+    (
+        4,
+        "inputs/example.txt",
+        &["rows: 10", "columns: 10", "distinct letters: 4"],
+    ),
+    (
+        5,
+        "inputs/example.txt",
+        &["rules: 21", "updates: 6", "pages: 26"],
+    ),
+    (
+        6,
+        "inputs/example.txt",
+        &["rows: 10", "columns: 10", "obstructions: 8"],
+    ),
+    (
+        7,
+        "inputs/example.txt",
+        &["equations: 9", "inputs: 27", "most inputs: 4"],
+    ),
+    (
+        8,
+        "inputs/example.txt",
+        &["rows: 12", "columns: 12", "antennas: 7", "frequencies: 2"],
+    ),
+    (
+        9,
+        "inputs/example.txt",
+        &[
+            "files: 10",
+            "file blocks: 28",
+            "free spaces: 9",
+            "free blocks: 14",
+        ],
+    ),
+    (
+        10,
+        "inputs/example.txt",
+        &["rows: 8", "columns: 8", "trailheads: 9", "summits: 7"],
+    ),
+    (
+        11,
+        "inputs/example.txt",
+        &["stones: 2", "distinct stones: 2"],
+    ),
+    (
+        12,
+        "inputs/example.txt",
+        &["rows: 10", "columns: 10", "plants: 9"],
+    ),
+    (
+        13,
+        "inputs/example.txt",
+        &["machines: 4", "parallel buttons: 0"],
+    ),
+    (
+        14,
+        "inputs/example.txt",
+        &["robots: 12", "outside map: 0", "stationary: 0"],
+    ),
+    (
+        15,
+        "inputs/example.txt",
+        &[
+            "rows: 10",
+            "columns: 10",
+            "robots: 1",
+            "boxes: 21",
+            "walls: 37",
+            "moves: 700",
+        ],
+    ),
+    (
+        16,
+        "inputs/example.txt",
+        &[
+            "rows: 15",
+            "columns: 15",
+            "open tiles: 104",
+            "weighted tiles: 0",
+        ],
+    ),
+    (
+        17,
+        "inputs/example.txt",
+        &["instructions: 3", "jumps: 1", "outputs: 1"],
+    ),
+    (
+        18,
+        "inputs/example.txt",
+        &[
+            "rows: 71",
+            "columns: 71",
+            "bytes: 25",
+            "duplicates: 0",
+            "out of bounds: 0",
+        ],
+    ),
+    (
+        19,
+        "inputs/example.txt",
+        &[
+            "towels: 8",
+            "longest towel: 3",
+            "patterns: 8",
+            "longest pattern: 6",
+        ],
+    ),
+];
+
+/// Gets the directory of the crate for the given day
+fn day_directory(day: u8) -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .parent()
+        .expect("The runner is not within the repository")
+        .join(format!("day{day}"))
+}
+
+/// Builds and runs the day's solution with the given arguments
+fn run_day(day: u8, args: &[&str]) -> Output {
+    Command::new(env!("CARGO"))
+        .args(["run", "--release", "--quiet", "--manifest-path"])
+        .arg(day_directory(day).join("Cargo.toml"))
+        .arg("--")
+        .args(args)
+        .output()
+        .expect("Could not run cargo")
+}
+
+#[test]
+fn examples_report_statistics() {
+    let mut mismatches = Vec::new();
+    for (day, input, expected) in STATISTICS {
+        // Only parse the example, which must succeed without solving it
+        let input = day_directory(*day).join(input);
+        let output = run_day(*day, &["1", input.to_str().unwrap(), "--parse-only"]);
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        let lines: Vec<&str> = stdout.lines().collect();
+        if !output.status.success() || lines != *expected {
+            mismatches.push(format!("day {day}: expected {expected:?}, got {lines:?}"));
+        }
+    }
+    assert!(mismatches.is_empty(), "{}", mismatches.join("\n"));
+}
+
+#[test]
+fn malformed_input_fails_to_parse() {
+    // Input that none of these days can parse exits with the parse error code
+    let directory = tempfile::tempdir().unwrap();
+    let input = directory.path().join("malformed.txt");
+    fs::write(&input, "1 2 x\n\u{1F384}\n").unwrap();
+    for day in [1, 2, 5, 7, 10, 13] {
+        let output = run_day(day, &["1", input.to_str().unwrap(), "--parse-only"]);
+        assert_eq!(output.status.code(), Some(2), "day {day}");
+        assert!(output.stdout.is_empty(), "day {day}");
+    }
+}
+
+#[test]
+fn malformed_input_fails_to_parse_when_solving() {
+    // Every day whose input has a fixed shape reports the parse error without relying on a panic,
+    // while days 3 and 8 accept any text
+    let directory = tempfile::tempdir().unwrap();
+    let input = directory.path().join("malformed.txt");
+    fs::write(&input, "garbage line !!\nfoo").unwrap();
+    for day in (1..=19).filter(|x| ![3, 8].contains(x)) {
+        let output = run_day(day, &["1", input.to_str().unwrap(), "--format", "json"]);
+        assert_eq!(output.status.code(), Some(2), "day {day}");
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        assert!(stdout.contains(r#""kind":"parse""#), "day {day}: {stdout}");
+    }
+}
+
+#[test]
+fn fuzzing_conflicts_with_parse_only() {
+    let output = run_day(15, &["1", "--fuzz", "1", "--parse-only"]);
+    assert_eq!(output.status.code(), Some(2));
+    assert!(String::from_utf8_lossy(&output.stderr).contains("cannot be used with"));
+}